@@ -0,0 +1,423 @@
+//! Versioned, frontend-agnostic command surface.
+//!
+//! `call` dispatches a command by name with JSON arguments and always answers
+//! with an `ApiEnvelope`, so alternative frontends (and HTTP/MCP bridges) get a
+//! uniform shape. `schema` describes the same commands and payload types; the
+//! two are kept in step by `tests::api`.
+
+use crate::error::{AppError, Result};
+use crate::models::TranslationRequest;
+use crate::ocr::mathpix::MathpixRequest;
+use crate::ocr::models::OcrRequest;
+use crate::tts::models::TtsRequest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+pub const API_VERSION: &str = "1";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiEnvelope<T> {
+    pub api_version: &'static str,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T> From<Result<T>> for ApiEnvelope<T> {
+    fn from(result: Result<T>) -> Self {
+        match result {
+            Ok(data) => ApiEnvelope {
+                api_version: API_VERSION,
+                data: Some(data),
+                error: None,
+            },
+            Err(e) => ApiEnvelope {
+                api_version: API_VERSION,
+                data: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldSpec {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub ty: &'static str,
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub params: &'static [FieldSpec],
+    pub returns: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeSpec {
+    pub name: &'static str,
+    pub fields: &'static [FieldSpec],
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSchema {
+    pub api_version: &'static str,
+    pub commands: &'static [CommandSpec],
+    pub types: &'static [TypeSpec],
+}
+
+const fn field(name: &'static str, ty: &'static str) -> FieldSpec {
+    FieldSpec { name, ty, optional: false }
+}
+
+const fn optional(name: &'static str, ty: &'static str) -> FieldSpec {
+    FieldSpec { name, ty, optional: true }
+}
+
+const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "translate",
+        description: "Translate text with one or more services",
+        params: &[field("request", "TranslationRequest")],
+        returns: "TranslationResponse",
+    },
+    CommandSpec {
+        name: "ocr",
+        description: "Recognize text in an image with the default engine",
+        params: &[field("request", "OcrRequest")],
+        returns: "OcrResult",
+    },
+    CommandSpec {
+        name: "ocr_with_engine",
+        description: "Recognize text in an image with a specific engine (windows, paddle, rapid, tesseract, llm_vision)",
+        params: &[field("request", "OcrRequest"), field("engine", "string")],
+        returns: "OcrResult",
+    },
+    CommandSpec {
+        name: "ocr_files",
+        description: "Recognize text in several image files concurrently, with a result or error per file",
+        params: &[field("paths", "string[]"), field("engine", "string"), optional("language", "string")],
+        returns: "OcrFileResult[]",
+    },
+    CommandSpec {
+        name: "capture_and_ocr",
+        description: "Capture a screen region and recognize its text",
+        params: &[
+            field("x", "integer"),
+            field("y", "integer"),
+            field("w", "integer"),
+            field("h", "integer"),
+            optional("language", "string"),
+        ],
+        returns: "OcrResult",
+    },
+    CommandSpec {
+        name: "speak",
+        description: "Speak text aloud with the system voice or Edge online neural voices",
+        params: &[field("request", "TtsRequest")],
+        returns: "TtsResponse",
+    },
+    CommandSpec {
+        name: "speak_to_file",
+        description: "Synthesize speech to a WAV/MP3 file at path, or return it base64-encoded",
+        params: &[field("request", "TtsRequest"), optional("path", "string")],
+        returns: "TtsAudio",
+    },
+    CommandSpec {
+        name: "stop_speaking",
+        description: "Stop the current speech playback and clear queued speech",
+        params: &[],
+        returns: "null",
+    },
+    CommandSpec {
+        name: "pause_speaking",
+        description: "Pause the current speech playback",
+        params: &[],
+        returns: "null",
+    },
+    CommandSpec {
+        name: "resume_speaking",
+        description: "Resume paused speech playback",
+        params: &[],
+        returns: "null",
+    },
+    CommandSpec {
+        name: "list_tts_voices",
+        description: "List installed system voices, plus Edge online voices when include_online is set",
+        params: &[optional("include_online", "boolean"), optional("proxy", "object")],
+        returns: "TtsVoice[]",
+    },
+    CommandSpec {
+        name: "get_phonetic",
+        description: "UK/US phonetics for a single English word",
+        params: &[field("text", "string")],
+        returns: "PhoneticResult | null",
+    },
+    CommandSpec {
+        name: "lookup_dictionary",
+        description: "Look up the first dictionary entry for a word",
+        params: &[field("word", "string")],
+        returns: "DictionaryEntry | null",
+    },
+    CommandSpec {
+        name: "recognize_formula",
+        description: "Recognize a formula image as LaTeX via Mathpix, SimpleTex or a local model",
+        params: &[field("request", "MathpixRequest"), optional("config", "object")],
+        returns: "MathpixResult",
+    },
+    CommandSpec {
+        name: "get_app_status",
+        description: "Background and power/network status",
+        params: &[],
+        returns: "AppStatus",
+    },
+];
+
+const TYPES: &[TypeSpec] = &[
+    TypeSpec {
+        name: "TranslationRequest",
+        fields: &[
+            field("text", "string"),
+            field("source_lang", "string"),
+            field("target_lang", "string"),
+            field("services", "string[]"),
+            optional("config", "object"),
+            optional("mode", "string"),
+            optional("format", "string"),
+        ],
+    },
+    TypeSpec {
+        name: "TranslationResult",
+        fields: &[
+            field("name", "string"),
+            field("text", "string"),
+            optional("error", "string"),
+            optional("alternatives", "string[]"),
+        ],
+    },
+    TypeSpec {
+        name: "TranslationResponse",
+        fields: &[field("results", "TranslationResult[]"), field("memory", "MemoryMatch[]")],
+    },
+    TypeSpec {
+        name: "MemoryMatch",
+        fields: &[
+            field("source", "string"),
+            field("text", "string"),
+            optional("service", "string"),
+            field("similarity", "number"),
+        ],
+    },
+    TypeSpec {
+        name: "OcrRequest",
+        fields: &[
+            optional("image_data", "string"),
+            optional("image_path", "string"),
+            optional("language", "string"),
+            optional("vertical", "boolean"),
+            optional("cleanup", "boolean"),
+        ],
+    },
+    TypeSpec {
+        name: "OcrResult",
+        fields: &[
+            field("text", "string"),
+            field("confidence", "number"),
+            field("lines", "OcrLine[]"),
+            optional("paragraphs", "OcrParagraph[]"),
+            optional("barcodes", "Barcode[]"),
+        ],
+    },
+    TypeSpec {
+        name: "Barcode",
+        fields: &[
+            field("format", "string"),
+            field("text", "string"),
+            field("x", "number"),
+            field("y", "number"),
+            field("width", "number"),
+            field("height", "number"),
+        ],
+    },
+    TypeSpec {
+        name: "OcrFileResult",
+        fields: &[field("path", "string"), optional("result", "OcrResult"), optional("error", "string")],
+    },
+    TypeSpec {
+        name: "OcrParagraph",
+        fields: &[
+            field("text", "string"),
+            field("column", "integer"),
+            field("x", "number"),
+            field("y", "number"),
+            field("width", "number"),
+            field("height", "number"),
+        ],
+    },
+    TypeSpec {
+        name: "OcrLine",
+        fields: &[
+            field("text", "string"),
+            field("x", "number"),
+            field("y", "number"),
+            field("width", "number"),
+            field("height", "number"),
+            optional("confidence", "number"),
+        ],
+    },
+    TypeSpec {
+        name: "TtsRequest",
+        fields: &[
+            field("text", "string"),
+            optional("voice", "string"),
+            optional("engine", "string"),
+            optional("proxy", "object"),
+        ],
+    },
+    TypeSpec {
+        name: "TtsVoice",
+        fields: &[
+            field("id", "string"),
+            field("name", "string"),
+            field("language", "string"),
+            field("gender", "string"),
+            field("engine", "string"),
+        ],
+    },
+    TypeSpec {
+        name: "TtsAudio",
+        fields: &[field("format", "string"), optional("path", "string"), optional("data", "string")],
+    },
+    TypeSpec {
+        name: "TtsResponse",
+        fields: &[field("success", "boolean"), field("message", "string")],
+    },
+    TypeSpec {
+        name: "PhoneticResult",
+        fields: &[optional("uk", "string"), optional("us", "string")],
+    },
+    TypeSpec {
+        name: "DictionaryEntry",
+        fields: &[
+            field("word", "string"),
+            optional("phonetic", "string"),
+            field("phonetics", "object[]"),
+            field("meanings", "object[]"),
+            optional("examples", "string[]"),
+            optional("level", "object"),
+            optional("stale", "boolean"),
+        ],
+    },
+    TypeSpec {
+        name: "MathpixRequest",
+        fields: &[optional("image_data", "string"), optional("image_url", "string"), optional("engine", "string")],
+    },
+    TypeSpec {
+        name: "MathpixResult",
+        fields: &[
+            field("latex", "string"),
+            optional("latex_styled", "string"),
+            optional("confidence", "number"),
+            optional("error", "string"),
+            optional("mathml", "string"),
+            optional("asciimath", "string"),
+            optional("text", "string"),
+        ],
+    },
+    TypeSpec {
+        name: "AppStatus",
+        fields: &[
+            field("backgroundPaused", "boolean"),
+            field("power", "object"),
+            field("conserving", "boolean"),
+        ],
+    },
+];
+
+pub fn schema() -> ApiSchema {
+    ApiSchema {
+        api_version: API_VERSION,
+        commands: COMMANDS,
+        types: TYPES,
+    }
+}
+
+/// Reads a named argument; missing arguments deserialize from `null` so optional params work.
+fn arg<T: DeserializeOwned>(args: &Value, name: &str) -> Result<T> {
+    let value = args.get(name).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value)
+        .map_err(|e| AppError::InvalidRequest(format!("Invalid argument '{}': {}", name, e)))
+}
+
+fn to_value<T: Serialize>(data: T) -> Result<Value> {
+    Ok(serde_json::to_value(data)?)
+}
+
+async fn dispatch(command: &str, args: &Value) -> Result<Value> {
+    match command {
+        "translate" => to_value(crate::services::translate(arg::<TranslationRequest>(args, "request")?).await?),
+        "ocr" => to_value(crate::ocr::perform_ocr(arg::<OcrRequest>(args, "request")?).await?),
+        "ocr_with_engine" => {
+            let request = arg::<OcrRequest>(args, "request")?;
+            let engine = arg::<String>(args, "engine")?;
+            to_value(crate::ocr::perform_ocr_with_engine(request, &engine).await?)
+        }
+        "ocr_files" => {
+            let paths = arg::<Vec<String>>(args, "paths")?;
+            let engine = arg::<String>(args, "engine")?;
+            to_value(crate::ocr::ocr_files(paths, &engine, arg(args, "language")?).await)
+        }
+        "capture_and_ocr" => to_value(
+            crate::ocr::capture_and_ocr(
+                arg(args, "x")?,
+                arg(args, "y")?,
+                arg(args, "w")?,
+                arg(args, "h")?,
+                arg(args, "language")?,
+            )
+            .await?,
+        ),
+        "speak" => to_value(crate::tts::speak(arg::<TtsRequest>(args, "request")?).await?),
+        "speak_to_file" => {
+            let request = arg::<TtsRequest>(args, "request")?;
+            to_value(crate::tts::speak_to_file(request, arg(args, "path")?).await?)
+        }
+        "stop_speaking" => to_value(crate::tts::stop()?),
+        "pause_speaking" => to_value(crate::tts::playback::pause()?),
+        "resume_speaking" => to_value(crate::tts::playback::resume()?),
+        "list_tts_voices" => {
+            let include_online = arg::<Option<bool>>(args, "include_online")?.unwrap_or(false);
+            to_value(crate::tts::list_voices(include_online, arg(args, "proxy")?).await?)
+        }
+        "get_phonetic" => {
+            let text = arg::<String>(args, "text")?;
+            if crate::phonetic::is_single_english_word(&text) {
+                to_value(crate::phonetic::get_phonetic_both(&text))
+            } else {
+                Ok(Value::Null)
+            }
+        }
+        "lookup_dictionary" => {
+            let word = arg::<String>(args, "word")?;
+            let entries = crate::dictionary::lookup_word(&word).await?;
+            to_value(entries.into_iter().next())
+        }
+        "recognize_formula" => {
+            let request = arg::<MathpixRequest>(args, "request")?;
+            let config = arg::<Option<Value>>(args, "config")?;
+            to_value(crate::ocr::recognize_formula(request, config.as_ref()).await?)
+        }
+        "get_app_status" => to_value(crate::background::app_status()),
+        other => Err(AppError::InvalidRequest(format!("Unknown command: {}", other))),
+    }
+}
+
+/// Runs `command` with named JSON `args` and wraps the outcome in an envelope.
+pub async fn call(command: &str, args: Value) -> ApiEnvelope<Value> {
+    dispatch(command, &args).await.into()
+}
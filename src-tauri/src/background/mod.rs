@@ -0,0 +1,76 @@
+pub mod power;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tokio::sync::watch;
+
+/// 全局后台任务暂停开关。后台循环（区域 OCR 监视、阅读会话词典预取）在每轮迭代前调用
+/// `wait_until_resumed`，暂停期间会挂起而不是退出，恢复后继续执行
+static PAUSED: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
+
+/// 节能模式下后台轮询间隔的放大倍数
+const CONSERVE_INTERVAL_FACTOR: u32 = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackgroundStatus {
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStatus {
+    pub background_paused: bool,
+    pub power: power::PowerStatus,
+    pub conserving: bool,
+}
+
+pub fn pause() {
+    PAUSED.send_replace(true);
+    println!("Background tasks paused");
+}
+
+pub fn resume() {
+    PAUSED.send_replace(false);
+    println!("Background tasks resumed");
+}
+
+pub fn is_paused() -> bool {
+    *PAUSED.borrow()
+}
+
+pub fn status() -> BackgroundStatus {
+    BackgroundStatus { paused: is_paused() }
+}
+
+/// 暂停期间阻塞，直到 `resume` 被调用
+pub async fn wait_until_resumed() {
+    let mut rx = PAUSED.subscribe();
+    while *rx.borrow_and_update() {
+        if rx.changed().await.is_err() {
+            break;
+        }
+    }
+}
+
+pub fn app_status() -> AppStatus {
+    let power = power::detect();
+    AppStatus {
+        background_paused: is_paused(),
+        conserving: power.should_conserve(),
+        power,
+    }
+}
+
+/// 根据当前电源/网络状态调整后台轮询间隔
+pub fn adjusted_interval(base: std::time::Duration) -> std::time::Duration {
+    if power::detect().should_conserve() {
+        base * CONSERVE_INTERVAL_FACTOR
+    } else {
+        base
+    }
+}
+
+/// 节能模式下暂停自动截图监视
+pub fn auto_watch_allowed() -> bool {
+    !power::detect().should_conserve()
+}
@@ -0,0 +1,150 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 检测结果的缓存时间：翻译和监视循环会频繁查询，平台检测可能需要启动外部命令
+const DETECT_TTL: Duration = Duration::from_secs(30);
+
+static LAST_STATUS: Lazy<Mutex<Option<(Instant, PowerStatus)>>> = Lazy::new(|| Mutex::new(None));
+
+/// 网络计费与电源状态，用于决定是否进入节能模式
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    pub metered: bool,
+    pub battery_saver: bool,
+    pub on_battery: bool,
+}
+
+impl PowerStatus {
+    /// 按流量计费或开启省电模式时减少后台活动
+    pub fn should_conserve(&self) -> bool {
+        self.metered || self.battery_saver
+    }
+}
+
+/// 当前电源/网络状态，最多每 30 秒重新检测一次
+pub fn detect() -> PowerStatus {
+    let mut last = LAST_STATUS.lock().unwrap();
+    if let Some((at, status)) = last.as_ref() {
+        if at.elapsed() < DETECT_TTL {
+            return status.clone();
+        }
+    }
+    let status = detect_now();
+    *last = Some((Instant::now(), status.clone()));
+    status
+}
+
+#[cfg(target_os = "windows")]
+fn detect_now() -> PowerStatus {
+    use windows::Networking::Connectivity::{NetworkCostType, NetworkInformation};
+    use windows::System::Power::{BatteryStatus, EnergySaverStatus, PowerManager};
+
+    let metered = NetworkInformation::GetInternetConnectionProfile()
+        .and_then(|profile| profile.GetConnectionCost())
+        .map(|cost| {
+            let cost_type = cost.NetworkCostType().unwrap_or(NetworkCostType::Unknown);
+            cost_type == NetworkCostType::Fixed
+                || cost_type == NetworkCostType::Variable
+                || cost.Roaming().unwrap_or(false)
+                || cost.OverDataLimit().unwrap_or(false)
+        })
+        .unwrap_or(false);
+
+    let battery_saver = PowerManager::EnergySaverStatus()
+        .map(|status| status == EnergySaverStatus::On)
+        .unwrap_or(false);
+
+    let on_battery = PowerManager::BatteryStatus()
+        .map(|status| status == BatteryStatus::Discharging)
+        .unwrap_or(false);
+
+    PowerStatus {
+        metered,
+        battery_saver,
+        on_battery,
+    }
+}
+
+/// `/sys/class/power_supply/*` 中有电池处于放电状态
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn on_battery_from_supplies<'a>(supplies: impl IntoIterator<Item = (&'a str, &'a str)>) -> bool {
+    supplies
+        .into_iter()
+        .any(|(kind, status)| kind.trim() == "Battery" && status.trim() == "Discharging")
+}
+
+/// `nmcli -t -g GENERAL.METERED device show` 的输出：每个设备一行，`yes` 或 `yes (guessed)` 表示计费
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) fn metered_from_nmcli(output: &str) -> bool {
+    output.lines().any(|line| line.trim().starts_with("yes"))
+}
+
+/// `pmset -g batt` 的首行为 `Now drawing from 'Battery Power'` 或 `'AC Power'`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn on_battery_from_pmset(output: &str) -> bool {
+    output.lines().next().is_some_and(|line| line.contains("'Battery Power'"))
+}
+
+/// `pmset -g` 中 `lowpowermode 1` 表示开启了低电量模式
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn low_power_from_pmset(output: &str) -> bool {
+    output.lines().any(|line| {
+        let mut tokens = line.split_whitespace();
+        tokens.next() == Some("lowpowermode") && tokens.next() == Some("1")
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "linux")]
+fn detect_now() -> PowerStatus {
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).unwrap_or_default();
+    let supplies: Vec<(String, String)> = std::fs::read_dir("/sys/class/power_supply")
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| (read(entry.path().join("type")), read(entry.path().join("status"))))
+                .collect()
+        })
+        .unwrap_or_default();
+    let on_battery = on_battery_from_supplies(supplies.iter().map(|(kind, status)| (kind.as_str(), status.as_str())));
+
+    // power-profiles-daemon 的省电模式，或固件平台配置为低功耗
+    let battery_saver = command_output("powerprofilesctl", &["get"]).is_some_and(|profile| profile.trim() == "power-saver")
+        || read("/sys/firmware/acpi/platform_profile".into()).trim() == "low-power";
+
+    let metered = command_output("nmcli", &["-t", "-g", "GENERAL.METERED", "device", "show"])
+        .is_some_and(|output| metered_from_nmcli(&output));
+
+    PowerStatus {
+        metered,
+        battery_saver,
+        on_battery,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn detect_now() -> PowerStatus {
+    PowerStatus {
+        // 系统没有提供命令行可查询的计费网络状态
+        metered: false,
+        battery_saver: command_output("pmset", &["-g"]).is_some_and(|output| low_power_from_pmset(&output)),
+        on_battery: command_output("pmset", &["-g", "batt"]).is_some_and(|output| on_battery_from_pmset(&output)),
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn detect_now() -> PowerStatus {
+    PowerStatus::default()
+}
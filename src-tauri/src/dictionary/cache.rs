@@ -0,0 +1,141 @@
+//! 词典查询结果的磁盘缓存，按来源和单词索引，超出容量时淘汰最久未使用的单词。查询成功时写入
+//! （延迟在后台写盘）；来源查询失败（如离线）时返回缓存的词条并标记为过期，之前查过的词离线也能显示释义。
+
+use crate::dictionary::DictionaryEntry;
+use crate::error::Result;
+use crate::lru::Lru;
+use crate::persist::DebouncedWrite;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const CAPACITY: usize = 2000;
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedLookup {
+    #[serde(default)]
+    source: String,
+    word: String,
+    entries: Vec<DictionaryEntry>,
+    /// 写入时间（Unix 秒）
+    cached_at: i64,
+}
+
+pub(crate) struct DictionaryCache {
+    entries: Lru<CachedLookup>,
+}
+
+impl Default for DictionaryCache {
+    fn default() -> Self {
+        DictionaryCache::with_capacity(CAPACITY)
+    }
+}
+
+/// 缓存键：来源加上去掉首尾空白并转为小写的单词
+fn cache_key(source: &str, word: &str) -> String {
+    format!("{}|{}", source, word.trim().to_lowercase())
+}
+
+impl DictionaryCache {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        DictionaryCache { entries: Lru::with_capacity(capacity) }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 取出缓存的词条，均标记为过期
+    pub(crate) fn get(&mut self, source: &str, word: &str) -> Option<Vec<DictionaryEntry>> {
+        let cached = self.entries.get(&cache_key(source, word))?;
+        Some(
+            cached
+                .entries
+                .iter()
+                .cloned()
+                .map(|entry| DictionaryEntry { stale: true, ..entry })
+                .collect(),
+        )
+    }
+
+    /// 写入查询结果；空结果不缓存
+    pub(crate) fn insert(&mut self, source: &str, word: &str, entries: &[DictionaryEntry], cached_at: i64) {
+        if word.trim().is_empty() || entries.is_empty() {
+            return;
+        }
+        let entries = entries.iter().cloned().map(|entry| DictionaryEntry { stale: false, ..entry }).collect();
+        self.entries.insert(
+            cache_key(source, word),
+            CachedLookup { source: source.to_string(), word: word.trim().to_string(), entries, cached_at },
+        );
+    }
+
+    /// 按最近使用顺序重新插入，恢复 LRU 顺序
+    pub(crate) fn parse(json: &str) -> Result<Self> {
+        let mut cache = DictionaryCache::default();
+        let lookups: Vec<CachedLookup> = serde_json::from_str(json)?;
+        for cached in lookups {
+            cache.insert(&cached.source, &cached.word, &cached.entries, cached.cached_at);
+        }
+        Ok(cache)
+    }
+
+    /// 从最久未使用到最近使用导出
+    pub(crate) fn to_json(&self) -> Result<String> {
+        let lookups: Vec<&CachedLookup> = self.entries.iter().map(|(_, cached)| cached).collect();
+        Ok(serde_json::to_string(&lookups)?)
+    }
+}
+
+static CACHE: Lazy<Mutex<DictionaryCache>> = Lazy::new(|| Mutex::new(load()));
+
+static SAVE: DebouncedWrite = DebouncedWrite::new("dictionary cache", Duration::from_secs(2));
+
+fn cache_file() -> PathBuf {
+    crate::paths::data_dir().join("dictionary_cache.json")
+}
+
+fn load() -> DictionaryCache {
+    let Ok(content) = std::fs::read_to_string(cache_file()) else {
+        return DictionaryCache::default();
+    };
+    DictionaryCache::parse(&content).unwrap_or_else(|e| {
+        println!("Ignoring unreadable dictionary cache: {}", e);
+        DictionaryCache::default()
+    })
+}
+
+fn snapshot() -> Option<(PathBuf, String)> {
+    match CACHE.lock().unwrap().to_json() {
+        Ok(json) => Some((cache_file(), json)),
+        Err(e) => {
+            println!("Failed to serialize dictionary cache: {}", e);
+            None
+        }
+    }
+}
+
+pub fn get(source: &str, word: &str) -> Option<Vec<DictionaryEntry>> {
+    CACHE.lock().unwrap().get(source, word)
+}
+
+pub fn put(source: &str, word: &str, entries: &[DictionaryEntry]) {
+    CACHE.lock().unwrap().insert(source, word, entries, chrono::Utc::now().timestamp());
+    SAVE.schedule(snapshot);
+}
+
+/// 清空缓存并删除磁盘文件，返回清除的单词数
+pub fn clear() -> usize {
+    let mut cache = CACHE.lock().unwrap();
+    let count = cache.len();
+    *cache = DictionaryCache::default();
+    let path = cache_file();
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(path) {
+            println!("Failed to remove dictionary cache file: {}", e);
+        }
+    }
+    count
+}
@@ -0,0 +1,112 @@
+//! Datamuse 近义词、反义词（数据来自 WordNet），在词典来源没有提供时补充到英文词条中。
+
+use crate::dictionary::DictionaryEntry;
+use crate::error::{AppError, Result};
+use serde::Deserialize;
+
+const DATAMUSE_API_URL: &str = "https://api.datamuse.com/words";
+/// 每个词义最多补充的近义词 / 反义词数
+const MAX_RELATED: usize = 10;
+
+/// 一个相关词及其可能的词性（`n`、`v`、`adj`、`adv`）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RelatedWord {
+    pub word: String,
+    #[serde(default, rename = "tags")]
+    pub parts_of_speech: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelatedWords {
+    pub synonyms: Vec<RelatedWord>,
+    pub antonyms: Vec<RelatedWord>,
+}
+
+/// 把各来源的词性写法归一为 Datamuse 的词性标签
+fn pos_tag(part_of_speech: &str) -> Option<&'static str> {
+    match part_of_speech.trim().trim_end_matches('.').to_lowercase().as_str() {
+        "noun" | "n" => Some("n"),
+        "verb" | "v" | "vt" | "vi" => Some("v"),
+        "adjective" | "adj" | "a" => Some("adj"),
+        "adverb" | "adv" | "ad" => Some("adv"),
+        _ => None,
+    }
+}
+
+fn has_related_words(entry: &DictionaryEntry) -> bool {
+    entry.meanings.iter().any(|meaning| {
+        !meaning.synonyms.is_empty()
+            || !meaning.antonyms.is_empty()
+            || meaning.definitions.iter().any(|d| !d.synonyms.is_empty() || !d.antonyms.is_empty())
+    })
+}
+
+/// 是否有英文词条缺少近义词和反义词
+pub(crate) fn needs_related_words(word: &str, entries: &[DictionaryEntry]) -> bool {
+    super::is_english(word) && entries.iter().any(|entry| !has_related_words(entry))
+}
+
+fn matching(words: &[RelatedWord], tag: &str) -> Vec<String> {
+    words
+        .iter()
+        .filter(|w| w.parts_of_speech.iter().any(|t| t == tag))
+        .map(|w| w.word.clone())
+        .take(MAX_RELATED)
+        .collect()
+}
+
+/// 按词性把相关词补充到没有近义词、反义词的词条；只补充词性相同的词义，词性未知或对不上的词义不补充
+pub(crate) fn supplement(entries: &mut [DictionaryEntry], related: &RelatedWords) {
+    for entry in entries.iter_mut().filter(|entry| !has_related_words(entry)) {
+        for meaning in &mut entry.meanings {
+            if let Some(tag) = pos_tag(&meaning.part_of_speech) {
+                meaning.synonyms = matching(&related.synonyms, tag);
+                meaning.antonyms = matching(&related.antonyms, tag);
+            }
+        }
+    }
+}
+
+async fn query(client: &reqwest::Client, base_url: &str, relation: &str, word: &str) -> Result<Vec<RelatedWord>> {
+    let mut url = url::Url::parse(base_url).map_err(|e| AppError::Config(format!("Invalid Datamuse URL: {}", e)))?;
+    url.query_pairs_mut()
+        .append_pair(relation, word)
+        .append_pair("md", "p")
+        .append_pair("max", "30");
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Datamuse", status, error_text));
+    }
+    Ok(response.json().await?)
+}
+
+pub async fn related(word: &str) -> Result<RelatedWords> {
+    related_from(DATAMUSE_API_URL, word).await
+}
+
+/// 使用指定的接口地址查询（便于测试或代理）
+pub async fn related_from(base_url: &str, word: &str) -> Result<RelatedWords> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+    let client = crate::services::http::client(None, std::time::Duration::from_secs(5))?;
+    let (synonyms, antonyms) = tokio::try_join!(
+        query(&client, base_url, "rel_syn", word),
+        query(&client, base_url, "rel_ant", word)
+    )?;
+    Ok(RelatedWords { synonyms, antonyms })
+}
+
+/// 词条缺少近义词、反义词时从 Datamuse 补充；查询失败不影响词条
+pub async fn add_related_words(word: &str, entries: &mut [DictionaryEntry]) {
+    if !needs_related_words(word, entries) {
+        return;
+    }
+    match related(word).await {
+        Ok(related) => supplement(entries, &related),
+        Err(e) => println!("Datamuse lookup for '{}' failed: {}", word, e),
+    }
+}
@@ -0,0 +1,341 @@
+//! ECDICT 离线英汉词典：下载 ECDICT 的 CSV 并导入本地 SQLite（表结构与官方 `stardict.db` 相同，
+//! 也可以直接导入官方数据库），无网络时提供中文释义、考试标签、词频和词形变化。
+
+use crate::dictionary::level;
+use crate::dictionary::settings::DictionarySettings;
+use crate::dictionary::store::Store;
+use crate::dictionary::{stardict, Definition, DictionaryEntry, DictionarySource, Meaning};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+const ECDICT_CSV_URL: &str = "https://raw.githubusercontent.com/skywind3000/ECDICT/master/ecdict.csv";
+
+/// CSV 中导入的列，顺序与 ECDICT 表头一致
+const COLUMNS: &[&str] = &[
+    "word", "phonetic", "definition", "translation", "pos", "collins", "oxford", "tag", "bnc", "frq", "exchange",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EcdictStatus {
+    pub installed: bool,
+    pub path: String,
+    pub word_count: i64,
+}
+
+/// 词形变化，`kind` 为 `past`、`past_participle`、`present_participle`、`third_person`、
+/// `comparative`、`superlative`、`plural`、`lemma` 或 `lemma_form`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExchangeForm {
+    pub kind: String,
+    pub word: String,
+}
+
+/// 一条离线词条
+#[derive(Debug, Clone, Serialize)]
+pub struct EcdictEntry {
+    pub word: String,
+    pub phonetic: Option<String>,
+    /// 英文释义，每行一条
+    pub definition: Vec<String>,
+    /// 中文释义，每行一条，如 `n. 苹果`
+    pub translation: Vec<String>,
+    /// 考试标签：`zk`（中考）、`gk`（高考）、`cet4`、`cet6`、`ky`（考研）、`toefl`、`ielts`、`gre`
+    pub tags: Vec<String>,
+    /// 柯林斯星级（0–5）
+    pub collins: i64,
+    /// 是否为牛津 3000 核心词
+    pub oxford: bool,
+    /// 英国国家语料库词频排名，0 表示无
+    pub bnc: i64,
+    /// 当代语料库词频排名，0 表示无
+    pub frq: i64,
+    pub exchanges: Vec<ExchangeForm>,
+}
+
+impl EcdictEntry {
+    /// 转为通用的词典条目，中文释义按词性分组
+    pub fn to_entry(&self) -> DictionaryEntry {
+        let mut meanings = stardict::meanings_from_text(&self.translation.join("\n"));
+        if meanings.is_empty() && !self.definition.is_empty() {
+            meanings.push(Meaning {
+                part_of_speech: String::new(),
+                definitions: self
+                    .definition
+                    .iter()
+                    .map(|definition| Definition { definition: definition.clone(), ..Default::default() })
+                    .collect(),
+                ..Default::default()
+            });
+        }
+        DictionaryEntry {
+            word: self.word.clone(),
+            phonetic: self.phonetic.as_ref().map(|p| format!("/{}/", p)),
+            phonetics: Vec::new(),
+            meanings,
+            examples: Vec::new(),
+            level: level::from_ecdict(self),
+            stale: false,
+        }
+    }
+}
+
+static DB: Store = Store::new("ECDICT", "ecdict.db");
+
+fn db_error(e: rusqlite::Error) -> AppError {
+    DB.error(e)
+}
+
+pub fn db_path() -> PathBuf {
+    DB.path()
+}
+
+/// 解析 ECDICT 的 exchange 字段，如 `p:perceived/d:perceived/i:perceiving/3:perceives`
+pub(crate) fn parse_exchange(exchange: &str) -> Vec<ExchangeForm> {
+    exchange
+        .split('/')
+        .filter_map(|item| {
+            let (kind, word) = item.split_once(':')?;
+            let kind = match kind {
+                "p" => "past",
+                "d" => "past_participle",
+                "i" => "present_participle",
+                "3" => "third_person",
+                "r" => "comparative",
+                "t" => "superlative",
+                "s" => "plural",
+                "0" => "lemma",
+                "1" => "lemma_form",
+                _ => return None,
+            };
+            (!word.is_empty()).then(|| ExchangeForm { kind: kind.to_string(), word: word.to_string() })
+        })
+        .collect()
+}
+
+fn lines(text: Option<String>) -> Vec<String> {
+    text.unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) fn query(conn: &Connection, word: &str) -> Result<Option<EcdictEntry>> {
+    conn.query_row(
+        "SELECT word, phonetic, definition, translation, tag, collins, oxford, bnc, frq, exchange
+         FROM stardict WHERE word = ?1 COLLATE NOCASE LIMIT 1",
+        params![word],
+        |row| {
+            Ok(EcdictEntry {
+                word: row.get(0)?,
+                phonetic: row.get::<_, Option<String>>(1)?.filter(|p| !p.is_empty()),
+                definition: lines(row.get(2)?),
+                translation: lines(row.get(3)?),
+                tags: row.get::<_, Option<String>>(4)?.unwrap_or_default().split_whitespace().map(str::to_string).collect(),
+                collins: row.get::<_, Option<i64>>(5)?.unwrap_or(0),
+                oxford: row.get::<_, Option<i64>>(6)?.unwrap_or(0) != 0,
+                bnc: row.get::<_, Option<i64>>(7)?.unwrap_or(0),
+                frq: row.get::<_, Option<i64>>(8)?.unwrap_or(0),
+                exchanges: parse_exchange(&row.get::<_, Option<String>>(9)?.unwrap_or_default()),
+            })
+        },
+    )
+    .optional()
+    .map_err(db_error)
+}
+
+/// 离线查询单词；词典未安装时返回 `Config` 错误
+pub fn lookup(word: &str) -> Result<Option<EcdictEntry>> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+    DB.with(|conn| query(conn, word))?
+        .ok_or_else(|| AppError::Config("ECDICT is not installed; download it first".to_string()))
+}
+
+pub fn status() -> Result<EcdictStatus> {
+    let word_count = DB.with(|conn| {
+        conn.query_row("SELECT COUNT(*) FROM stardict", [], |row| row.get(0)).map_err(db_error)
+    })?;
+    Ok(EcdictStatus {
+        installed: word_count.is_some(),
+        path: db_path().to_string_lossy().to_string(),
+        word_count: word_count.unwrap_or(0),
+    })
+}
+
+/// 解析一条 CSV 记录（支持双引号包裹的字段和 `""` 转义），返回字段和剩余文本
+pub(crate) fn parse_csv_record(input: &str) -> Option<(Vec<String>, &str)> {
+    if input.is_empty() {
+        return None;
+    }
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek().is_some_and(|(_, next)| *next == '"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field).trim_end_matches('\r').to_string());
+                return Some((fields, &input[i + 1..]));
+            }
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim_end_matches('\r').to_string());
+    Some((fields, ""))
+}
+
+fn create_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS stardict (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            word TEXT COLLATE NOCASE NOT NULL UNIQUE,
+            phonetic TEXT,
+            definition TEXT,
+            translation TEXT,
+            pos TEXT,
+            collins INTEGER DEFAULT 0,
+            oxford INTEGER DEFAULT 0,
+            tag TEXT,
+            bnc INTEGER DEFAULT 0,
+            frq INTEGER DEFAULT 0,
+            exchange TEXT
+        )",
+    )
+    .map_err(db_error)
+}
+
+/// 把 ECDICT CSV 导入 `db` 指向的新数据库，返回导入的词条数。CSV 中的换行写作 `\n`
+pub(crate) fn import_csv(csv: &str, db: &Path) -> Result<usize> {
+    let _ = std::fs::remove_file(db);
+    let mut conn = Connection::open(db).map_err(db_error)?;
+    create_schema(&conn)?;
+
+    let (header, mut rest) = parse_csv_record(csv).ok_or_else(|| AppError::InvalidRequest("ECDICT CSV is empty".to_string()))?;
+    let positions: Vec<Option<usize>> = COLUMNS.iter().map(|column| header.iter().position(|h| h.trim() == *column)).collect();
+    if positions[0].is_none() {
+        return Err(AppError::InvalidRequest("ECDICT CSV has no word column".to_string()));
+    }
+
+    let tx = conn.transaction().map_err(db_error)?;
+    let mut count = 0;
+    {
+        let mut insert = tx
+            .prepare(&format!(
+                "INSERT OR IGNORE INTO stardict ({}) VALUES ({})",
+                COLUMNS.join(", "),
+                (1..=COLUMNS.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ")
+            ))
+            .map_err(db_error)?;
+        while let Some((record, tail)) = parse_csv_record(rest) {
+            rest = tail;
+            let values: Vec<String> = positions
+                .iter()
+                .map(|position| position.and_then(|p| record.get(p)).map(|v| v.replace("\\n", "\n")).unwrap_or_default())
+                .collect();
+            if values[0].trim().is_empty() {
+                continue;
+            }
+            count += insert.execute(rusqlite::params_from_iter(values.iter())).map_err(db_error)?;
+        }
+    }
+    tx.commit().map_err(db_error)?;
+    Ok(count)
+}
+
+/// 用新数据库替换已安装的词典
+fn install(db: &Path) -> Result<EcdictStatus> {
+    DB.replace(|path| Ok(std::fs::rename(db, path)?))?;
+    status()
+}
+
+/// 导入本地文件：ECDICT 的 CSV，或官方发布的 `stardict.db`
+pub fn import(path: &str) -> Result<EcdictStatus> {
+    let source = Path::new(path);
+    let staging = crate::paths::data_dir().join("ecdict.db.partial");
+    std::fs::create_dir_all(crate::paths::data_dir())?;
+
+    if source.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv")) {
+        let count = import_csv(&std::fs::read_to_string(source)?, &staging)?;
+        println!("ECDICT: imported {} words from {}", count, path);
+    } else {
+        Connection::open(source)
+            .map_err(db_error)?
+            .query_row("SELECT COUNT(*) FROM stardict", [], |row| row.get::<_, i64>(0))
+            .map_err(|_| AppError::InvalidRequest(format!("{} is not an ECDICT database", path)))?;
+        std::fs::copy(source, &staging)?;
+    }
+    install(&staging)
+}
+
+/// 下载 ECDICT CSV 并导入；`url` 可指定镜像地址，`proxy` 格式同服务配置中的代理设置
+pub async fn download(url: Option<String>, proxy: Option<serde_json::Value>) -> Result<EcdictStatus> {
+    let url = url.unwrap_or_else(|| ECDICT_CSV_URL.to_string());
+    let config = proxy.map(|proxy| serde_json::json!({ "proxy": proxy }));
+    let client = crate::services::http::client(config.as_ref(), std::time::Duration::from_secs(600))?;
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(AppError::Network(format!("Failed to download ECDICT: {}", response.status())));
+    }
+
+    let csv_path = crate::paths::data_dir().join("ecdict_download.csv");
+    std::fs::create_dir_all(crate::paths::data_dir())?;
+    let mut file = tokio::fs::File::create(&csv_path).await?;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        file.write_all(&chunk?).await?;
+    }
+    file.flush().await?;
+    drop(file);
+
+    let csv_file = csv_path.to_string_lossy().to_string();
+    let result = tokio::task::spawn_blocking(move || import(&csv_file))
+        .await
+        .map_err(|e| AppError::Unknown(format!("ECDICT import task failed: {}", e)))?;
+    let _ = std::fs::remove_file(&csv_path);
+    result
+}
+
+pub struct Ecdict;
+
+#[async_trait]
+impl DictionarySource for Ecdict {
+    fn name(&self) -> &'static str {
+        "ecdict"
+    }
+
+    fn offline(&self) -> bool {
+        true
+    }
+
+    fn supports(&self, word: &str) -> bool {
+        super::is_english(word)
+    }
+
+    async fn lookup(&self, word: &str, _settings: &DictionarySettings) -> Result<Vec<DictionaryEntry>> {
+        let word = word.to_string();
+        match super::blocking(move || lookup(&word)).await {
+            Ok(entry) => Ok(entry.iter().map(EcdictEntry::to_entry).collect()),
+            Err(AppError::Config(_)) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+}
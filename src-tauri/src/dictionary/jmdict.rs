@@ -0,0 +1,649 @@
+//! JMdict / JMnedict 日语词典：导入 jmdict-simplified 发布的 JSON（可为 `.gz`）到本地 SQLite，
+//! 按汉字和假名建立索引；导入时边解析边写入，不整体读入内存。查询前先对动词、形容词的活用形做还原，
+//! 划词时按最长前缀匹配。
+
+use crate::dictionary::settings::DictionarySettings;
+use crate::dictionary::store::Store;
+use crate::dictionary::{Definition, DictionaryEntry, DictionarySource, Meaning};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// 划词时最多尝试的前缀长度（字符数）
+const MAX_SCAN_LENGTH: usize = 20;
+const MAX_RESULTS: usize = 20;
+
+/// 词形类别，活用还原规则按类别串联
+const V1: u16 = 1;
+const V5: u16 = 1 << 1;
+const VS: u16 = 1 << 2;
+const VK: u16 = 1 << 3;
+const ADJ_I: u16 = 1 << 4;
+/// て形，只作为中间形态（如 `ている` → `て`）
+const TE: u16 = 1 << 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JmdictSense {
+    /// 词性代码，如 `v5k`、`adj-i`；JMnedict 中为名称类型，如 `surname`、`place`
+    pub part_of_speech: Vec<String>,
+    pub glosses: Vec<String>,
+    pub misc: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JmdictEntry {
+    pub id: i64,
+    /// `jmdict` 或 `jmnedict`
+    pub source: String,
+    pub kanji: Vec<String>,
+    pub readings: Vec<String>,
+    pub common: bool,
+    pub senses: Vec<JmdictSense>,
+}
+
+impl JmdictEntry {
+    /// 转为通用的词典条目：有汉字写法时以汉字为词头、第一个读音为音标
+    pub fn to_entry(&self) -> DictionaryEntry {
+        let word = self.kanji.first().or(self.readings.first()).cloned().unwrap_or_default();
+        DictionaryEntry {
+            word,
+            phonetic: if self.kanji.is_empty() { None } else { self.readings.first().cloned() },
+            phonetics: Vec::new(),
+            meanings: self
+                .senses
+                .iter()
+                .map(|sense| Meaning {
+                    part_of_speech: sense.part_of_speech.join(", "),
+                    definitions: vec![Definition { definition: sense.glosses.join("; "), ..Default::default() }],
+                    ..Default::default()
+                })
+                .collect(),
+            examples: Vec::new(),
+            level: None,
+            stale: false,
+        }
+    }
+
+    fn types(&self) -> u16 {
+        self.senses
+            .iter()
+            .flat_map(|sense| &sense.part_of_speech)
+            .fold(0, |types, pos| types | pos_types(pos))
+    }
+}
+
+/// 一条查询结果
+#[derive(Debug, Clone, Serialize)]
+pub struct JmdictMatch {
+    /// 选中文本中被匹配的部分
+    pub matched: String,
+    /// 还原后的辞书形
+    pub term: String,
+    /// 从辞书形到原文依次经过的活用，如 `["negative", "past"]`
+    pub inflections: Vec<String>,
+    pub entry: JmdictEntry,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JmdictStatus {
+    pub installed: bool,
+    pub path: String,
+    pub jmdict_count: i64,
+    pub jmnedict_count: i64,
+}
+
+fn pos_types(pos: &str) -> u16 {
+    match pos {
+        p if p.starts_with("v1") => V1,
+        p if p.starts_with("v5") => V5,
+        "vs" | "vs-i" | "vs-s" => VS,
+        "vk" => VK,
+        "adj-i" | "adj-ix" => ADJ_I,
+        _ => 0,
+    }
+}
+
+/// 活用还原规则：以 `from` 结尾、类别属于 `types_in` 的词形还原为以 `to` 结尾、类别为 `types_out` 的词形。
+/// `types_in` 为 0 的规则只作用于原文
+struct Rule {
+    from: String,
+    to: String,
+    types_in: u16,
+    types_out: u16,
+    reason: &'static str,
+}
+
+fn rule(from: impl Into<String>, to: impl Into<String>, types_in: u16, types_out: u16, reason: &'static str) -> Rule {
+    Rule { from: from.into(), to: to.into(), types_in, types_out, reason }
+}
+
+/// 五段动词词尾：辞书形、未然形、连用形、假定形、推量形、て形、た形
+const GODAN: &[[&str; 7]] = &[
+    ["う", "わ", "い", "え", "お", "って", "った"],
+    ["く", "か", "き", "け", "こ", "いて", "いた"],
+    ["ぐ", "が", "ぎ", "げ", "ご", "いで", "いだ"],
+    ["す", "さ", "し", "せ", "そ", "して", "した"],
+    ["つ", "た", "ち", "て", "と", "って", "った"],
+    ["ぬ", "な", "に", "ね", "の", "んで", "んだ"],
+    ["ぶ", "ば", "び", "べ", "ぼ", "んで", "んだ"],
+    ["む", "ま", "み", "め", "も", "んで", "んだ"],
+    ["る", "ら", "り", "れ", "ろ", "って", "った"],
+];
+
+const POLITE: &[(&str, &str)] = &[
+    ("ます", "polite"),
+    ("ました", "polite past"),
+    ("ません", "polite negative"),
+    ("ませんでした", "polite past negative"),
+    ("ましょう", "polite volitional"),
+];
+
+/// サ变、カ变动词的活用形（辞书形为 `する` / `くる`）
+const SURU: &[(&str, u16, &str)] = &[
+    ("しない", ADJ_I, "negative"),
+    ("した", 0, "past"),
+    ("して", TE, "te"),
+    ("したら", 0, "conditional"),
+    ("したり", 0, "tari"),
+    ("したい", ADJ_I, "want"),
+    ("できる", V1, "potential"),
+    ("される", V1, "passive"),
+    ("させる", V1, "causative"),
+    ("しよう", 0, "volitional"),
+    ("しろ", 0, "imperative"),
+    ("すれば", 0, "provisional"),
+];
+
+const KURU: &[(&str, u16, &str)] = &[
+    ("こない", ADJ_I, "negative"),
+    ("きた", 0, "past"),
+    ("きて", TE, "te"),
+    ("きたら", 0, "conditional"),
+    ("きたり", 0, "tari"),
+    ("きたい", ADJ_I, "want"),
+    ("こられる", V1, "potential or passive"),
+    ("こさせる", V1, "causative"),
+    ("こよう", 0, "volitional"),
+    ("こい", 0, "imperative"),
+    ("くれば", 0, "provisional"),
+];
+
+static RULES: Lazy<Vec<Rule>> = Lazy::new(|| {
+    let mut rules = Vec::new();
+
+    for [u, a, i, e, o, te, ta] in GODAN {
+        rules.push(rule(format!("{}ない", a), *u, ADJ_I, V5, "negative"));
+        for (suffix, reason) in POLITE {
+            rules.push(rule(format!("{}{}", i, suffix), *u, 0, V5, reason));
+        }
+        rules.push(rule(*ta, *u, 0, V5, "past"));
+        rules.push(rule(*te, *u, TE, V5, "te"));
+        rules.push(rule(format!("{}ら", ta), *u, 0, V5, "conditional"));
+        rules.push(rule(format!("{}り", ta), *u, 0, V5, "tari"));
+        rules.push(rule(format!("{}たい", i), *u, ADJ_I, V5, "want"));
+        rules.push(rule(*i, *u, 0, V5, "masu stem"));
+        rules.push(rule(format!("{}る", e), *u, V1, V5, "potential"));
+        rules.push(rule(*e, *u, 0, V5, "imperative"));
+        rules.push(rule(format!("{}ば", e), *u, 0, V5, "provisional"));
+        rules.push(rule(format!("{}れる", a), *u, V1, V5, "passive"));
+        rules.push(rule(format!("{}せる", a), *u, V1, V5, "causative"));
+        rules.push(rule(format!("{}う", o), *u, 0, V5, "volitional"));
+    }
+    // 行く的て形、た形不规则
+    for stem in ["い", "行"] {
+        rules.push(rule(format!("{}って", stem), format!("{}く", stem), TE, V5, "te"));
+        rules.push(rule(format!("{}った", stem), format!("{}く", stem), 0, V5, "past"));
+    }
+
+    for (suffix, types_in, reason) in [
+        ("ない", ADJ_I, "negative"),
+        ("た", 0, "past"),
+        ("て", TE, "te"),
+        ("たら", 0, "conditional"),
+        ("たり", 0, "tari"),
+        ("たい", ADJ_I, "want"),
+        ("", 0, "masu stem"),
+        ("られる", V1, "potential or passive"),
+        ("させる", V1, "causative"),
+        ("よう", 0, "volitional"),
+        ("ろ", 0, "imperative"),
+        ("れば", 0, "provisional"),
+    ] {
+        rules.push(rule(suffix, "る", types_in, V1, reason));
+    }
+    for (suffix, reason) in POLITE {
+        rules.push(rule(*suffix, "る", 0, V1, reason));
+    }
+
+    for (form, types_in, reason) in SURU {
+        rules.push(rule(*form, "する", *types_in, VS, reason));
+    }
+    for (suffix, reason) in POLITE {
+        rules.push(rule(format!("し{}", suffix), "する", 0, VS, reason));
+    }
+    // 名词 + する（如 勉強する）
+    rules.push(rule("する", "", VS, VS, "suru"));
+
+    for (form, types_in, reason) in KURU {
+        rules.push(rule(*form, "くる", *types_in, VK, reason));
+        let rest: String = form.chars().skip(1).collect();
+        rules.push(rule(format!("来{}", rest), "来る", *types_in, VK, reason));
+    }
+    for (suffix, reason) in POLITE {
+        rules.push(rule(format!("き{}", suffix), "くる", 0, VK, reason));
+        rules.push(rule(format!("来{}", suffix), "来る", 0, VK, reason));
+    }
+
+    for (suffix, types_in, reason) in [
+        ("くない", ADJ_I, "negative"),
+        ("かった", 0, "past"),
+        ("くて", 0, "te"),
+        ("く", 0, "adverbial"),
+        ("ければ", 0, "provisional"),
+        ("かったら", 0, "conditional"),
+        ("かろう", 0, "volitional"),
+        ("さ", 0, "noun"),
+        ("そう", 0, "seemingness"),
+    ] {
+        rules.push(rule(suffix, "い", types_in, ADJ_I, reason));
+    }
+
+    // て形后接的补助动词
+    for (suffix, te, types_in, reason) in [
+        ("ている", "て", V1, "progressive"),
+        ("でいる", "で", V1, "progressive"),
+        ("てる", "て", V1, "progressive"),
+        ("でる", "で", V1, "progressive"),
+        ("てしまう", "て", V5, "completion"),
+        ("でしまう", "で", V5, "completion"),
+        ("ちゃう", "て", V5, "completion"),
+        ("じゃう", "で", V5, "completion"),
+        ("ておく", "て", V5, "in advance"),
+        ("でおく", "で", V5, "in advance"),
+    ] {
+        rules.push(rule(suffix, te, types_in, TE, reason));
+    }
+
+    rules
+});
+
+/// 一个活用还原候选
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Deinflection {
+    pub term: String,
+    /// 候选辞书形要求的词形类别，0 表示原文（不限类别）
+    pub types: u16,
+    pub reasons: Vec<&'static str>,
+}
+
+impl Deinflection {
+    fn accepts(&self, entry: &JmdictEntry) -> bool {
+        self.types == 0 || entry.types() & self.types != 0
+    }
+}
+
+/// 列出原文及其所有可能的还原形式，还原步骤少的在前
+pub(crate) fn deinflect(text: &str) -> Vec<Deinflection> {
+    let mut results = vec![Deinflection { term: text.to_string(), types: 0, reasons: Vec::new() }];
+    let mut i = 0;
+    while i < results.len() {
+        let current = results[i].clone();
+        i += 1;
+        for rule in RULES.iter() {
+            if current.types != 0 && current.types & rule.types_in == 0 {
+                continue;
+            }
+            let Some(stem) = current.term.strip_suffix(rule.from.as_str()) else {
+                continue;
+            };
+            let term = format!("{}{}", stem, rule.to);
+            if term.is_empty() || results.iter().any(|r| r.term == term && r.types == rule.types_out) {
+                continue;
+            }
+            let mut reasons = vec![rule.reason];
+            reasons.extend(&current.reasons);
+            results.push(Deinflection { term, types: rule.types_out, reasons });
+        }
+    }
+    results
+}
+
+/// 是否含有假名。只有汉字的文本无法与中文区分，不当作日语，以免中文查询都先落到 JMdict
+pub(crate) fn is_japanese(text: &str) -> bool {
+    text.chars().any(|c| matches!(c, '\u{3040}'..='\u{309f}' | '\u{30a0}'..='\u{30ff}'))
+}
+
+#[derive(Debug, Deserialize)]
+struct SimplifiedWord {
+    id: String,
+    #[serde(default)]
+    kanji: Vec<Form>,
+    #[serde(default)]
+    kana: Vec<Form>,
+    #[serde(default)]
+    sense: Vec<SimplifiedSense>,
+    /// 只在 JMnedict 中出现
+    #[serde(default)]
+    translation: Vec<NameTranslation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Form {
+    text: String,
+    #[serde(default)]
+    common: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimplifiedSense {
+    #[serde(default)]
+    part_of_speech: Vec<String>,
+    #[serde(default)]
+    misc: Vec<String>,
+    #[serde(default)]
+    gloss: Vec<Gloss>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Gloss {
+    #[serde(default)]
+    lang: String,
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NameTranslation {
+    #[serde(default, rename = "type")]
+    kind: Vec<String>,
+    #[serde(default)]
+    translation: Vec<Gloss>,
+}
+
+/// 优先保留英文释义，没有时保留全部
+fn gloss_texts(glosses: Vec<Gloss>) -> Vec<String> {
+    let has_english = glosses.iter().any(|g| g.lang == "eng");
+    glosses
+        .into_iter()
+        .filter(|g| !has_english || g.lang == "eng")
+        .map(|g| g.text)
+        .collect()
+}
+
+fn convert(word: SimplifiedWord, source: &str) -> Option<JmdictEntry> {
+    let common = word.kanji.iter().chain(&word.kana).any(|form| form.common);
+    let senses = if source == "jmnedict" {
+        word.translation
+            .into_iter()
+            .map(|t| JmdictSense { part_of_speech: t.kind, glosses: gloss_texts(t.translation), misc: Vec::new() })
+            .collect()
+    } else {
+        // 省略词性的义项沿用前一个义项的词性
+        let mut previous_pos = Vec::new();
+        word.sense
+            .into_iter()
+            .map(|sense| {
+                if !sense.part_of_speech.is_empty() {
+                    previous_pos = sense.part_of_speech;
+                }
+                JmdictSense { part_of_speech: previous_pos.clone(), glosses: gloss_texts(sense.gloss), misc: sense.misc }
+            })
+            .collect()
+    };
+    Some(JmdictEntry {
+        id: word.id.parse().ok()?,
+        source: source.to_string(),
+        kanji: word.kanji.into_iter().map(|form| form.text).collect(),
+        readings: word.kana.into_iter().map(|form| form.text).collect(),
+        common,
+        senses,
+    })
+}
+
+/// 逐个解析 `words` 数组中的词，其余字段跳过；`on_word` 返回 false 时中止
+struct WordsVisitor<'a, F>(&'a mut F);
+
+impl<'de, F: FnMut(SimplifiedWord) -> bool> Visitor<'de> for WordsVisitor<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a jmdict-simplified JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        let mut has_words = false;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "words" {
+                map.next_value_seed(WordsVisitor(&mut *self.0))?;
+                has_words = true;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        if !has_words {
+            return Err(de::Error::missing_field("words"));
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        while let Some(word) = seq.next_element::<SimplifiedWord>()? {
+            if !(self.0)(word) {
+                return Err(de::Error::custom("import aborted"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de, F: FnMut(SimplifiedWord) -> bool> DeserializeSeed<'de> for WordsVisitor<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+/// 流式解析 jmdict-simplified 的 JSON，每解析出一个词条就交给 `on_entry`（附来源 `jmdict` / `jmnedict`），
+/// 不把整个文件读入内存。`on_entry` 出错时停止解析并返回该错误
+pub(crate) fn read_simplified(
+    reader: impl Read,
+    mut on_entry: impl FnMut(&'static str, JmdictEntry) -> Result<()>,
+) -> Result<()> {
+    let mut source = None;
+    let mut failed = None;
+    let parsed = {
+        let mut on_word = |word: SimplifiedWord| {
+            // 同一文件只含一种词典，按第一个词判断
+            let source = *source.get_or_insert(if word.translation.is_empty() { "jmdict" } else { "jmnedict" });
+            match convert(word, source).map_or(Ok(()), |entry| on_entry(source, entry)) {
+                Ok(()) => true,
+                Err(e) => {
+                    failed = Some(e);
+                    false
+                }
+            }
+        };
+        let mut deserializer = serde_json::Deserializer::from_reader(std::io::BufReader::new(reader));
+        deserializer
+            .deserialize_map(WordsVisitor(&mut on_word))
+            .and_then(|_| deserializer.end())
+    };
+    if let Some(e) = failed {
+        return Err(e);
+    }
+    parsed.map_err(|e| AppError::InvalidRequest(format!("Not a jmdict-simplified JSON file: {}", e)))
+}
+
+static DB: Store = Store::new("JMdict", "jmdict.db");
+
+fn db_error(e: rusqlite::Error) -> AppError {
+    DB.error(e)
+}
+
+pub fn db_path() -> PathBuf {
+    DB.path()
+}
+
+fn create_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS entries (
+            id INTEGER PRIMARY KEY,
+            source TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS terms (
+            term TEXT NOT NULL,
+            entry_id INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS terms_term ON terms (term);",
+    )
+    .map_err(db_error)
+}
+
+/// 写入一个词条并为它的汉字写法和读音建立索引
+fn insert_entry(conn: &Connection, source: &str, entry: &JmdictEntry) -> Result<()> {
+    conn.prepare_cached("INSERT OR REPLACE INTO entries (id, source, data) VALUES (?1, ?2, ?3)")
+        .map_err(db_error)?
+        .execute(params![entry.id, source, serde_json::to_string(entry)?])
+        .map_err(db_error)?;
+    let mut insert_term = conn.prepare_cached("INSERT INTO terms (term, entry_id) VALUES (?1, ?2)").map_err(db_error)?;
+    for term in entry.kanji.iter().chain(&entry.readings) {
+        insert_term.execute(params![term, entry.id]).map_err(db_error)?;
+    }
+    Ok(())
+}
+
+/// 边解析边写入 jmdict-simplified 的 JSON，替换同一来源之前导入的词条；返回来源和词条数
+pub(crate) fn import_from(conn: &mut Connection, reader: impl Read) -> Result<(String, usize)> {
+    create_schema(conn)?;
+    let tx = conn.transaction().map_err(db_error)?;
+    let mut imported = None;
+    let mut count = 0;
+    read_simplified(reader, |source, entry| {
+        if imported.is_none() {
+            tx.execute("DELETE FROM terms WHERE entry_id IN (SELECT id FROM entries WHERE source = ?1)", params![source])
+                .map_err(db_error)?;
+            tx.execute("DELETE FROM entries WHERE source = ?1", params![source]).map_err(db_error)?;
+            imported = Some(source);
+        }
+        insert_entry(&tx, source, &entry)?;
+        count += 1;
+        Ok(())
+    })?;
+    tx.commit().map_err(db_error)?;
+    Ok((imported.unwrap_or("jmdict").to_string(), count))
+}
+
+pub(crate) fn query(conn: &Connection, term: &str) -> Result<Vec<JmdictEntry>> {
+    let mut statement = conn
+        .prepare("SELECT e.data FROM terms t JOIN entries e ON e.id = t.entry_id WHERE t.term = ?1")
+        .map_err(db_error)?;
+    let rows = statement.query_map(params![term], |row| row.get::<_, String>(0)).map_err(db_error)?;
+    let mut entries = Vec::new();
+    for data in rows {
+        entries.push(serde_json::from_str(&data.map_err(db_error)?)?);
+    }
+    Ok(entries)
+}
+
+/// 查询一段文本开头的词：还原活用后按索引查找，从最长的前缀开始，返回第一个有结果的前缀的全部词条
+pub(crate) fn scan(conn: &Connection, text: &str) -> Result<Vec<JmdictMatch>> {
+    let chars: Vec<char> = text.trim().chars().collect();
+    for length in (1..=chars.len().min(MAX_SCAN_LENGTH)).rev() {
+        let matched: String = chars[..length].iter().collect();
+        let mut matches: Vec<JmdictMatch> = Vec::new();
+        for candidate in deinflect(&matched) {
+            for entry in query(conn, &candidate.term)? {
+                if candidate.accepts(&entry) && !matches.iter().any(|m| m.entry.id == entry.id) {
+                    matches.push(JmdictMatch {
+                        matched: matched.clone(),
+                        term: candidate.term.clone(),
+                        inflections: candidate.reasons.iter().map(|r| r.to_string()).collect(),
+                        entry,
+                    });
+                }
+            }
+        }
+        if !matches.is_empty() {
+            // 活用步骤少的在前，其次常用词在前
+            matches.sort_by_key(|m| (m.inflections.len(), !m.entry.common));
+            matches.truncate(MAX_RESULTS);
+            return Ok(matches);
+        }
+    }
+    Ok(Vec::new())
+}
+
+/// 查询日语文本；词典未导入时返回 `Config` 错误
+pub fn lookup(text: &str) -> Result<Vec<JmdictMatch>> {
+    if text.trim().is_empty() {
+        return Err(AppError::InvalidRequest("Text is empty".to_string()));
+    }
+    DB.with(|conn| scan(conn, text))?
+        .ok_or_else(|| AppError::Config("JMdict is not installed; import it first".to_string()))
+}
+
+pub fn status() -> Result<JmdictStatus> {
+    let counts = DB.with(|conn| {
+        conn.query_row(
+            "SELECT COALESCE(SUM(source = 'jmdict'), 0), COALESCE(SUM(source = 'jmnedict'), 0) FROM entries",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(db_error)
+    })?;
+    let (jmdict_count, jmnedict_count) = counts.unwrap_or((0, 0));
+    Ok(JmdictStatus {
+        installed: counts.is_some(),
+        path: db_path().to_string_lossy().to_string(),
+        jmdict_count,
+        jmnedict_count,
+    })
+}
+
+/// 导入 jmdict-simplified 的 JMdict 或 JMnedict JSON（支持 `.gz`），两者可分别导入
+pub fn import(path: &str) -> Result<JmdictStatus> {
+    let file = std::fs::File::open(path)?;
+    let reader: Box<dyn Read> = if Path::new(path).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gz")) {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+
+    let (source, count) = DB.write(|conn| import_from(conn, reader))?;
+    println!("JMdict: imported {} {} entries from {}", count, source, path);
+    status()
+}
+
+pub struct Jmdict;
+
+#[async_trait]
+impl DictionarySource for Jmdict {
+    fn name(&self) -> &'static str {
+        "jmdict"
+    }
+
+    fn offline(&self) -> bool {
+        true
+    }
+
+    fn supports(&self, word: &str) -> bool {
+        is_japanese(word)
+    }
+
+    async fn lookup(&self, word: &str, _settings: &DictionarySettings) -> Result<Vec<DictionaryEntry>> {
+        let word = word.to_string();
+        match super::blocking(move || lookup(&word)).await {
+            Ok(matches) => Ok(matches.iter().map(|m| m.entry.to_entry()).collect()),
+            Err(AppError::Config(_)) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! 词频排名与 CEFR / COCA 等级。词频取自已安装的 ECDICT（skywind3000/ECDICT，MIT 许可）：
+//! `frq` 列为当代美国英语语料库（COCA）词频顺序，`oxford` 列标记牛津 3000 核心词。
+//! ECDICT 没有 CEFR 等级，CEFR 只来自用户在数据目录放置的词表（如由 CEFR-J Wordlist 或
+//! 牛津 5000 转换而来），用户词表优先。
+//!
+//! 不内置词表：没有找到可随应用分发、来源和许可清楚的词频 / CEFR 数据，因此等级是可选功能。
+//! 新安装时 `level` 总是返回 None，下载 ECDICT 后才有词频等级，放置用户词表后才有 CEFR。
+
+use crate::dictionary::ecdict::{self, EcdictEntry};
+use crate::dictionary::DictionaryEntry;
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 数据目录中的用户词表
+const USER_TABLE_FILE: &str = "wordlevel.txt";
+/// COCA 等级按每 1000 词划分
+const COCA_BAND_SIZE: u32 = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordLevel {
+    /// 词频排名，1 为最常见
+    pub rank: u32,
+    /// CEFR 等级（A1–C2）
+    pub cefr: Option<String>,
+    /// COCA 词频等级：前 1000 词为 1，1001–2000 为 2，依此类推
+    pub coca: u32,
+    /// 是否为牛津 3000 核心词
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub oxford: bool,
+}
+
+impl WordLevel {
+    pub fn new(rank: u32, cefr: Option<String>) -> Self {
+        WordLevel { rank, cefr, coca: rank.saturating_sub(1) / COCA_BAND_SIZE + 1, oxford: false }
+    }
+}
+
+/// 一张词表：词 → (排名, CEFR)
+#[derive(Debug, Default)]
+pub(crate) struct LevelTable(HashMap<String, (u32, Option<String>)>);
+
+fn is_cefr(token: &str) -> bool {
+    matches!(token.as_bytes(), [b'A' | b'B' | b'C', b'1' | b'2'])
+}
+
+impl LevelTable {
+    /// 每行 `词 [排名] [CEFR]`，`#` 开头为注释；没有排名时按行序
+    pub(crate) fn parse(text: &str) -> Self {
+        let mut table = HashMap::new();
+        let mut next_rank = 1;
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let mut tokens = line.split_whitespace();
+            let Some(word) = tokens.next() else {
+                continue;
+            };
+            let mut rank = None;
+            let mut cefr = None;
+            for token in tokens {
+                if let Ok(value) = token.parse::<u32>() {
+                    rank = Some(value);
+                } else if is_cefr(&token.to_uppercase()) {
+                    cefr = Some(token.to_uppercase());
+                }
+            }
+            let rank = rank.unwrap_or(next_rank);
+            next_rank = rank + 1;
+            table.entry(word.to_lowercase()).or_insert((rank, cefr));
+        }
+        LevelTable(table)
+    }
+
+    /// 查询单词，查不到时尝试去掉常见的屈折词尾
+    pub(crate) fn get(&self, word: &str) -> Option<WordLevel> {
+        lemma_candidates(word).iter().find_map(|candidate| {
+            self.0.get(candidate).map(|(rank, cefr)| WordLevel::new(*rank, cefr.clone()))
+        })
+    }
+}
+
+fn lemma_candidates(word: &str) -> Vec<String> {
+    let word = word.trim().to_lowercase();
+    let mut candidates = vec![word.clone()];
+    for (suffix, replacement) in [("ies", "y"), ("es", ""), ("s", ""), ("ied", "y"), ("ed", ""), ("ed", "e"), ("ing", ""), ("ing", "e")] {
+        if let Some(stem) = word.strip_suffix(suffix).filter(|stem| stem.len() > 1) {
+            candidates.push(format!("{}{}", stem, replacement));
+        }
+    }
+    candidates
+}
+
+static USER_TABLE: Lazy<LevelTable> = Lazy::new(|| {
+    LevelTable::parse(&std::fs::read_to_string(crate::paths::data_dir().join(USER_TABLE_FILE)).unwrap_or_default())
+});
+
+/// ECDICT 词条的 COCA 词频排名，CEFR 等级取自用户词表；没有词频时返回 None
+pub(crate) fn from_ecdict(entry: &EcdictEntry) -> Option<WordLevel> {
+    let rank = u32::try_from(entry.frq).ok().filter(|&frq| frq > 0)?;
+    let cefr = USER_TABLE.get(&entry.word).and_then(|level| level.cefr);
+    Some(WordLevel { oxford: entry.oxford, ..WordLevel::new(rank, cefr) })
+}
+
+/// 查询等级：先查用户词表，再查 ECDICT。会读取数据库，不要在异步任务中直接调用
+pub fn level(word: &str) -> Option<WordLevel> {
+    USER_TABLE.get(word).or_else(|| ecdict_level(word, ecdict::lookup))
+}
+
+/// 用 `lookup` 在 ECDICT 中查询；该词没有收录时才尝试去掉屈折词尾，
+/// 收录但没有词频时返回 None（如不会让 `news` 取 `new` 的等级）
+pub(crate) fn ecdict_level(word: &str, lookup: impl Fn(&str) -> Result<Option<EcdictEntry>>) -> Option<WordLevel> {
+    for candidate in lemma_candidates(word) {
+        match lookup(&candidate) {
+            Ok(Some(entry)) => return from_ecdict(&entry),
+            Ok(None) => {}
+            // 未安装 ECDICT
+            Err(AppError::Config(_)) => return None,
+            Err(e) => {
+                println!("Word level lookup for '{}' failed: {}", candidate, e);
+                return None;
+            }
+        }
+    }
+    None
+}
+
+/// 为还没有等级信息的词条补充词频和等级
+pub async fn annotate(entries: &mut [DictionaryEntry]) {
+    let words: Vec<String> = entries.iter().filter(|entry| entry.level.is_none()).map(|entry| entry.word.clone()).collect();
+    if words.is_empty() {
+        return;
+    }
+    let levels = super::blocking(move || Ok(words.iter().map(|word| level(word)).collect::<Vec<_>>()))
+        .await
+        .unwrap_or_default();
+    for (entry, level) in entries.iter_mut().filter(|entry| entry.level.is_none()).zip(levels) {
+        entry.level = level;
+    }
+}
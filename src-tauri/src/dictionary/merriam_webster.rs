@@ -0,0 +1,206 @@
+//! Merriam-Webster 词典（Collegiate 或 Learner's），需要用户在 dictionaryapi.com 申请的密钥。
+//! 返回简明释义和带发音音频地址的音标。
+
+use crate::dictionary::settings::DictionarySettings;
+use crate::dictionary::{Definition, DictionaryEntry, DictionarySource, Meaning, Phonetic};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+const MERRIAM_WEBSTER_API_URL: &str = "https://www.dictionaryapi.com/api/v3/references";
+const AUDIO_URL: &str = "https://media.merriam-webster.com/audio/prons/en/us/mp3";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MerriamWebsterDictionary {
+    #[default]
+    Collegiate,
+    Learners,
+}
+
+impl MerriamWebsterDictionary {
+    fn reference(self) -> &'static str {
+        match self {
+            MerriamWebsterDictionary::Collegiate => "collegiate",
+            MerriamWebsterDictionary::Learners => "learners",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MerriamWebsterConfig {
+    /// 所选词典对应的密钥（Collegiate 与 Learner's 的密钥不通用）
+    pub api_key: String,
+    #[serde(default)]
+    pub dictionary: MerriamWebsterDictionary,
+    /// 自定义接口地址（便于测试或代理）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
+impl MerriamWebsterConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.api_key.trim().is_empty() {
+            return Err(AppError::Config("Merriam-Webster API key is required".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Entry {
+    meta: Meta,
+    #[serde(default)]
+    hwi: Option<Headword>,
+    #[serde(default)]
+    fl: Option<String>,
+    #[serde(default)]
+    shortdef: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Meta {
+    id: String,
+    #[serde(default)]
+    stems: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Headword {
+    hw: String,
+    #[serde(default)]
+    prs: Vec<Pronunciation>,
+}
+
+/// Collegiate 的音标在 `mw` 中，Learner's 在 `ipa` 中
+#[derive(Debug, Deserialize)]
+struct Pronunciation {
+    #[serde(default)]
+    mw: Option<String>,
+    #[serde(default)]
+    ipa: Option<String>,
+    #[serde(default)]
+    sound: Option<Sound>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sound {
+    audio: String,
+}
+
+/// 音频文件地址：子目录由文件名决定（`bix`、`gg` 开头、数字或标点开头为 `number`，否则为首字母）
+pub(crate) fn audio_url(audio: &str) -> String {
+    let subdirectory = if audio.starts_with("bix") {
+        "bix".to_string()
+    } else if audio.starts_with("gg") {
+        "gg".to_string()
+    } else {
+        match audio.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => c.to_ascii_lowercase().to_string(),
+            _ => "number".to_string(),
+        }
+    };
+    format!("{}/{}/{}.mp3", AUDIO_URL, subdirectory, audio)
+}
+
+impl From<Entry> for DictionaryEntry {
+    fn from(entry: Entry) -> Self {
+        let (word, prs) = match entry.hwi {
+            // 词头中的 `*` 是音节分隔符
+            Some(hwi) => (hwi.hw.replace('*', ""), hwi.prs),
+            None => (entry.meta.id.split(':').next().unwrap_or_default().to_string(), Vec::new()),
+        };
+        let phonetics: Vec<Phonetic> = prs
+            .into_iter()
+            .map(|pr| Phonetic {
+                text: pr.mw.or(pr.ipa).map(|text| format!("/{}/", text)),
+                audio: pr.sound.map(|sound| audio_url(&sound.audio)),
+                source_url: None,
+                license: None,
+            })
+            .collect();
+        DictionaryEntry {
+            word,
+            phonetic: phonetics.iter().find_map(|p| p.text.clone()),
+            phonetics,
+            meanings: vec![Meaning {
+                part_of_speech: entry.fl.unwrap_or_default(),
+                definitions: entry
+                    .shortdef
+                    .into_iter()
+                    .map(|definition| Definition { definition, ..Default::default() })
+                    .collect(),
+                ..Default::default()
+            }],
+            examples: Vec::new(),
+            level: None,
+            stale: false,
+        }
+    }
+}
+
+/// 解析接口响应。查不到单词时接口返回拼写建议（字符串数组），此时返回空列表；
+/// 只保留词形（stems）包含该词的条目，没有时保留全部
+pub(crate) fn parse_response(word: &str, json: serde_json::Value) -> Result<Vec<DictionaryEntry>> {
+    if json.as_array().is_some_and(|items| items.iter().all(|item| item.is_string())) {
+        return Ok(Vec::new());
+    }
+    let entries: Vec<Entry> = serde_json::from_value(json)?;
+    let matches_word = |entry: &Entry| entry.meta.stems.iter().any(|stem| stem.eq_ignore_ascii_case(word));
+    let keep_all = !entries.iter().any(matches_word);
+    Ok(entries
+        .into_iter()
+        .filter(|entry| keep_all || matches_word(entry))
+        .filter(|entry| !entry.shortdef.is_empty())
+        .map(DictionaryEntry::from)
+        .collect())
+}
+
+pub async fn lookup(word: &str, config: &MerriamWebsterConfig) -> Result<Vec<DictionaryEntry>> {
+    config.validate()?;
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+
+    let base_url = config.api_url.as_deref().unwrap_or(MERRIAM_WEBSTER_API_URL);
+    let mut url = url::Url::parse(base_url).map_err(|e| AppError::Config(format!("Invalid Merriam-Webster URL: {}", e)))?;
+    url.path_segments_mut()
+        .map_err(|_| AppError::Config("Invalid Merriam-Webster URL".to_string()))?
+        .pop_if_empty()
+        .extend([config.dictionary.reference(), "json", word]);
+    url.query_pairs_mut().append_pair("key", config.api_key.trim());
+
+    let client = crate::services::http::client(None, std::time::Duration::from_secs(10))?;
+    let response = client.get(url).send().await?;
+    let status = response.status();
+    let body = response.text().await?;
+    // 密钥无效时接口返回 200 和纯文本说明
+    if body.starts_with("Invalid API key") || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(AppError::AuthFailed { service: "Merriam-Webster".to_string() });
+    }
+    if !status.is_success() {
+        return Err(AppError::from_status("Merriam-Webster", status, body));
+    }
+    parse_response(word, serde_json::from_str(&body)?)
+}
+
+pub struct MerriamWebster;
+
+#[async_trait]
+impl DictionarySource for MerriamWebster {
+    fn name(&self) -> &'static str {
+        "merriam_webster"
+    }
+
+    fn supports(&self, word: &str) -> bool {
+        super::is_english(word)
+    }
+
+    async fn lookup(&self, word: &str, settings: &DictionarySettings) -> Result<Vec<DictionaryEntry>> {
+        match &settings.merriam_webster {
+            Some(config) => lookup(word, config).await,
+            None => Ok(Vec::new()),
+        }
+    }
+}
@@ -1,5 +1,25 @@
+pub mod cache;
+pub mod datamuse;
+pub mod ecdict;
+pub mod jmdict;
+pub mod level;
+pub mod merriam_webster;
+pub mod quick;
+pub mod settings;
+pub mod stardict;
+pub(crate) mod store;
+pub mod tatoeba;
+pub mod wiktionary;
+pub mod youdao;
+
 use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DictionaryEntry {
@@ -7,6 +27,15 @@ pub struct DictionaryEntry {
     pub phonetic: Option<String>,
     pub phonetics: Vec<Phonetic>,
     pub meanings: Vec<Meaning>,
+    /// 不属于具体义项的例句，如有道的双语例句
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+    /// 词频排名与 CEFR / COCA 等级
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<level::WordLevel>,
+    /// 来自离线缓存（查询失败时返回的旧结果）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stale: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,27 +52,279 @@ pub struct License {
     pub url: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Meaning {
     #[serde(rename = "partOfSpeech")]
     pub part_of_speech: String,
     pub definitions: Vec<Definition>,
+    #[serde(default)]
+    pub synonyms: Vec<String>,
+    #[serde(default)]
+    pub antonyms: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Definition {
     pub definition: String,
     pub example: Option<String>,
+    #[serde(default)]
+    pub synonyms: Vec<String>,
+    #[serde(default)]
+    pub antonyms: Vec<String>,
+}
+
+const FREE_DICTIONARY_API_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries/en";
+
+/// 单个来源的最长等待时间，超时按查询失败处理
+const SOURCE_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// 一个来源的查询结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceEntries {
+    pub source: String,
+    pub entries: Vec<DictionaryEntry>,
+    /// 查询失败时的错误信息；有缓存时 `entries` 为标记过期的缓存结果
+    pub error: Option<String>,
+}
+
+#[async_trait]
+pub trait DictionarySource: Send + Sync {
+    /// 词典设置中使用的来源名
+    fn name(&self) -> &'static str;
+
+    /// 能否查询这段文本，不能时直接跳过（如 JMdict 只查日语）
+    fn supports(&self, _word: &str) -> bool {
+        true
+    }
+
+    /// 是否只查询本地数据、不访问网络
+    fn offline(&self) -> bool {
+        false
+    }
+
+    /// 未配置的来源（未安装离线词典、没有密钥）返回空列表
+    async fn lookup(&self, word: &str, settings: &settings::DictionarySettings) -> Result<Vec<DictionaryEntry>>;
+}
+
+pub struct FreeDictionary;
+
+#[async_trait]
+impl DictionarySource for FreeDictionary {
+    fn name(&self) -> &'static str {
+        "free_dictionary"
+    }
+
+    fn supports(&self, word: &str) -> bool {
+        is_english(word)
+    }
+
+    async fn lookup(&self, word: &str, _settings: &settings::DictionarySettings) -> Result<Vec<DictionaryEntry>> {
+        lookup_word_from(FREE_DICTIONARY_API_URL, word).await
+    }
+}
+
+/// 所有来源，顺序即默认优先级
+static REGISTRY: Lazy<Vec<Arc<dyn DictionarySource>>> = Lazy::new(|| {
+    vec![
+        Arc::new(stardict::StarDict),
+        Arc::new(jmdict::Jmdict),
+        Arc::new(FreeDictionary),
+        Arc::new(wiktionary::Wiktionary),
+        Arc::new(merriam_webster::MerriamWebster),
+        Arc::new(youdao::Youdao),
+        Arc::new(ecdict::Ecdict),
+    ]
+});
+
+pub fn find(name: &str) -> Option<Arc<dyn DictionarySource>> {
+    REGISTRY.iter().find(|source| source.name() == name).cloned()
+}
+
+pub fn source_names() -> Vec<&'static str> {
+    REGISTRY.iter().map(|source| source.name()).collect()
+}
+
+/// 英文词典只查含拉丁字母的文本
+pub(crate) fn is_english(word: &str) -> bool {
+    word.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// 在阻塞线程池中查询本地词典，避免并发查询时阻塞其他来源
+pub(crate) async fn blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::Unknown(format!("Dictionary lookup task failed: {}", e)))?
+}
+
+/// 启用且支持该文本的来源，按优先级排列
+fn active_sources(word: &str, settings: &settings::DictionarySettings) -> Vec<Arc<dyn DictionarySource>> {
+    settings
+        .enabled_sources()
+        .into_iter()
+        .filter_map(find)
+        .filter(|source| source.supports(word))
+        .collect()
+}
+
+/// 查询一个来源，超过 `timeout` 未返回时按失败处理
+async fn query_source(
+    source: &dyn DictionarySource,
+    word: &str,
+    settings: &settings::DictionarySettings,
+    timeout: Duration,
+) -> Result<Vec<DictionaryEntry>> {
+    tokio::time::timeout(timeout, source.lookup(word, settings))
+        .await
+        .unwrap_or_else(|_| Err(AppError::Timeout(format!("Dictionary source {} did not respond", source.name()))))
+}
+
+/// 并发查询所有来源，结果按传入顺序排列
+pub(crate) async fn query_all(
+    sources: &[Arc<dyn DictionarySource>],
+    word: &str,
+    settings: &settings::DictionarySettings,
+    timeout: Duration,
+) -> Vec<(&'static str, Result<Vec<DictionaryEntry>>)> {
+    let results = join_all(sources.iter().map(|source| query_source(source.as_ref(), word, settings, timeout))).await;
+    sources.iter().map(|source| source.name()).zip(results).collect()
+}
+
+/// 并发查询所有来源，返回按传入顺序第一个有结果的来源；一旦它之前的来源都已返回就不再等待其余来源。
+/// 都没有结果时返回最后一个错误
+pub(crate) async fn query_first(
+    sources: &[Arc<dyn DictionarySource>],
+    word: &str,
+    settings: &settings::DictionarySettings,
+    timeout: Duration,
+) -> Result<Option<(&'static str, Vec<DictionaryEntry>)>> {
+    let mut pending: FuturesUnordered<_> = sources
+        .iter()
+        .enumerate()
+        .map(|(i, source)| async move { (i, query_source(source.as_ref(), word, settings, timeout).await) })
+        .collect();
+    let mut results: Vec<Option<Result<Vec<DictionaryEntry>>>> = sources.iter().map(|_| None).collect();
+    let mut next = 0;
+    let mut last_error = None;
+    while let Some((i, result)) = pending.next().await {
+        results[i] = Some(result);
+        // 按优先级处理已返回的来源，遇到仍在查询的来源时继续等待
+        while let Some(result) = results.get_mut(next).and_then(Option::take) {
+            let source = sources[next].name();
+            next += 1;
+            match result {
+                Ok(entries) if !entries.is_empty() => return Ok(Some((source, entries))),
+                Ok(_) => {}
+                Err(e) => {
+                    println!("Dictionary source {} failed for '{}': {}", source, word, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+    }
+    last_error.map_or(Ok(None), Err)
 }
 
-/// 查询 Free Dictionary API
+/// 按来源分组返回所有启用来源的结果（缺少近义词时从 Datamuse 补充）；既没有结果也没有出错的来源不列出
+pub async fn lookup_grouped(word: &str) -> Result<Vec<SourceEntries>> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+    let settings = settings::settings();
+    Ok(group_sources(&active_sources(word, &settings), word, &settings, SOURCE_TIMEOUT).await)
+}
+
+/// 分组查询给定的来源并写入缓存；查询失败的来源返回缓存中标记为过期的条目（保留错误信息）
+pub(crate) async fn group_sources(
+    sources: &[Arc<dyn DictionarySource>],
+    word: &str,
+    settings: &settings::DictionarySettings,
+    timeout: Duration,
+) -> Vec<SourceEntries> {
+    let mut groups: Vec<SourceEntries> = query_all(sources, word, settings, timeout)
+        .await
+        .into_iter()
+        .filter_map(|(source, result)| match result {
+            Ok(entries) if entries.is_empty() => None,
+            Ok(entries) => Some(SourceEntries { source: source.to_string(), entries, error: None }),
+            Err(e) => {
+                println!("Dictionary source {} failed for '{}': {}", source, word, e);
+                let entries = cache::get(source, word).unwrap_or_default();
+                Some(SourceEntries { source: source.to_string(), entries, error: Some(e.to_string()) })
+            }
+        })
+        .collect();
+
+    // 所有来源共用一次近义词查询
+    let fresh = |group: &SourceEntries| group.error.is_none();
+    if groups.iter().filter(|group| fresh(group)).any(|group| datamuse::needs_related_words(word, &group.entries)) {
+        match datamuse::related(word).await {
+            Ok(related) => groups
+                .iter_mut()
+                .filter(|group| fresh(group))
+                .for_each(|group| datamuse::supplement(&mut group.entries, &related)),
+            Err(e) => println!("Datamuse lookup for '{}' failed: {}", word, e),
+        }
+    }
+    for group in groups.iter_mut().filter(|group| fresh(group)) {
+        level::annotate(&mut group.entries).await;
+        cache::put(&group.source, word, &group.entries);
+    }
+    groups
+}
+
+/// 返回优先级最高且有结果的来源的条目（缺少近义词时从 Datamuse 补充），并写入磁盘缓存；
+/// 全部失败时返回缓存中标记为过期的条目，没有缓存时返回最后一个错误
 pub async fn lookup_word(word: &str) -> Result<Vec<DictionaryEntry>> {
-    let url = format!("https://api.dictionaryapi.dev/api/v2/entries/en/{}", word);
+    let settings = settings::settings();
+    let sources = active_sources(word.trim(), &settings);
+    lookup_sources(&sources, word.trim(), &settings, SOURCE_TIMEOUT).await
+}
+
+/// 按优先级查询给定的来源，见 [`lookup_word`]
+pub(crate) async fn lookup_sources(
+    sources: &[Arc<dyn DictionarySource>],
+    word: &str,
+    settings: &settings::DictionarySettings,
+    timeout: Duration,
+) -> Result<Vec<DictionaryEntry>> {
+    match query_first(sources, word, settings, timeout).await {
+        Ok(Some((source, mut entries))) => {
+            datamuse::add_related_words(word, &mut entries).await;
+            level::annotate(&mut entries).await;
+            cache::put(source, word, &entries);
+            Ok(entries)
+        }
+        Ok(None) => Ok(Vec::new()),
+        // 所有来源都失败（如离线）时按优先级返回之前缓存的结果
+        Err(e) => match sources.iter().find_map(|source| cache::get(source.name(), word)) {
+            Some(entries) => {
+                println!("Serving cached dictionary entries for '{}'", word);
+                Ok(entries)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// 只查询启用的离线来源，返回优先级最高且有结果的来源的条目；不访问网络，也不写入缓存
+pub async fn lookup_offline(word: &str) -> Result<Vec<DictionaryEntry>> {
+    let settings = settings::settings();
+    let sources: Vec<_> = active_sources(word.trim(), &settings).into_iter().filter(|source| source.offline()).collect();
+    let Some((_, mut entries)) = query_first(&sources, word.trim(), &settings, SOURCE_TIMEOUT).await? else {
+        return Ok(Vec::new());
+    };
+    level::annotate(&mut entries).await;
+    Ok(entries)
+}
+
+/// 使用指定的 API 地址查询（便于测试或自建镜像）
+pub async fn lookup_word_from(base_url: &str, word: &str) -> Result<Vec<DictionaryEntry>> {
+    let url = format!("{}/{}", base_url.trim_end_matches('/'), word);
     
-    let client = reqwest::Client::new();
+    let client = crate::services::http::client(None, std::time::Duration::from_secs(10))?;
     let response = client
         .get(&url)
-        .timeout(std::time::Duration::from_secs(10))
         .send()
         .await
         .map_err(|e| AppError::Network(format!("Failed to fetch dictionary: {}", e)))?;
@@ -116,3 +397,30 @@ pub fn get_audio_url(entry: &DictionaryEntry, accent: &str) -> Option<String> {
         p.audio.as_ref().filter(|a| !a.is_empty()).cloned()
     })
 }
+
+/// 去掉 HTML / Pango / XDXF 标签，换行类标签转为换行，并还原常见实体。本地词典和 Wiktionary 的释义共用
+pub(crate) fn strip_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            out.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim_start_matches('/').to_ascii_lowercase();
+        let name = tag.split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or_default();
+        if matches!(name, "br" | "p" | "div" | "li" | "tr" | "def" | "blockquote") {
+            out.push('\n');
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,152 @@
+use crate::error::{AppError, Result};
+use crate::phonetic::{self, PhoneticResult};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 悬停/划词查询的总时间预算
+const LOOKUP_BUDGET: Duration = Duration::from_millis(300);
+/// 超过该时长的缓存仍会返回，但会在后台刷新
+const FRESH_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const CACHE_CAPACITY: usize = 2000;
+const DEFAULT_SERVICE: &str = "GoogleFree";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuickSource {
+    Cache,
+    Offline,
+    Provider,
+    /// 预算内未返回，结果将在后台写入缓存
+    Pending,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickLookupResult {
+    pub word: String,
+    pub target_lang: String,
+    pub translation: Option<String>,
+    pub phonetic: Option<PhoneticResult>,
+    pub source: QuickSource,
+    pub stale: bool,
+}
+
+struct CacheEntry {
+    translation: String,
+    fetched_at: Instant,
+}
+
+static CACHE: Lazy<Mutex<HashMap<(String, String), CacheEntry>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn cache_get(key: &(String, String)) -> Option<(String, bool)> {
+    let cache = CACHE.lock().unwrap();
+    cache
+        .get(key)
+        .map(|entry| (entry.translation.clone(), entry.fetched_at.elapsed() > FRESH_TTL))
+}
+
+fn cache_put(key: (String, String), translation: String) {
+    let mut cache = CACHE.lock().unwrap();
+    if cache.len() >= CACHE_CAPACITY && !cache.contains_key(&key) {
+        if let Some(oldest) = cache
+            .iter()
+            .min_by_key(|(_, entry)| entry.fetched_at)
+            .map(|(key, _)| key.clone())
+        {
+            cache.remove(&oldest);
+        }
+    }
+    cache.insert(key, CacheEntry { translation, fetched_at: Instant::now() });
+}
+
+async fn fetch(word: String, target_lang: String, service: String, config: Option<serde_json::Value>) -> Result<String> {
+    let provider = crate::translate::find(&service)
+        .ok_or_else(|| AppError::InvalidRequest(format!("Unknown service: {}", service)))?;
+    let result = provider.translate(&word, "auto", &target_lang, config.as_ref()).await?;
+    let translation = result.text.trim().to_string();
+    cache_put((word.to_lowercase(), target_lang), translation.clone());
+    Ok(translation)
+}
+
+/// 在后台查询并写入缓存；调用方可以等待返回的任务，也可以不等，任务都会完成
+fn spawn_fetch(
+    word: String,
+    target_lang: String,
+    service: String,
+    config: Option<serde_json::Value>,
+) -> tauri::async_runtime::JoinHandle<Result<String>> {
+    tauri::async_runtime::spawn(async move {
+        let result = fetch(word.clone(), target_lang, service, config).await;
+        if let Err(e) = &result {
+            println!("Quick lookup for '{}' failed: {}", word, e);
+        }
+        result
+    })
+}
+
+/// 单词快速查询：缓存 → 离线音标表 → 单个快速服务，总耗时不超过 300ms
+pub async fn quick_lookup(
+    word: &str,
+    target_lang: &str,
+    service: Option<String>,
+    config: Option<serde_json::Value>,
+) -> Result<QuickLookupResult> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+
+    let started = Instant::now();
+    let service = service.unwrap_or_else(|| DEFAULT_SERVICE.to_string());
+    let key = (word.to_lowercase(), target_lang.to_string());
+    let phonetic = phonetic::get_phonetic_both(word);
+
+    let mut result = QuickLookupResult {
+        word: word.to_string(),
+        target_lang: target_lang.to_string(),
+        translation: None,
+        phonetic,
+        source: QuickSource::Offline,
+        stale: false,
+    };
+
+    if let Some((translation, stale)) = cache_get(&key) {
+        if stale {
+            spawn_fetch(word.to_string(), target_lang.to_string(), service, config);
+        }
+        result.translation = Some(translation);
+        result.source = QuickSource::Cache;
+        result.stale = stale;
+        return Ok(result);
+    }
+
+    let remaining = LOOKUP_BUDGET.saturating_sub(started.elapsed());
+    let lookup = spawn_fetch(word.to_string(), target_lang.to_string(), service, config);
+
+    // 超时只是不再等待，任务继续运行并把结果写入缓存，不会重复请求
+    match tokio::time::timeout(remaining, lookup).await {
+        Ok(Ok(Ok(translation))) => {
+            result.translation = Some(translation);
+            result.source = QuickSource::Provider;
+        }
+        Ok(Ok(Err(e))) => {
+            if result.phonetic.is_none() {
+                return Err(e);
+            }
+        }
+        Ok(Err(e)) => {
+            if result.phonetic.is_none() {
+                return Err(AppError::Unknown(format!("Quick lookup task failed: {}", e)));
+            }
+        }
+        Err(_) => {
+            // 超出预算：先返回已有的离线数据，结果在后台补齐
+            result.source = QuickSource::Pending;
+        }
+    }
+
+    Ok(result)
+}
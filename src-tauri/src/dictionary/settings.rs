@@ -0,0 +1,158 @@
+//! 词典设置：各来源是否启用及优先级（并发查询，结果按优先级排列），以及需要密钥的来源的配置。
+//! 来源设置保存在数据目录的 `dictionary_settings.json` 中；Merriam-Webster 的密钥与翻译服务的密钥
+//! 一样保存在前端的设置存储（`settings.json`）里，启动时由 `load_api_keys` 读回。
+
+use crate::dictionary::merriam_webster::MerriamWebsterConfig;
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+/// Merriam-Webster 配置在前端设置存储中的键
+const MERRIAM_WEBSTER_KEY: &str = "merriamWebster";
+
+/// 默认启用的来源：本地 StarDict 词典优先，日语文本查 JMdict，在线查询失败时回退到离线 ECDICT
+const ENABLED_BY_DEFAULT: &[&str] = &["stardict", "jmdict", "free_dictionary", "ecdict"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceSettings {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 数值小的优先；相同时按列表顺序
+    #[serde(default)]
+    pub priority: i32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictionarySettings {
+    /// 未列出的来源视为停用
+    #[serde(default = "default_sources")]
+    pub sources: Vec<SourceSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merriam_webster: Option<MerriamWebsterConfig>,
+}
+
+fn default_sources() -> Vec<SourceSettings> {
+    super::source_names()
+        .into_iter()
+        .enumerate()
+        .map(|(priority, name)| SourceSettings {
+            name: name.to_string(),
+            enabled: ENABLED_BY_DEFAULT.contains(&name),
+            priority: priority as i32,
+        })
+        .collect()
+}
+
+impl Default for DictionarySettings {
+    fn default() -> Self {
+        DictionarySettings { sources: default_sources(), merriam_webster: None }
+    }
+}
+
+impl DictionarySettings {
+    pub fn validate(&self) -> Result<()> {
+        let names = super::source_names();
+        for (i, source) in self.sources.iter().enumerate() {
+            if !names.contains(&source.name.as_str()) {
+                return Err(AppError::InvalidRequest(format!(
+                    "Unknown dictionary source: {} (expected one of {})",
+                    source.name,
+                    names.join(", ")
+                )));
+            }
+            if self.sources[..i].iter().any(|s| s.name == source.name) {
+                return Err(AppError::InvalidRequest(format!("Duplicate dictionary source: {}", source.name)));
+            }
+        }
+        if !self.sources.iter().any(|s| s.enabled) {
+            return Err(AppError::InvalidRequest("At least one dictionary source must be enabled".to_string()));
+        }
+        if let Some(config) = &self.merriam_webster {
+            config.validate()?;
+        }
+        Ok(())
+    }
+
+    /// 启用的来源名，按优先级排列
+    pub fn enabled_sources(&self) -> Vec<&str> {
+        let mut sources: Vec<&SourceSettings> = self.sources.iter().filter(|s| s.enabled).collect();
+        sources.sort_by_key(|s| s.priority);
+        sources.into_iter().map(|s| s.name.as_str()).collect()
+    }
+}
+
+/// None 表示尚未从磁盘加载
+static SETTINGS: Lazy<Mutex<Option<DictionarySettings>>> = Lazy::new(|| Mutex::new(None));
+
+fn settings_file() -> PathBuf {
+    crate::paths::data_dir().join("dictionary_settings.json")
+}
+
+/// 读取保存的来源设置；文件不存在或已失效（如来源被移除）时使用默认设置
+fn load() -> DictionarySettings {
+    std::fs::read_to_string(settings_file())
+        .ok()
+        .and_then(|content| serde_json::from_str::<DictionarySettings>(&content).ok())
+        .filter(|settings| settings.validate().is_ok())
+        .unwrap_or_default()
+}
+
+/// 写入来源设置；密钥不写入数据目录
+fn write(settings: &DictionarySettings) -> Result<()> {
+    let path = settings_file();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let saved = DictionarySettings { merriam_webster: None, ..settings.clone() };
+    std::fs::write(path, serde_json::to_string_pretty(&saved)?)?;
+    Ok(())
+}
+
+pub fn settings() -> DictionarySettings {
+    SETTINGS.lock().unwrap().get_or_insert_with(load).clone()
+}
+
+/// 校验并保存设置；密钥由调用方通过 `save_api_keys` 写入设置存储
+pub fn set_settings(settings: DictionarySettings) -> Result<()> {
+    settings.validate()?;
+    let mut guard = SETTINGS.lock().unwrap();
+    write(&settings)?;
+    *guard = Some(settings);
+    Ok(())
+}
+
+/// 把 Merriam-Webster 配置写入前端的设置存储；未配置时删除
+pub fn save_api_keys<R: Runtime>(app: &AppHandle<R>, settings: &DictionarySettings) -> Result<()> {
+    let store = app
+        .store(crate::paths::SETTINGS_STORE)
+        .map_err(|e| AppError::Config(format!("Failed to open settings store: {}", e)))?;
+    match &settings.merriam_webster {
+        Some(config) => store.set(MERRIAM_WEBSTER_KEY, serde_json::to_value(config)?),
+        None => {
+            store.delete(MERRIAM_WEBSTER_KEY);
+        }
+    }
+    store
+        .save()
+        .map_err(|e| AppError::Config(format!("Failed to save settings store: {}", e)))
+}
+
+/// 启动时从前端的设置存储读回 Merriam-Webster 配置
+pub fn load_api_keys<R: Runtime>(app: &AppHandle<R>) {
+    let config = app
+        .store(crate::paths::SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(MERRIAM_WEBSTER_KEY))
+        .and_then(|value| serde_json::from_value::<MerriamWebsterConfig>(value).ok())
+        .filter(|config| config.validate().is_ok());
+    SETTINGS.lock().unwrap().get_or_insert_with(load).merriam_webster = config;
+}
@@ -0,0 +1,478 @@
+//! StarDict 本地词典：从用户指定的文件夹加载 `.ifo` / `.idx(.gz)` / `.dict(.dz)`，
+//! 离线查询并转换为 `DictionaryEntry`。`.dict.dz` 按 dictzip 分块随机读取，不整体解压。
+
+use crate::dictionary::settings::DictionarySettings;
+use crate::dictionary::{Definition, DictionaryEntry, DictionarySource, Meaning};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use flate2::read::GzDecoder;
+use flate2::{Decompress, FlushDecompress};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// 已加载词典的概要
+#[derive(Debug, Clone, Serialize)]
+pub struct StarDictInfo {
+    pub name: String,
+    pub word_count: usize,
+    pub path: String,
+}
+
+/// `.ifo` 中用到的字段
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Ifo {
+    pub name: String,
+    pub word_count: usize,
+    pub same_type_sequence: Option<String>,
+    pub offset_bits: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IndexEntry {
+    pub word: String,
+    /// 小写的词条，用于不区分大小写的查找
+    pub key: String,
+    pub offset: u64,
+    pub size: u32,
+}
+
+/// dictzip 头部：压缩数据的起始位置、每块解压后的长度和各块压缩后的长度
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DictZip {
+    pub data_start: u64,
+    pub chunk_len: u32,
+    pub chunk_sizes: Vec<u16>,
+}
+
+enum Data {
+    Plain(PathBuf),
+    Zipped { path: PathBuf, header: DictZip, chunk_offsets: Vec<u64> },
+}
+
+struct Dictionary {
+    ifo: Ifo,
+    ifo_path: PathBuf,
+    index: Vec<IndexEntry>,
+    data: Data,
+}
+
+static DICTIONARIES: Lazy<Mutex<Vec<Arc<Dictionary>>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+fn format_error(path: &Path, message: &str) -> AppError {
+    AppError::InvalidRequest(format!("Invalid StarDict file {}: {}", path.display(), message))
+}
+
+/// 解压 dictzip 的一块。每块以 full flush 结束而没有结束块，可以单独解压；
+/// 按流式读取会把缺少的结束块当作错误，所以这里按同步刷新解压，只把真正的数据错误报告出来
+fn inflate_chunk(compressed: &[u8], chunk_len: usize, path: &Path) -> Result<Vec<u8>> {
+    let mut decompress = Decompress::new(false);
+    let mut text = Vec::with_capacity(chunk_len);
+    decompress
+        .decompress_vec(compressed, &mut text, FlushDecompress::Sync)
+        .map_err(|e| format_error(path, &format!("corrupt compressed chunk: {}", e)))?;
+    if decompress.total_in() != compressed.len() as u64 {
+        return Err(format_error(path, "corrupt compressed chunk"));
+    }
+    Ok(text)
+}
+
+pub(crate) fn parse_ifo(text: &str) -> Option<Ifo> {
+    let mut lines = text.lines();
+    if lines.next()?.trim() != "StarDict's dict ifo file" {
+        return None;
+    }
+    let mut ifo = Ifo { name: String::new(), word_count: 0, same_type_sequence: None, offset_bits: 32 };
+    for (key, value) in lines.filter_map(|line| line.split_once('=')) {
+        let value = value.trim();
+        match key.trim() {
+            "bookname" => ifo.name = value.to_string(),
+            "wordcount" => ifo.word_count = value.parse().ok()?,
+            "sametypesequence" if !value.is_empty() => ifo.same_type_sequence = Some(value.to_string()),
+            "idxoffsetbits" => ifo.offset_bits = value.parse().ok()?,
+            _ => {}
+        }
+    }
+    Some(ifo)
+}
+
+/// `.idx`：每项为以 NUL 结尾的 UTF-8 词条，后接大端序的偏移（32 或 64 位）和长度（32 位）。
+/// 返回按小写词条排序的索引
+pub(crate) fn parse_idx(bytes: &[u8], offset_bits: u32) -> Option<Vec<IndexEntry>> {
+    let offset_len = if offset_bits == 64 { 8 } else { 4 };
+    let mut entries = Vec::new();
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let end = rest.iter().position(|&b| b == 0)?;
+        let word = String::from_utf8_lossy(&rest[..end]).into_owned();
+        let fields = rest.get(end + 1..end + 1 + offset_len + 4)?;
+        let offset = if offset_len == 8 {
+            u64::from_be_bytes(fields[..8].try_into().ok()?)
+        } else {
+            u32::from_be_bytes(fields[..4].try_into().ok()?) as u64
+        };
+        let size = u32::from_be_bytes(fields[offset_len..].try_into().ok()?);
+        entries.push(IndexEntry { key: word.to_lowercase(), word, offset, size });
+        rest = &rest[end + 1 + offset_len + 4..];
+    }
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    Some(entries)
+}
+
+/// 解析 gzip 头部 FEXTRA 中的 dictzip `RA` 子字段
+pub(crate) fn parse_dictzip_header(bytes: &[u8]) -> Option<DictZip> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    if bytes.get(..3)? != [0x1f, 0x8b, 0x08] {
+        return None;
+    }
+    let flags = *bytes.get(3)?;
+    if flags & FEXTRA == 0 {
+        return None;
+    }
+    let extra_len = u16::from_le_bytes([*bytes.get(10)?, *bytes.get(11)?]) as usize;
+    let extra = bytes.get(12..12 + extra_len)?;
+    let mut pos = 12 + extra_len;
+
+    let mut header = None;
+    let mut fields = extra;
+    while fields.len() >= 4 {
+        let len = u16::from_le_bytes([fields[2], fields[3]]) as usize;
+        let data = fields.get(4..4 + len)?;
+        if &fields[..2] == b"RA" && data.len() >= 6 {
+            let chunk_len = u16::from_le_bytes([data[2], data[3]]) as u32;
+            let count = u16::from_le_bytes([data[4], data[5]]) as usize;
+            let chunk_sizes = data
+                .get(6..6 + count * 2)?
+                .chunks_exact(2)
+                .map(|size| u16::from_le_bytes([size[0], size[1]]))
+                .collect();
+            header = Some((chunk_len, chunk_sizes));
+        }
+        fields = &fields[4 + len..];
+    }
+
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            pos += bytes.get(pos..)?.iter().position(|&b| b == 0)? + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    let (chunk_len, chunk_sizes) = header?;
+    Some(DictZip { data_start: pos as u64, chunk_len, chunk_sizes })
+}
+
+fn read_gzip(path: &Path) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    GzDecoder::new(File::open(path)?).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// 同名的 `.ifo` 旁找第一个存在的文件
+fn sibling(ifo_path: &Path, extensions: &[&str]) -> Option<PathBuf> {
+    extensions.iter().map(|ext| ifo_path.with_extension(ext)).find(|path| path.exists())
+}
+
+fn open(ifo_path: &Path) -> Result<Dictionary> {
+    let ifo = parse_ifo(&std::fs::read_to_string(ifo_path)?).ok_or_else(|| format_error(ifo_path, "bad .ifo header"))?;
+
+    let idx_path = sibling(ifo_path, &["idx", "idx.gz"]).ok_or_else(|| format_error(ifo_path, "missing .idx"))?;
+    let idx = if idx_path.extension().is_some_and(|ext| ext == "gz") { read_gzip(&idx_path)? } else { std::fs::read(&idx_path)? };
+    let index = parse_idx(&idx, ifo.offset_bits).ok_or_else(|| format_error(&idx_path, "truncated index"))?;
+
+    let data = match sibling(ifo_path, &["dict", "dict.dz"]) {
+        Some(path) if path.extension().is_some_and(|ext| ext == "dz") => {
+            let mut head = vec![0u8; 64 * 1024];
+            let read = File::open(&path)?.read(&mut head)?;
+            let header = parse_dictzip_header(&head[..read]).ok_or_else(|| format_error(&path, "not a dictzip file"))?;
+            let chunk_offsets = header
+                .chunk_sizes
+                .iter()
+                .scan(header.data_start, |offset, size| {
+                    let start = *offset;
+                    *offset += *size as u64;
+                    Some(start)
+                })
+                .collect();
+            Data::Zipped { path, header, chunk_offsets }
+        }
+        Some(path) => Data::Plain(path),
+        None => return Err(format_error(ifo_path, "missing .dict")),
+    };
+
+    Ok(Dictionary { ifo, ifo_path: ifo_path.to_path_buf(), index, data })
+}
+
+impl Dictionary {
+    fn info(&self) -> StarDictInfo {
+        StarDictInfo {
+            name: self.ifo.name.clone(),
+            word_count: self.index.len(),
+            path: self.ifo_path.display().to_string(),
+        }
+    }
+
+    fn read(&self, offset: u64, size: u32) -> Result<Vec<u8>> {
+        match &self.data {
+            Data::Plain(path) => {
+                let mut file = File::open(path)?;
+                file.seek(SeekFrom::Start(offset))?;
+                let mut buf = vec![0u8; size as usize];
+                file.read_exact(&mut buf)?;
+                Ok(buf)
+            }
+            Data::Zipped { path, header, chunk_offsets } => {
+                let chunk_len = header.chunk_len as u64;
+                let first = (offset / chunk_len) as usize;
+                let last = ((offset + size as u64).saturating_sub(1) / chunk_len) as usize;
+                let mut file = File::open(path)?;
+                let mut text = Vec::new();
+                let chunks = chunk_offsets.iter().zip(&header.chunk_sizes).enumerate().take(last + 1).skip(first);
+                for (index, (chunk_offset, chunk_size)) in chunks {
+                    file.seek(SeekFrom::Start(*chunk_offset))?;
+                    let mut compressed = vec![0u8; *chunk_size as usize];
+                    file.read_exact(&mut compressed)?;
+                    let chunk_text = inflate_chunk(&compressed, header.chunk_len as usize, path)?;
+                    // 除最后一块外，每块解压后正好是 chunk_len 字节，不足说明数据被截断
+                    if index + 1 < header.chunk_sizes.len() && chunk_text.len() != header.chunk_len as usize {
+                        return Err(format_error(path, "truncated compressed chunk"));
+                    }
+                    text.extend_from_slice(&chunk_text);
+                }
+                let start = (offset - first as u64 * chunk_len) as usize;
+                text.get(start..start + size as usize)
+                    .map(<[u8]>::to_vec)
+                    .ok_or_else(|| format_error(path, "entry outside the data file"))
+            }
+        }
+    }
+
+    fn lookup(&self, word: &str) -> Result<Vec<DictionaryEntry>> {
+        let key = word.to_lowercase();
+        let start = self.index.partition_point(|entry| entry.key < key);
+        self.index[start..]
+            .iter()
+            .take_while(|entry| entry.key == key)
+            .map(|entry| {
+                let data = self.read(entry.offset, entry.size)?;
+                let fields = parse_article(&data, self.ifo.same_type_sequence.as_deref());
+                Ok(to_entry(&entry.word, &fields))
+            })
+            .collect()
+    }
+}
+
+/// 拆分词条数据为（类型, 文本）字段。有 `sametypesequence` 时字段不带类型字节，最后一个字段不带结尾标记；
+/// 小写类型是以 NUL 结尾的字符串，大写类型以 32 位大端序长度开头
+pub(crate) fn parse_article(data: &[u8], same_type_sequence: Option<&str>) -> Vec<(char, String)> {
+    let mut fields = Vec::new();
+    let mut rest = data;
+    let mut types = same_type_sequence.map(|s| s.chars().collect::<Vec<_>>());
+
+    loop {
+        let (kind, is_last) = match types.as_mut() {
+            Some(types) if types.is_empty() => break,
+            Some(types) => (types.remove(0), types.is_empty()),
+            None => match rest.split_first() {
+                Some((&kind, tail)) => {
+                    rest = tail;
+                    (kind as char, false)
+                }
+                None => break,
+            },
+        };
+        let content: &[u8] = if kind.is_ascii_lowercase() {
+            let end = if is_last { rest.len() } else { rest.iter().position(|&b| b == 0).unwrap_or(rest.len()) };
+            let content = &rest[..end];
+            rest = rest.get(end + 1..).unwrap_or_default();
+            content
+        } else {
+            let len = if is_last {
+                rest.len()
+            } else {
+                match rest.get(..4) {
+                    Some(len) => {
+                        let len = u32::from_be_bytes(len.try_into().unwrap_or_default()) as usize;
+                        rest = &rest[4..];
+                        len.min(rest.len())
+                    }
+                    None => break,
+                }
+            };
+            let content = &rest[..len];
+            rest = &rest[len..];
+            content
+        };
+        fields.push((kind, String::from_utf8_lossy(content).into_owned()));
+    }
+    fields
+}
+
+/// 行首的词性标记，如 `n.`、`vt.`、`adj.`
+fn part_of_speech(line: &str) -> Option<(&str, &str)> {
+    let (marker, rest) = line.split_once('.')?;
+    let is_marker = (1..=5).contains(&marker.len()) && marker.chars().all(|c| c.is_ascii_lowercase());
+    is_marker.then(|| (marker, rest.trim()))
+}
+
+/// 把释义文本按行拆成义项，行首带词性标记时开始新的词性分组
+pub(crate) fn meanings_from_text(text: &str) -> Vec<Meaning> {
+    let mut meanings: Vec<Meaning> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (pos, definition) = match part_of_speech(line) {
+            Some((pos, rest)) => (Some(pos), rest),
+            None => (None, line),
+        };
+        if pos.is_some() || meanings.is_empty() {
+            meanings.push(Meaning { part_of_speech: pos.unwrap_or_default().to_string(), ..Default::default() });
+        }
+        if !definition.is_empty() {
+            if let Some(meaning) = meanings.last_mut() {
+                meaning.definitions.push(Definition { definition: definition.to_string(), ..Default::default() });
+            }
+        }
+    }
+    meanings.retain(|meaning| !meaning.definitions.is_empty());
+    meanings
+}
+
+pub(crate) fn to_entry(word: &str, fields: &[(char, String)]) -> DictionaryEntry {
+    let mut phonetic = None;
+    let mut meanings = Vec::new();
+    for (kind, content) in fields {
+        match kind {
+            't' | 'y' => phonetic = Some(format!("/{}/", content.trim().trim_matches('/'))),
+            'm' | 'l' => meanings.extend(meanings_from_text(content)),
+            'h' | 'g' | 'x' => meanings.extend(meanings_from_text(&super::strip_markup(content))),
+            // 图片、音频等二进制字段
+            _ => {}
+        }
+    }
+    DictionaryEntry {
+        word: word.to_string(),
+        phonetic,
+        phonetics: Vec::new(),
+        meanings,
+        examples: Vec::new(),
+        level: None,
+        stale: false,
+    }
+}
+
+/// 递归查找文件夹中的 `.ifo`
+fn find_ifo_files(dir: &Path, found: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            find_ifo_files(&path, found)?;
+        } else if path.extension().is_some_and(|ext| ext == "ifo") {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 加载文件夹（含子文件夹）中的全部词典，替换之前加载的词典；单个词典损坏时跳过
+pub fn load_dir(dir: &str) -> Result<Vec<StarDictInfo>> {
+    let dir = Path::new(dir);
+    if !dir.is_dir() {
+        return Err(AppError::InvalidRequest(format!("Not a folder: {}", dir.display())));
+    }
+    let mut files = Vec::new();
+    find_ifo_files(dir, &mut files)?;
+    files.sort();
+
+    let mut dictionaries = Vec::new();
+    for file in files {
+        match open(&file) {
+            Ok(dictionary) => dictionaries.push(Arc::new(dictionary)),
+            Err(e) => println!("StarDict: skipping {}: {}", file.display(), e),
+        }
+    }
+    let infos = dictionaries.iter().map(|d| d.info()).collect();
+    *DICTIONARIES.lock().unwrap() = dictionaries;
+    Ok(infos)
+}
+
+/// 记住的词典文件夹
+#[derive(Serialize, Deserialize)]
+struct SavedDir {
+    dir: String,
+}
+
+fn dir_file() -> PathBuf {
+    crate::paths::data_dir().join("stardict.json")
+}
+
+/// 加载词典文件夹并记住它，下次启动时由 `restore` 重新加载
+pub fn set_dir(dir: &str) -> Result<Vec<StarDictInfo>> {
+    let infos = load_dir(dir)?;
+    let path = dir_file();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&SavedDir { dir: dir.to_string() })?)?;
+    Ok(infos)
+}
+
+/// 重新加载上次设置的词典文件夹；从未设置过时不加载任何词典
+pub fn restore() -> Result<Vec<StarDictInfo>> {
+    let saved = std::fs::read_to_string(dir_file())
+        .ok()
+        .and_then(|content| serde_json::from_str::<SavedDir>(&content).ok());
+    match saved {
+        Some(saved) => load_dir(&saved.dir),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn loaded() -> Vec<StarDictInfo> {
+    DICTIONARIES.lock().unwrap().iter().map(|d| d.info()).collect()
+}
+
+/// 在全部已加载词典中查询，按词典加载顺序返回。个别词典读取失败时跳过它；
+/// 没有任何结果时返回遇到的第一个错误，而不是当作没查到
+pub fn lookup(word: &str) -> Result<Vec<DictionaryEntry>> {
+    let dictionaries = DICTIONARIES.lock().unwrap().clone();
+    let mut entries = Vec::new();
+    let mut error = None;
+    for dictionary in dictionaries {
+        match dictionary.lookup(word.trim()) {
+            Ok(found) => entries.extend(found),
+            Err(e) => {
+                println!("StarDict: lookup in {} failed: {}", dictionary.ifo.name, e);
+                error.get_or_insert(e);
+            }
+        }
+    }
+    match error {
+        Some(error) if entries.is_empty() => Err(error),
+        _ => Ok(entries),
+    }
+}
+
+pub struct StarDict;
+
+#[async_trait]
+impl DictionarySource for StarDict {
+    fn name(&self) -> &'static str {
+        "stardict"
+    }
+
+    fn offline(&self) -> bool {
+        true
+    }
+
+    async fn lookup(&self, word: &str, _settings: &DictionarySettings) -> Result<Vec<DictionaryEntry>> {
+        let word = word.to_string();
+        super::blocking(move || lookup(&word)).await
+    }
+}
@@ -0,0 +1,62 @@
+//! 本地 SQLite 词库（ECDICT、JMdict、Tatoeba）共用的连接管理：数据库文件放在数据目录，
+//! 首次访问时打开，之后所有查询和导入共用同一个连接。
+
+use crate::error::{AppError, Result};
+use rusqlite::Connection;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+pub(crate) struct Store {
+    /// 用于错误信息，如 `ECDICT`
+    name: &'static str,
+    /// 数据目录中的文件名
+    file: &'static str,
+    conn: Mutex<Option<Connection>>,
+}
+
+impl Store {
+    pub(crate) const fn new(name: &'static str, file: &'static str) -> Self {
+        Store { name, file, conn: Mutex::new(None) }
+    }
+
+    pub(crate) fn path(&self) -> PathBuf {
+        crate::paths::data_dir().join(self.file)
+    }
+
+    pub(crate) fn error(&self, e: rusqlite::Error) -> AppError {
+        AppError::Unknown(format!("{} database error: {}", self.name, e))
+    }
+
+    /// 在打开的数据库上执行操作；数据库文件不存在（未导入）时返回 None
+    pub(crate) fn with<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<Option<T>> {
+        let mut guard = self.conn.lock().unwrap();
+        if guard.is_none() {
+            let path = self.path();
+            if !path.exists() {
+                return Ok(None);
+            }
+            *guard = Some(Connection::open(path).map_err(|e| self.error(e))?);
+        }
+        f(guard.as_ref().unwrap()).map(Some)
+    }
+
+    /// 导入时使用：数据库文件不存在时创建，导入期间其他查询等待
+    pub(crate) fn write<T>(&self, f: impl FnOnce(&mut Connection) -> Result<T>) -> Result<T> {
+        std::fs::create_dir_all(crate::paths::data_dir())?;
+        let mut guard = self.conn.lock().unwrap();
+        let mut conn = match guard.take() {
+            Some(conn) => conn,
+            None => Connection::open(self.path()).map_err(|e| self.error(e))?,
+        };
+        let result = f(&mut conn);
+        *guard = Some(conn);
+        result
+    }
+
+    /// 关闭连接后替换数据库文件，下次访问时重新打开
+    pub(crate) fn replace(&self, f: impl FnOnce(PathBuf) -> Result<()>) -> Result<()> {
+        let mut guard = self.conn.lock().unwrap();
+        guard.take();
+        f(self.path())
+    }
+}
@@ -0,0 +1,336 @@
+//! Tatoeba 双语例句：优先查询本地索引（由 Tatoeba 导出的句对 TSV 导入 SQLite，按单词建立索引），
+//! 本地没有对应语言对时调用 Tatoeba 搜索接口。
+
+use crate::dictionary::store::Store;
+use crate::error::{AppError, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const TATOEBA_API_URL: &str = "https://tatoeba.org/en/api_v0/search";
+const DEFAULT_LIMIT: usize = 10;
+
+/// 应用使用的语言代码 → Tatoeba 使用的 ISO 639-3 代码
+const LANGUAGE_CODES: &[(&str, &str)] = &[
+    ("en", "eng"),
+    ("zh", "cmn"),
+    ("zh-cn", "cmn"),
+    ("zh-tw", "cmn"),
+    ("ja", "jpn"),
+    ("ko", "kor"),
+    ("fr", "fra"),
+    ("de", "deu"),
+    ("es", "spa"),
+    ("it", "ita"),
+    ("pt", "por"),
+    ("ru", "rus"),
+    ("uk", "ukr"),
+    ("ar", "ara"),
+    ("hi", "hin"),
+    ("th", "tha"),
+    ("el", "ell"),
+    ("he", "heb"),
+    ("nl", "nld"),
+    ("pl", "pol"),
+    ("tr", "tur"),
+    ("vi", "vie"),
+    ("id", "ind"),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExampleSentence {
+    pub sentence: String,
+    pub translation: String,
+    pub source_lang: String,
+    pub target_lang: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguagePairCount {
+    pub source_lang: String,
+    pub target_lang: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TatoebaStatus {
+    pub installed: bool,
+    pub path: String,
+    pub pairs: Vec<LanguagePairCount>,
+}
+
+/// 转为 Tatoeba 的语言代码；已是三字母代码时原样返回
+pub(crate) fn tatoeba_code(lang: &str) -> String {
+    let lang = lang.trim().to_lowercase();
+    LANGUAGE_CODES
+        .iter()
+        .find(|(code, _)| *code == lang)
+        .map(|(_, tatoeba)| tatoeba.to_string())
+        .unwrap_or(lang)
+}
+
+/// 句子中的单词（小写），用于建立索引
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+/// 汉字、假名等不以空格分词的文字，只能按子串匹配
+fn is_unspaced(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(c, '\u{3040}'..='\u{30ff}' | '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{ac00}'..='\u{d7af}'))
+}
+
+/// 解析 Tatoeba 导出的句对 TSV：`句子 ID \t 句子 \t 译文 ID \t 译文`
+pub(crate) fn parse_pairs_tsv(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.trim_end_matches('\r').split('\t').collect();
+            match fields.as_slice() {
+                [_, sentence, _, translation, ..] if !sentence.is_empty() && !translation.is_empty() => {
+                    Some((sentence.to_string(), translation.to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<SearchSentence>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchSentence {
+    text: String,
+    #[serde(default)]
+    lang: Option<String>,
+    /// 直接译文和间接译文两组
+    #[serde(default)]
+    translations: Vec<Vec<SearchSentence>>,
+}
+
+/// 解析搜索接口响应，每个句子取第一条目标语言的译文（优先直接译文）
+pub(crate) fn parse_search(json: serde_json::Value, source_lang: &str, target_lang: &str) -> Result<Vec<ExampleSentence>> {
+    let response: SearchResponse = serde_json::from_value(json)?;
+    Ok(response
+        .results
+        .into_iter()
+        .filter_map(|sentence| {
+            let translation = sentence
+                .translations
+                .iter()
+                .flatten()
+                .find(|t| t.lang.as_deref() == Some(target_lang))?;
+            Some(ExampleSentence {
+                sentence: sentence.text.clone(),
+                translation: translation.text.clone(),
+                source_lang: sentence.lang.clone().unwrap_or_else(|| source_lang.to_string()),
+                target_lang: target_lang.to_string(),
+            })
+        })
+        .collect())
+}
+
+static DB: Store = Store::new("Tatoeba", "tatoeba.db");
+
+fn db_error(e: rusqlite::Error) -> AppError {
+    DB.error(e)
+}
+
+pub fn db_path() -> PathBuf {
+    DB.path()
+}
+
+fn create_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS pairs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source_lang TEXT NOT NULL,
+            target_lang TEXT NOT NULL,
+            sentence TEXT NOT NULL,
+            translation TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS words (
+            word TEXT NOT NULL,
+            pair_id INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS pairs_langs ON pairs (source_lang, target_lang);
+        CREATE INDEX IF NOT EXISTS words_word ON words (word);",
+    )
+    .map_err(db_error)
+}
+
+/// 写入句对并按单词建立索引，替换同一语言对之前导入的句对
+pub(crate) fn insert_pairs(
+    conn: &mut Connection,
+    source_lang: &str,
+    target_lang: &str,
+    pairs: &[(String, String)],
+) -> Result<usize> {
+    create_schema(conn)?;
+    let tx = conn.transaction().map_err(db_error)?;
+    tx.execute(
+        "DELETE FROM words WHERE pair_id IN (SELECT id FROM pairs WHERE source_lang = ?1 AND target_lang = ?2)",
+        params![source_lang, target_lang],
+    )
+    .map_err(db_error)?;
+    tx.execute("DELETE FROM pairs WHERE source_lang = ?1 AND target_lang = ?2", params![source_lang, target_lang])
+        .map_err(db_error)?;
+    {
+        let mut insert_pair = tx
+            .prepare("INSERT INTO pairs (source_lang, target_lang, sentence, translation) VALUES (?1, ?2, ?3, ?4)")
+            .map_err(db_error)?;
+        let mut insert_word = tx.prepare("INSERT INTO words (word, pair_id) VALUES (?1, ?2)").map_err(db_error)?;
+        for (sentence, translation) in pairs {
+            insert_pair.execute(params![source_lang, target_lang, sentence, translation]).map_err(db_error)?;
+            let pair_id = tx.last_insert_rowid();
+            for word in tokenize(sentence) {
+                insert_word.execute(params![word, pair_id]).map_err(db_error)?;
+            }
+        }
+    }
+    tx.commit().map_err(db_error)?;
+    Ok(pairs.len())
+}
+
+/// 查询本地句对，短句在前
+pub(crate) fn query(conn: &Connection, word: &str, source_lang: &str, target_lang: &str, limit: usize) -> Result<Vec<ExampleSentence>> {
+    let word = word.trim().to_lowercase();
+    let (sql, pattern) = if is_unspaced(&word) {
+        (
+            "SELECT sentence, translation FROM pairs
+             WHERE source_lang = ?1 AND target_lang = ?2 AND sentence LIKE ?3
+             ORDER BY length(sentence) LIMIT ?4",
+            format!("%{}%", word),
+        )
+    } else {
+        (
+            "SELECT p.sentence, p.translation FROM words w JOIN pairs p ON p.id = w.pair_id
+             WHERE p.source_lang = ?1 AND p.target_lang = ?2 AND w.word = ?3
+             ORDER BY length(p.sentence) LIMIT ?4",
+            word,
+        )
+    };
+    let mut statement = conn.prepare(sql).map_err(db_error)?;
+    let rows = statement
+        .query_map(params![source_lang, target_lang, pattern, limit as i64], |row| {
+            Ok(ExampleSentence {
+                sentence: row.get(0)?,
+                translation: row.get(1)?,
+                source_lang: source_lang.to_string(),
+                target_lang: target_lang.to_string(),
+            })
+        })
+        .map_err(db_error)?;
+    let mut examples = Vec::new();
+    for example in rows {
+        examples.push(example.map_err(db_error)?);
+    }
+    Ok(examples)
+}
+
+pub fn status() -> Result<TatoebaStatus> {
+    let pairs = DB.with(|conn| {
+        let mut statement = conn
+            .prepare("SELECT source_lang, target_lang, COUNT(*) FROM pairs GROUP BY source_lang, target_lang")
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok(LanguagePairCount { source_lang: row.get(0)?, target_lang: row.get(1)?, count: row.get(2)? })
+            })
+            .map_err(db_error)?;
+        let mut pairs = Vec::new();
+        for pair in rows {
+            pairs.push(pair.map_err(db_error)?);
+        }
+        Ok(pairs)
+    })?;
+    Ok(TatoebaStatus {
+        installed: pairs.is_some(),
+        path: db_path().to_string_lossy().to_string(),
+        pairs: pairs.unwrap_or_default(),
+    })
+}
+
+/// 导入 Tatoeba 下载页导出的句对 TSV；`source_lang` / `target_lang` 为句子和译文的语言
+pub fn import(path: &str, source_lang: &str, target_lang: &str) -> Result<TatoebaStatus> {
+    let pairs = parse_pairs_tsv(&std::fs::read_to_string(Path::new(path))?);
+    if pairs.is_empty() {
+        return Err(AppError::InvalidRequest(format!("{} has no Tatoeba sentence pairs", path)));
+    }
+    let (source_lang, target_lang) = (tatoeba_code(source_lang), tatoeba_code(target_lang));
+
+    let count = DB.write(|conn| insert_pairs(conn, &source_lang, &target_lang, &pairs))?;
+    println!("Tatoeba: imported {} {}-{} pairs from {}", count, source_lang, target_lang, path);
+    status()
+}
+
+/// 使用指定的接口地址搜索（便于测试或代理）
+pub async fn search_from(
+    base_url: &str,
+    word: &str,
+    source_lang: &str,
+    target_lang: &str,
+    limit: usize,
+) -> Result<Vec<ExampleSentence>> {
+    let mut url = url::Url::parse(base_url).map_err(|e| AppError::Config(format!("Invalid Tatoeba URL: {}", e)))?;
+    url.query_pairs_mut()
+        .append_pair("query", word)
+        .append_pair("from", source_lang)
+        .append_pair("to", target_lang)
+        .append_pair("trans_to", target_lang)
+        .append_pair("orphans", "no")
+        .append_pair("unapproved", "no")
+        .append_pair("sort", "words");
+
+    let client = crate::services::http::client(None, std::time::Duration::from_secs(10))?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Tatoeba", status, error_text));
+    }
+    let mut examples = parse_search(response.json().await?, source_lang, target_lang)?;
+    examples.truncate(limit);
+    Ok(examples)
+}
+
+/// 查询包含该词的例句及译文：`source_lang` 为词条所属的语言（单个词无法可靠地检测语言）。
+/// 本地索引有该语言对时只查本地，否则调用 Tatoeba 接口
+pub async fn examples(word: &str, source_lang: &str, target_lang: &str, limit: Option<usize>) -> Result<Vec<ExampleSentence>> {
+    let word = word.trim().to_string();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let source_lang = tatoeba_code(source_lang);
+    let target_lang = tatoeba_code(target_lang);
+
+    let local = {
+        let (word, source_lang, target_lang) = (word.clone(), source_lang.clone(), target_lang.clone());
+        super::blocking(move || {
+            let has_pair = status()?
+                .pairs
+                .iter()
+                .any(|pair| pair.source_lang == source_lang && pair.target_lang == target_lang);
+            if !has_pair {
+                return Ok(None);
+            }
+            DB.with(|conn| query(conn, &word, &source_lang, &target_lang, limit))
+        })
+        .await?
+    };
+    match local {
+        Some(examples) => Ok(examples),
+        None => search_from(TATOEBA_API_URL, &word, &source_lang, &target_lang, limit).await,
+    }
+}
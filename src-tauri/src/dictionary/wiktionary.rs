@@ -0,0 +1,126 @@
+//! Wiktionary 词典：通过英文维基词典的 REST 接口查询，覆盖的语言和词条（屈折形式、俚语等）
+//! 远多于 dictionaryapi.dev。释义为英文，按请求的语言取对应章节。
+
+use crate::dictionary::settings::DictionarySettings;
+use crate::dictionary::{strip_markup, Definition, DictionaryEntry, DictionarySource, Meaning};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const WIKTIONARY_API_URL: &str = "https://en.wiktionary.org/api/rest_v1/page/definition";
+/// Wikimedia 要求客户端提供可识别的 User-Agent
+const USER_AGENT: &str = "dict-win/0.1 (https://github.com/chichu1439/dict)";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Section {
+    #[serde(default)]
+    part_of_speech: String,
+    #[serde(default)]
+    definitions: Vec<WikiDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WikiDefinition {
+    definition: String,
+    #[serde(default)]
+    parsed_examples: Vec<ParsedExample>,
+    #[serde(default)]
+    examples: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedExample {
+    example: String,
+}
+
+fn clean(html: &str) -> String {
+    strip_markup(html).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 把接口返回的某一语言章节转换为词典条目；该语言没有释义时返回 None
+pub(crate) fn parse_response(word: &str, json: serde_json::Value, language: &str) -> Result<Option<DictionaryEntry>> {
+    let mut languages: HashMap<String, Vec<Section>> = serde_json::from_value(json)?;
+    let Some(sections) = languages.remove(language) else {
+        return Ok(None);
+    };
+
+    let meanings: Vec<Meaning> = sections
+        .into_iter()
+        .map(|section| Meaning {
+            part_of_speech: section.part_of_speech.to_lowercase(),
+            definitions: section
+                .definitions
+                .into_iter()
+                .map(|d| Definition {
+                    definition: clean(&d.definition),
+                    example: d
+                        .parsed_examples
+                        .first()
+                        .map(|e| clean(&e.example))
+                        .or_else(|| d.examples.first().map(|e| clean(e))),
+                    ..Default::default()
+                })
+                .filter(|d| !d.definition.is_empty())
+                .collect(),
+            ..Default::default()
+        })
+        .filter(|meaning| !meaning.definitions.is_empty())
+        .collect();
+
+    Ok((!meanings.is_empty()).then(|| DictionaryEntry {
+        word: word.to_string(),
+        phonetic: None,
+        phonetics: Vec::new(),
+        meanings,
+        examples: Vec::new(),
+        level: None,
+        stale: false,
+    }))
+}
+
+/// 查询 Wiktionary；`language` 为词条所属语言的代码（如 `en`、`fr`、`ja`），默认英语
+pub async fn lookup(word: &str, language: Option<&str>) -> Result<Option<DictionaryEntry>> {
+    lookup_from(WIKTIONARY_API_URL, word, language).await
+}
+
+/// 使用指定的接口地址查询（便于测试或自建镜像）
+pub async fn lookup_from(base_url: &str, word: &str, language: Option<&str>) -> Result<Option<DictionaryEntry>> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+    let mut url = url::Url::parse(base_url).map_err(|e| AppError::Config(format!("Invalid Wiktionary URL: {}", e)))?;
+    url.path_segments_mut()
+        .map_err(|_| AppError::Config("Invalid Wiktionary URL".to_string()))?
+        .pop_if_empty()
+        // 页面标题中的空格写作下划线
+        .push(&word.replace(' ', "_"));
+
+    let client = crate::services::http::client_with_user_agent(None, std::time::Duration::from_secs(10), USER_AGENT)?;
+    let response = client.get(url).send().await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Wiktionary", status, error_text));
+    }
+    parse_response(word, response.json().await?, language.unwrap_or("en"))
+}
+
+pub struct Wiktionary;
+
+#[async_trait]
+impl DictionarySource for Wiktionary {
+    fn name(&self) -> &'static str {
+        "wiktionary"
+    }
+
+    async fn lookup(&self, word: &str, _settings: &DictionarySettings) -> Result<Vec<DictionaryEntry>> {
+        Ok(lookup(word, None).await?.into_iter().collect())
+    }
+}
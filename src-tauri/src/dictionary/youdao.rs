@@ -0,0 +1,364 @@
+//! 有道词典：通过网页版 jsonapi 接口查询英文单词的中文释义、柯林斯星级和双语例句（无需密钥）。
+
+use crate::dictionary::settings::DictionarySettings;
+use crate::dictionary::{stardict, strip_markup, Definition, DictionaryEntry, DictionarySource, Meaning, Phonetic};
+use crate::error::{AppError, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+const YOUDAO_API_URL: &str = "https://dict.youdao.com/jsonapi";
+const VOICE_URL: &str = "https://dict.youdao.com/dictvoice";
+/// 最多保留的双语例句数
+const MAX_EXAMPLES: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BilingualExample {
+    pub english: String,
+    pub chinese: String,
+}
+
+/// 柯林斯词典的一个义项：英文解释后跟中文释义
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollinsSense {
+    pub part_of_speech: String,
+    pub definition: String,
+    pub examples: Vec<BilingualExample>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YoudaoEntry {
+    pub word: String,
+    pub uk_phonetic: Option<String>,
+    pub us_phonetic: Option<String>,
+    pub uk_audio: Option<String>,
+    pub us_audio: Option<String>,
+    /// 简明中文释义，每行一条，如 `int. 喂；哈罗`
+    pub translations: Vec<String>,
+    /// 考试类型，如 `CET4`、`考研`
+    pub exam_types: Vec<String>,
+    /// 柯林斯星级（1–5），未收录时为 None
+    pub collins_star: Option<u8>,
+    pub collins: Vec<CollinsSense>,
+    pub examples: Vec<BilingualExample>,
+}
+
+impl YoudaoEntry {
+    /// 转为通用的词典条目：中文释义按词性分组。双语例句与具体释义无关，放在条目上；
+    /// 柯林斯义项自带例句，附在对应释义上
+    pub fn to_entry(&self) -> DictionaryEntry {
+        let mut meanings = stardict::meanings_from_text(&self.translations.join("\n"));
+        if meanings.is_empty() && !self.collins.is_empty() {
+            meanings = self
+                .collins
+                .iter()
+                .map(|sense| Meaning {
+                    part_of_speech: sense.part_of_speech.to_lowercase(),
+                    definitions: vec![Definition {
+                        definition: sense.definition.clone(),
+                        example: sense.examples.first().map(|e| format!("{} {}", e.english, e.chinese)),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+                .collect();
+        }
+
+        let phonetics: Vec<Phonetic> = [(&self.uk_phonetic, &self.uk_audio), (&self.us_phonetic, &self.us_audio)]
+            .into_iter()
+            .filter(|(text, audio)| text.is_some() || audio.is_some())
+            .map(|(text, audio)| Phonetic {
+                text: text.as_ref().map(|t| format!("/{}/", t)),
+                audio: audio.clone(),
+                source_url: None,
+                license: None,
+            })
+            .collect();
+        DictionaryEntry {
+            word: self.word.clone(),
+            phonetic: phonetics.iter().find_map(|p| p.text.clone()),
+            phonetics,
+            meanings,
+            examples: self.examples.iter().map(|e| format!("{} {}", e.english, e.chinese)).collect(),
+            level: None,
+            stale: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Response {
+    #[serde(default)]
+    ec: Option<Ec>,
+    #[serde(default)]
+    collins: Option<Collins>,
+    #[serde(default)]
+    blng_sents_part: Option<BilingualSentences>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Ec {
+    #[serde(default)]
+    exam_type: Vec<String>,
+    #[serde(default)]
+    word: Vec<EcWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EcWord {
+    #[serde(default)]
+    ukphone: Option<String>,
+    #[serde(default)]
+    usphone: Option<String>,
+    #[serde(default)]
+    ukspeech: Option<String>,
+    #[serde(default)]
+    usspeech: Option<String>,
+    #[serde(default)]
+    trs: Vec<EcTranslation>,
+    #[serde(default, rename = "return-phrase")]
+    return_phrase: Option<Label>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EcTranslation {
+    #[serde(default)]
+    tr: Vec<Label>,
+}
+
+/// 形如 `{"l": {"i": ...}}` 的文本节点，`i` 可能是字符串或字符串数组
+#[derive(Debug, Deserialize)]
+struct Label {
+    l: LabelInner,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelInner {
+    #[serde(default)]
+    i: serde_json::Value,
+}
+
+impl Label {
+    fn text(&self) -> String {
+        match &self.l.i {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Array(items) => items.iter().filter_map(|item| item.as_str()).collect(),
+            _ => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Collins {
+    #[serde(default)]
+    collins_entries: Vec<CollinsEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollinsEntry {
+    #[serde(default)]
+    star: serde_json::Value,
+    #[serde(default)]
+    entries: Option<CollinsEntries>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollinsEntries {
+    #[serde(default)]
+    entry: Vec<CollinsEntryItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollinsEntryItem {
+    #[serde(default)]
+    tran_entry: Vec<TranEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranEntry {
+    #[serde(default)]
+    pos_entry: Option<PosEntry>,
+    #[serde(default)]
+    tran: Option<String>,
+    #[serde(default)]
+    exam_sents: Option<ExamSentences>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PosEntry {
+    #[serde(default)]
+    pos: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExamSentences {
+    #[serde(default)]
+    sent: Vec<ExamSentence>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExamSentence {
+    eng_sent: String,
+    chn_sent: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BilingualSentences {
+    #[serde(default, rename = "sentence-pair")]
+    sentence_pair: Vec<SentencePair>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SentencePair {
+    sentence: String,
+    #[serde(rename = "sentence-translation")]
+    sentence_translation: String,
+}
+
+fn clean(html: &str) -> String {
+    strip_markup(html).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 星级可能是数字、数字字符串或 `★★★` 形式
+pub(crate) fn parse_star(star: &serde_json::Value) -> Option<u8> {
+    let star = match star {
+        serde_json::Value::Number(n) => n.as_u64().and_then(|n| u8::try_from(n).ok()),
+        serde_json::Value::String(s) => s
+            .trim()
+            .parse()
+            .ok()
+            .or_else(|| u8::try_from(s.chars().filter(|&c| c == '★').count()).ok()),
+        _ => None,
+    };
+    star.filter(|star| (1..=5).contains(star))
+}
+
+/// ukspeech / usspeech 是未编码的 `hello&type=1` 形式；拆出末尾的 `type` 后重新编码，
+/// 避免词组或含 `&`、`#`、空格的词破坏查询字符串
+pub(crate) fn voice_url(speech: &str) -> String {
+    let (audio, voice_type) = match speech.rsplit_once("&type=") {
+        Some((audio, voice_type)) if voice_type.chars().all(|c| c.is_ascii_digit()) => (audio, Some(voice_type)),
+        _ => (speech, None),
+    };
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    query.append_pair("audio", audio);
+    if let Some(voice_type) = voice_type {
+        query.append_pair("type", voice_type);
+    }
+    format!("{}?{}", VOICE_URL, query.finish())
+}
+
+/// 解析接口响应；没有中文释义、柯林斯义项和例句时返回 None
+pub(crate) fn parse_response(word: &str, json: serde_json::Value) -> Result<Option<YoudaoEntry>> {
+    let response: Response = serde_json::from_value(json)?;
+
+    let mut entry = YoudaoEntry {
+        word: word.to_string(),
+        uk_phonetic: None,
+        us_phonetic: None,
+        uk_audio: None,
+        us_audio: None,
+        translations: Vec::new(),
+        exam_types: Vec::new(),
+        collins_star: None,
+        collins: Vec::new(),
+        examples: Vec::new(),
+    };
+
+    if let Some(ec) = response.ec {
+        entry.exam_types = ec.exam_type;
+        if let Some(ec_word) = ec.word.into_iter().next() {
+            if let Some(phrase) = ec_word.return_phrase.map(|p| p.text()).filter(|p| !p.is_empty()) {
+                entry.word = phrase;
+            }
+            entry.uk_phonetic = ec_word.ukphone.filter(|p| !p.is_empty());
+            entry.us_phonetic = ec_word.usphone.filter(|p| !p.is_empty());
+            entry.uk_audio = ec_word.ukspeech.as_deref().map(voice_url);
+            entry.us_audio = ec_word.usspeech.as_deref().map(voice_url);
+            entry.translations = ec_word
+                .trs
+                .iter()
+                .flat_map(|trs| trs.tr.iter().map(Label::text))
+                .filter(|text| !text.is_empty())
+                .collect();
+        }
+    }
+
+    if let Some(collins) = response.collins.and_then(|c| c.collins_entries.into_iter().next()) {
+        entry.collins_star = parse_star(&collins.star);
+        entry.collins = collins
+            .entries
+            .into_iter()
+            .flat_map(|entries| entries.entry)
+            .flat_map(|item| item.tran_entry)
+            .filter_map(|tran| {
+                let definition = clean(tran.tran.as_deref()?);
+                (!definition.is_empty()).then(|| CollinsSense {
+                    part_of_speech: tran.pos_entry.map(|p| p.pos).unwrap_or_default(),
+                    definition,
+                    examples: tran
+                        .exam_sents
+                        .map(|s| s.sent)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|s| BilingualExample { english: clean(&s.eng_sent), chinese: clean(&s.chn_sent) })
+                        .collect(),
+                })
+            })
+            .collect();
+    }
+
+    if let Some(sentences) = response.blng_sents_part {
+        entry.examples = sentences
+            .sentence_pair
+            .into_iter()
+            .map(|pair| BilingualExample {
+                english: clean(&pair.sentence),
+                chinese: clean(&pair.sentence_translation),
+            })
+            .take(MAX_EXAMPLES)
+            .collect();
+    }
+
+    let empty = entry.translations.is_empty() && entry.collins.is_empty() && entry.examples.is_empty();
+    Ok((!empty).then_some(entry))
+}
+
+pub async fn lookup(word: &str) -> Result<Option<YoudaoEntry>> {
+    lookup_from(YOUDAO_API_URL, word).await
+}
+
+/// 使用指定的接口地址查询（便于测试或代理）
+pub async fn lookup_from(base_url: &str, word: &str) -> Result<Option<YoudaoEntry>> {
+    let word = word.trim();
+    if word.is_empty() {
+        return Err(AppError::InvalidRequest("Word is empty".to_string()));
+    }
+    let mut url = url::Url::parse(base_url).map_err(|e| AppError::Config(format!("Invalid Youdao URL: {}", e)))?;
+    url.query_pairs_mut().append_pair("q", word).append_pair("le", "eng");
+
+    let client = crate::services::http::client(None, std::time::Duration::from_secs(10))?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Youdao Dictionary", status, error_text));
+    }
+    parse_response(word, response.json().await?)
+}
+
+pub struct Youdao;
+
+#[async_trait]
+impl DictionarySource for Youdao {
+    fn name(&self) -> &'static str {
+        "youdao"
+    }
+
+    fn supports(&self, word: &str) -> bool {
+        super::is_english(word)
+    }
+
+    async fn lookup(&self, word: &str, _settings: &DictionarySettings) -> Result<Vec<DictionaryEntry>> {
+        Ok(lookup(word).await?.iter().map(YoudaoEntry::to_entry).collect())
+    }
+}
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,6 +15,10 @@ pub enum AppError {
     #[error("API error: {service} - {message}")]
     Api { service: String, message: String },
 
+    /// 上游返回了非成功的 HTTP 状态码
+    #[error("API error: {service} - HTTP {status}: {message}")]
+    HttpStatus { service: String, status: reqwest::StatusCode, message: String },
+
     #[error("Configuration error: {0}")]
     Config(String),
 
@@ -50,6 +55,130 @@ pub enum AppError {
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
+impl AppError {
+    /// 上游返回非成功状态码时的错误，`message` 通常为响应体
+    pub fn from_status(service: &str, status: reqwest::StatusCode, message: impl Into<String>) -> Self {
+        AppError::HttpStatus { service: service.to_string(), status, message: message.into() }
+    }
+
+    /// 鉴权失败：只以真实的 401/403 状态判断；额度用尽的 403 不算
+    pub fn is_auth_failure(&self) -> bool {
+        match self {
+            AppError::AuthFailed { .. } => true,
+            AppError::HttpStatus { status, .. } => {
+                matches!(status.as_u16(), 401 | 403) && !self.is_quota_exhausted()
+            }
+            _ => false,
+        }
+    }
+
+    /// 额度用尽（如 DeepL 的 456、OpenAI 的 `insufficient_quota`、Google 的 `quotaExceeded`）。
+    /// 不同服务的状态码不一（429/403/456），只能结合响应体判断
+    pub fn is_quota_exhausted(&self) -> bool {
+        match self {
+            AppError::HttpStatus { status, .. } if status.as_u16() == 456 => true,
+            AppError::Api { message, .. } | AppError::HttpStatus { message, .. } => {
+                message.to_lowercase().contains("quota")
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            AppError::RateLimitExceeded { .. } => true,
+            AppError::HttpStatus { status, .. } if status.as_u16() == 429 => true,
+            _ if self.is_quota_exhausted() => true,
+            AppError::Api { message, .. } => message.to_lowercase().contains("rate limit"),
+            _ => false,
+        }
+    }
+
+    /// 供界面区分错误类型的粗略分类
+    pub fn category(&self) -> &'static str {
+        if self.is_auth_failure() {
+            return "auth";
+        }
+        if self.is_rate_limited() {
+            return "rate_limit";
+        }
+        match self {
+            AppError::Network(_) | AppError::Http(_) => "network",
+            AppError::Timeout(_) => "timeout",
+            AppError::Config(_) | AppError::InvalidRequest(_) => "config",
+            AppError::ServiceUnavailable(_) => "unavailable",
+            AppError::PlatformNotSupported(_) => "unsupported",
+            AppError::Api { .. } | AppError::HttpStatus { .. } => "api",
+            _ => "unknown",
+        }
+    }
+
+    /// 错误对应的 HTTP 状态码
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            AppError::AuthFailed { .. } => Some(401),
+            AppError::RateLimitExceeded { .. } => Some(429),
+            AppError::HttpStatus { status, .. } => Some(status.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// 稍后重试可能成功：限流、网络、超时和服务端 5xx
+    pub fn is_retryable(&self) -> bool {
+        match self.category() {
+            "rate_limit" | "network" | "timeout" | "unavailable" => true,
+            _ => self.http_status().is_some_and(|status| status >= 500),
+        }
+    }
+
+    /// 补全服务名：reqwest 错误转换时不知道来源服务；各服务自行包装的限流错误也统一为 `RateLimitExceeded`
+    pub fn for_service(self, service: &str) -> Self {
+        match self {
+            AppError::AuthFailed { service: unknown } if unknown == "unknown" => {
+                AppError::AuthFailed { service: service.to_string() }
+            }
+            AppError::HttpStatus { service: unknown, status, message } if unknown == "unknown" => {
+                AppError::HttpStatus { service: service.to_string(), status, message }
+            }
+            error if error.is_rate_limited() => AppError::RateLimitExceeded { service: service.to_string() },
+            error => error,
+        }
+    }
+
+    /// 替换服务名，供多个服务共用同一实现时标注实际的服务
+    pub fn with_service(self, service: &str) -> Self {
+        let service = service.to_string();
+        match self {
+            AppError::Api { message, .. } => AppError::Api { service, message },
+            AppError::HttpStatus { status, message, .. } => AppError::HttpStatus { service, status, message },
+            AppError::AuthFailed { .. } => AppError::AuthFailed { service },
+            AppError::RateLimitExceeded { .. } => AppError::RateLimitExceeded { service },
+            error => error,
+        }
+    }
+
+    pub fn detail(&self, service: &str) -> ErrorDetail {
+        ErrorDetail {
+            service: service.to_string(),
+            category: self.category(),
+            status: self.http_status(),
+            retryable: self.is_retryable(),
+            message: self.to_string(),
+        }
+    }
+}
+
+/// 发送给前端的结构化错误
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorDetail {
+    pub service: String,
+    pub category: &'static str,
+    pub status: Option<u16>,
+    pub retryable: bool,
+    pub message: String,
+}
+
 impl From<reqwest::Error> for AppError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
@@ -64,7 +193,7 @@ impl From<reqwest::Error> for AppError {
                     } else if status.as_u16() == 429 {
                         AppError::RateLimitExceeded { service: "unknown".to_string() }
                     } else {
-                        AppError::Http(format!("HTTP {}: {}", status, err))
+                        AppError::from_status("unknown", status, err.to_string())
                     }
                 }
                 None => AppError::Http(err.to_string()),
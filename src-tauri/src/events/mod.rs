@@ -0,0 +1,224 @@
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Emitter, Manager, Runtime, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// 事件投递到窗口时的行为控制
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EmitOptions {
+    /// 窗口不存在时按预设创建
+    pub create: bool,
+    pub show: bool,
+    pub unminimize: bool,
+    pub focus: bool,
+}
+
+impl Default for EmitOptions {
+    fn default() -> Self {
+        Self {
+            create: true,
+            show: true,
+            unminimize: true,
+            focus: true,
+        }
+    }
+}
+
+impl EmitOptions {
+    /// 仅投递事件，不改变窗口状态
+    pub fn silent() -> Self {
+        Self {
+            create: false,
+            show: false,
+            unminimize: false,
+            focus: false,
+        }
+    }
+}
+
+struct WindowSpec {
+    label: &'static str,
+    title: &'static str,
+    url: &'static str,
+    width: f64,
+    height: f64,
+    decorations: bool,
+    always_on_top: bool,
+    skip_taskbar: bool,
+}
+
+/// 可按需创建的窗口预设，与 tauri.conf.json 中的静态窗口保持一致
+const WINDOW_SPECS: &[WindowSpec] = &[
+    WindowSpec {
+        label: "main",
+        title: "Dictionary App",
+        url: "index.html",
+        width: 800.0,
+        height: 600.0,
+        decorations: true,
+        always_on_top: false,
+        skip_taskbar: false,
+    },
+    WindowSpec {
+        label: "overlay",
+        title: "Screenshot Overlay",
+        url: "overlay.html",
+        width: 800.0,
+        height: 600.0,
+        decorations: false,
+        always_on_top: true,
+        skip_taskbar: true,
+    },
+    WindowSpec {
+        label: "float",
+        title: "Float Translation",
+        url: "float.html",
+        width: 400.0,
+        height: 300.0,
+        decorations: false,
+        always_on_top: true,
+        skip_taskbar: true,
+    },
+    WindowSpec {
+        label: "history",
+        title: "History",
+        url: "index.html?view=history",
+        width: 600.0,
+        height: 500.0,
+        decorations: true,
+        always_on_top: false,
+        skip_taskbar: false,
+    },
+];
+
+/// 后端发出的事件名到前端自定义事件名的映射；未配置的事件保持内置名称。None 表示尚未从磁盘加载
+static EVENT_NAMES: Lazy<Mutex<Option<HashMap<String, String>>>> = Lazy::new(|| Mutex::new(None));
+
+fn event_names_file() -> PathBuf {
+    crate::paths::data_dir().join("event_names.json")
+}
+
+fn load_event_names() -> HashMap<String, String> {
+    std::fs::read_to_string(event_names_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Tauri 事件名只允许字母、数字和 `-`、`/`、`:`、`_`
+pub(crate) fn is_valid_event_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '/' | ':' | '_'))
+}
+
+pub fn event_names() -> HashMap<String, String> {
+    EVENT_NAMES.lock().unwrap().get_or_insert_with(load_event_names).clone()
+}
+
+/// 替换整个事件名映射并写入磁盘；映射到自身的条目会被忽略
+pub fn set_event_names(names: HashMap<String, String>) -> Result<()> {
+    if let Some((event, name)) = names.iter().find(|(event, name)| !is_valid_event_name(event) || !is_valid_event_name(name)) {
+        return Err(AppError::InvalidRequest(format!("Invalid event name mapping: {} -> {}", event, name)));
+    }
+    let names: HashMap<String, String> = names.into_iter().filter(|(event, name)| event != name).collect();
+    let path = event_names_file();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&names)?)?;
+    *EVENT_NAMES.lock().unwrap() = Some(names);
+    Ok(())
+}
+
+/// 内置事件名对应的实际事件名
+pub fn event_name(event: &str) -> String {
+    EVENT_NAMES
+        .lock()
+        .unwrap()
+        .get_or_insert_with(load_event_names)
+        .get(event)
+        .cloned()
+        .unwrap_or_else(|| event.to_string())
+}
+
+/// 将前端使用的窗口别名映射为实际的窗口 label
+fn resolve_label(window: &str) -> &str {
+    match window {
+        "popup" => "float",
+        other => other,
+    }
+}
+
+fn apply_focus_policy<R: Runtime>(window: &WebviewWindow<R>, options: &EmitOptions) {
+    if options.unminimize && window.is_minimized().unwrap_or(false) {
+        let _ = window.unminimize();
+    }
+    if options.show {
+        let _ = window.show();
+    }
+    if options.focus {
+        let _ = window.set_focus();
+    }
+}
+
+/// 向指定窗口发送事件（按配置替换事件名）；窗口不存在且允许创建时，待页面加载完成后再投递，
+/// 避免前端监听器尚未注册导致事件丢失
+pub fn emit_to_window<R: Runtime, S: Serialize + Clone + Send + Sync + 'static>(
+    app: &AppHandle<R>,
+    window: &str,
+    event: &str,
+    payload: S,
+    options: &EmitOptions,
+) -> Result<()> {
+    let label = resolve_label(window);
+    let event = event_name(event);
+    let event = event.as_str();
+
+    if let Some(target) = app.get_webview_window(label) {
+        apply_focus_policy(&target, options);
+        return target
+            .emit(event, payload)
+            .map_err(|e| AppError::Unknown(format!("Failed to emit {} to {}: {}", event, label, e)));
+    }
+
+    if !options.create {
+        return Err(AppError::Unknown(format!("Window '{}' not found", label)));
+    }
+
+    let spec = WINDOW_SPECS
+        .iter()
+        .find(|spec| spec.label == label)
+        .ok_or_else(|| AppError::InvalidRequest(format!("Unknown window: {}", window)))?;
+
+    println!("Creating window '{}' to deliver event {}", label, event);
+
+    let event_name = event.to_string();
+    let delivered = AtomicBool::new(false);
+    let focus_options = options.clone();
+
+    WebviewWindowBuilder::new(app, spec.label, WebviewUrl::App(spec.url.into()))
+        .title(spec.title)
+        .inner_size(spec.width, spec.height)
+        .decorations(spec.decorations)
+        .always_on_top(spec.always_on_top)
+        .skip_taskbar(spec.skip_taskbar)
+        .visible(false)
+        .on_page_load(move |window, load| {
+            if load.event() != PageLoadEvent::Finished || delivered.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            apply_focus_policy(&window, &focus_options);
+            if let Err(e) = window.emit(&event_name, payload.clone()) {
+                println!("Failed to emit {} to new window: {}", event_name, e);
+            }
+        })
+        .build()
+        .map_err(|e| AppError::Unknown(format!("Failed to create window '{}': {}", label, e)))?;
+
+    Ok(())
+}
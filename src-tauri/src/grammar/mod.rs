@@ -0,0 +1,132 @@
+use crate::error::{AppError, Result};
+use serde::Serialize;
+
+const LANGUAGETOOL_API_URL: &str = "https://api.languagetool.org/v2/check";
+
+/// 一处拼写/语法问题。`offset`/`length` 为 UTF-16 码元，与前端字符串下标一致
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrammarIssue {
+    pub offset: usize,
+    pub length: usize,
+    pub message: String,
+    pub short_message: Option<String>,
+    pub rule_id: Option<String>,
+    pub category: Option<String>,
+    pub replacements: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrammarCheckResult {
+    pub issues: Vec<GrammarIssue>,
+    /// 启用自动更正时，按每处问题的首个建议替换后的文本
+    pub corrected: Option<String>,
+}
+
+/// 检查文本。`config` 中可指定自建 LanguageTool 服务（apiUrl）及高级账号（username/apiKey）
+pub async fn check_text(
+    text: &str,
+    lang: &str,
+    config: Option<&serde_json::Value>,
+    auto_correct: bool,
+) -> Result<GrammarCheckResult> {
+    let get = |key: &str| {
+        config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+
+    match get("engine").unwrap_or("languagetool") {
+        "languagetool" => {}
+        other => return Err(AppError::Config(format!("Unsupported grammar engine: {}", other))),
+    }
+
+    let api_url = get("apiUrl").unwrap_or(LANGUAGETOOL_API_URL);
+    let language = if lang.is_empty() { "auto" } else { lang };
+
+    let mut form = vec![("text", text), ("language", language)];
+    if let (Some(username), Some(api_key)) = (get("username"), get("apiKey")) {
+        form.push(("username", username));
+        form.push(("apiKey", api_key));
+    }
+
+    let client = crate::services::http::client(config, crate::services::request_timeout(config))?;
+
+    let response = client.post(api_url).form(&form).send().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("LanguageTool", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+    let matches = json["matches"]
+        .as_array()
+        .ok_or_else(|| AppError::Unknown("No matches in LanguageTool response".to_string()))?;
+
+    let issues: Vec<GrammarIssue> = matches
+        .iter()
+        .filter_map(|m| {
+            Some(GrammarIssue {
+                offset: m["offset"].as_u64()? as usize,
+                length: m["length"].as_u64()? as usize,
+                message: m["message"].as_str().unwrap_or_default().to_string(),
+                short_message: m["shortMessage"].as_str().filter(|s| !s.is_empty()).map(|s| s.to_string()),
+                rule_id: m["rule"]["id"].as_str().map(|s| s.to_string()),
+                category: m["rule"]["category"]["name"].as_str().map(|s| s.to_string()),
+                replacements: m["replacements"]
+                    .as_array()
+                    .map(|list| {
+                        list.iter()
+                            .filter_map(|r| r["value"].as_str().map(|s| s.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    let corrected = auto_correct.then(|| apply_corrections(text, &issues));
+
+    Ok(GrammarCheckResult { issues, corrected })
+}
+
+/// UTF-16 偏移转换为字节偏移
+fn utf16_to_byte(text: &str, utf16_offset: usize) -> Option<usize> {
+    let mut units = 0;
+    for (byte, c) in text.char_indices() {
+        if units == utf16_offset {
+            return Some(byte);
+        }
+        units += c.len_utf16();
+    }
+    (units == utf16_offset).then_some(text.len())
+}
+
+/// 从后往前替换，避免前面的修改影响后续偏移；重叠的问题只保留靠后的一处
+pub fn apply_corrections(text: &str, issues: &[GrammarIssue]) -> String {
+    let mut edits: Vec<(usize, usize, &str)> = issues
+        .iter()
+        .filter_map(|issue| {
+            let replacement = issue.replacements.first()?;
+            let start = utf16_to_byte(text, issue.offset)?;
+            let end = utf16_to_byte(text, issue.offset + issue.length)?;
+            Some((start, end, replacement.as_str()))
+        })
+        .collect();
+    edits.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut corrected = text.to_string();
+    let mut limit = text.len();
+    for (start, end, replacement) in edits {
+        if end > limit {
+            continue;
+        }
+        corrected.replace_range(start..end, replacement);
+        limit = start;
+    }
+    corrected
+}
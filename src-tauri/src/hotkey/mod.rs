@@ -1,11 +1,14 @@
 use tauri::{AppHandle, Manager, Runtime, Emitter};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
 use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_store::StoreExt;
 use std::sync::Mutex;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Duration;
 use enigo::{Enigo, Key, Keyboard, Settings, Direction};
+use crate::events::{self, EmitOptions};
+use crate::ocr::presets;
 
 pub mod models;
 use models::{HotkeyConfig, HotkeyAction};
@@ -251,10 +254,13 @@ pub fn handle_shortcut<R: Runtime>(app: &AppHandle<R>, shortcut: &Shortcut) {
             "silent_ocr" => {
                 handle_screenshot_ocr(app, true);
             },
+            action if action.starts_with(presets::HOTKEY_ACTION_PREFIX) => {
+                handle_region_ocr(app, action[presets::HOTKEY_ACTION_PREFIX.len()..].to_string());
+            },
             _ => {
                 println!("Unknown action: {}", action);
                 if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.emit("global-shortcut", shortcut_str);
+                    let _ = window.emit(&events::event_name("global-shortcut"), shortcut_str);
                 }
                 // Clear processing flag for unknown actions
                 *state.is_processing.lock().unwrap() = false;
@@ -272,7 +278,7 @@ fn handle_input_translation<R: Runtime>(app: &AppHandle<R>) {
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.show();
         let _ = window.set_focus();
-        let _ = window.emit("focus-input", ());
+        let _ = window.emit(&events::event_name("focus-input"), ());
         println!("Emitted focus-input event");
     }
     // Clear processing flag
@@ -288,26 +294,23 @@ fn handle_screenshot_ocr<R: Runtime>(app: &AppHandle<R>, silent: bool) {
     let windows = app.webview_windows();
     println!("Available windows: {:?}", windows.keys());
 
-    // Instead of emitting to main window, we show the overlay window
-    if let Some(overlay) = app.get_webview_window("overlay") {
-        let event_name = if silent { "trigger-silent-ocr-v2" } else { "trigger-screenshot-v2" };
-        
-        println!("Found overlay window, emitting event: {}", event_name);
-        
-        // Show the window first to ensure it's active?
-        // let _ = overlay.show();
-        // let _ = overlay.set_focus();
-        
-        let _ = overlay.emit(event_name, ());
-        
-        // Also log to main window for debugging
-        if let Some(main) = app.get_webview_window("main") {
-             let _ = main.emit("debug-log", format!("Backend emitted {} to overlay (Targeted)", event_name));
+    // Instead of emitting to main window, we target the overlay window
+    let event_name = if silent { "trigger-silent-ocr-v2" } else { "trigger-screenshot-v2" };
+
+    // The overlay shows itself once it has captured the screen, so leave its state alone
+    match events::emit_to_window(app, "overlay", event_name, (), &EmitOptions::silent()) {
+        Ok(()) => {
+            println!("Emitted {} to overlay window", event_name);
+            // Also log to main window for debugging
+            if let Some(main) = app.get_webview_window("main") {
+                let _ = main.emit(&events::event_name("debug-log"), format!("Backend emitted {} to overlay (Targeted)", event_name));
+            }
         }
-    } else {
-        println!("Overlay window NOT found!");
-        if let Some(window) = app.get_webview_window("main") {
-            let _ = window.emit("debug-log", "CRITICAL ERROR: Overlay window missing in backend!".to_string());
+        Err(e) => {
+            println!("Overlay window NOT found! {}", e);
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.emit(&events::event_name("debug-log"), "CRITICAL ERROR: Overlay window missing in backend!".to_string());
+            }
         }
     }
     
@@ -318,6 +321,46 @@ fn handle_screenshot_ocr<R: Runtime>(app: &AppHandle<R>, silent: bool) {
     }
 }
 
+/// 设置页选择的 OCR 引擎和语言；未保存过时与前端默认值一致
+fn ocr_settings<R: Runtime>(app: &AppHandle<R>) -> (String, Option<String>) {
+    let store = app.store(crate::paths::SETTINGS_STORE).ok();
+    let get = |key: &str| {
+        store
+            .as_ref()
+            .and_then(|store| store.get(key))
+            .and_then(|value| value.as_str().map(str::to_string))
+            .filter(|value| !value.is_empty())
+    };
+    (get("ocrEngine").unwrap_or_else(|| "windows".to_string()), get("ocrLanguage"))
+}
+
+/// 识别已保存的命名区域，结果与框选识别一样交给主窗口翻译
+fn handle_region_ocr<R: Runtime>(app: &AppHandle<R>, name: String) {
+    println!("Handling OCR of saved region: {}", name);
+    let app_handle = app.clone();
+    let (engine, language) = ocr_settings(app);
+    tauri::async_runtime::spawn(async move {
+        match crate::ocr::ocr_region(&name, language, &engine).await {
+            Ok(result) if !result.text.trim().is_empty() => {
+                let payload = serde_json::json!({
+                    "text": result.text,
+                    "ocrInfo": { "confidence": result.confidence },
+                    "autoShow": true,
+                });
+                let options = EmitOptions { create: false, ..Default::default() };
+                if let Err(e) = events::emit_to_window(&app_handle, "main", "request-translation", payload.to_string(), &options) {
+                    println!("Failed to send region OCR result to main window: {}", e);
+                }
+            }
+            Ok(_) => println!("No text recognized in region: {}", name),
+            Err(e) => println!("Region OCR failed for {}: {}", name, e),
+        }
+        if let Some(state) = app_handle.try_state::<HotkeyState>() {
+            *state.is_processing.lock().unwrap() = false;
+        }
+    });
+}
+
 async fn perform_selection_translation<R: Runtime>(app: AppHandle<R>) {
     println!("Starting selection translation...");
     
@@ -393,7 +436,7 @@ async fn perform_selection_translation<R: Runtime>(app: AppHandle<R>) {
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.show();
         let _ = window.set_focus();
-        let _ = window.emit("selection-translation", clipboard_text.clone());
+        let _ = window.emit(&events::event_name("selection-translation"), clipboard_text.clone());
         println!("Emitted selection-translation event with text: {}", clipboard_text);
     }
     
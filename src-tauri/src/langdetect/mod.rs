@@ -0,0 +1,171 @@
+use serde::Serialize;
+
+/// 语言检测结果，`lang` 为 ISO 639-1 代码（与前端语言选项一致）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Detection {
+    pub lang: String,
+    pub script: &'static str,
+    /// 0.0–1.0，脚本判定或停用词命中比例
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Script {
+    Latin,
+    Han,
+    Kana,
+    Hangul,
+    Cyrillic,
+    Arabic,
+    Devanagari,
+    Thai,
+    Greek,
+    Hebrew,
+}
+
+impl Script {
+    fn name(self) -> &'static str {
+        match self {
+            Script::Latin => "Latin",
+            Script::Han => "Han",
+            Script::Kana => "Kana",
+            Script::Hangul => "Hangul",
+            Script::Cyrillic => "Cyrillic",
+            Script::Arabic => "Arabic",
+            Script::Devanagari => "Devanagari",
+            Script::Thai => "Thai",
+            Script::Greek => "Greek",
+            Script::Hebrew => "Hebrew",
+        }
+    }
+}
+
+fn script_of(c: char) -> Option<Script> {
+    match c as u32 {
+        0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x024F | 0x1E00..=0x1EFF => Some(Script::Latin),
+        0x4E00..=0x9FFF | 0x3400..=0x4DBF | 0xF900..=0xFAFF => Some(Script::Han),
+        0x3040..=0x30FF | 0x31F0..=0x31FF => Some(Script::Kana),
+        0xAC00..=0xD7AF | 0x1100..=0x11FF | 0x3130..=0x318F => Some(Script::Hangul),
+        0x0400..=0x04FF => Some(Script::Cyrillic),
+        0x0600..=0x06FF | 0x0750..=0x077F => Some(Script::Arabic),
+        0x0900..=0x097F => Some(Script::Devanagari),
+        0x0E00..=0x0E7F => Some(Script::Thai),
+        0x0370..=0x03FF => Some(Script::Greek),
+        0x0590..=0x05FF => Some(Script::Hebrew),
+        _ => None,
+    }
+}
+
+/// 拉丁字母语言的高频词
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "is", "are", "of", "to", "in", "that", "it", "you", "this", "with", "for", "was", "have", "not", "be", "on"]),
+    ("fr", &["le", "la", "les", "et", "est", "des", "une", "un", "du", "que", "pas", "pour", "dans", "ce", "je", "vous", "avec", "sur"]),
+    ("de", &["der", "die", "das", "und", "ist", "nicht", "ich", "ein", "eine", "zu", "mit", "sie", "es", "den", "auf", "für", "von", "sich"]),
+    ("es", &["el", "la", "los", "las", "y", "es", "de", "que", "en", "un", "una", "por", "con", "para", "no", "se", "del", "lo"]),
+    ("it", &["il", "la", "di", "che", "e", "è", "un", "una", "per", "non", "con", "sono", "del", "della", "lo", "gli", "le", "si"]),
+    ("pt", &["o", "a", "os", "as", "e", "é", "de", "que", "em", "um", "uma", "não", "para", "com", "do", "da", "se", "por"]),
+    ("nl", &["de", "het", "een", "en", "is", "van", "niet", "ik", "dat", "je", "op", "te", "met", "zijn", "voor", "er", "die", "ook"]),
+    ("id", &["yang", "dan", "di", "ini", "itu", "dengan", "untuk", "tidak", "dari", "dalam", "akan", "saya", "ada", "ke", "juga", "kami"]),
+    ("tr", &["ve", "bir", "bu", "için", "ile", "da", "de", "değil", "ne", "çok", "daha", "gibi", "ben", "sen", "olarak", "mi"]),
+];
+
+/// 带有特定变音符号时直接判定的语言
+fn latin_by_diacritics(text: &str) -> Option<&'static str> {
+    if text.chars().any(|c| matches!(c, 'ơ' | 'ư' | 'đ' | 'ă' | 'ạ' | 'ả' | 'ấ' | 'ầ' | 'ệ' | 'ộ' | 'ợ' | 'ữ')) {
+        return Some("vi");
+    }
+    if text.chars().any(|c| matches!(c, 'ğ' | 'ş' | 'ı' | 'İ')) {
+        return Some("tr");
+    }
+    None
+}
+
+fn detect_latin(text: &str) -> Option<(&'static str, f64)> {
+    if let Some(lang) = latin_by_diacritics(text) {
+        return Some((lang, 0.9));
+    }
+
+    let lowered = text.to_lowercase();
+    let words: Vec<&str> = lowered
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let (lang, hits) = STOPWORDS
+        .iter()
+        .map(|(lang, stopwords)| (*lang, words.iter().filter(|w| stopwords.contains(w)).count()))
+        .max_by_key(|(_, hits)| *hits)?;
+
+    if hits == 0 {
+        // 单个词或无常用词时按英文处理，置信度较低
+        return Some(("en", 0.3));
+    }
+    Some((lang, (hits as f64 / words.len() as f64 * 2.0).min(1.0)))
+}
+
+pub fn detect(text: &str) -> Option<Detection> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    for script in text.chars().filter_map(script_of) {
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let count_of = |script: Script| counts.iter().find(|(s, _)| *s == script).map(|(_, c)| *c).unwrap_or(0);
+    let (dominant, dominant_count) = counts.iter().copied().max_by_key(|(_, count)| *count)?;
+    let share = dominant_count as f64 / total as f64;
+
+    // 日文混用汉字和假名，出现假名即判定为日文
+    let (lang, script, confidence) = if count_of(Script::Kana) > 0 && matches!(dominant, Script::Han | Script::Kana) {
+        ("ja", Script::Kana, share.max(0.8))
+    } else {
+        match dominant {
+            Script::Han => ("zh", dominant, share),
+            Script::Kana => ("ja", dominant, share),
+            Script::Hangul => ("ko", dominant, share),
+            Script::Cyrillic => {
+                let ukrainian = text.chars().any(|c| matches!(c, 'і' | 'ї' | 'є' | 'ґ'));
+                (if ukrainian { "uk" } else { "ru" }, dominant, share)
+            }
+            Script::Arabic => ("ar", dominant, share),
+            Script::Devanagari => ("hi", dominant, share),
+            Script::Thai => ("th", dominant, share),
+            Script::Greek => ("el", dominant, share),
+            Script::Hebrew => ("he", dominant, share),
+            Script::Latin => {
+                let (lang, confidence) = detect_latin(text)?;
+                (lang, dominant, confidence * share)
+            }
+        }
+    };
+
+    Some(Detection {
+        lang: lang.to_string(),
+        script: script.name(),
+        confidence,
+    })
+}
+
+/// 自动替换源语言所需的最低置信度；低于该值时交给服务自行识别
+const RESOLVE_MIN_CONFIDENCE: f64 = 0.5;
+
+/// `source_lang` 为 "auto" 时替换为检测结果；检测失败或置信度不足（如单个没有常用词的拉丁单词）
+/// 则保持 "auto"
+pub fn resolve_source(text: &str, source_lang: &str) -> String {
+    if source_lang != "auto" {
+        return source_lang.to_string();
+    }
+    detect(text)
+        .filter(|d| d.confidence >= RESOLVE_MIN_CONFIDENCE)
+        .map(|d| d.lang)
+        .unwrap_or_else(|| "auto".to_string())
+}
@@ -0,0 +1,62 @@
+//! 按最近使用淘汰的定长映射，供翻译缓存和词典缓存使用。访问和插入都是 O(log n)。
+
+use std::collections::{BTreeMap, HashMap};
+
+pub(crate) struct Lru<V> {
+    entries: HashMap<String, (V, u64)>,
+    /// 最近使用时间 -> 键，最小的即最久未使用
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    capacity: usize,
+}
+
+impl<V> Lru<V> {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Lru { entries: HashMap::new(), order: BTreeMap::new(), tick: 0, capacity: capacity.max(1) }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+        if let Some((_, last_used)) = self.entries.get_mut(key) {
+            let key = self.order.remove(last_used).unwrap_or_else(|| key.to_string());
+            *last_used = self.tick;
+            self.order.insert(self.tick, key);
+        }
+    }
+
+    /// 取出并标记为最近使用
+    pub(crate) fn get(&mut self, key: &str) -> Option<&V> {
+        self.touch(key);
+        self.entries.get(key).map(|(value, _)| value)
+    }
+
+    /// 插入或覆盖；超出容量时淘汰最久未使用的条目
+    pub(crate) fn insert(&mut self, key: String, value: V) {
+        if let Some((_, last_used)) = self.entries.remove(&key) {
+            self.order.remove(&last_used);
+        }
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// 从最久未使用到最近使用依次遍历，按此顺序重新插入即可恢复 LRU 顺序
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&String, &V)> {
+        self.order.values().filter_map(|key| self.entries.get_key_value(key).map(|(key, (value, _))| (key, value)))
+    }
+}
@@ -1,17 +1,32 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod api;
+mod background;
 mod error;
 mod models;
 mod ocr;
+mod paths;
 mod hotkey;
 mod tts;
 mod services;
+mod session;
+mod translate;
 mod phonetic;
+mod pins;
 mod dictionary;
+mod events;
+mod grammar;
+mod langdetect;
+mod lru;
+mod persist;
+mod worksheet;
+
+#[cfg(test)]
+mod tests;
 
 use error::AppError;
-use models::{TranslationRequest, TranslationResponse};
+use models::{MemoryMatch, TranslationRequest, TranslationResponse};
 use ocr::models::{OcrRequest, OcrResult};
 use ocr::mathpix::{MathpixRequest, MathpixResult};
 use tts::models::{TtsRequest, TtsResponse};
@@ -30,6 +45,95 @@ async fn translate_stream(app: tauri::AppHandle, request: TranslationRequest, re
     services::translate_stream(app, request, request_id).await.map_err(error_to_string)
 }
 
+#[tauri::command]
+async fn translate_draft(
+    draft_id: String,
+    text: String,
+    source_lang: String,
+    target_lang: String,
+    service: String,
+    config: Option<std::collections::HashMap<String, serde_json::Value>>,
+) -> Result<Option<services::draft::DraftResult>, String> {
+    services::draft::translate_draft(&draft_id, &text, &source_lang, &target_lang, &service, config.as_ref())
+        .await
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+fn discard_draft(draft_id: String) {
+    services::draft::discard(&draft_id)
+}
+
+#[tauri::command]
+fn get_key_pool_status() -> Vec<translate::keys::KeyStatus> {
+    translate::keys::status()
+}
+
+#[tauri::command]
+fn reset_key_pools() {
+    translate::keys::reset()
+}
+
+#[tauri::command]
+fn get_circuit_status() -> Vec<translate::breaker::CircuitStatus> {
+    translate::breaker::status()
+}
+
+#[tauri::command]
+fn reset_circuit_breakers() {
+    translate::breaker::reset()
+}
+
+#[tauri::command]
+async fn test_service(
+    service: String,
+    config: Option<serde_json::Value>,
+    proxy: Option<serde_json::Value>,
+) -> services::probe::ServiceTestResult {
+    services::probe::test_service(&service, config, proxy).await
+}
+
+#[tauri::command]
+fn confirm_translation(source: String, text: String, source_lang: String, target_lang: String, service: Option<String>) {
+    services::memory::confirm(&source, &text, &source_lang, &target_lang, service)
+}
+
+#[tauri::command]
+fn search_translation_memory(
+    text: String,
+    source_lang: String,
+    target_lang: String,
+    config: Option<std::collections::HashMap<String, serde_json::Value>>,
+) -> Vec<MemoryMatch> {
+    let options = services::memory::MemoryOptions::from_config(config.as_ref());
+    services::memory::lookup(&text, &source_lang, &target_lang, options)
+}
+
+#[tauri::command]
+fn clear_translation_memory() -> usize {
+    services::memory::clear()
+}
+
+#[tauri::command]
+fn list_services(config: Option<std::collections::HashMap<String, serde_json::Value>>) -> Vec<translate::ServiceInfo> {
+    translate::list_services(config.as_ref())
+}
+
+#[tauri::command]
+fn get_service_languages(service: String) -> Result<translate::languages::ServiceLanguages, String> {
+    translate::service_languages(&service).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn clear_translation_cache() -> usize {
+    services::cache::clear()
+}
+
+#[tauri::command]
+fn detect_language(text: String) -> Option<langdetect::Detection> {
+    langdetect::detect(&text)
+}
+
 #[tauri::command]
 async fn ocr(request: OcrRequest) -> Result<OcrResult, String> {
     ocr::perform_ocr(request).await.map_err(|e: AppError| e.to_string())
@@ -40,6 +144,11 @@ async fn ocr_with_engine(request: OcrRequest, engine: String) -> Result<OcrResul
     ocr::perform_ocr_with_engine(request, &engine).await.map_err(|e: AppError| e.to_string())
 }
 
+#[tauri::command]
+async fn ocr_files(paths: Vec<String>, engine: String, language: Option<String>) -> Vec<ocr::models::OcrFileResult> {
+    ocr::ocr_files(paths, &engine, language).await
+}
+
 #[tauri::command]
 async fn capture_and_ocr(x: i32, y: i32, w: i32, h: i32, language: Option<String>) -> Result<OcrResult, String> {
     ocr::capture_and_ocr(x, y, w, h, language).await.map_err(|e: AppError| e.to_string())
@@ -50,6 +159,48 @@ async fn capture_and_ocr_with_engine(x: i32, y: i32, w: i32, h: i32, language: O
     ocr::capture_and_ocr_with_engine(x, y, w, h, language, &engine).await.map_err(|e: AppError| e.to_string())
 }
 
+#[tauri::command]
+async fn capture_ocr_translate(
+    app: tauri::AppHandle,
+    request: ocr::models::CaptureTranslateRequest,
+    request_id: String,
+) -> Result<OcrResult, String> {
+    ocr::capture_ocr_translate(app, request, request_id).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn translate_capture_lines(request: ocr::models::CaptureTranslateRequest) -> Result<Vec<ocr::overlay::OverlayLine>, String> {
+    ocr::overlay::translate_capture(request).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+fn start_ocr_watch(app: tauri::AppHandle, request: ocr::models::OcrWatchRequest) -> Result<(), String> {
+    ocr::watch::start(app, request).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn stop_ocr_watch() {
+    ocr::watch::stop()
+}
+
+#[tauri::command]
+fn set_llm_vision_config(config: serde_json::Value) -> Result<(), String> {
+    ocr::llm_vision::set_config(config).map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn silent_ocr(
+    app: tauri::AppHandle,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    language: Option<String>,
+    engine: String,
+) -> Result<OcrResult, String> {
+    ocr::silent_ocr(app, x, y, w, h, language, &engine).await.map_err(error_to_string)
+}
+
 #[tauri::command]
 async fn init_paddle_ocr_cmd() -> Result<String, String> {
     ocr::paddle::init_paddle_ocr().await.map(|_| "PaddleOCR initialized successfully".to_string()).map_err(|e: AppError| e.to_string())
@@ -61,8 +212,138 @@ fn check_paddle_ocr_status() -> bool {
 }
 
 #[tauri::command]
-async fn capture_screen(x: i32, y: i32, w: i32, h: i32) -> Result<String, String> {
-    ocr::capture_screen(x, y, w, h).await.map_err(|e: AppError| e.to_string())
+async fn start_paddle_worker() -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(ocr::paddle::start_worker)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+fn stop_paddle_worker() {
+    ocr::paddle::stop_worker()
+}
+
+#[tauri::command]
+fn paddle_worker_status() -> bool {
+    ocr::paddle::is_worker_running()
+}
+
+#[tauri::command]
+fn set_paddle_options(options: ocr::paddle::PaddleOptions) -> Result<(), String> {
+    ocr::paddle::set_options(options).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn get_paddle_options() -> ocr::paddle::PaddleOptions {
+    ocr::paddle::options()
+}
+
+#[tauri::command]
+fn get_ocr_history(limit: Option<usize>, offset: Option<usize>) -> Result<Vec<ocr::history::OcrHistoryEntry>, String> {
+    ocr::history::list(limit, offset).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn delete_ocr_history(ids: Option<Vec<i64>>) -> Result<usize, String> {
+    ocr::history::delete(ids.as_deref()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn list_ocr_languages() -> Result<Vec<ocr::models::OcrLanguage>, String> {
+    ocr::list_ocr_languages().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn check_rapid_ocr_status() -> ocr::rapid::RapidStatus {
+    ocr::rapid::status()
+}
+
+#[tauri::command]
+async fn check_tesseract_status() -> ocr::tesseract::TesseractStatus {
+    ocr::tesseract::status().await
+}
+
+#[tauri::command]
+fn set_tesseract_options(options: ocr::tesseract::TesseractOptions) {
+    ocr::tesseract::set_options(options)
+}
+
+#[tauri::command]
+fn get_tesseract_options() -> ocr::tesseract::TesseractOptions {
+    ocr::tesseract::options()
+}
+
+#[tauri::command]
+fn set_ocr_spell_correction(enabled: bool) {
+    ocr::correct::set_enabled(enabled)
+}
+
+#[tauri::command]
+fn get_ocr_spell_correction() -> bool {
+    ocr::correct::is_enabled()
+}
+
+#[tauri::command]
+fn list_window_rects() -> Result<Vec<ocr::region::WindowRect>, String> {
+    ocr::region::list_window_rects().map_err(|e: AppError| e.to_string())
+}
+
+#[tauri::command]
+async fn capture_region_and_ocr(points: Vec<ocr::region::Point>, language: Option<String>, engine: Option<String>) -> Result<OcrResult, String> {
+    ocr::capture_region_and_ocr(points, language, engine.as_deref().unwrap_or("windows")).await.map_err(|e: AppError| e.to_string())
+}
+
+#[tauri::command]
+fn list_ocr_regions() -> Vec<ocr::presets::RegionPreset> {
+    ocr::presets::list()
+}
+
+#[tauri::command]
+fn save_ocr_region(name: String, rect: ocr::presets::RegionRect) -> Result<ocr::presets::RegionPreset, String> {
+    ocr::presets::save(&name, rect).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn delete_ocr_region(name: String) -> Result<(), String> {
+    ocr::presets::delete(&name).map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn ocr_region(name: String, language: Option<String>, engine: Option<String>) -> Result<OcrResult, String> {
+    ocr::ocr_region(&name, language, engine.as_deref().unwrap_or("windows")).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn capture_window(target: String) -> Result<String, String> {
+    ocr::capture_window(&target).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn capture_window_and_ocr(target: String, language: Option<String>, engine: Option<String>) -> Result<OcrResult, String> {
+    ocr::capture_window_and_ocr(&target, language, engine.as_deref().unwrap_or("windows")).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn capture_screen(x: i32, y: i32, w: i32, h: i32, capture_id: Option<String>) -> Result<String, String> {
+    ocr::capture_screen(x, y, w, h, capture_id).await.map_err(|e: AppError| e.to_string())
+}
+
+#[tauri::command]
+async fn ocr_capture_region(
+    capture_id: String,
+    rect: ocr::presets::RegionRect,
+    language: Option<String>,
+    engine: Option<String>,
+) -> Result<OcrResult, String> {
+    ocr::ocr_capture_region(&capture_id, rect, language, engine.as_deref().unwrap_or("windows"))
+        .await
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+fn release_capture(capture_id: String) {
+    ocr::frames::release(&capture_id)
 }
 
 #[tauri::command]
@@ -70,6 +351,44 @@ async fn speak(request: TtsRequest) -> Result<TtsResponse, String> {
     tts::speak(request).await.map_err(|e: AppError| e.to_string())
 }
 
+#[tauri::command]
+async fn speak_to_file(request: TtsRequest, path: Option<String>) -> Result<tts::models::TtsAudio, String> {
+    tts::speak_to_file(request, path).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+fn stop_speaking() -> Result<(), String> {
+    tts::stop().map_err(error_to_string)
+}
+
+#[tauri::command]
+fn set_tts_queue_policy(policy: tts::queue::QueuePolicy) -> Result<(), String> {
+    tts::queue::set_policy(policy).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn get_tts_queue_policy() -> tts::queue::QueuePolicy {
+    tts::queue::policy()
+}
+
+#[tauri::command]
+fn pause_speaking() -> Result<(), String> {
+    tts::playback::pause().map_err(error_to_string)
+}
+
+#[tauri::command]
+fn resume_speaking() -> Result<(), String> {
+    tts::playback::resume().map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn list_tts_voices(
+    include_online: Option<bool>,
+    proxy: Option<serde_json::Value>,
+) -> Result<Vec<tts::models::TtsVoice>, String> {
+    tts::list_voices(include_online.unwrap_or(false), proxy).await.map_err(error_to_string)
+}
+
 #[tauri::command]
 fn get_phonetic(text: String) -> Result<Option<phonetic::PhoneticResult>, String> {
     if phonetic::is_single_english_word(&text) {
@@ -79,6 +398,130 @@ fn get_phonetic(text: String) -> Result<Option<phonetic::PhoneticResult>, String
     }
 }
 
+#[tauri::command]
+fn set_dictionary_settings(
+    app: tauri::AppHandle,
+    settings: dictionary::settings::DictionarySettings,
+) -> Result<(), String> {
+    dictionary::settings::set_settings(settings.clone()).map_err(error_to_string)?;
+    dictionary::settings::save_api_keys(&app, &settings).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn get_dictionary_settings() -> dictionary::settings::DictionarySettings {
+    dictionary::settings::settings()
+}
+
+#[tauri::command]
+async fn lookup_wiktionary(word: String, language: Option<String>) -> Result<Option<dictionary::DictionaryEntry>, String> {
+    dictionary::wiktionary::lookup(&word, language.as_deref()).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn lookup_youdao(word: String) -> Result<Option<dictionary::youdao::YoudaoEntry>, String> {
+    dictionary::youdao::lookup(&word).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn lookup_word_offline(word: String) -> Result<Option<dictionary::ecdict::EcdictEntry>, String> {
+    tauri::async_runtime::spawn_blocking(move || dictionary::ecdict::lookup(&word))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+/// 统计词条数要扫描整张表，放到阻塞线程池，避免卡住界面；JMdict、Tatoeba 的状态同理
+#[tauri::command]
+async fn ecdict_status() -> Result<dictionary::ecdict::EcdictStatus, String> {
+    tauri::async_runtime::spawn_blocking(dictionary::ecdict::status)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn download_ecdict(
+    url: Option<String>,
+    proxy: Option<serde_json::Value>,
+) -> Result<dictionary::ecdict::EcdictStatus, String> {
+    dictionary::ecdict::download(url, proxy).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn import_ecdict(path: String) -> Result<dictionary::ecdict::EcdictStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || dictionary::ecdict::import(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn lookup_japanese(text: String) -> Result<Vec<dictionary::jmdict::JmdictMatch>, String> {
+    tauri::async_runtime::spawn_blocking(move || dictionary::jmdict::lookup(&text))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn jmdict_status() -> Result<dictionary::jmdict::JmdictStatus, String> {
+    tauri::async_runtime::spawn_blocking(dictionary::jmdict::status)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn import_jmdict(path: String) -> Result<dictionary::jmdict::JmdictStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || dictionary::jmdict::import(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn get_examples(
+    word: String,
+    source_lang: String,
+    target_lang: String,
+    limit: Option<usize>,
+) -> Result<Vec<dictionary::tatoeba::ExampleSentence>, String> {
+    dictionary::tatoeba::examples(&word, &source_lang, &target_lang, limit).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn tatoeba_status() -> Result<dictionary::tatoeba::TatoebaStatus, String> {
+    tauri::async_runtime::spawn_blocking(dictionary::tatoeba::status)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn import_tatoeba(
+    path: String,
+    source_lang: String,
+    target_lang: String,
+) -> Result<dictionary::tatoeba::TatoebaStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || dictionary::tatoeba::import(&path, &source_lang, &target_lang))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn set_stardict_dir(path: String) -> Result<Vec<dictionary::stardict::StarDictInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || dictionary::stardict::set_dir(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+fn list_stardict_dictionaries() -> Vec<dictionary::stardict::StarDictInfo> {
+    dictionary::stardict::loaded()
+}
+
 #[tauri::command]
 async fn lookup_dictionary(word: String) -> Result<Option<dictionary::DictionaryEntry>, String> {
     match dictionary::lookup_word(&word).await {
@@ -96,14 +539,72 @@ async fn lookup_dictionary(word: String) -> Result<Option<dictionary::Dictionary
     }
 }
 
+#[tauri::command]
+async fn lookup_dictionary_grouped(word: String) -> Result<Vec<dictionary::SourceEntries>, String> {
+    dictionary::lookup_grouped(&word).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+fn clear_dictionary_cache() -> usize {
+    dictionary::cache::clear()
+}
+
+#[tauri::command]
+async fn get_word_level(word: String) -> Option<dictionary::level::WordLevel> {
+    dictionary::blocking(move || Ok(dictionary::level::level(&word))).await.ok().flatten()
+}
+
+#[tauri::command]
+async fn quick_lookup(
+    word: String,
+    target_lang: String,
+    service: Option<String>,
+    config: Option<serde_json::Value>,
+) -> Result<dictionary::quick::QuickLookupResult, String> {
+    dictionary::quick::quick_lookup(&word, &target_lang, service, config)
+        .await
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn check_text(
+    text: String,
+    lang: String,
+    config: Option<serde_json::Value>,
+    auto_correct: Option<bool>,
+) -> Result<grammar::GrammarCheckResult, String> {
+    grammar::check_text(&text, &lang, config.as_ref(), auto_correct.unwrap_or(false))
+        .await
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn recognize_formula_local(image_data: String) -> Result<MathpixResult, String> {
+    tauri::async_runtime::spawn_blocking(move || ocr::formula_local::recognize_formula(&image_data))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(error_to_string)
+}
+
+#[tauri::command]
+fn check_formula_local_status() -> bool {
+    ocr::formula_local::is_available()
+}
+
+#[tauri::command]
+fn stop_formula_local_worker() {
+    ocr::formula_local::stop_worker()
+}
+
 #[tauri::command]
 async fn recognize_formula(request: MathpixRequest, config: Option<serde_json::Value>) -> Result<MathpixResult, String> {
-    ocr::mathpix::recognize_formula(request.image_data, request.image_url, config.as_ref())
+    ocr::recognize_formula(request, config.as_ref())
         .await
         .map_err(|e: AppError| e.to_string())
 }
 
 use serde::Serialize;
+use tauri::Emitter;
 
 #[derive(Serialize)]
 struct MonitorInfo {
@@ -148,25 +649,150 @@ async fn get_mouse_monitor() -> Result<MonitorInfo, String> {
     Err(AppError::PlatformNotSupported("Monitor info only available on Windows".to_string()).to_string())
 }
 
-use tauri::Emitter;
-use tauri::Manager;
-
 #[tauri::command]
 async fn emit_to_main<R: tauri::Runtime>(app: tauri::AppHandle<R>, event: String, payload: String) -> Result<(), String> {
-    if let Some(main) = app.get_webview_window("main") {
-        if let Ok(is_minimized) = main.is_minimized() {
-            if is_minimized {
-                let _ = main.unminimize();
-            }
-        }
-        
-        let _ = main.show();
-        let _ = main.set_focus();
+    let options = events::EmitOptions {
+        create: false,
+        ..Default::default()
+    };
+    events::emit_to_window(&app, "main", &event, payload, &options).map_err(error_to_string)
+}
 
-        main.emit(&event, payload).map_err(|e| AppError::Unknown(e.to_string()).to_string())
-    } else {
-        Err(AppError::Unknown("Main window not found".to_string()).to_string())
-    }
+#[tauri::command]
+async fn emit_to_window<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    window: String,
+    event: String,
+    payload: serde_json::Value,
+    options: Option<events::EmitOptions>,
+) -> Result<(), String> {
+    events::emit_to_window(&app, &window, &event, payload, &options.unwrap_or_default()).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn get_event_names() -> std::collections::HashMap<String, String> {
+    events::event_names()
+}
+
+#[tauri::command]
+fn set_event_names(names: std::collections::HashMap<String, String>) -> Result<(), String> {
+    events::set_event_names(names).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn pause_background_tasks<R: tauri::Runtime>(app: tauri::AppHandle<R>) -> background::BackgroundStatus {
+    background::pause();
+    let status = background::status();
+    let _ = app.emit(&events::event_name("background-status"), status.clone());
+    status
+}
+
+#[tauri::command]
+fn resume_background_tasks<R: tauri::Runtime>(app: tauri::AppHandle<R>) -> background::BackgroundStatus {
+    background::resume();
+    let status = background::status();
+    let _ = app.emit(&events::event_name("background-status"), status.clone());
+    status
+}
+
+#[tauri::command]
+fn get_app_status() -> background::AppStatus {
+    background::app_status()
+}
+
+#[tauri::command]
+fn get_nllb_status() -> services::nllb::NllbStatus {
+    services::nllb::status()
+}
+
+#[tauri::command]
+async fn download_nllb_model(model: String) -> Result<services::nllb::NllbModelInfo, String> {
+    services::nllb::download_model(&model).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+fn delete_nllb_model(model: String) -> Result<(), String> {
+    services::nllb::delete_model(&model).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn stop_nllb_worker() {
+    services::nllb::stop_worker()
+}
+
+#[tauri::command]
+fn start_session(title: Option<String>) -> session::Session {
+    session::start(title)
+}
+
+#[tauri::command]
+fn add_session_entry(session_id: String, entry: session::SessionEntry) -> Result<usize, String> {
+    session::add_entry(&session_id, entry).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn export_session_report(session_id: String, format: session::report::ReportFormat) -> Result<String, String> {
+    let session = session::get(&session_id).map_err(error_to_string)?;
+    Ok(session::report::render(&session, format))
+}
+
+#[tauri::command]
+async fn generate_worksheet(
+    entries: Vec<worksheet::VocabEntry>,
+    options: worksheet::WorksheetOptions,
+    format: worksheet::WorksheetFormat,
+) -> Result<worksheet::WorksheetOutput, String> {
+    worksheet::generate(entries, options, format).await.map_err(error_to_string)
+}
+
+#[tauri::command]
+fn open_reading_session(text: String) -> session::reading::ReadingSession {
+    session::reading::open(&text)
+}
+
+#[tauri::command]
+fn close_reading_session(session_id: String) {
+    session::reading::close(&session_id)
+}
+
+#[tauri::command]
+fn lookup_token(session_id: String, token_index: usize) -> Result<session::reading::TokenInfo, String> {
+    session::reading::lookup_token(&session_id, token_index).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn list_pins() -> Vec<pins::Pin> {
+    pins::list()
+}
+
+#[tauri::command]
+fn pin_result(pin: pins::NewPin) -> Result<pins::Pin, String> {
+    pins::pin(pin).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn unpin_result(id: String) -> Result<(), String> {
+    pins::unpin(&id).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn annotate_pin(id: String, note: Option<String>) -> Result<pins::Pin, String> {
+    pins::annotate(&id, note).map_err(error_to_string)
+}
+
+#[tauri::command]
+fn reorder_pins(ids: Vec<String>) -> Result<Vec<pins::Pin>, String> {
+    pins::reorder(&ids).map_err(error_to_string)
+}
+
+#[tauri::command]
+async fn api_call(command: String, args: Option<serde_json::Value>) -> api::ApiEnvelope<serde_json::Value> {
+    api::call(&command, args.unwrap_or(serde_json::Value::Null)).await
+}
+
+#[tauri::command]
+fn get_api_schema() -> api::ApiSchema {
+    api::schema()
 }
 
 #[tauri::command]
@@ -177,32 +803,143 @@ fn ocr_ready_check() -> Result<(), String> {
 fn main() {
     tauri::Builder::default()
         .manage(hotkey::HotkeyState::new())
+        .setup(|app| {
+            tts::events::init(app.handle().clone());
+            dictionary::settings::load_api_keys(app.handle());
+            tauri::async_runtime::spawn_blocking(|| {
+                if let Err(e) = dictionary::stardict::restore() {
+                    println!("StarDict: failed to reload dictionaries: {}", e);
+                }
+            });
+            Ok(())
+        })
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().with_handler(|app, shortcut, _event| {
             hotkey::handle_shortcut(app, shortcut);
         }).build())
         .invoke_handler(tauri::generate_handler![
             translate, 
             translate_stream, 
+            translate_draft,
+            discard_draft,
+            get_key_pool_status,
+            reset_key_pools,
+            get_circuit_status,
+            reset_circuit_breakers,
+            clear_translation_cache,
+            get_service_languages,
+            list_services,
+            confirm_translation,
+            search_translation_memory,
+            clear_translation_memory,
+            test_service,
+            detect_language,
             ocr, 
             ocr_with_engine,
+            ocr_files,
             capture_and_ocr, 
             capture_and_ocr_with_engine,
-            capture_screen, 
+            capture_ocr_translate,
+            translate_capture_lines,
+            start_ocr_watch,
+            stop_ocr_watch,
+            silent_ocr,
+            capture_screen,
+            ocr_capture_region,
+            release_capture,
+            set_ocr_spell_correction,
+            get_ocr_spell_correction,
+            list_window_rects,
+            capture_region_and_ocr, 
+            list_ocr_regions,
+            save_ocr_region,
+            delete_ocr_region,
+            ocr_region,
+            capture_window,
+            capture_window_and_ocr,
             speak,
+            speak_to_file,
+            stop_speaking,
+            set_tts_queue_policy,
+            get_tts_queue_policy,
+            pause_speaking,
+            resume_speaking,
+            list_tts_voices,
             get_phonetic,
             lookup_dictionary,
+            lookup_dictionary_grouped,
+            clear_dictionary_cache,
+            get_word_level,
+            set_dictionary_settings,
+            get_dictionary_settings,
+            lookup_wiktionary,
+            lookup_youdao,
+            lookup_word_offline,
+            ecdict_status,
+            download_ecdict,
+            import_ecdict,
+            lookup_japanese,
+            jmdict_status,
+            import_jmdict,
+            get_examples,
+            tatoeba_status,
+            import_tatoeba,
+            set_stardict_dir,
+            list_stardict_dictionaries,
+            quick_lookup,
+            check_text,
             recognize_formula,
+            recognize_formula_local,
+            check_formula_local_status,
+            stop_formula_local_worker,
             init_paddle_ocr_cmd,
             check_paddle_ocr_status,
+            start_paddle_worker,
+            stop_paddle_worker,
+            paddle_worker_status,
+            set_llm_vision_config,
+            set_paddle_options,
+            get_paddle_options,
+            list_ocr_languages,
+            get_ocr_history,
+            delete_ocr_history,
+            check_rapid_ocr_status,
+            check_tesseract_status,
+            set_tesseract_options,
+            get_tesseract_options,
             hotkey::get_hotkeys, 
             hotkey::set_hotkey, 
             hotkey::register_hotkeys, 
             hotkey::clear_hotkey_processing, 
             get_mouse_monitor,
             emit_to_main,
+            emit_to_window,
+            get_event_names,
+            set_event_names,
+            pause_background_tasks,
+            resume_background_tasks,
+            get_app_status,
+            get_nllb_status,
+            download_nllb_model,
+            delete_nllb_model,
+            stop_nllb_worker,
+            start_session,
+            add_session_entry,
+            export_session_report,
+            generate_worksheet,
+            open_reading_session,
+            close_reading_session,
+            lookup_token,
+            list_pins,
+            pin_result,
+            unpin_result,
+            annotate_pin,
+            reorder_pins,
+            api_call,
+            get_api_schema,
             ocr_ready_check
         ])
         .run(tauri::generate_context!())
@@ -7,6 +7,29 @@ pub struct TranslationRequest {
     pub target_lang: String,
     pub services: Vec<String>,
     pub config: Option<std::collections::HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub mode: TranslationMode,
+    #[serde(default)]
+    pub format: TextFormat,
+}
+
+/// 原文格式；`markdown` / `html` 只翻译文本节点，保留代码块、链接和标签
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextFormat {
+    #[default]
+    Plain,
+    Markdown,
+    Html,
+}
+
+/// `all` 同时请求所有服务；`fallback` 按顺序尝试，返回第一个成功结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationMode {
+    #[default]
+    All,
+    Fallback,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,9 +37,23 @@ pub struct TranslationResult {
     pub name: String,
     pub text: String,
     pub error: Option<String>,
+    /// 其他候选译文，最佳结果在 `text` 中
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslationResponse {
     pub results: Vec<TranslationResult>,
+    /// 翻译记忆中的相似译文
+    #[serde(default)]
+    pub memory: Vec<MemoryMatch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryMatch {
+    pub source: String,
+    pub text: String,
+    pub service: Option<String>,
+    pub similarity: f64,
 }
@@ -0,0 +1,51 @@
+//! 截图中的二维码识别（rqrr），解码内容随 OCR 结果一并返回。
+
+use crate::ocr::models::Barcode;
+use image::GrayImage;
+
+/// 识别灰度图中的所有二维码；无法解码的（残缺、模糊）直接跳过
+pub fn detect(image: &GrayImage) -> Vec<Barcode> {
+    let (w, h) = image.dimensions();
+    let mut prepared =
+        rqrr::PreparedImage::prepare_from_greyscale(w as usize, h as usize, |x, y| image.get_pixel(x as u32, y as u32)[0]);
+    prepared
+        .detect_grids()
+        .into_iter()
+        .filter_map(|grid| {
+            let (_, text) = grid
+                .decode()
+                .map_err(|e| println!("Failed to decode QR code: {}", e))
+                .ok()?;
+            let xs = grid.bounds.iter().map(|p| p.x as f64);
+            let ys = grid.bounds.iter().map(|p| p.y as f64);
+            let (left, right) = (xs.clone().fold(f64::MAX, f64::min), xs.fold(f64::MIN, f64::max));
+            let (top, bottom) = (ys.clone().fold(f64::MAX, f64::min), ys.fold(f64::MIN, f64::max));
+            Some(Barcode {
+                format: "qr".to_string(),
+                text,
+                x: left,
+                y: top,
+                width: right - left,
+                height: bottom - top,
+            })
+        })
+        .collect()
+}
+
+/// 识别编码后的图像（PNG/BMP 等）中的二维码；无法解码的图像视为没有二维码
+pub fn detect_encoded(image_data: &[u8]) -> Vec<Barcode> {
+    image::load_from_memory(image_data)
+        .map(|image| detect(&image.to_luma8()))
+        .unwrap_or_default()
+}
+
+/// 识别 BGRA 截图中的二维码
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn detect_bgra(pixels: &[u8], w: i32, h: i32) -> Vec<Barcode> {
+    let image = GrayImage::from_fn(w as u32, h as u32, |x, y| {
+        let i = ((y * w as u32 + x) * 4) as usize;
+        let (b, g, r) = (pixels[i] as u32, pixels[i + 1] as u32, pixels[i + 2] as u32);
+        image::Luma([((r * 299 + g * 587 + b * 114) / 1000) as u8])
+    });
+    detect(&image)
+}
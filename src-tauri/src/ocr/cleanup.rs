@@ -0,0 +1,75 @@
+//! 识别文本的整理：排版造成的硬换行合并为句子，行尾被连字符断开的单词重新接上，
+//! 中日韩文字之间多余的空格被去掉。否则每个视觉行在翻译时都会被当作单独的句子。
+//! 空行分隔的段落保持不变。
+
+use crate::ocr::tesseract::is_cjk;
+
+/// 句末标点：以它们结尾的行保留换行
+const SENTENCE_ENDS: &[char] = &['.', '!', '?', ':', ';', '。', '！', '？', '：', '；', '…'];
+
+/// 列表项开头：以它们开头的行不并入上一行
+const BULLETS: &[char] = &['•', '·', '-', '*', '–', '—', '●', '■', '▪'];
+
+fn is_cjk_text(c: char) -> bool {
+    is_cjk(c) || ('\u{3000}'..='\u{303F}').contains(&c)
+}
+
+/// 去掉两侧都是中日韩文字（含标点）的空格
+fn remove_cjk_spaces(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut output = String::with_capacity(line.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let before = output.chars().last();
+            let after = chars[i + 1..].iter().find(|c| **c != ' ');
+            if before.is_some_and(is_cjk_text) && after.is_some_and(|c| is_cjk_text(*c)) {
+                continue;
+            }
+        }
+        output.push(c);
+    }
+    output
+}
+
+fn starts_list_item(line: &str) -> bool {
+    let mut chars = line.chars();
+    match chars.next() {
+        Some(c) if BULLETS.contains(&c) => chars.next().is_some_and(char::is_whitespace),
+        Some(c) if c.is_ascii_digit() => {
+            let rest = line.trim_start_matches(|c: char| c.is_ascii_digit());
+            rest.starts_with(". ") || rest.starts_with(") ")
+        }
+        _ => false,
+    }
+}
+
+/// 合并一个段落内的各行
+fn merge_lines(paragraph: &str) -> String {
+    let mut merged = String::new();
+    for line in paragraph.lines().map(|line| remove_cjk_spaces(line.trim())).filter(|line| !line.is_empty()) {
+        let Some(last) = merged.chars().last() else {
+            merged = line;
+            continue;
+        };
+        let first = line.chars().next().unwrap();
+        let before_hyphen = merged.chars().rev().nth(1);
+        if last == '-' && before_hyphen.is_some_and(char::is_alphabetic) && first.is_lowercase() {
+            // “exam-\nple” → “example”
+            merged.pop();
+        } else if SENTENCE_ENDS.contains(&last) || starts_list_item(&line) {
+            merged.push('\n');
+        } else if !(last == '-' || is_cjk_text(last) || is_cjk_text(first)) {
+            merged.push(' ');
+        }
+        merged.push_str(&line);
+    }
+    merged
+}
+
+pub fn clean(text: &str) -> String {
+    text.split("\n\n")
+        .map(merge_lines)
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
@@ -0,0 +1,29 @@
+//! 不提供置信度的引擎（Windows OCR）的置信度估计：按识别出的字符中“像正常文字”的比例打分，
+//! 孤立符号（多为噪点）和过短的结果会拉低分数。得分在 0~1 之间，与其他引擎一致。
+
+/// 结果少于该字符数时按比例降低可信度
+const SUPPORT_CHARS: f64 = 8.0;
+
+/// 常见标点，与字母、数字、表意文字一样视为正常文字
+const PUNCTUATION: &str = ",.;:!?'\"()[]-–—%&/+=#@$€£¥、。，；：！？「」『』（）《》〈〉【】“”‘’…·～";
+
+fn is_plausible(c: char) -> bool {
+    c.is_alphanumeric() || PUNCTUATION.contains(c)
+}
+
+/// 由识别出的词估计置信度；没有文字时为 0
+pub fn estimate(words: &[&str]) -> f64 {
+    let words: Vec<&str> = words.iter().map(|w| w.trim()).filter(|w| !w.is_empty()).collect();
+    let total = words.iter().map(|w| w.chars().count()).sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let plausible = words.iter().flat_map(|w| w.chars()).filter(|c| is_plausible(*c)).count();
+    let noise = words.iter().filter(|w| !w.chars().any(char::is_alphanumeric)).count();
+
+    let ratio = plausible as f64 / total as f64;
+    let noise_penalty = 1.0 - 0.5 * noise as f64 / words.len() as f64;
+    let support = 0.6 + 0.4 * (total as f64 / SUPPORT_CHARS).min(1.0);
+    ratio * noise_penalty * support
+}
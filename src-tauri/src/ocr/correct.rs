@@ -0,0 +1,203 @@
+//! OCR 输出纠错。对低置信度的行，将既不在词频表、也不在离线词典（ECDICT，已安装时）
+//! 中的英文单词替换为编辑距离为 1 的最常见词，并优先考虑 OCR 常见的字形混淆
+//! （`rn`→`m`、`vv`→`w`、`0`→`o` 等）；以数字为主的片段则把形近字母还原为数字
+//! （`l`→`1`、`O`→`0`）。引擎未提供置信度时无法判断哪些行可疑，不做处理。默认关闭。
+
+use crate::ocr::models::OcrResult;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 内置的常用英文词表，按词频从高到低排列
+const BUILTIN_WORDS: &str = include_str!("wordfreq.txt");
+/// 用户可在数据目录放置更大的词频表（每行一个词，可附带次数，按词频降序）
+const USER_WORDS_FILE: &str = "wordfreq.txt";
+
+/// 行置信度低于该值才纠错
+const CONFIDENCE_THRESHOLD: f64 = 0.85;
+
+/// 字形混淆对：`(识别结果, 实际字符)`
+const CONFUSIONS: &[(&str, &str)] = &[
+    ("rn", "m"),
+    ("m", "rn"),
+    ("vv", "w"),
+    ("cl", "d"),
+    ("li", "h"),
+    ("0", "o"),
+    ("1", "l"),
+    ("5", "s"),
+    ("8", "b"),
+    ("I", "l"),
+    ("l", "i"),
+];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 词 → 频次排名（越小越常见）
+static RANKS: Lazy<HashMap<String, usize>> = Lazy::new(|| {
+    let user_words = std::fs::read_to_string(crate::paths::data_dir().join(USER_WORDS_FILE)).unwrap_or_default();
+    let mut ranks = HashMap::new();
+    let words = user_words
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .chain(BUILTIN_WORDS.split_whitespace());
+    for word in words {
+        let next = ranks.len();
+        ranks.entry(word.to_lowercase()).or_insert(next);
+    }
+    ranks
+});
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 开关开启时纠正低置信度的行
+pub fn apply(result: OcrResult) -> OcrResult {
+    if !is_enabled() {
+        return result;
+    }
+    correct_low_confidence(result)
+}
+
+/// 只纠正置信度低于阈值的行；没有任何行级置信度时原样返回
+pub(crate) fn correct_low_confidence(mut result: OcrResult) -> OcrResult {
+    if !result.lines.iter().any(|l| l.confidence.is_some()) {
+        return result;
+    }
+
+    for line in &mut result.lines {
+        if line.confidence.unwrap_or(0.0) >= CONFIDENCE_THRESHOLD {
+            continue;
+        }
+        let corrected = correct_text(&line.text);
+        if corrected != line.text {
+            result.text = result.text.replacen(&line.text, &corrected, 1);
+            line.text = corrected;
+        }
+    }
+    result
+}
+
+/// 词频表或离线词典中存在的词视为拼写正确
+fn is_known_word(lower: &str) -> bool {
+    RANKS.contains_key(lower) || matches!(crate::dictionary::ecdict::lookup(lower), Ok(Some(_)))
+}
+
+/// 逐个纠正由字母和数字组成的片段，其余字符原样保留
+pub fn correct_text(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut token = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            token.push(c);
+        } else {
+            output.push_str(&correct_token(&token));
+            token.clear();
+            output.push(c);
+        }
+    }
+    output.push_str(&correct_token(&token));
+    output
+}
+
+fn correct_token(token: &str) -> String {
+    if token.len() < 2 {
+        return token.to_string();
+    }
+
+    let digits = token.chars().filter(|c| c.is_ascii_digit()).count();
+    if digits * 2 >= token.len() {
+        return token
+            .chars()
+            .map(|c| match c {
+                'l' | 'I' | '|' => '1',
+                'O' | 'o' => '0',
+                'S' => '5',
+                'B' => '8',
+                other => other,
+            })
+            .collect();
+    }
+
+    let lower = token.to_lowercase();
+    if is_known_word(&lower) {
+        return token.to_string();
+    }
+
+    let best = confusion_candidates(token)
+        .into_iter()
+        .filter_map(|c| RANKS.get(&c).map(|rank| (*rank, c)))
+        .min()
+        .or_else(|| {
+            edit_candidates(&lower)
+                .into_iter()
+                .filter_map(|c| RANKS.get(&c).map(|rank| (*rank, c)))
+                .min()
+        });
+
+    match best {
+        Some((_, word)) => match_case(token, &word),
+        None => token.to_string(),
+    }
+}
+
+/// 对每处混淆字符串做一次替换
+fn confusion_candidates(token: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    for (seen, actual) in CONFUSIONS {
+        for (pos, _) in token.match_indices(seen) {
+            let candidate = format!("{}{}{}", &token[..pos], actual, &token[pos + seen.len()..]);
+            candidates.push(candidate.to_lowercase());
+        }
+    }
+    candidates
+}
+
+/// 删除、换位、替换、插入各一次
+fn edit_candidates(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut candidates = Vec::new();
+    for i in 0..=chars.len() {
+        if i < chars.len() {
+            let mut deleted = chars.clone();
+            deleted.remove(i);
+            candidates.push(deleted.iter().collect());
+        }
+        if i + 1 < chars.len() {
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            candidates.push(swapped.iter().collect());
+        }
+        for c in 'a'..='z' {
+            if i < chars.len() {
+                let mut replaced = chars.clone();
+                replaced[i] = c;
+                candidates.push(replaced.iter().collect());
+            }
+            let mut inserted = chars.clone();
+            inserted.insert(i, c);
+            candidates.push(inserted.iter().collect());
+        }
+    }
+    candidates
+}
+
+fn match_case(original: &str, word: &str) -> String {
+    let letters: Vec<char> = original.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) {
+        word.to_uppercase()
+    } else if original.chars().next().is_some_and(|c| c.is_uppercase()) {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    } else {
+        word.to_string()
+    }
+}
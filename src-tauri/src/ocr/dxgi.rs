@@ -0,0 +1,232 @@
+//! 基于 DXGI 桌面复制（Desktop Duplication）的截图。与 GDI 的 BitBlt 相比，
+//! 能正确截取硬件加速窗口，HDR 显示器的画面由系统转换为 8 位 BGRA；
+//! 复制对象会被缓存，画面无变化时直接复用上一帧，连续截图（实时 OCR）开销很小。
+
+use crate::error::{AppError, Result};
+use crate::ocr::region;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use windows::core::ComInterface;
+use windows::Win32::Foundation::{HMODULE, RECT};
+use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_UNKNOWN;
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D, D3D11_CPU_ACCESS_READ,
+    D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAPPED_SUBRESOURCE, D3D11_MAP_READ, D3D11_SDK_VERSION,
+    D3D11_TEXTURE2D_DESC, D3D11_USAGE_STAGING,
+};
+use windows::Win32::Graphics::Dxgi::Common::{
+    DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_MODE_ROTATION_IDENTITY, DXGI_MODE_ROTATION_UNSPECIFIED, DXGI_SAMPLE_DESC,
+};
+use windows::Win32::Graphics::Dxgi::{
+    CreateDXGIFactory1, IDXGIAdapter1, IDXGIFactory1, IDXGIOutput1, IDXGIOutputDuplication, DXGI_ERROR_WAIT_TIMEOUT,
+    DXGI_OUTDUPL_DESC, DXGI_OUTDUPL_FRAME_INFO,
+};
+
+/// 新建复制对象后等待首帧的时间
+const FIRST_FRAME_TIMEOUT_MS: u32 = 500;
+
+/// 一台显示器的复制对象及保存最近一帧的 CPU 可读纹理
+struct Output {
+    context: ID3D11DeviceContext,
+    duplication: IDXGIOutputDuplication,
+    staging: ID3D11Texture2D,
+    /// 显示器在虚拟屏幕中的位置
+    rect: RECT,
+    has_frame: bool,
+}
+
+struct Duplicator {
+    outputs: Vec<Output>,
+    /// 无法复制的显示器（如旋转的显示器），涉及它们的区域改用 GDI
+    skipped: Vec<(i32, i32, i32, i32)>,
+}
+
+// D3D11 设备是自由线程的；设备上下文和复制对象只在 DUPLICATOR 的锁内使用
+unsafe impl Send for Duplicator {}
+
+static DUPLICATOR: Lazy<Mutex<Option<Duplicator>>> = Lazy::new(|| Mutex::new(None));
+
+fn dxgi_error(action: &str, error: windows::core::Error) -> AppError {
+    AppError::Ocr(format!("{} failed: {}", action, error))
+}
+
+impl Duplicator {
+    /// 为每个适配器创建 D3D11 设备，并复制其上所有连接到桌面、未旋转的显示器
+    unsafe fn new() -> Result<Self> {
+        let factory: IDXGIFactory1 = CreateDXGIFactory1().map_err(|e| dxgi_error("CreateDXGIFactory1", e))?;
+        let mut outputs = Vec::new();
+        let mut skipped = Vec::new();
+        let mut adapter_index = 0;
+        while let Ok(adapter) = factory.EnumAdapters1(adapter_index) {
+            adapter_index += 1;
+            let mut output_index = 0;
+            let mut device: Option<(ID3D11Device, ID3D11DeviceContext)> = None;
+            while let Ok(output) = adapter.EnumOutputs(output_index) {
+                output_index += 1;
+                let Ok(desc) = output.GetDesc() else { continue };
+                if !desc.AttachedToDesktop.as_bool() {
+                    continue;
+                }
+                if device.is_none() {
+                    device = Some(create_device(&adapter)?);
+                }
+                let (device, context) = device.as_ref().unwrap();
+                let rect = desc.DesktopCoordinates;
+                let duplicated = output
+                    .cast::<IDXGIOutput1>()
+                    .map_err(|e| dxgi_error("IDXGIOutput1", e))
+                    .and_then(|output| duplicate(&output, device));
+                match duplicated {
+                    Ok((duplication, staging)) => outputs.push(Output {
+                        context: context.clone(),
+                        duplication,
+                        staging,
+                        rect,
+                        has_frame: false,
+                    }),
+                    Err(e) => {
+                        println!("Skipping display for desktop duplication: {}", e);
+                        skipped.push((rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top));
+                    }
+                }
+            }
+        }
+        if outputs.is_empty() {
+            return Err(AppError::Ocr("No display supports desktop duplication".to_string()));
+        }
+        Ok(Duplicator { outputs, skipped })
+    }
+}
+
+unsafe fn create_device(adapter: &IDXGIAdapter1) -> Result<(ID3D11Device, ID3D11DeviceContext)> {
+    let mut device = None;
+    let mut context = None;
+    D3D11CreateDevice(
+        adapter,
+        D3D_DRIVER_TYPE_UNKNOWN,
+        HMODULE::default(),
+        D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+        None,
+        D3D11_SDK_VERSION,
+        Some(&mut device),
+        None,
+        Some(&mut context),
+    )
+    .map_err(|e| dxgi_error("D3D11CreateDevice", e))?;
+    match (device, context) {
+        (Some(device), Some(context)) => Ok((device, context)),
+        _ => Err(AppError::Ocr("D3D11CreateDevice returned no device".to_string())),
+    }
+}
+
+/// 创建复制对象和同尺寸的暂存纹理；旋转的显示器画面方向与桌面坐标不一致，交给 GDI 处理
+unsafe fn duplicate(output: &IDXGIOutput1, device: &ID3D11Device) -> Result<(IDXGIOutputDuplication, ID3D11Texture2D)> {
+    let duplication = output.DuplicateOutput(device).map_err(|e| dxgi_error("DuplicateOutput", e))?;
+    let mut desc = DXGI_OUTDUPL_DESC::default();
+    duplication.GetDesc(&mut desc);
+    if desc.Rotation != DXGI_MODE_ROTATION_IDENTITY && desc.Rotation != DXGI_MODE_ROTATION_UNSPECIFIED {
+        return Err(AppError::Ocr("Rotated display".to_string()));
+    }
+
+    let texture_desc = D3D11_TEXTURE2D_DESC {
+        Width: desc.ModeDesc.Width,
+        Height: desc.ModeDesc.Height,
+        MipLevels: 1,
+        ArraySize: 1,
+        Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+        SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
+        Usage: D3D11_USAGE_STAGING,
+        BindFlags: 0,
+        CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+        MiscFlags: 0,
+    };
+    let mut staging = None;
+    device
+        .CreateTexture2D(&texture_desc, None, Some(&mut staging))
+        .map_err(|e| dxgi_error("CreateTexture2D", e))?;
+    let staging = staging.ok_or_else(|| AppError::Ocr("CreateTexture2D returned no texture".to_string()))?;
+    Ok((duplication, staging))
+}
+
+impl Output {
+    fn bounds(&self) -> (i32, i32, i32, i32) {
+        (self.rect.left, self.rect.top, self.rect.right - self.rect.left, self.rect.bottom - self.rect.top)
+    }
+
+    /// 取最新一帧到暂存纹理；画面自上次以来没有变化时保留原内容
+    unsafe fn update(&mut self) -> Result<()> {
+        let timeout = if self.has_frame { 0 } else { FIRST_FRAME_TIMEOUT_MS };
+        let mut info = DXGI_OUTDUPL_FRAME_INFO::default();
+        let mut resource = None;
+        match self.duplication.AcquireNextFrame(timeout, &mut info, &mut resource) {
+            Ok(()) => {}
+            Err(e) if e.code() == DXGI_ERROR_WAIT_TIMEOUT && self.has_frame => return Ok(()),
+            Err(e) => return Err(dxgi_error("AcquireNextFrame", e)),
+        }
+
+        let copied = resource
+            .ok_or_else(|| AppError::Ocr("AcquireNextFrame returned no resource".to_string()))
+            .and_then(|resource| resource.cast::<ID3D11Texture2D>().map_err(|e| dxgi_error("ID3D11Texture2D", e)))
+            .map(|texture| self.context.CopyResource(&self.staging, &texture));
+        let _ = self.duplication.ReleaseFrame();
+        copied?;
+        self.has_frame = true;
+        Ok(())
+    }
+
+    /// 从暂存纹理中读出 `part`（虚拟屏幕坐标）的像素（BGRA，自上而下）
+    unsafe fn read(&self, part: (i32, i32, i32, i32)) -> Result<Vec<u8>> {
+        let (x, y, w, h) = part;
+        let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+        self.context
+            .Map(&self.staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+            .map_err(|e| dxgi_error("Map", e))?;
+
+        let row_bytes = (w * 4) as usize;
+        let mut pixels = vec![0u8; row_bytes * h as usize];
+        let source = mapped.pData as *const u8;
+        for row in 0..h as usize {
+            let offset = (y - self.rect.top) as usize * mapped.RowPitch as usize
+                + row * mapped.RowPitch as usize
+                + (x - self.rect.left) as usize * 4;
+            std::ptr::copy_nonoverlapping(source.add(offset), pixels.as_mut_ptr().add(row * row_bytes), row_bytes);
+        }
+
+        self.context.Unmap(&self.staging, 0);
+        Ok(pixels)
+    }
+}
+
+/// 截取虚拟屏幕坐标下的区域，返回 BGRA 像素；区域可跨多个显示器，不在任何显示器上的部分为白色。
+/// 区域涉及无法复制的显示器或完全不在显示器上时返回 `None`
+pub fn capture(x: i32, y: i32, w: i32, h: i32) -> Result<Option<Vec<u8>>> {
+    if w <= 0 || h <= 0 {
+        return Ok(None);
+    }
+    let mut guard = DUPLICATOR.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(unsafe { Duplicator::new()? });
+    }
+    let duplicator = guard.as_mut().unwrap();
+    let target = (x, y, w, h);
+    if duplicator.skipped.iter().any(|rect| region::intersect(target, *rect).is_some()) {
+        return Ok(None);
+    }
+
+    let mut pixels = vec![255u8; (w * h * 4) as usize];
+    let mut covered = false;
+    let result = duplicator.outputs.iter_mut().try_for_each(|output| {
+        let Some(part) = region::intersect(target, output.bounds()) else {
+            return Ok(());
+        };
+        covered = true;
+        let part_pixels = unsafe { output.update().and_then(|_| output.read(part))? };
+        region::blit(&mut pixels, target, part, &part_pixels);
+        Ok(())
+    });
+    if result.is_err() {
+        // 分辨率变化、切换到安全桌面等会使复制对象失效，下次截图时重新创建
+        *guard = None;
+    }
+    result.map(|_| covered.then_some(pixels))
+}
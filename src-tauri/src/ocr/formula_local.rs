@@ -0,0 +1,89 @@
+//! 本地公式识别：在常驻的 Python 工作进程中运行 Pix2Text 的 LaTeX-OCR 模型，
+//! 作为 Mathpix（需付费密钥）的免费替代。需要 `pip install pix2text`。
+
+use crate::error::{AppError, Result};
+use crate::ocr::mathpix::MathpixResult;
+use crate::ocr::paddle::{create_command, find_python};
+use crate::ocr::worker::{self, WorkerSlot};
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+
+/// 常驻工作进程脚本：每个请求给出公式图像的路径，响应 LaTeX 及模型给出的分数
+const WORKER_SCRIPT: &str = r#"
+import io
+import json
+import sys
+
+out = sys.stdout
+sys.stdout = sys.stderr
+requests = io.TextIOWrapper(sys.stdin.buffer, encoding='utf-8')
+
+def reply(payload):
+    out.write(json.dumps(payload) + "\n")
+    out.flush()
+
+try:
+    from pix2text import LatexOCR
+    model = LatexOCR.from_config() if hasattr(LatexOCR, 'from_config') else LatexOCR()
+except Exception as e:
+    reply({"error": str(e)})
+    sys.exit(1)
+
+reply({"ready": True})
+
+for request in requests:
+    try:
+        result = model.recognize(json.loads(request)["path"])
+        if isinstance(result, dict):
+            reply({"latex": result.get("text", ""), "confidence": result.get("score")})
+        else:
+            reply({"latex": str(result), "confidence": None})
+    except Exception as e:
+        reply({"error": str(e)})
+"#;
+
+static WORKER: WorkerSlot = WorkerSlot::new("Pix2Text", WORKER_SCRIPT);
+
+#[derive(Deserialize)]
+struct LocalFormula {
+    latex: String,
+    confidence: Option<f64>,
+}
+
+pub fn is_available() -> bool {
+    let Some(python_cmd) = find_python() else {
+        return false;
+    };
+    create_command(&python_cmd)
+        .args(["-c", "import pix2text"])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// 停止工作进程，释放模型占用的内存
+pub fn stop_worker() {
+    WORKER.stop()
+}
+
+/// 识别 base64 编码的公式图像，返回与 Mathpix 相同结构的结果；首次调用时加载模型
+pub fn recognize_formula(image_data: &str) -> Result<MathpixResult> {
+    let image = general_purpose::STANDARD
+        .decode(image_data)
+        .map_err(|e| AppError::InvalidRequest(format!("Invalid base64 image data: {}", e)))?;
+    let response = worker::with_temp_image("formula_local", &image, |path| {
+        WORKER.request(&[], &serde_json::json!({ "path": path.to_string_lossy() }))
+    })?;
+    let formula: LocalFormula = serde_json::from_value(response)
+        .map_err(|e| AppError::Ocr(format!("Failed to parse Pix2Text output: {}", e)))?;
+
+    Ok(MathpixResult {
+        latex: formula.latex.trim().to_string(),
+        latex_styled: None,
+        confidence: formula.confidence,
+        error: None,
+        mathml: None,
+        asciimath: None,
+        text: None,
+    })
+}
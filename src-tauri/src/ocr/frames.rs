@@ -0,0 +1,62 @@
+//! 保留最近的截图帧：遮罩窗口调整选区后可直接在已截取的图像上重新识别，
+//! 无需再次截屏（再次截屏会闪烁，且画面可能已经变化）。
+
+use crate::error::{AppError, Result};
+use crate::ocr::presets::RegionRect;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// 最多保留的帧数；超出时丢弃最早的帧，避免整屏截图长期占用内存
+const MAX_FRAMES: usize = 4;
+
+/// (截图 ID, 编码后的图像)
+type Frame = (String, Vec<u8>);
+
+/// 最新的帧在末尾
+static FRAMES: Lazy<Mutex<VecDeque<Frame>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// 保留一帧；同一 ID 再次保留时替换旧帧
+pub fn retain(capture_id: &str, image_data: Vec<u8>) {
+    let mut frames = FRAMES.lock().unwrap();
+    frames.retain(|(id, _)| id != capture_id);
+    if frames.len() == MAX_FRAMES {
+        frames.pop_front();
+    }
+    frames.push_back((capture_id.to_string(), image_data));
+}
+
+pub fn get(capture_id: &str) -> Result<Vec<u8>> {
+    FRAMES
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(id, _)| id == capture_id)
+        .map(|(_, data)| data.clone())
+        .ok_or_else(|| AppError::InvalidRequest(format!("Capture not found or expired: {}", capture_id)))
+}
+
+/// 不再需要时释放帧
+pub fn release(capture_id: &str) {
+    FRAMES.lock().unwrap().retain(|(id, _)| id != capture_id);
+}
+
+/// 从帧中裁出选区（帧内像素坐标）并编码为 PNG；超出帧的部分被裁掉
+pub fn crop(image_data: &[u8], rect: RegionRect) -> Result<Vec<u8>> {
+    let image = image::load_from_memory(image_data)
+        .map_err(|e| AppError::Ocr(format!("Failed to decode captured frame: {}", e)))?;
+    let left = rect.x.max(0) as u32;
+    let top = rect.y.max(0) as u32;
+    let right = (rect.x.saturating_add(rect.width).max(0) as u32).min(image.width());
+    let bottom = (rect.y.saturating_add(rect.height).max(0) as u32).min(image.height());
+    if right <= left || bottom <= top {
+        return Err(AppError::InvalidRequest("Selection is outside the captured frame".to_string()));
+    }
+
+    let mut data = Vec::new();
+    image
+        .crop_imm(left, top, right - left, bottom - top)
+        .write_to(&mut std::io::Cursor::new(&mut data), image::ImageFormat::Png)
+        .map_err(|e| AppError::Ocr(format!("Failed to encode selection: {}", e)))?;
+    Ok(data)
+}
@@ -0,0 +1,150 @@
+//! OCR 历史记录：每次识别的文本、引擎、时间和截图缩略图保存在本地 SQLite 数据库中，
+//! 误关的静默识别结果也能找回。只保留最近 `CAPACITY` 条。
+
+use crate::error::{AppError, Result};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Utc};
+use image::DynamicImage;
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::sync::Mutex;
+
+const CAPACITY: i64 = 500;
+/// 缩略图最长边的像素数
+const THUMBNAIL_SIZE: u32 = 160;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrHistoryEntry {
+    pub id: i64,
+    pub text: String,
+    pub engine: String,
+    pub created_at: DateTime<Utc>,
+    /// base64 编码的 PNG 缩略图
+    pub thumbnail: Option<String>,
+}
+
+/// None 表示尚未打开数据库
+static DB: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+
+fn db_error(e: rusqlite::Error) -> AppError {
+    AppError::Ocr(format!("OCR history database error: {}", e))
+}
+
+fn open() -> Result<Connection> {
+    let dir = crate::paths::data_dir();
+    std::fs::create_dir_all(&dir)?;
+    let conn = Connection::open(dir.join("ocr_history.db")).map_err(db_error)?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ocr_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            text TEXT NOT NULL,
+            engine TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            thumbnail BLOB
+        )",
+    )
+    .map_err(db_error)?;
+    Ok(conn)
+}
+
+/// 在打开的数据库上执行操作
+fn with_db<T>(f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let mut guard = DB.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open()?);
+    }
+    f(guard.as_ref().unwrap())
+}
+
+fn thumbnail(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .write_to(&mut std::io::Cursor::new(&mut data), image::ImageFormat::Png)
+        .ok()?;
+    Some(data)
+}
+
+fn insert(text: &str, engine: &str, image: Option<DynamicImage>) -> Result<i64> {
+    let thumbnail = image.as_ref().and_then(thumbnail);
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO ocr_history (text, engine, created_at, thumbnail) VALUES (?1, ?2, ?3, ?4)",
+            params![text, engine, Utc::now().to_rfc3339(), thumbnail],
+        )
+        .map_err(db_error)?;
+        let id = conn.last_insert_rowid();
+        conn.execute("DELETE FROM ocr_history WHERE id <= ?1", params![id - CAPACITY])
+            .map_err(db_error)?;
+        Ok(id)
+    })
+}
+
+/// 记录一次识别结果；空文本不记录，写入失败只打印日志，不影响识别本身
+pub fn record(text: &str, engine: &str, image: Option<DynamicImage>) {
+    if text.trim().is_empty() {
+        return;
+    }
+    if let Err(e) = insert(text, engine, image) {
+        println!("Failed to record OCR history: {}", e);
+    }
+}
+
+/// 记录编码后图像（PNG/BMP 等）的识别结果
+pub fn record_encoded(text: &str, engine: &str, image_data: &[u8]) {
+    record(text, engine, image::load_from_memory(image_data).ok());
+}
+
+/// 记录 BGRA 截图的识别结果
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn record_bgra(text: &str, engine: &str, pixels: &[u8], w: i32, h: i32) {
+    let rgba: Vec<u8> = pixels.chunks_exact(4).flat_map(|p| [p[2], p[1], p[0], 255]).collect();
+    let image = image::RgbaImage::from_raw(w as u32, h as u32, rgba).map(DynamicImage::ImageRgba8);
+    record(text, engine, image);
+}
+
+/// 按时间倒序列出历史记录
+pub fn list(limit: Option<usize>, offset: Option<usize>) -> Result<Vec<OcrHistoryEntry>> {
+    let limit = limit.map(|n| n as i64).unwrap_or(CAPACITY);
+    let offset = offset.unwrap_or(0) as i64;
+    with_db(|conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, text, engine, created_at, thumbnail FROM ocr_history
+                 ORDER BY id DESC LIMIT ?1 OFFSET ?2",
+            )
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![limit, offset], |row| {
+                let created_at: String = row.get(3)?;
+                let thumbnail: Option<Vec<u8>> = row.get(4)?;
+                Ok(OcrHistoryEntry {
+                    id: row.get(0)?,
+                    text: row.get(1)?,
+                    engine: row.get(2)?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map(|time| time.with_timezone(&Utc))
+                        .unwrap_or_default(),
+                    thumbnail: thumbnail.map(|data| general_purpose::STANDARD.encode(data)),
+                })
+            })
+            .map_err(db_error)?;
+        rows.collect::<std::result::Result<Vec<_>, _>>().map_err(db_error)
+    })
+}
+
+/// 删除指定记录；`ids` 为 `None` 时清空全部。返回删除的条数
+pub fn delete(ids: Option<&[i64]>) -> Result<usize> {
+    with_db(|conn| {
+        let Some(ids) = ids else {
+            return conn.execute("DELETE FROM ocr_history", params![]).map_err(db_error);
+        };
+        let mut deleted = 0;
+        for id in ids {
+            deleted += conn.execute("DELETE FROM ocr_history WHERE id = ?1", params![id]).map_err(db_error)?;
+        }
+        Ok(deleted)
+    })
+}
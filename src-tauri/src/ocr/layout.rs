@@ -0,0 +1,180 @@
+//! 多栏版面的阅读顺序重排。引擎按行从上到下输出时，双栏文档的左右两栏会交错；
+//! 这里利用行的外接矩形把页面切成若干水平带（跨栏的标题等宽行作为分隔），
+//! 每个带内按水平投影的空白间隙聚成栏，再按“先左栏自上而下、再右栏”的顺序输出。
+//! 在此基础上按行距、首行缩进和短行把每栏的行归并为段落。
+
+use crate::ocr::models::{OcrLine, OcrParagraph};
+
+/// 宽度超过页面该比例的行视为跨栏行
+const SPANNING_RATIO: f64 = 0.6;
+/// 栏间空白至少为行高中位数的倍数
+const MIN_GAP_LINE_HEIGHTS: f64 = 1.5;
+/// 行间空白超过行高中位数的该倍数时分段
+const PARAGRAPH_GAP_LINE_HEIGHTS: f64 = 0.75;
+/// 首行缩进至少为行高中位数的该倍数
+const INDENT_LINE_HEIGHTS: f64 = 1.0;
+/// 行宽不足所在栏宽的该比例时视为段落末行
+const SHORT_LINE_RATIO: f64 = 0.7;
+
+/// 阅读顺序中的一块：同一栏内连续的行
+struct Block<'a> {
+    column: usize,
+    lines: Vec<&'a OcrLine>,
+}
+
+/// 版面分析结果
+pub struct Layout {
+    /// 检测到的最多栏数
+    pub columns: usize,
+    pub paragraphs: Vec<OcrParagraph>,
+}
+
+impl Layout {
+    /// 多栏时段落之间空一行、段内保留原有换行；单栏时不分段，逐行输出
+    pub fn text(&self) -> String {
+        let separator = if self.columns > 1 { "\n\n" } else { "\n" };
+        self.paragraphs.iter().map(|p| p.text.as_str()).collect::<Vec<_>>().join(separator)
+    }
+}
+
+fn median_height(lines: &[OcrLine]) -> f64 {
+    let mut heights: Vec<f64> = lines.iter().map(|l| l.height).collect();
+    heights.sort_by(|a, b| a.total_cmp(b));
+    heights[heights.len() / 2]
+}
+
+/// 按阅读顺序切分为块，并返回最多的栏数。跨栏行和只有一栏的水平带并入同一个通栏块
+fn blocks(lines: &[OcrLine], page_width: f64, min_gap: f64) -> (Vec<Block<'_>>, usize) {
+    let mut sorted: Vec<&OcrLine> = lines.iter().collect();
+    sorted.sort_by(|a, b| a.y.total_cmp(&b.y));
+
+    let mut blocks = Vec::new();
+    let mut flow: Vec<&OcrLine> = Vec::new();
+    let mut band: Vec<&OcrLine> = Vec::new();
+    let mut max_columns = 1;
+
+    for line in sorted {
+        if line.width >= page_width * SPANNING_RATIO {
+            max_columns = max_columns.max(flush_band(&mut band, &mut flow, &mut blocks, min_gap));
+            flow.push(line);
+        } else {
+            band.push(line);
+        }
+    }
+    max_columns = max_columns.max(flush_band(&mut band, &mut flow, &mut blocks, min_gap));
+    if !flow.is_empty() {
+        blocks.push(Block { column: 0, lines: flow });
+    }
+    (blocks, max_columns)
+}
+
+/// 结束一个水平带：只有一栏时并入通栏块，否则按栏各成一块。返回栏数
+fn flush_band<'a>(band: &mut Vec<&'a OcrLine>, flow: &mut Vec<&'a OcrLine>, blocks: &mut Vec<Block<'a>>, min_gap: f64) -> usize {
+    let columns = split_columns(band, min_gap);
+    band.clear();
+    let count = columns.len();
+    if count <= 1 {
+        for mut column in columns {
+            column.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+            flow.extend(column);
+        }
+        return count;
+    }
+    if !flow.is_empty() {
+        blocks.push(Block { column: 0, lines: std::mem::take(flow) });
+    }
+    for (index, mut column) in columns.into_iter().enumerate() {
+        column.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        blocks.push(Block { column: index, lines: column });
+    }
+    count
+}
+
+fn page_width(lines: &[OcrLine]) -> f64 {
+    let left = lines.iter().map(|l| l.x).fold(f64::INFINITY, f64::min);
+    let right = lines.iter().map(|l| l.x + l.width).fold(f64::NEG_INFINITY, f64::max);
+    right - left
+}
+
+/// 版面分析：按阅读顺序返回段落。没有行坐标时返回 `None`
+pub fn analyze(lines: &[OcrLine]) -> Option<Layout> {
+    let page_width = page_width(lines);
+    if lines.is_empty() || page_width <= 0.0 {
+        return None;
+    }
+    let line_height = median_height(lines);
+    let (blocks, columns) = blocks(lines, page_width, line_height * MIN_GAP_LINE_HEIGHTS);
+
+    let paragraphs = blocks
+        .iter()
+        .flat_map(|block| split_paragraphs(&block.lines, line_height).into_iter().map(move |p| (block.column, p)))
+        .map(|(column, lines)| paragraph(column, &lines))
+        .collect();
+    Some(Layout { columns, paragraphs })
+}
+
+/// 在一栏内分段：行距明显变大、本行比上一行缩进，或上一行明显短于栏宽
+fn split_paragraphs<'a>(lines: &[&'a OcrLine], line_height: f64) -> Vec<Vec<&'a OcrLine>> {
+    let left = lines.iter().map(|l| l.x).fold(f64::INFINITY, f64::min);
+    let right = lines.iter().map(|l| l.x + l.width).fold(f64::NEG_INFINITY, f64::max);
+    let column_width = right - left;
+
+    let mut paragraphs: Vec<Vec<&OcrLine>> = Vec::new();
+    for line in lines {
+        let starts_new = match paragraphs.last().and_then(|p| p.last()) {
+            None => true,
+            Some(previous) => {
+                let gap = line.y - (previous.y + previous.height);
+                let indented = line.x - previous.x >= line_height * INDENT_LINE_HEIGHTS;
+                let previous_short = previous.x + previous.width < left + column_width * SHORT_LINE_RATIO;
+                gap > line_height * PARAGRAPH_GAP_LINE_HEIGHTS || indented || previous_short
+            }
+        };
+        if starts_new {
+            paragraphs.push(vec![line]);
+        } else if let Some(paragraph) = paragraphs.last_mut() {
+            paragraph.push(line);
+        }
+    }
+    paragraphs
+}
+
+fn paragraph(column: usize, lines: &[&OcrLine]) -> OcrParagraph {
+    let left = lines.iter().map(|l| l.x).fold(f64::INFINITY, f64::min);
+    let top = lines.iter().map(|l| l.y).fold(f64::INFINITY, f64::min);
+    let right = lines.iter().map(|l| l.x + l.width).fold(f64::NEG_INFINITY, f64::max);
+    let bottom = lines.iter().map(|l| l.y + l.height).fold(f64::NEG_INFINITY, f64::max);
+    OcrParagraph {
+        text: lines.iter().map(|l| l.text.as_str()).collect::<Vec<_>>().join("\n"),
+        column,
+        x: left,
+        y: top,
+        width: right - left,
+        height: bottom - top,
+    }
+}
+
+/// 合并各行在 x 轴上的覆盖区间，间隙足够宽处即为栏的分界
+fn split_columns<'a>(band: &[&'a OcrLine], min_gap: f64) -> Vec<Vec<&'a OcrLine>> {
+    let mut spans: Vec<(f64, f64)> = band.iter().map(|l| (l.x, l.x + l.width)).collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start - last.1 < min_gap => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let mut columns: Vec<Vec<&OcrLine>> = vec![Vec::new(); merged.len()];
+    for line in band {
+        let index = merged
+            .iter()
+            .position(|(start, end)| line.x >= *start && line.x < *end)
+            .unwrap_or(0);
+        columns[index].push(line);
+    }
+    columns.retain(|c| !c.is_empty());
+    columns
+}
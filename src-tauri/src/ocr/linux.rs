@@ -0,0 +1,99 @@
+use crate::error::{AppError, Result};
+use crate::ocr::paddle::create_command;
+use crate::ocr::region::{self, Point};
+
+/// 截图工具及其参数；Wayland 下优先 grim，X11 下依次尝试 maim 和 ImageMagick
+fn capture_commands(x: i32, y: i32, w: i32, h: i32) -> Vec<(&'static str, Vec<String>)> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let grim = ("grim", vec!["-g".to_string(), format!("{},{} {}x{}", x, y, w, h), "-".to_string()]);
+    let maim = ("maim", vec!["-g".to_string(), format!("{}x{}+{}+{}", w, h, x, y)]);
+    let import = (
+        "import",
+        vec![
+            "-silent".to_string(),
+            "-window".to_string(),
+            "root".to_string(),
+            "-crop".to_string(),
+            format!("{}x{}+{}+{}", w, h, x, y),
+            "+repage".to_string(),
+            "png:-".to_string(),
+        ],
+    );
+    if wayland {
+        vec![grim, maim, import]
+    } else {
+        vec![maim, import, grim]
+    }
+}
+
+/// 异步运行外部截图工具并收集输出，不占用运行时线程；调用方取消时结束进程
+async fn output<I, S>(program: &str, args: I) -> std::io::Result<std::process::Output>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    tokio::process::Command::from(create_command(program))
+        .args(args)
+        .kill_on_drop(true)
+        .output()
+        .await
+}
+
+/// 截取屏幕区域，返回 PNG 数据
+pub async fn capture_png(x: i32, y: i32, w: i32, h: i32) -> Result<Vec<u8>> {
+    if w <= 0 || h <= 0 {
+        return Err(AppError::InvalidRequest(format!("Invalid capture size {}x{}", w, h)));
+    }
+    let mut failures = Vec::new();
+    for (program, args) in capture_commands(x, y, w, h) {
+        match output(program, &args).await {
+            Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+                println!("Captured screen region with {}", program);
+                return Ok(output.stdout);
+            }
+            Ok(output) => failures.push(format!("{}: {}", program, String::from_utf8_lossy(&output.stderr).trim())),
+            // 未安装的工具直接跳过
+            Err(_) => {}
+        }
+    }
+    if failures.is_empty() {
+        return Err(AppError::PlatformNotSupported(
+            "No screenshot tool found. Please install grim (Wayland) or maim/ImageMagick (X11).".to_string(),
+        ));
+    }
+    Err(AppError::Ocr(format!("Screen capture failed: {}", failures.join("; "))))
+}
+
+/// 按窗口 id 或标题截取 X11 窗口（ImageMagick `import`），返回 PNG 数据；
+/// Wayland 不允许截取其他程序的窗口
+pub async fn capture_window_png(target: &str) -> Result<Vec<u8>> {
+    let window = region::parse_handle(target).map(|id| id.to_string()).unwrap_or_else(|| target.trim().to_string());
+    if window.is_empty() {
+        return Err(AppError::InvalidRequest("Window title cannot be empty".to_string()));
+    }
+    match output("import", ["-silent", "-window", window.as_str(), "png:-"]).await {
+        Ok(output) if output.status.success() && !output.stdout.is_empty() => Ok(output.stdout),
+        Ok(output) => Err(AppError::Ocr(format!(
+            "Window capture failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+        Err(_) => Err(AppError::PlatformNotSupported(
+            "Window capture requires ImageMagick (import) on X11".to_string(),
+        )),
+    }
+}
+
+/// 多边形以外的像素涂白后重新编码为 PNG
+pub fn mask_png(png: &[u8], origin: (i32, i32), points: &[Point]) -> Result<Vec<u8>> {
+    let mut image = image::load_from_memory(png)
+        .map_err(|e| AppError::Ocr(format!("Failed to decode screenshot: {}", e)))?
+        .to_rgba8();
+    let (w, h) = (image.width() as i32, image.height() as i32);
+    region::apply_polygon_mask(&mut image, w, h, origin, points);
+
+    let mut data = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut data), image::ImageFormat::Png)
+        .map_err(|e| AppError::Ocr(format!("Failed to encode screenshot: {}", e)))?;
+    Ok(data)
+}
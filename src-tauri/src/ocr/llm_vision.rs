@@ -0,0 +1,173 @@
+//! 多模态大模型识别：把截图发给配置的视觉模型（GPT-4o、Gemini、Qwen-VL 等）转写文字。
+//! 手写体、艺术字和多语言混排截图的效果远好于系统 OCR，但需要联网且按调用计费。
+//! 配置与翻译服务相同：`provider`（`openai` 兼容接口或 `gemini`）、`apiKey`、`apiUrl`、`model`、
+//! 可选的 `prompt` 以及代理设置。
+
+use crate::error::{AppError, Result};
+use crate::ocr::confidence;
+use crate::ocr::models::OcrResult;
+use crate::services::http::SendRetrying;
+use base64::{engine::general_purpose, Engine as _};
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const DEFAULT_OPENAI_URL: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_GEMINI_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+/// 视觉模型处理图像较慢，超时比翻译请求宽松
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+const DEFAULT_PROMPT: &str = "Transcribe all text in this image exactly as it appears, preserving line breaks \
+and reading order. Output only the transcribed text, without translation, commentary or formatting. \
+If the image contains no text, output nothing.";
+
+/// None 表示尚未配置
+static CONFIG: Lazy<Mutex<Option<serde_json::Value>>> = Lazy::new(|| Mutex::new(None));
+
+fn get<'a>(config: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    config.get(key).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty())
+}
+
+pub fn set_config(config: serde_json::Value) -> Result<()> {
+    if get(&config, "apiKey").is_none() {
+        return Err(AppError::Config("Vision model API key not configured".to_string()));
+    }
+    if get(&config, "model").is_none() {
+        return Err(AppError::Config("Vision model not configured".to_string()));
+    }
+    match get(&config, "provider").unwrap_or("openai") {
+        "openai" | "gemini" => {}
+        other => return Err(AppError::InvalidRequest(format!("Unknown vision model provider: {}", other))),
+    }
+    *CONFIG.lock().unwrap() = Some(config);
+    Ok(())
+}
+
+/// 返回 (MIME 类型, base64 数据)；PNG、JPEG 原样发送，其他格式（如 BMP）转为 PNG
+fn encode_image(image_data: &[u8]) -> Result<(&'static str, String)> {
+    match image::guess_format(image_data) {
+        Ok(image::ImageFormat::Png) => Ok(("image/png", general_purpose::STANDARD.encode(image_data))),
+        Ok(image::ImageFormat::Jpeg) => Ok(("image/jpeg", general_purpose::STANDARD.encode(image_data))),
+        _ => {
+            let image = image::load_from_memory(image_data)
+                .map_err(|e| AppError::Ocr(format!("Failed to decode image: {}", e)))?;
+            let mut png = Vec::new();
+            image
+                .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+                .map_err(|e| AppError::Ocr(format!("Failed to encode image: {}", e)))?;
+            Ok(("image/png", general_purpose::STANDARD.encode(png)))
+        }
+    }
+}
+
+fn prompt(config: &serde_json::Value, language: Option<&str>) -> String {
+    let prompt = get(config, "prompt").unwrap_or(DEFAULT_PROMPT).to_string();
+    match language.filter(|l| !l.is_empty() && *l != "auto") {
+        Some(language) => format!("{}\nThe text is mostly in language: {}.", prompt, language),
+        None => prompt,
+    }
+}
+
+/// 去掉模型有时包裹在转写结果外的 Markdown 代码块
+pub(crate) fn strip_code_fence(output: &str) -> String {
+    let output = output.trim();
+    let Some(inner) = output.strip_prefix("```").and_then(|rest| rest.strip_suffix("```")) else {
+        return output.to_string();
+    };
+    // 第一行是可选的语言标记
+    let inner = match inner.split_once('\n') {
+        Some((tag, body)) if !tag.contains(' ') => body,
+        _ => inner,
+    };
+    inner.trim().to_string()
+}
+
+pub(crate) async fn request_openai(config: &serde_json::Value, api_key: &str, model: &str, prompt: &str, mime: &str, data: &str) -> Result<String> {
+    let client = crate::services::http::client(Some(config), REQUEST_TIMEOUT)?;
+    let body = serde_json::json!({
+        "model": model,
+        "temperature": 0,
+        "messages": [{
+            "role": "user",
+            "content": [
+                { "type": "text", "text": prompt },
+                { "type": "image_url", "image_url": { "url": format!("data:{};base64,{}", mime, data) } }
+            ]
+        }]
+    });
+    let response = client
+        .post(get(config, "apiUrl").unwrap_or(DEFAULT_OPENAI_URL))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .json(&body)
+        .send_retrying()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Vision model", status, error_text));
+    }
+    let json: serde_json::Value = response.json().await?;
+    json["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Ocr("No text in vision model response".to_string()))
+}
+
+pub(crate) async fn request_gemini(config: &serde_json::Value, api_key: &str, model: &str, prompt: &str, mime: &str, data: &str) -> Result<String> {
+    let client = crate::services::http::client(Some(config), REQUEST_TIMEOUT)?;
+    let api_url = get(config, "apiUrl").unwrap_or(DEFAULT_GEMINI_URL).trim_end_matches('/');
+    let body = serde_json::json!({
+        "contents": [{
+            "role": "user",
+            "parts": [
+                { "text": prompt },
+                { "inline_data": { "mime_type": mime, "data": data } }
+            ]
+        }],
+        "generationConfig": { "temperature": 0 }
+    });
+    let response = client
+        .post(format!("{}/models/{}:generateContent", api_url, model))
+        .query(&[("key", api_key)])
+        .json(&body)
+        .send_retrying()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Vision model", status, error_text));
+    }
+    let json: serde_json::Value = response.json().await?;
+    let parts = json["candidates"][0]["content"]["parts"]
+        .as_array()
+        .ok_or_else(|| AppError::Ocr("No text in vision model response".to_string()))?;
+    Ok(parts.iter().filter_map(|part| part["text"].as_str()).collect())
+}
+
+/// 识别编码后的图像；模型不给出行坐标，结果只有全文
+pub async fn recognize(image_data: &[u8], language: Option<&str>) -> Result<OcrResult> {
+    let config = CONFIG
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| AppError::Config("Vision model OCR is not configured".to_string()))?;
+    let api_key = get(&config, "apiKey").unwrap_or_default();
+    let model = get(&config, "model").unwrap_or_default();
+    let prompt = prompt(&config, language);
+    let (mime, data) = encode_image(image_data)?;
+
+    let output = match get(&config, "provider").unwrap_or("openai") {
+        "gemini" => request_gemini(&config, api_key, model, &prompt, mime, &data).await?,
+        _ => request_openai(&config, api_key, model, &prompt, mime, &data).await?,
+    };
+    let text = strip_code_fence(&output);
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    Ok(OcrResult {
+        confidence: confidence::estimate(&words),
+        text,
+        lines: Vec::new(),
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    })
+}
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 pub struct MathpixRequest {
     pub image_data: Option<String>,
     pub image_url: Option<String>,
+    /// 识别后端：`mathpix`（默认）、`simpletex` 或 `local`（Pix2Text）
+    #[serde(default)]
+    pub engine: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +15,75 @@ pub struct MathpixResult {
     pub latex_styled: Option<String>,
     pub confidence: Option<f64>,
     pub error: Option<String>,
+    /// 以下格式仅在配置的 `formats` 中请求时返回
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mathml: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asciimath: Option<String>,
+    /// Mathpix Markdown 纯文本，公式以 `math_inline_delimiters` 包裹
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// 可选的附加输出格式
+const EXTRA_FORMATS: &[&str] = &["mathml", "asciimath", "text"];
+
+/// 配置中 `formats` 列出的附加格式，忽略未知项
+fn requested_formats(config: Option<&serde_json::Value>) -> Vec<&str> {
+    config
+        .and_then(|c| c.get("formats"))
+        .and_then(|v| v.as_array())
+        .map(|formats| {
+            formats
+                .iter()
+                .filter_map(|f| f.as_str())
+                .filter(|f| EXTRA_FORMATS.contains(f))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 构造请求体（不含图像）：按需加入附加格式，并透传 Mathpix 的文本选项
+pub(crate) fn request_body(config: Option<&serde_json::Value>) -> serde_json::Value {
+    let wanted = requested_formats(config);
+    let mut formats = vec!["latex_simplified", "latex_styled"];
+    if wanted.contains(&"text") {
+        formats.push("text");
+    }
+    if wanted.contains(&"mathml") || wanted.contains(&"asciimath") {
+        formats.push("data");
+    }
+
+    let mut body = serde_json::json!({
+        "formats": formats,
+        "data_options": {
+            "include_asciimath": wanted.contains(&"asciimath"),
+            "include_latex": true,
+            "include_mathml": wanted.contains(&"mathml")
+        }
+    });
+
+    let options = [
+        ("rmSpaces", "rm_spaces"),
+        ("mathInlineDelimiters", "math_inline_delimiters"),
+        ("mathDisplayDelimiters", "math_display_delimiters"),
+    ];
+    for (key, option) in options {
+        if let Some(value) = config.and_then(|c| c.get(key)).filter(|v| !v.is_null()) {
+            body[option] = value.clone();
+        }
+    }
+    body
+}
+
+/// `data` 数组中指定类型（`mathml`、`asciimath`）的值
+fn data_value(json: &serde_json::Value, kind: &str) -> Option<String> {
+    json["data"]
+        .as_array()?
+        .iter()
+        .find(|item| item["type"].as_str() == Some(kind))
+        .and_then(|item| item["value"].as_str())
+        .map(|s| s.to_string())
 }
 
 pub async fn recognize_formula(
@@ -36,19 +108,9 @@ pub async fn recognize_formula(
         .and_then(|v| v.as_str())
         .unwrap_or("https://api.mathpix.com/v3/text");
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| crate::error::AppError::Network(format!("Failed to create HTTP client: {}", e)))?;
+    let client = crate::services::http::client(config, std::time::Duration::from_secs(30))?;
 
-    let mut body = serde_json::json!({
-        "formats": ["latex_simplified", "latex_styled"],
-        "data_options": {
-            "include_asciimath": false,
-            "include_latex": true,
-            "include_mathml": false
-        }
-    });
+    let mut body = request_body(config);
 
     if let Some(data) = image_data {
         body["src"] = serde_json::json!(format!("data:image/png;base64,{}", data));
@@ -70,11 +132,9 @@ pub async fn recognize_formula(
         .await?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(crate::error::AppError::Api {
-            service: "Mathpix".to_string(),
-            message: error_text,
-        });
+        return Err(crate::error::AppError::from_status("Mathpix", status, error_text));
     }
 
     let json: serde_json::Value = response.json().await?;
@@ -96,5 +156,8 @@ pub async fn recognize_formula(
         latex_styled,
         confidence,
         error: None,
+        mathml: data_value(&json, "mathml"),
+        asciimath: data_value(&json, "asciimath"),
+        text: json["text"].as_str().map(|s| s.to_string()),
     })
 }
@@ -1,9 +1,39 @@
 pub mod models;
+pub mod barcode;
+pub mod cleanup;
+pub mod confidence;
+pub mod formula_local;
+pub mod frames;
+pub mod history;
+pub mod llm_vision;
 pub mod mathpix;
+pub mod overlay;
 pub mod paddle;
+pub mod presets;
+pub mod region;
+#[cfg(target_os = "windows")]
+mod dxgi;
+pub mod layout;
+pub mod correct;
+pub mod tesseract;
+pub mod rapid;
+pub mod simpletex;
+pub mod vertical;
+pub mod watch;
+pub mod worker;
+#[cfg(target_os = "linux")]
+pub mod linux;
 
-use crate::ocr::models::{OcrRequest, OcrResult as AppOcrResult};
+use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+use crate::ocr::models::{CaptureTranslateRequest, OcrFileResult, OcrLanguage, OcrRequest, OcrResult as AppOcrResult};
+use crate::ocr::presets::RegionRect;
 use crate::error::{AppError, Result};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::Semaphore;
 
 #[cfg(target_os = "windows")]
 use windows::{
@@ -13,16 +43,16 @@ use windows::{
     core::HSTRING,
     Storage::Streams::{InMemoryRandomAccessStream, DataWriter},
     Win32::Graphics::Gdi::{GetDC, CreateCompatibleDC, CreateCompatibleBitmap, SelectObject, BitBlt, GetDIBits, SRCCOPY, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, DeleteObject, DeleteDC, ReleaseDC},
-    Win32::UI::WindowsAndMessaging::GetDesktopWindow,
+    Win32::Foundation::{HWND, RECT},
+    Win32::Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS},
+    Win32::UI::WindowsAndMessaging::{GetWindowRect, GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN},
 };
 
-#[cfg(target_os = "windows")]
 use base64::{Engine as _, engine::general_purpose};
 
+#[cfg(not(target_os = "linux"))]
 pub trait ScreenshotCapture {
     fn capture_screen(&self, x: i32, y: i32, w: i32, h: i32) -> Result<String>;
-    #[allow(dead_code)]
-    fn capture_and_ocr(&self, x: i32, y: i32, w: i32, h: i32, language: Option<String>) -> Result<AppOcrResult>;
 }
 
 #[cfg(target_os = "windows")]
@@ -35,89 +65,173 @@ impl ScreenshotCapture for WindowsOcr {
         let bmp_data = create_bmp_file(&pixels, w, h);
         Ok(general_purpose::STANDARD.encode(&bmp_data))
     }
-    
-    fn capture_and_ocr(&self, x: i32, y: i32, w: i32, h: i32, language: Option<String>) -> Result<AppOcrResult> {
-        let (raw_pixels, w, h) = unsafe { capture_bitmap(x, y, w, h)? };
-        let (processed_pixels, new_w, new_h) = preprocess_image(&raw_pixels, w, h);
-        let bmp_data = create_bmp_file(&processed_pixels, new_w, new_h);
-
-        let rt = tokio::runtime::Handle::current();
-        rt.block_on(recognize_bytes(bmp_data, language))
-    }
 }
 
-#[cfg(not(target_os = "windows"))]
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 struct FallbackOcr;
 
-#[cfg(not(target_os = "windows"))]
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 impl ScreenshotCapture for FallbackOcr {
     fn capture_screen(&self, _x: i32, _y: i32, _w: i32, _h: i32) -> Result<String> {
         Err(AppError::PlatformNotSupported("Screenshot capture requires native implementation".to_string()))
     }
-    
-    fn capture_and_ocr(&self, _x: i32, _y: i32, _w: i32, _h: i32, _language: Option<String>) -> Result<AppOcrResult> {
-        Err(AppError::PlatformNotSupported("Native OCR not available on this platform".to_string()))
-    }
 }
 
+/// Linux 通过外部截图工具异步截图，见 `linux::capture_png`
+#[cfg(not(target_os = "linux"))]
 fn get_ocr_impl() -> Box<dyn ScreenshotCapture> {
     #[cfg(target_os = "windows")]
     {
         Box::new(WindowsOcr)
     }
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
     {
         Box::new(FallbackOcr)
     }
 }
 
+/// 在阻塞线程池中执行截图、像素处理等同步操作，避免大区域截图时占住异步运行时、拖慢其他命令
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| AppError::Ocr(format!("Capture task failed: {}", e)))?
+}
+
+/// 截取虚拟屏幕坐标下的区域（可跨显示器），返回 BGRA 像素；优先使用 DXGI 桌面复制，不可用时退回 GDI
 #[cfg(target_os = "windows")]
 unsafe fn capture_bitmap(x: i32, y: i32, w: i32, h: i32) -> Result<(Vec<u8>, i32, i32)> {
-    let hwnd = GetDesktopWindow();
-    let hdc_screen = GetDC(hwnd);
+    match dxgi::capture(x, y, w, h) {
+        Ok(Some(pixels)) => return Ok((pixels, w, h)),
+        Ok(None) => {}
+        Err(e) => println!("Desktop duplication unavailable, falling back to GDI: {}", e),
+    }
+    capture_bitmap_gdi(x, y, w, h)
+}
+
+/// 虚拟屏幕（所有显示器的外接矩形）；主显示器左侧或上方的显示器坐标为负
+#[cfg(target_os = "windows")]
+fn virtual_screen() -> (i32, i32, i32, i32) {
+    unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    }
+}
+
+/// 用 GDI 截取虚拟屏幕坐标下的区域；超出虚拟屏幕的部分为白色
+#[cfg(target_os = "windows")]
+unsafe fn capture_bitmap_gdi(x: i32, y: i32, w: i32, h: i32) -> Result<(Vec<u8>, i32, i32)> {
+    let mut pixels: Vec<u8> = vec![255; (w * h * 4) as usize];
+    let Some(part) = region::intersect((x, y, w, h), virtual_screen()) else {
+        return Ok((pixels, w, h));
+    };
+    let (px, py, pw, ph) = part;
+
+    // 整个虚拟屏幕的 DC，坐标原点为主显示器左上角
+    let hdc_screen = GetDC(HWND(0));
     let hdc_mem = CreateCompatibleDC(hdc_screen);
     
-    let hbm_screen = CreateCompatibleBitmap(hdc_screen, w, h);
+    let hbm_screen = CreateCompatibleBitmap(hdc_screen, pw, ph);
     SelectObject(hdc_mem, hbm_screen);
     
-    if BitBlt(hdc_mem, 0, 0, w, h, hdc_screen, x, y, SRCCOPY).is_err() {
+    if BitBlt(hdc_mem, 0, 0, pw, ph, hdc_screen, px, py, SRCCOPY).is_err() {
         DeleteObject(hbm_screen);
         DeleteDC(hdc_mem);
-        ReleaseDC(hwnd, hdc_screen);
+        ReleaseDC(HWND(0), hdc_screen);
         return Err(AppError::Ocr("BitBlt failed".to_string()));
     }
     
     let mut bi = BITMAPINFOHEADER {
         biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-        biWidth: w,
-        biHeight: -h,
+        biWidth: pw,
+        biHeight: -ph,
         biPlanes: 1,
         biBitCount: 32,
         biCompression: BI_RGB.0,
         ..Default::default()
     };
     
-    let mut pixels: Vec<u8> = vec![0; (w * h * 4) as usize];
+    let mut part_pixels: Vec<u8> = vec![0; (pw * ph * 4) as usize];
     
     if GetDIBits(
         hdc_screen, 
         hbm_screen, 
         0, 
-        h as u32, 
-        Some(pixels.as_mut_ptr() as *mut _), 
+        ph as u32, 
+        Some(part_pixels.as_mut_ptr() as *mut _), 
         &mut bi as *mut _ as *mut _, 
         DIB_RGB_COLORS
     ) == 0 {
         DeleteObject(hbm_screen);
         DeleteDC(hdc_mem);
-        ReleaseDC(hwnd, hdc_screen);
+        ReleaseDC(HWND(0), hdc_screen);
         return Err(AppError::Ocr("GetDIBits failed".to_string()));
     }
     
     DeleteObject(hbm_screen);
     DeleteDC(hdc_mem);
-    ReleaseDC(hwnd, hdc_screen);
-    
+    ReleaseDC(HWND(0), hdc_screen);
+
+    region::blit(&mut pixels, (x, y, w, h), part, &part_pixels);
+    Ok((pixels, w, h))
+}
+
+/// 让 DirectComposition 绘制的窗口（浏览器、UWP 等）也能被 PrintWindow 截取，Windows 8.1 起可用
+#[cfg(target_os = "windows")]
+const PW_RENDERFULLCONTENT: PRINT_WINDOW_FLAGS = PRINT_WINDOW_FLAGS(2);
+
+/// 用 PrintWindow 让窗口把自身绘制到内存位图，返回 BGRA 像素；窗口被遮挡或部分移出屏幕时也能完整截取
+#[cfg(target_os = "windows")]
+unsafe fn capture_window_bitmap(hwnd: HWND) -> Result<(Vec<u8>, i32, i32)> {
+    let mut rect = RECT::default();
+    GetWindowRect(hwnd, &mut rect).map_err(|e| AppError::Ocr(format!("Failed to get window bounds: {}", e)))?;
+    let (w, h) = (rect.right - rect.left, rect.bottom - rect.top);
+    if w <= 0 || h <= 0 {
+        return Err(AppError::InvalidRequest("Window has no area".to_string()));
+    }
+
+    let hdc_screen = GetDC(HWND(0));
+    let hdc_mem = CreateCompatibleDC(hdc_screen);
+    let hbm_window = CreateCompatibleBitmap(hdc_screen, w, h);
+    let previous = SelectObject(hdc_mem, hbm_window);
+    let printed = PrintWindow(hwnd, hdc_mem, PW_RENDERFULLCONTENT).as_bool();
+    // GetDIBits 要求位图未被选入任何 DC
+    SelectObject(hdc_mem, previous);
+
+    let mut bi = BITMAPINFOHEADER {
+        biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+        biWidth: w,
+        biHeight: -h,
+        biPlanes: 1,
+        biBitCount: 32,
+        biCompression: BI_RGB.0,
+        ..Default::default()
+    };
+    let mut pixels: Vec<u8> = vec![0; (w * h * 4) as usize];
+    let copied = printed
+        && GetDIBits(
+            hdc_screen,
+            hbm_window,
+            0,
+            h as u32,
+            Some(pixels.as_mut_ptr() as *mut _),
+            &mut bi as *mut _ as *mut _,
+            DIB_RGB_COLORS,
+        ) != 0;
+
+    DeleteObject(hbm_window);
+    DeleteDC(hdc_mem);
+    ReleaseDC(HWND(0), hdc_screen);
+
+    if !printed {
+        return Err(AppError::Ocr("PrintWindow failed".to_string()));
+    }
+    if !copied {
+        return Err(AppError::Ocr("GetDIBits failed".to_string()));
+    }
     Ok((pixels, w, h))
 }
 
@@ -186,10 +300,15 @@ fn bilinear_interpolate(pixels: &[u8], w: i32, h: i32, x: f64, y: f64) -> (u8, u
     (interp(0), interp(1), interp(2), interp(3))
 }
 
+#[cfg(target_os = "windows")]
+const PREPROCESS_SCALE: i32 = 2;
+#[cfg(target_os = "windows")]
+const PREPROCESS_PADDING: i32 = 20;
+
 #[cfg(target_os = "windows")]
 fn preprocess_image(src_pixels: &[u8], w: i32, h: i32) -> (Vec<u8>, i32, i32) {
-    let scale = 2;
-    let padding = 20;
+    let scale = PREPROCESS_SCALE;
+    let padding = PREPROCESS_PADDING;
     
     let new_w = w * scale + padding * 2;
     let new_h = h * scale + padding * 2;
@@ -324,22 +443,98 @@ async fn recognize_bytes(image_data: Vec<u8>, language: Option<String>) -> Resul
         },
     };
 
-    let confidence = if let Some(angle_ref) = result.TextAngle().ok() {
-        if let Some(angle) = angle_ref.Value().ok() {
-            angle.abs() as f64
-        } else {
-            0.0
+    let mut lines = Vec::new();
+    let mut word_texts = Vec::new();
+    if let Ok(ocr_lines) = result.Lines() {
+        for ocr_line in ocr_lines {
+            let line_text = ocr_line.Text().map(|t| t.to_string()).unwrap_or_default();
+            let Ok(words) = ocr_line.Words() else { continue };
+            let words: Vec<_> = words.into_iter().collect();
+            word_texts.extend(words.iter().filter_map(|word| word.Text().ok()).map(|t| t.to_string()));
+            let rects: Vec<_> = words.iter().filter_map(|word| word.BoundingRect().ok()).collect();
+            if rects.is_empty() {
+                continue;
+            }
+            let left = rects.iter().map(|r| r.X).fold(f32::INFINITY, f32::min);
+            let top = rects.iter().map(|r| r.Y).fold(f32::INFINITY, f32::min);
+            let right = rects.iter().map(|r| r.X + r.Width).fold(f32::NEG_INFINITY, f32::max);
+            let bottom = rects.iter().map(|r| r.Y + r.Height).fold(f32::NEG_INFINITY, f32::max);
+            lines.push(models::OcrLine {
+                text: line_text,
+                x: left as f64,
+                y: top as f64,
+                width: (right - left) as f64,
+                height: (bottom - top) as f64,
+                confidence: None,
+            });
         }
-    } else {
-        0.0
-    };
+    }
+
+    // Windows OCR 不提供置信度，按识别出的文字估计
+    let words: Vec<&str> = word_texts.iter().map(String::as_str).collect();
+    let confidence = confidence::estimate(&words);
 
     Ok(AppOcrResult {
         text,
         confidence,
+        lines,
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
     })
 }
 
+/// 识别后处理：可选的拼写纠错；`layout` 时按行坐标给出段落，只有检测到多栏时才按阅读顺序重排文本，
+/// 单栏保留引擎的原始输出；`cleanup` 时整理全文和各段落的换行与空格（见 `cleanup::clean`）
+pub(crate) fn postprocess(result: AppOcrResult, layout: bool, cleanup: bool) -> AppOcrResult {
+    let mut result = correct::apply(result);
+    if let Some(layout) = layout.then(|| layout::analyze(&result.lines)).flatten() {
+        if layout.columns > 1 {
+            println!("Multi-column layout detected, reordering {} lines", result.lines.len());
+            result.text = layout.text();
+        }
+        result.paragraphs = layout.paragraphs;
+    }
+    if cleanup {
+        result.text = cleanup::clean(&result.text);
+        for paragraph in &mut result.paragraphs {
+            paragraph.text = cleanup::clean(&paragraph.text);
+        }
+    }
+    result
+}
+
+/// 列出系统已安装的 OCR 语言。Windows 下为 Windows OCR 可用的识别语言（在系统设置中添加语言的 OCR 功能后出现），
+/// Linux 下为 Tesseract 已安装的语言包
+pub async fn list_ocr_languages() -> Result<Vec<OcrLanguage>> {
+    #[cfg(target_os = "windows")]
+    {
+        let languages = OcrEngine::AvailableRecognizerLanguages()
+            .map_err(|e| AppError::Ocr(format!("Failed to list OCR languages: {}", e)))?;
+        Ok(languages
+            .into_iter()
+            .map(|language| OcrLanguage {
+                tag: language.LanguageTag().map(|s| s.to_string()).unwrap_or_default(),
+                display_name: language.DisplayName().map(|s| s.to_string()).unwrap_or_default(),
+                native_name: language.NativeName().map(|s| s.to_string()).unwrap_or_default(),
+            })
+            .collect())
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Ok(tesseract::status()
+            .await
+            .languages
+            .into_iter()
+            .filter(|language| language != "osd")
+            .map(|language| OcrLanguage { display_name: language.clone(), native_name: language.clone(), tag: language })
+            .collect())
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        Err(AppError::PlatformNotSupported("Windows OCR API is only available on Windows platform".to_string()))
+    }
+}
+
 pub async fn perform_ocr(request: OcrRequest) -> Result<AppOcrResult> {
     perform_ocr_with_engine(request, "windows").await
 }
@@ -373,26 +568,153 @@ pub async fn perform_ocr_with_engine(request: OcrRequest, engine: &str) -> Resul
         return Err(AppError::InvalidRequest("No image data provided. Please provide either image_path or image_data.".to_string()));
     };
 
+    recognize_image(image_data, request.language, request.vertical, request.cleanup, engine).await
+}
+
+/// 识别已载入的图像：二维码检测、横排或竖排识别、可选的文本整理，并记入历史
+async fn recognize_image(
+    image_data: Vec<u8>,
+    language: Option<String>,
+    vertical: Option<bool>,
+    cleanup: bool,
+    engine: &str,
+) -> Result<AppOcrResult> {
     println!("Processing image with OCR, size: {} bytes", image_data.len());
 
+    // 解码、二维码检测和竖排判断都是同步计算，放到阻塞线程池
+    let hint = language.clone();
+    let (image_data, decoded, barcodes, is_vertical) = blocking(move || {
+        let decoded = image::load_from_memory(&image_data).ok();
+        let barcodes = decoded.as_ref().map(|image| barcode::detect(&image.to_luma8())).unwrap_or_default();
+        let is_vertical = vertical::wanted(vertical, hint.as_deref(), &image_data);
+        Ok((image_data, decoded, barcodes, is_vertical))
+    })
+    .await?;
+    let mut result = if is_vertical {
+        println!("Recognizing vertical text");
+        // 竖排结果每列一行，已按从右到左排列，不再做横排版面分析
+        postprocess(recognize_vertical(image_data, language, engine).await?, false, cleanup)
+    } else {
+        postprocess(recognize_encoded(image_data, language, engine).await?, true, cleanup)
+    };
+    result.barcodes = barcodes;
+    // 缩略图编码和写入数据库同样放到阻塞线程池
+    let (text, engine) = (result.text.clone(), engine.to_string());
+    let record = blocking(move || {
+        history::record(&text, &engine, decoded);
+        Ok(())
+    });
+    if let Err(e) = record.await {
+        println!("Failed to record OCR history: {}", e);
+    }
+    Ok(result)
+}
+
+/// 按引擎识别编码后的图像（PNG/BMP 等）
+async fn recognize_encoded(image_data: Vec<u8>, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
     if engine == "paddle" {
-        paddle::paddle_ocr_recognize(&image_data)
+        // 与常驻进程同步读写，首次调用还要加载模型，不能占住异步运行时
+        blocking(move || paddle::paddle_ocr_recognize(&image_data)).await
+    } else if engine == "tesseract" {
+        tesseract::recognize(&image_data, language.as_deref()).await
+    } else if engine == "rapid" {
+        rapid::recognize(image_data).await
+    } else if engine == "llm_vision" {
+        llm_vision::recognize(&image_data, language.as_deref()).await
     } else {
         #[cfg(target_os = "windows")]
         {
-            recognize_bytes(image_data, request.language).await
+            recognize_bytes(image_data, language).await
+        }
+        #[cfg(target_os = "linux")]
+        {
+            tesseract::recognize(&image_data, language.as_deref()).await
         }
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             Err(AppError::PlatformNotSupported("Windows OCR API is only available on Windows platform".to_string()))
         }
     }
 }
 
-pub async fn capture_screen(x: i32, y: i32, w: i32, h: i32) -> Result<String> {
+/// 竖排识别：Tesseract 使用竖排语言包，其余引擎识别重排成横排的图像后映射回各列
+async fn recognize_vertical(image_data: Vec<u8>, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    if engine == "llm_vision" {
+        // 视觉模型能直接读竖排文字，重排后反而丢失版面
+        return llm_vision::recognize(&image_data, language.as_deref()).await;
+    }
+    let use_tesseract = engine == "tesseract" || (cfg!(target_os = "linux") && !matches!(engine, "paddle" | "rapid"));
+    if use_tesseract {
+        return tesseract::recognize_vertical(&image_data, language.as_deref()).await;
+    }
+    let (stacked, strips) = vertical::restack(&image_data)?;
+    let result = recognize_encoded(stacked, language, engine).await?;
+    Ok(vertical::assemble(result, &strips))
+}
+
+/// 批量识别时同时处理的文件数上限
+const MAX_CONCURRENT_FILES: usize = 4;
+
+/// 批量识别图像文件（如拍摄的多页文档），结果与 `paths` 顺序一致；单个文件失败不影响其余文件
+pub async fn ocr_files(paths: Vec<String>, engine: &str, language: Option<String>) -> Vec<OcrFileResult> {
+    println!("Starting batch OCR of {} files with engine: {}", paths.len(), engine);
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_FILES));
+    let tasks: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let semaphore = semaphore.clone();
+            let request = OcrRequest {
+                image_data: None,
+                image_path: Some(path.clone()),
+                language: language.clone(),
+                vertical: None,
+                cleanup: false,
+            };
+            let engine = engine.to_string();
+            // 每个文件单独成任务，Tesseract 子进程和在线引擎可以并行；Paddle 所有请求都经同一个常驻进程，仍逐个识别
+            let task = tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                perform_ocr_with_engine(request, &engine).await
+            });
+            (path, task)
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for (path, task) in tasks {
+        let outcome = task
+            .await
+            .unwrap_or_else(|e| Err(AppError::Ocr(format!("OCR task failed: {}", e))));
+        results.push(match outcome {
+            Ok(result) => OcrFileResult { path, result: Some(result), error: None },
+            Err(e) => OcrFileResult { path, result: None, error: Some(e.to_string()) },
+        });
+    }
+    results
+}
+
+/// 截图并返回 base64 编码的图像；指定 `capture_id` 时保留该帧，供 `ocr_capture_region` 重新识别
+pub async fn capture_screen(x: i32, y: i32, w: i32, h: i32, capture_id: Option<String>) -> Result<String> {
     println!("Capturing screenshot at ({}, {}) size ({}x{})", x, y, w, h);
-    let ocr_impl = get_ocr_impl();
-    ocr_impl.capture_screen(x, y, w, h)
+    #[cfg(target_os = "linux")]
+    let encoded = general_purpose::STANDARD.encode(linux::capture_png(x, y, w, h).await?);
+    #[cfg(not(target_os = "linux"))]
+    let encoded = blocking(move || get_ocr_impl().capture_screen(x, y, w, h)).await?;
+    if let Some(capture_id) = capture_id {
+        let image_data = general_purpose::STANDARD
+            .decode(&encoded)
+            .map_err(|e| AppError::Ocr(format!("Failed to decode screenshot: {}", e)))?;
+        frames::retain(&capture_id, image_data);
+    }
+    Ok(encoded)
+}
+
+/// 在保留的帧上识别选区（帧内像素坐标），不重新截屏
+pub async fn ocr_capture_region(capture_id: &str, rect: RegionRect, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    println!("Re-running OCR on capture {} with engine: {}", capture_id, engine);
+    let frame = frames::get(capture_id)?;
+    let selection = blocking(move || frames::crop(&frame, rect)).await?;
+    recognize_image(selection, language, None, false, engine).await
 }
 
 pub async fn capture_and_ocr(x: i32, y: i32, w: i32, h: i32, language: Option<String>) -> Result<AppOcrResult> {
@@ -404,24 +726,278 @@ pub async fn capture_and_ocr_with_engine(x: i32, y: i32, w: i32, h: i32, languag
     
     #[cfg(target_os = "windows")]
     {
-        let (raw_pixels, w, h) = unsafe { capture_bitmap(x, y, w, h)? };
-        
-        if engine == "paddle" {
-            let png_data = create_png_from_pixels(&raw_pixels, w, h);
-            paddle::paddle_ocr_recognize(&png_data)
-        } else {
-            println!("Preprocessing image: {}x{} -> Upscaling 2x with padding", w, h);
-            let (processed_pixels, new_w, new_h) = preprocess_image(&raw_pixels, w, h);
-            let bmp_data = create_bmp_file(&processed_pixels, new_w, new_h);
-            recognize_bytes(bmp_data, language).await
-        }
+        let (raw_pixels, w, h) = blocking(move || unsafe { capture_bitmap(x, y, w, h) }).await?;
+        recognize_pixels(raw_pixels, w, h, language, engine).await
     }
     
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "linux")]
+    {
+        let png_data = linux::capture_png(x, y, w, h).await?;
+        recognize_png(png_data, language, engine).await
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        let _ = (x, y, w, h, language, engine);
+        Err(AppError::PlatformNotSupported("Native OCR not available on this platform".to_string()))
+    }
+}
+
+/// `capture-ocr-result` 事件的负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptureOcrPayload {
+    request_id: String,
+    result: AppOcrResult,
+}
+
+/// 截图→识别→翻译一次完成，省去截图和识别文本在前后端之间的往返。
+/// 识别结果先以 `capture-ocr-result` 事件推送，译文随后与 `translate_stream` 一样通过
+/// `translation-stream` 事件流式推送；未识别到文本时不翻译
+pub async fn capture_ocr_translate(app: AppHandle, request: CaptureTranslateRequest, request_id: String) -> Result<AppOcrResult> {
+    let engine = request.engine.as_deref().unwrap_or("windows");
+    let result =
+        capture_and_ocr_with_engine(request.x, request.y, request.w, request.h, request.ocr_lang.clone(), engine).await?;
+    let _ = app.emit(
+        &crate::events::event_name("capture-ocr-result"),
+        CaptureOcrPayload { request_id: request_id.clone(), result: result.clone() },
+    );
+
+    if result.text.trim().is_empty() {
+        println!("No text recognized, skipping translation");
+        return Ok(result);
+    }
+    let translation = TranslationRequest {
+        text: result.text.clone(),
+        source_lang: request.ocr_lang.unwrap_or_else(|| "auto".to_string()),
+        target_lang: request.target_lang,
+        services: request.services,
+        config: request.config,
+        mode: TranslationMode::All,
+        format: TextFormat::Plain,
+    };
+    crate::services::translate_stream(app, translation, request_id).await?;
+    Ok(result)
+}
+
+/// 静默识别：截图识别后由后端直接把文本写入剪贴板并弹出系统通知，
+/// 不依赖主窗口或遮罩窗口，窗口全部隐藏时也能使用
+pub async fn silent_ocr(app: AppHandle, x: i32, y: i32, w: i32, h: i32, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    let outcome = capture_and_ocr_with_engine(x, y, w, h, language, engine).await.and_then(|result| {
+        let text = result.text.trim();
+        if !text.is_empty() {
+            app.clipboard()
+                .write_text(text.to_string())
+                .map_err(|e| AppError::Unknown(format!("Failed to write clipboard: {}", e)))?;
+        }
+        Ok(result)
+    });
+
+    let message = match &outcome {
+        Ok(result) if result.text.trim().is_empty() => "No text detected".to_string(),
+        Ok(result) => format!("{} characters copied", result.text.trim().chars().count()),
+        Err(e) => format!("OCR failed: {}", e),
+    };
+    if let Err(e) = app.notification().builder().title("Silent OCR").body(message).show() {
+        println!("Failed to show notification: {}", e);
+    }
+    outcome
+}
+
+/// 识别已保存的命名区域，无需重新框选
+pub async fn ocr_region(name: &str, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    let rect = presets::get(name)?.rect;
+    capture_and_ocr_with_engine(rect.x, rect.y, rect.width, rect.height, language, engine).await
+}
+
+/// 按标题或句柄截取指定窗口（即使被其他窗口遮挡），返回 base64 编码的图像
+pub async fn capture_window(target: &str) -> Result<String> {
+    println!("Capturing window: {}", target);
+
+    #[cfg(target_os = "windows")]
+    {
+        let target = target.to_string();
+        blocking(move || {
+            let window = region::find_window(&target)?;
+            let (pixels, w, h) = unsafe { capture_window_bitmap(HWND(window.hwnd))? };
+            Ok(general_purpose::STANDARD.encode(create_bmp_file(&pixels, w, h)))
+        })
+        .await
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Ok(general_purpose::STANDARD.encode(linux::capture_window_png(target).await?))
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        Err(AppError::PlatformNotSupported("Window capture requires native implementation".to_string()))
+    }
+}
+
+/// 截取指定窗口并识别，适合翻译后台窗口中的文字
+pub async fn capture_window_and_ocr(target: &str, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    println!("Capturing window {} and performing OCR with engine: {}", target, engine);
+
+    #[cfg(target_os = "windows")]
+    {
+        let target = target.to_string();
+        let (raw_pixels, w, h) = blocking(move || {
+            let window = region::find_window(&target)?;
+            unsafe { capture_window_bitmap(HWND(window.hwnd)) }
+        })
+        .await?;
+        recognize_pixels(raw_pixels, w, h, language, engine).await
+    }
+
+    #[cfg(target_os = "linux")]
     {
-        let ocr_impl = get_ocr_impl();
-        ocr_impl.capture_and_ocr(x, y, w, h, language)
+        let png_data = linux::capture_window_png(target).await?;
+        recognize_png(png_data, language, engine).await
     }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        let _ = (language, engine);
+        Err(AppError::PlatformNotSupported("Window capture requires native implementation".to_string()))
+    }
+}
+
+/// 按 `request.engine` 选择公式识别后端，默认使用 Mathpix
+pub async fn recognize_formula(
+    request: mathpix::MathpixRequest,
+    config: Option<&serde_json::Value>,
+) -> Result<mathpix::MathpixResult> {
+    match request.engine.as_deref().unwrap_or("mathpix") {
+        "mathpix" => mathpix::recognize_formula(request.image_data, request.image_url, config).await,
+        "simpletex" => simpletex::recognize_formula(request.image_data, request.image_url, config).await,
+        "local" => {
+            let image_data = request
+                .image_data
+                .ok_or_else(|| AppError::InvalidRequest("Local formula recognition needs image_data".to_string()))?;
+            tokio::task::spawn_blocking(move || formula_local::recognize_formula(&image_data))
+                .await
+                .map_err(|e| AppError::Ocr(format!("Formula recognition task failed: {}", e)))?
+        }
+        other => Err(AppError::InvalidRequest(format!("Unknown formula engine: {}", other))),
+    }
+}
+
+/// 截取自由形状选区：按外接矩形截图，多边形外的像素涂白后再识别
+pub async fn capture_region_and_ocr(points: Vec<region::Point>, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    let (x, y, w, h) = region::bounding_box(&points)?;
+    println!("Capturing freeform region with {} points, bounds ({}, {}) size ({}x{})", points.len(), x, y, w, h);
+
+    #[cfg(target_os = "windows")]
+    {
+        let (raw_pixels, w, h) = blocking(move || {
+            let (mut raw_pixels, w, h) = unsafe { capture_bitmap(x, y, w, h)? };
+            region::apply_polygon_mask(&mut raw_pixels, w, h, (x, y), &points);
+            Ok((raw_pixels, w, h))
+        })
+        .await?;
+        recognize_pixels(raw_pixels, w, h, language, engine).await
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let png_data = linux::capture_png(x, y, w, h).await?;
+        let png_data = blocking(move || linux::mask_png(&png_data, (x, y), &points)).await?;
+        recognize_png(png_data, language, engine).await
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        let _ = (points, language, engine);
+        Err(AppError::PlatformNotSupported("Native OCR not available on this platform".to_string()))
+    }
+}
+
+/// Linux 下识别截图：默认使用 Tesseract，`paddle` 和 `rapid` 引擎分别走 PaddleOCR 和内置 ONNX 模型，
+/// `llm_vision` 请求视觉模型；本地引擎在阻塞线程中运行
+#[cfg(target_os = "linux")]
+async fn recognize_png(png_data: Vec<u8>, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    // PaddleOCR 在阻塞线程中运行，其余引擎异步识别
+    let recognized = match engine {
+        "llm_vision" => Some(llm_vision::recognize(&png_data, language.as_deref()).await?),
+        "rapid" => Some(rapid::recognize(png_data.clone()).await?),
+        "paddle" => None,
+        _ => Some(tesseract::recognize(&png_data, language.as_deref()).await?),
+    };
+    let engine = engine.to_string();
+    blocking(move || {
+        let barcodes = barcode::detect_encoded(&png_data);
+        let result = match recognized {
+            Some(result) => result,
+            None => paddle::paddle_ocr_recognize(&png_data)?,
+        };
+        let mut result = postprocess(result, true, false);
+        result.barcodes = barcodes;
+        history::record_encoded(&result.text, &engine, &png_data);
+        Ok(result)
+    })
+    .await
+}
+
+/// 在阻塞线程中准备好的截图：同步引擎已直接给出结果，系统 OCR 则得到预处理后的 BMP，
+/// 视觉模型、Tesseract 和 RapidOCR 得到 PNG
+#[cfg(target_os = "windows")]
+enum Prepared {
+    Recognized(AppOcrResult),
+    Bitmap(Vec<u8>),
+    Png(Vec<u8>),
+}
+
+#[cfg(target_os = "windows")]
+async fn recognize_pixels(raw_pixels: Vec<u8>, w: i32, h: i32, language: Option<String>, engine: &str) -> Result<AppOcrResult> {
+    let pixels = Arc::new(raw_pixels);
+    let (barcodes, prepared) = {
+        let (pixels, engine) = (pixels.clone(), engine.to_string());
+        blocking(move || {
+            let barcodes = barcode::detect_bgra(&pixels, w, h);
+            let prepared = match engine.as_str() {
+                "paddle" => Prepared::Recognized(paddle::paddle_ocr_recognize(&create_png_from_pixels(&pixels, w, h))?),
+                "llm_vision" | "tesseract" | "rapid" => Prepared::Png(create_png_from_pixels(&pixels, w, h)),
+                _ => {
+                    println!("Preprocessing image: {}x{} -> Upscaling 2x with padding", w, h);
+                    let (processed_pixels, new_w, new_h) = preprocess_image(&pixels, w, h);
+                    Prepared::Bitmap(create_bmp_file(&processed_pixels, new_w, new_h))
+                }
+            };
+            Ok((barcodes, prepared))
+        })
+        .await?
+    };
+
+    let mut result = match prepared {
+        Prepared::Recognized(result) => result,
+        Prepared::Png(png_data) if engine == "tesseract" => tesseract::recognize(&png_data, language.as_deref()).await?,
+        Prepared::Png(png_data) if engine == "rapid" => rapid::recognize(png_data).await?,
+        Prepared::Png(png_data) => llm_vision::recognize(&png_data, language.as_deref()).await?,
+        Prepared::Bitmap(bmp_data) => {
+            let mut result = recognize_bytes(bmp_data, language).await?;
+            // 行坐标换算回截图坐标
+            let (scale, padding) = (PREPROCESS_SCALE as f64, PREPROCESS_PADDING as f64);
+            for line in &mut result.lines {
+                line.x = (line.x - padding) / scale;
+                line.y = (line.y - padding) / scale;
+                line.width /= scale;
+                line.height /= scale;
+            }
+            result
+        }
+    };
+    result = postprocess(result, true, false);
+    result.barcodes = barcodes;
+
+    let (text, engine) = (result.text.clone(), engine.to_string());
+    blocking(move || {
+        history::record_bgra(&text, &engine, &pixels, w, h);
+        Ok(())
+    })
+    .await?;
+    Ok(result)
 }
 
 #[cfg(target_os = "windows")]
@@ -5,10 +5,120 @@ pub struct OcrRequest {
     pub image_data: Option<String>,
     pub image_path: Option<String>,
     pub language: Option<String>,
+    /// 竖排文字（漫画、古籍等）；不指定时对中日文自动判断
+    #[serde(default)]
+    pub vertical: Option<bool>,
+    /// 合并硬换行、接回连字符断开的单词并去掉中日韩文字间的空格
+    #[serde(default)]
+    pub cleanup: bool,
+}
+
+/// 截图→识别→翻译一次完成的请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaptureTranslateRequest {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    /// 识别语言，同时作为翻译的源语言；不指定时自动检测
+    #[serde(default)]
+    pub ocr_lang: Option<String>,
+    pub target_lang: String,
+    #[serde(default)]
+    pub services: Vec<String>,
+    /// 识别引擎，默认使用系统 OCR
+    #[serde(default)]
+    pub engine: Option<String>,
+    /// 与翻译请求相同的服务配置
+    #[serde(default)]
+    pub config: Option<std::collections::HashMap<String, serde_json::Value>>,
+}
+
+/// 区域 OCR 监视的请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct OcrWatchRequest {
+    pub rect: crate::ocr::presets::RegionRect,
+    /// 截图间隔（毫秒）
+    pub interval_ms: u64,
+    #[serde(default)]
+    pub language: Option<String>,
+    pub target_lang: String,
+    /// 识别引擎，默认使用系统 OCR
+    #[serde(default)]
+    pub engine: Option<String>,
+    #[serde(default)]
+    pub services: Vec<String>,
+    /// 与翻译请求相同的服务配置
+    #[serde(default)]
+    pub config: Option<std::collections::HashMap<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OcrResult {
     pub text: String,
     pub confidence: f64,
+    /// 带坐标的文本行，仅部分引擎提供
+    #[serde(default)]
+    pub lines: Vec<OcrLine>,
+    /// 版面分析得到的段落，按阅读顺序排列
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paragraphs: Vec<OcrParagraph>,
+    /// 图像中识别到的二维码
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub barcodes: Vec<Barcode>,
+}
+
+/// 一行识别结果及其外接矩形（图像像素坐标）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrLine {
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub confidence: Option<f64>,
+}
+
+/// 一个段落及其外接矩形
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrParagraph {
+    /// 段内各行以换行连接
+    pub text: String,
+    /// 所在栏（从左到右，从 0 开始）；通栏内容为 0
+    pub column: usize,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// 一个已解码的二维码及其外接矩形
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Barcode {
+    /// 码制，目前只有 `qr`
+    pub format: String,
+    /// 解码后的内容
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// 已安装、可用于识别的语言
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OcrLanguage {
+    /// BCP-47 标签（Windows）或语言包名（Tesseract），可直接作为识别请求的 `language`
+    pub tag: String,
+    pub display_name: String,
+    pub native_name: String,
+}
+
+/// 批量识别中单个文件的结果，`result` 与 `error` 二者有一
+#[derive(Debug, Clone, Serialize)]
+pub struct OcrFileResult {
+    pub path: String,
+    pub result: Option<OcrResult>,
+    pub error: Option<String>,
 }
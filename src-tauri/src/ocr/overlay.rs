@@ -0,0 +1,91 @@
+//! 图像内翻译：识别截图后逐行翻译，返回每行的位置、原文和译文，
+//! 供遮罩窗口把译文绘制在原文所在的位置上（类似 Google 智能镜头）。
+
+use crate::error::{AppError, Result};
+use crate::models::{TextFormat, TranslationMode, TranslationRequest, TranslationResponse};
+use crate::ocr::models::{CaptureTranslateRequest, OcrLine};
+use serde::Serialize;
+
+/// 行的外接矩形，相对于截图区域左上角的物理像素坐标
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LineBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OverlayLine {
+    pub bbox: LineBox,
+    pub source: String,
+    pub translation: String,
+}
+
+/// 第一个成功服务的译文
+fn first_translation(response: TranslationResponse) -> Option<String> {
+    response.results.into_iter().find(|result| result.error.is_none()).map(|result| result.text)
+}
+
+fn overlay_line(line: &OcrLine, translation: &str) -> OverlayLine {
+    OverlayLine {
+        bbox: LineBox { x: line.x, y: line.y, width: line.width, height: line.height },
+        source: line.text.trim().to_string(),
+        translation: translation.trim().to_string(),
+    }
+}
+
+/// 把按行拼接翻译得到的译文拆回各行；行数对不上（服务合并或拆分了行）时返回 None
+pub(crate) fn pair_lines(lines: &[OcrLine], translated: &str) -> Option<Vec<OverlayLine>> {
+    let translations: Vec<&str> = translated.trim().lines().collect();
+    if translations.len() != lines.len() {
+        return None;
+    }
+    Some(lines.iter().zip(translations).map(|(line, translation)| overlay_line(line, translation)).collect())
+}
+
+/// 截图识别后翻译每一行。先把所有行合成一次请求翻译，译文行数对不上时再逐行翻译
+pub async fn translate_capture(request: CaptureTranslateRequest) -> Result<Vec<OverlayLine>> {
+    let engine = request.engine.as_deref().unwrap_or("windows");
+    let result =
+        super::capture_and_ocr_with_engine(request.x, request.y, request.w, request.h, request.ocr_lang.clone(), engine)
+            .await?;
+    let lines: Vec<OcrLine> = result.lines.into_iter().filter(|line| !line.text.trim().is_empty()).collect();
+    if lines.is_empty() {
+        if !result.text.trim().is_empty() {
+            return Err(AppError::Ocr(format!("OCR engine {} does not report line positions", engine)));
+        }
+        println!("No text recognized, nothing to translate");
+        return Ok(Vec::new());
+    }
+
+    let translation_request = |text: String| TranslationRequest {
+        text,
+        source_lang: request.ocr_lang.clone().unwrap_or_else(|| "auto".to_string()),
+        target_lang: request.target_lang.clone(),
+        services: request.services.clone(),
+        config: request.config.clone(),
+        mode: TranslationMode::Fallback,
+        format: TextFormat::Plain,
+    };
+
+    let joined = lines.iter().map(|line| line.text.trim()).collect::<Vec<_>>().join("\n");
+    let translated = crate::services::translate(translation_request(joined))
+        .await
+        .map(first_translation)?
+        .ok_or_else(|| AppError::Translation("All translation services failed".to_string()))?;
+    if let Some(paired) = pair_lines(&lines, &translated) {
+        return Ok(paired);
+    }
+
+    println!("Translation changed the line count, translating {} lines separately", lines.len());
+    let responses = futures_util::future::try_join_all(
+        lines.iter().map(|line| crate::services::translate(translation_request(line.text.trim().to_string()))),
+    )
+    .await?;
+    Ok(lines
+        .iter()
+        .zip(responses)
+        .map(|(line, response)| overlay_line(line, &first_translation(response).unwrap_or_default()))
+        .collect())
+}
@@ -1,7 +1,10 @@
 use crate::error::{AppError, Result};
-use crate::ocr::models::OcrResult;
+use crate::ocr::models::{OcrLine, OcrResult};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use crate::ocr::worker::{self, WorkerSlot};
 use std::process::Command;
-use serde::Deserialize;
+use std::sync::Mutex;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
@@ -13,21 +16,51 @@ const CREATE_NO_WINDOW: u32 = 0x08000000;
 struct PaddleOcrResult {
     text: String,
     confidence: f64,
+    #[serde(default)]
+    lines: Vec<PaddleLine>,
+}
+
+/// `box` 为文本框四个顶点
+#[derive(Deserialize)]
+struct PaddleLine {
+    text: String,
+    confidence: f64,
+    #[serde(rename = "box")]
+    points: Vec<[f64; 2]>,
+}
+
+impl From<PaddleLine> for OcrLine {
+    fn from(line: PaddleLine) -> Self {
+        let xs = line.points.iter().map(|p| p[0]);
+        let ys = line.points.iter().map(|p| p[1]);
+        let left = xs.clone().fold(f64::INFINITY, f64::min);
+        let right = xs.fold(f64::NEG_INFINITY, f64::max);
+        let top = ys.clone().fold(f64::INFINITY, f64::min);
+        let bottom = ys.fold(f64::NEG_INFINITY, f64::max);
+        OcrLine {
+            text: line.text,
+            x: left,
+            y: top,
+            width: (right - left).max(0.0),
+            height: (bottom - top).max(0.0),
+            confidence: Some(line.confidence),
+        }
+    }
 }
 
 #[cfg(target_os = "windows")]
-fn create_command(program: &str) -> Command {
+pub(crate) fn create_command(program: &str) -> Command {
     let mut cmd = Command::new(program);
     cmd.creation_flags(CREATE_NO_WINDOW);
     cmd
 }
 
 #[cfg(not(target_os = "windows"))]
-fn create_command(program: &str) -> Command {
+pub(crate) fn create_command(program: &str) -> Command {
     Command::new(program)
 }
 
-fn find_python() -> Option<String> {
+pub(crate) fn find_python() -> Option<String> {
     let candidates = ["python", "python3", "py"];
     
     for cmd_name in candidates {
@@ -73,79 +106,164 @@ pub fn is_paddle_ocr_available() -> bool {
     }
 }
 
-pub fn paddle_ocr_recognize(image_data: &[u8]) -> Result<OcrResult> {
-    let python_cmd = find_python()
-        .ok_or_else(|| AppError::Ocr("Python not found. Please install Python.".to_string()))?;
-    
-    let temp_dir = std::env::temp_dir();
-    let temp_path = temp_dir.join("paddle_ocr_temp.png");
-    
-    println!("Writing temp image to: {:?}", temp_path);
-    std::fs::write(&temp_path, image_data)
-        .map_err(|e| AppError::Ocr(format!("Failed to write temp image: {}", e)))?;
-    
-    let script = r#"
-import paddleocr
+/// PaddleOCR 的引擎参数，原样作为 `PaddleOCR(...)` 的关键字参数；未指定的项使用 PaddleOCR 默认值
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PaddleOptions {
+    /// 使用 CUDA（需要安装 paddlepaddle-gpu）
+    #[serde(default)]
+    pub use_gpu: bool,
+    /// 模型版本：`PP-OCRv3` 或 `PP-OCRv4`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ocr_version: Option<String>,
+    /// 检测像素阈值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub det_db_thresh: Option<f64>,
+    /// 检测框阈值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub det_db_box_thresh: Option<f64>,
+    /// 识别置信度低于该值的行被丢弃
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drop_score: Option<f64>,
+}
+
+const OCR_VERSIONS: &[&str] = &["PP-OCRv3", "PP-OCRv4"];
+
+impl PaddleOptions {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(version) = &self.ocr_version {
+            if !OCR_VERSIONS.contains(&version.as_str()) {
+                return Err(AppError::InvalidRequest(format!(
+                    "Unsupported PaddleOCR model version: {} (expected one of {})",
+                    version,
+                    OCR_VERSIONS.join(", ")
+                )));
+            }
+        }
+        let thresholds = [
+            ("det_db_thresh", self.det_db_thresh),
+            ("det_db_box_thresh", self.det_db_box_thresh),
+            ("drop_score", self.drop_score),
+        ];
+        for (name, value) in thresholds {
+            if value.is_some_and(|v| !(0.0..=1.0).contains(&v)) {
+                return Err(AppError::InvalidRequest(format!("{} must be between 0 and 1", name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+static OPTIONS: Lazy<Mutex<PaddleOptions>> = Lazy::new(|| Mutex::new(PaddleOptions::default()));
+
+/// 常驻工作进程脚本：启动参数为引擎参数的 JSON，每个请求给出待识别图像的路径
+const WORKER_SCRIPT: &str = r#"
+import io
 import json
 import sys
 
+out = sys.stdout
+sys.stdout = sys.stderr
+requests = io.TextIOWrapper(sys.stdin.buffer, encoding='utf-8')
+
+def reply(payload):
+    out.write(json.dumps(payload) + "\n")
+    out.flush()
+
 try:
-    ocr = paddleocr.PaddleOCR(use_angle_cls=True, lang='ch', show_log=False)
-    result = ocr.ocr(sys.argv[1], cls=True)
+    import paddleocr
+    options = json.loads(sys.argv[1])
+    ocr = paddleocr.PaddleOCR(use_angle_cls=True, lang='ch', show_log=False, **options)
+except Exception as e:
+    reply({"error": str(e)})
+    sys.exit(1)
 
-    text_lines = []
-    total_conf = 0.0
-    count = 0
+reply({"ready": True})
 
-    for line in result:
-        if line:
-            for word_info in line:
-                text_lines.append(word_info[1][0])
-                total_conf += word_info[1][1]
-                count += 1
+for request in requests:
+    try:
+        result = ocr.ocr(json.loads(request)["path"], cls=True)
 
-    avg_conf = total_conf / count if count > 0 else 0.0
+        text_lines = []
+        boxes = []
+        total_conf = 0.0
+        count = 0
 
-    output = {
-        "text": "\n".join(text_lines),
-        "confidence": avg_conf
-    }
-    print(json.dumps(output, ensure_ascii=False))
-except Exception as e:
-    import traceback
-    error_output = {"error": str(e), "traceback": traceback.format_exc()}
-    print(json.dumps(error_output, ensure_ascii=False))
-    sys.exit(1)
+        for line in result:
+            if line:
+                for word_info in line:
+                    text_lines.append(word_info[1][0])
+                    boxes.append({
+                        "text": word_info[1][0],
+                        "confidence": word_info[1][1],
+                        "box": [[float(x), float(y)] for x, y in word_info[0]]
+                    })
+                    total_conf += word_info[1][1]
+                    count += 1
+
+        reply({
+            "text": "\n".join(text_lines),
+            "confidence": total_conf / count if count > 0 else 0.0,
+            "lines": boxes
+        })
+    except Exception as e:
+        reply({"error": str(e)})
 "#;
-    
-    let path_str = temp_path.to_string_lossy();
-    println!("Running PaddleOCR with image: {}", path_str);
-    
-    let output = create_command(&python_cmd)
-        .args(["-c", script, &path_str])
-        .output()
-        .map_err(|e| AppError::Ocr(format!("Failed to run PaddleOCR: {}", e)))?;
-    
-    let _ = std::fs::remove_file(&temp_path);
-    
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    
-    println!("PaddleOCR stdout: {}", stdout);
-    if !stderr.is_empty() {
-        println!("PaddleOCR stderr: {}", stderr);
-    }
-    
-    if !output.status.success() {
-        return Err(AppError::Ocr(format!("PaddleOCR failed: {} {}", stdout, stderr)));
+
+static WORKER: WorkerSlot = WorkerSlot::new("PaddleOCR", WORKER_SCRIPT);
+
+/// 工作进程的启动参数：引擎参数的 JSON
+fn worker_args() -> Result<Vec<String>> {
+    Ok(vec![serde_json::to_string(&*OPTIONS.lock().unwrap())?])
+}
+
+/// 启动常驻工作进程；已在运行时直接返回
+pub fn start_worker() -> Result<()> {
+    WORKER.start(&worker_args()?)
+}
+
+/// 停止常驻工作进程，释放模型占用的内存
+pub fn stop_worker() {
+    WORKER.stop()
+}
+
+pub fn is_worker_running() -> bool {
+    WORKER.is_running()
+}
+
+pub fn options() -> PaddleOptions {
+    OPTIONS.lock().unwrap().clone()
+}
+
+/// 更新引擎参数；参数在模型加载时生效，因此变化时停止正在运行的工作进程，下次识别时按新参数启动
+pub fn set_options(options: PaddleOptions) -> Result<()> {
+    options.validate()?;
+    let changed = {
+        let mut current = OPTIONS.lock().unwrap();
+        let changed = *current != options;
+        *current = options;
+        changed
+    };
+    if changed {
+        stop_worker();
     }
-    
-    let result: PaddleOcrResult = serde_json::from_str(&stdout)
-        .map_err(|e| AppError::Ocr(format!("Failed to parse PaddleOCR output: {} (output was: {})", e, stdout)))?;
-    
+    Ok(())
+}
+
+/// 使用常驻工作进程识别，首次调用时加载模型
+pub fn paddle_ocr_recognize(image_data: &[u8]) -> Result<OcrResult> {
+    let args = worker_args()?;
+    let response = worker::with_temp_image("paddle_ocr", image_data, |path| {
+        WORKER.request(&args, &serde_json::json!({ "path": path.to_string_lossy() }))
+    })?;
+    let result: PaddleOcrResult = serde_json::from_value(response)
+        .map_err(|e| AppError::Ocr(format!("Failed to parse PaddleOCR output: {}", e)))?;
+
     Ok(OcrResult {
         text: result.text,
         confidence: result.confidence,
+        lines: result.lines.into_iter().filter(|l| !l.points.is_empty()).map(OcrLine::from).collect(),
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
     })
 }
 
@@ -0,0 +1,100 @@
+//! 命名的截图区域预设：反复识别同一块屏幕区域（如游戏对话框、字幕栏）时，
+//! 保存一次即可按名称识别，或绑定到快捷键（动作名 `ocr_region:<名称>`）而无需重新框选。
+
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// 快捷键动作名前缀，后接预设名称
+pub const HOTKEY_ACTION_PREFIX: &str = "ocr_region:";
+
+/// 截图区域（虚拟屏幕的物理像素坐标）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RegionRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionPreset {
+    pub name: String,
+    #[serde(flatten)]
+    pub rect: RegionRect,
+}
+
+/// None 表示尚未从磁盘加载
+static PRESETS: Lazy<Mutex<Option<Vec<RegionPreset>>>> = Lazy::new(|| Mutex::new(None));
+
+fn presets_file() -> PathBuf {
+    crate::paths::data_dir().join("ocr_regions.json")
+}
+
+fn load() -> Vec<RegionPreset> {
+    std::fs::read_to_string(presets_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write(presets: &[RegionPreset]) -> Result<()> {
+    let path = presets_file();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(presets)?)?;
+    Ok(())
+}
+
+/// 在加载后的列表上执行修改并写回磁盘
+fn with_presets<T>(f: impl FnOnce(&mut Vec<RegionPreset>) -> Result<T>) -> Result<T> {
+    let mut guard = PRESETS.lock().unwrap();
+    let presets = guard.get_or_insert_with(load);
+    let value = f(presets)?;
+    write(presets)?;
+    Ok(value)
+}
+
+fn not_found(name: &str) -> AppError {
+    AppError::InvalidRequest(format!("OCR region not found: {}", name))
+}
+
+pub fn list() -> Vec<RegionPreset> {
+    PRESETS.lock().unwrap().get_or_insert_with(load).clone()
+}
+
+pub fn get(name: &str) -> Result<RegionPreset> {
+    let name = name.trim();
+    list().into_iter().find(|preset| preset.name == name).ok_or_else(|| not_found(name))
+}
+
+/// 保存预设；同名预设被覆盖
+pub fn save(name: &str, rect: RegionRect) -> Result<RegionPreset> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidRequest("OCR region name cannot be empty".to_string()));
+    }
+    if rect.width <= 0 || rect.height <= 0 {
+        return Err(AppError::InvalidRequest("OCR region has no area".to_string()));
+    }
+    let preset = RegionPreset { name: name.to_string(), rect };
+    with_presets(|presets| {
+        match presets.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = preset.clone(),
+            None => presets.push(preset.clone()),
+        }
+        Ok(preset)
+    })
+}
+
+pub fn delete(name: &str) -> Result<()> {
+    let name = name.trim();
+    with_presets(|presets| {
+        let index = presets.iter().position(|p| p.name == name).ok_or_else(|| not_found(name))?;
+        presets.remove(index);
+        Ok(())
+    })
+}
@@ -0,0 +1,356 @@
+//! 内置 PP-OCR 引擎（RapidOCR 同款 ONNX 模型），通过 onnxruntime 在进程内推理，
+//! 不依赖 Python。需在构建时启用 `rapid-ocr` 特性（默认不开启）。
+//!
+//! 模型文件（`DET_MODEL`、`REC_MODEL`、`KEYS_FILE`）依次在以下目录查找，
+//! 见 README 的「内置 RapidOCR 引擎」一节：
+//! - 可执行文件旁的 `rapidocr/`（随安装包附带）
+//! - 数据目录下的 `models/rapidocr/`：Windows 为 `%LOCALAPPDATA%\dict\models\rapidocr`，
+//!   其他平台为 `~/.local/share/dict/models/rapidocr`
+#![cfg_attr(not(feature = "rapid-ocr"), allow(dead_code))]
+
+use crate::error::{AppError, Result};
+#[cfg(feature = "rapid-ocr")]
+use crate::ocr::models::OcrLine;
+use crate::ocr::models::OcrResult;
+use serde::Serialize;
+use std::path::PathBuf;
+
+pub const DET_MODEL: &str = "ch_PP-OCRv4_det_infer.onnx";
+pub const REC_MODEL: &str = "ch_PP-OCRv4_rec_infer.onnx";
+pub const KEYS_FILE: &str = "ppocr_keys_v1.txt";
+
+/// 检测输入的长边上限
+const DET_LIMIT_SIDE: u32 = 960;
+/// 概率图二值化阈值
+const DET_THRESH: f32 = 0.3;
+/// 文本框平均概率低于该值时丢弃
+const DET_BOX_THRESH: f64 = 0.5;
+/// 文本框外扩比例（DB 算法的 unclip）
+const DET_UNCLIP_RATIO: f64 = 1.6;
+/// 文本框短边下限（像素）
+const DET_MIN_SIZE: f64 = 3.0;
+
+const REC_HEIGHT: u32 = 48;
+const REC_MIN_WIDTH: u32 = 320;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RapidStatus {
+    /// 当前构建是否包含该引擎
+    pub compiled: bool,
+    pub models_dir: Option<String>,
+    pub missing_files: Vec<String>,
+}
+
+/// 检测出的文本框（检测输入图的像素坐标）
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DetBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub score: f64,
+}
+
+fn candidate_dirs() -> Vec<PathBuf> {
+    let bundled = std::env::current_exe().ok().and_then(|exe| exe.parent().map(|dir| dir.join("rapidocr")));
+    bundled
+        .into_iter()
+        .chain(std::iter::once(crate::paths::data_dir().join("models").join("rapidocr")))
+        .collect()
+}
+
+fn missing_files(dir: &std::path::Path) -> Vec<String> {
+    [DET_MODEL, REC_MODEL, KEYS_FILE]
+        .iter()
+        .filter(|file| !dir.join(file).is_file())
+        .map(|file| file.to_string())
+        .collect()
+}
+
+/// 第一个模型文件齐全的目录
+fn models_dir() -> Option<PathBuf> {
+    candidate_dirs().into_iter().find(|dir| missing_files(dir).is_empty())
+}
+
+pub fn status() -> RapidStatus {
+    let dirs = candidate_dirs();
+    let found = dirs.iter().find(|dir| missing_files(dir).is_empty());
+    // 都不齐全时报告数据目录缺少的文件，用户把模型放到那里即可
+    let reported = found.or(dirs.last());
+    RapidStatus {
+        compiled: cfg!(feature = "rapid-ocr"),
+        models_dir: reported.map(|dir| dir.to_string_lossy().into_owned()),
+        missing_files: reported.map(|dir| missing_files(dir)).unwrap_or_default(),
+    }
+}
+
+/// 字符表：每行一个字符，末尾追加空格（模型的最后一类）
+pub(crate) fn parse_keys(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .chain(std::iter::once(" ".to_string()))
+        .collect()
+}
+
+/// 检测输入尺寸：长边不超过上限，宽高取 32 的倍数
+pub(crate) fn det_input_size(width: u32, height: u32) -> (u32, u32) {
+    let longest = width.max(height).max(1);
+    let ratio = if longest > DET_LIMIT_SIDE { DET_LIMIT_SIDE as f64 / longest as f64 } else { 1.0 };
+    let round = |side: u32| (((side as f64 * ratio) / 32.0).round() as u32 * 32).max(32);
+    (round(width), round(height))
+}
+
+/// 按 PaddleOCR 的方式归一化为 CHW（BGR 通道顺序）
+fn normalize(image: &image::RgbImage, mean: [f32; 3], std: [f32; 3], out: &mut [f32], plane_width: usize, plane_size: usize) {
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let offset = y as usize * plane_width + x as usize;
+        for (channel, value) in [pixel[2], pixel[1], pixel[0]].into_iter().enumerate() {
+            out[channel * plane_size + offset] = (value as f32 / 255.0 - mean[channel]) / std[channel];
+        }
+    }
+}
+
+pub(crate) fn det_tensor(image: &image::RgbImage) -> Vec<f32> {
+    let (w, h) = (image.width() as usize, image.height() as usize);
+    let mut data = vec![0.0; 3 * w * h];
+    normalize(image, [0.485, 0.456, 0.406], [0.229, 0.224, 0.225], &mut data, w, w * h);
+    data
+}
+
+/// 识别输入：高度缩放到 48，宽度至少 320，右侧补零
+pub(crate) fn rec_tensor(image: &image::RgbImage) -> (Vec<f32>, u32) {
+    let (w, h) = (image.width().max(1), image.height().max(1));
+    let resized_width = ((REC_HEIGHT as f64 * w as f64 / h as f64).ceil() as u32).max(1);
+    let tensor_width = resized_width.max(REC_MIN_WIDTH);
+    let resized = image::imageops::resize(image, resized_width, REC_HEIGHT, image::imageops::FilterType::Triangle);
+
+    let plane_size = (REC_HEIGHT * tensor_width) as usize;
+    let mut data = vec![0.0; 3 * plane_size];
+    normalize(&resized, [0.5; 3], [0.5; 3], &mut data, tensor_width as usize, plane_size);
+    (data, tensor_width)
+}
+
+/// 从 DB 概率图中提取文本框：二值化后取 8 连通域的外接矩形，按 unclip 比例外扩
+pub(crate) fn boxes_from_map(map: &[f32], width: usize, height: usize) -> Vec<DetBox> {
+    let mut visited = vec![false; width * height];
+    let mut boxes = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..width * height {
+        if visited[start] || map[start] <= DET_THRESH {
+            continue;
+        }
+        visited[start] = true;
+        stack.push(start);
+        let (mut left, mut top, mut right, mut bottom) = (usize::MAX, usize::MAX, 0, 0);
+        let (mut sum, mut count) = (0.0f64, 0usize);
+
+        while let Some(index) = stack.pop() {
+            let (x, y) = (index % width, index / width);
+            left = left.min(x);
+            right = right.max(x);
+            top = top.min(y);
+            bottom = bottom.max(y);
+            sum += map[index] as f64;
+            count += 1;
+
+            for dy in -1i64..=1 {
+                for dx in -1i64..=1 {
+                    let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+                    if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+                        continue;
+                    }
+                    let next = ny as usize * width + nx as usize;
+                    if !visited[next] && map[next] > DET_THRESH {
+                        visited[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        let (w, h) = ((right - left + 1) as f64, (bottom - top + 1) as f64);
+        let score = sum / count as f64;
+        if w.min(h) < DET_MIN_SIZE || score < DET_BOX_THRESH {
+            continue;
+        }
+        // 收缩后的文本核按面积/周长比例外扩回完整文本框
+        let distance = w * h * DET_UNCLIP_RATIO / (2.0 * (w + h));
+        let x0 = (left as f64 - distance).max(0.0);
+        let y0 = (top as f64 - distance).max(0.0);
+        let x1 = (right as f64 + 1.0 + distance).min(width as f64);
+        let y1 = (bottom as f64 + 1.0 + distance).min(height as f64);
+        boxes.push(DetBox { x: x0, y: y0, width: x1 - x0, height: y1 - y0, score });
+    }
+
+    sort_reading_order(&mut boxes);
+    boxes
+}
+
+/// 自上而下排序；纵坐标相差不到 10 像素的视为同一行，按横坐标排序
+fn sort_reading_order(boxes: &mut [DetBox]) {
+    boxes.sort_by(|a, b| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    for i in 1..boxes.len() {
+        let mut j = i;
+        while j > 0 && (boxes[j].y - boxes[j - 1].y).abs() < 10.0 && boxes[j].x < boxes[j - 1].x {
+            boxes.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// CTC 贪心解码：逐帧取最大概率类别，去掉空白（0）和连续重复；置信度为保留字符概率的平均值
+pub(crate) fn ctc_decode(probs: &[f32], steps: usize, classes: usize, keys: &[String]) -> (String, f64) {
+    let mut text = String::new();
+    let mut scores = Vec::new();
+    let mut previous = 0;
+    for step in probs.chunks(classes).take(steps) {
+        let (index, score) = step
+            .iter()
+            .enumerate()
+            .fold((0, f32::MIN), |best, (i, &p)| if p > best.1 { (i, p) } else { best });
+        if index != 0 && index != previous {
+            if let Some(key) = keys.get(index - 1) {
+                text.push_str(key);
+                scores.push(score as f64);
+            }
+        }
+        previous = index;
+    }
+    let confidence = if scores.is_empty() { 0.0 } else { scores.iter().sum::<f64>() / scores.len() as f64 };
+    (text, confidence)
+}
+
+#[cfg(feature = "rapid-ocr")]
+mod engine {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use ort::session::Session;
+    use ort::value::Tensor;
+    use std::sync::Mutex;
+
+    pub(super) struct Engine {
+        det: Session,
+        rec: Session,
+        keys: Vec<String>,
+    }
+
+    /// 模型加载较慢，首次识别时加载后常驻
+    pub(super) static ENGINE: Lazy<Mutex<Option<Engine>>> = Lazy::new(|| Mutex::new(None));
+
+    fn ort_error(e: ort::Error) -> AppError {
+        AppError::Ocr(format!("ONNX Runtime error: {}", e))
+    }
+
+    fn load_session(path: PathBuf) -> Result<Session> {
+        Session::builder().map_err(ort_error)?.commit_from_file(path).map_err(ort_error)
+    }
+
+    impl Engine {
+        pub(super) fn load() -> Result<Self> {
+            let dir = models_dir().ok_or_else(|| {
+                AppError::Ocr(format!(
+                    "RapidOCR models not found. Place {}, {} and {} in {}",
+                    DET_MODEL,
+                    REC_MODEL,
+                    KEYS_FILE,
+                    crate::paths::data_dir().join("models").join("rapidocr").display()
+                ))
+            })?;
+            println!("Loading RapidOCR models from {:?}", dir);
+            Ok(Engine {
+                det: load_session(dir.join(DET_MODEL))?,
+                rec: load_session(dir.join(REC_MODEL))?,
+                keys: parse_keys(&std::fs::read_to_string(dir.join(KEYS_FILE))?),
+            })
+        }
+
+        /// 返回概率图及其宽高
+        pub(super) fn detect(&mut self, image: &image::RgbImage) -> Result<(Vec<f32>, usize, usize)> {
+            let (w, h) = (image.width() as usize, image.height() as usize);
+            let input = Tensor::from_array(([1usize, 3, h, w], det_tensor(image))).map_err(ort_error)?;
+            let outputs = self.det.run(ort::inputs![input]).map_err(ort_error)?;
+            let (_, map) = outputs[0].try_extract_tensor::<f32>().map_err(ort_error)?;
+            Ok((map.to_vec(), w, h))
+        }
+
+        pub(super) fn recognize_line(&mut self, image: &image::RgbImage) -> Result<(String, f64)> {
+            let (data, width) = rec_tensor(image);
+            let input = Tensor::from_array(([1usize, 3, REC_HEIGHT as usize, width as usize], data)).map_err(ort_error)?;
+            let outputs = self.rec.run(ort::inputs![input]).map_err(ort_error)?;
+            let (shape, probs) = outputs[0].try_extract_tensor::<f32>().map_err(ort_error)?;
+            // 输出形状为 [1, 时间步, 类别数]
+            let (steps, classes) = (shape[1] as usize, shape[2] as usize);
+            Ok(ctc_decode(probs, steps, classes, &self.keys))
+        }
+    }
+}
+
+/// 识别编码后的图像：在阻塞线程池中检测文本框后逐行识别，不占用异步运行时
+#[cfg(feature = "rapid-ocr")]
+pub async fn recognize(image_data: Vec<u8>) -> Result<OcrResult> {
+    tokio::task::spawn_blocking(move || recognize_blocking(&image_data))
+        .await
+        .map_err(|e| AppError::Ocr(format!("RapidOCR task failed: {}", e)))?
+}
+
+#[cfg(feature = "rapid-ocr")]
+fn recognize_blocking(image_data: &[u8]) -> Result<OcrResult> {
+    let image = image::load_from_memory(image_data)
+        .map_err(|e| AppError::Ocr(format!("Failed to decode image: {}", e)))?
+        .to_rgb8();
+    let (det_w, det_h) = det_input_size(image.width(), image.height());
+    let det_image = image::imageops::resize(&image, det_w, det_h, image::imageops::FilterType::Triangle);
+
+    // 会话推理需要独占引擎，解码和缩放在加锁前完成
+    let mut guard = engine::ENGINE.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(engine::Engine::load()?);
+    }
+    let engine = guard.as_mut().unwrap();
+
+    let (map, map_w, map_h) = engine.detect(&det_image)?;
+    let (scale_x, scale_y) = (image.width() as f64 / det_w as f64, image.height() as f64 / det_h as f64);
+
+    let mut lines = Vec::new();
+    for det_box in boxes_from_map(&map, map_w, map_h) {
+        let x = (det_box.x * scale_x).floor() as u32;
+        let y = (det_box.y * scale_y).floor() as u32;
+        let width = ((det_box.width * scale_x).ceil() as u32).min(image.width().saturating_sub(x)).max(1);
+        let height = ((det_box.height * scale_y).ceil() as u32).min(image.height().saturating_sub(y)).max(1);
+        let crop = image::imageops::crop_imm(&image, x, y, width, height).to_image();
+        let (text, confidence) = engine.recognize_line(&crop)?;
+        if text.trim().is_empty() {
+            continue;
+        }
+        lines.push(OcrLine {
+            text,
+            x: x as f64,
+            y: y as f64,
+            width: width as f64,
+            height: height as f64,
+            confidence: Some(confidence),
+        });
+    }
+
+    let confidence = if lines.is_empty() {
+        0.0
+    } else {
+        lines.iter().filter_map(|line| line.confidence).sum::<f64>() / lines.len() as f64
+    };
+    Ok(OcrResult {
+        text: lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join("\n"),
+        confidence,
+        lines,
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    })
+}
+
+#[cfg(not(feature = "rapid-ocr"))]
+pub async fn recognize(_image_data: Vec<u8>) -> Result<OcrResult> {
+    Err(AppError::PlatformNotSupported(
+        "RapidOCR engine is not included in this build (enable the `rapid-ocr` feature)".to_string(),
+    ))
+}
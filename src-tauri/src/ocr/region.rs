@@ -0,0 +1,225 @@
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "windows")]
+use windows::Win32::{
+    Foundation::{BOOL, HWND, LPARAM, POINT, RECT},
+    Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS},
+    UI::WindowsAndMessaging::{
+        EnumWindows, GetCursorPos, GetWindowRect, GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindow,
+        IsWindowVisible,
+    },
+};
+
+/// 可供“吸附到窗口”选择的窗口边界（屏幕坐标）
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowRect {
+    pub hwnd: isize,
+    pub title: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// 自由形状选区的顶点（屏幕坐标）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// 返回光标下所有可见的顶层窗口，按 Z 序从上到下排列；本进程的窗口（选区遮罩）被跳过
+#[cfg(target_os = "windows")]
+pub fn list_window_rects() -> Result<Vec<WindowRect>> {
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let windows = &mut *(lparam.0 as *mut Vec<WindowRect>);
+        if let Some(rect) = window_rect(hwnd) {
+            windows.push(rect);
+        }
+        BOOL(1)
+    }
+
+    let mut cursor = POINT::default();
+    unsafe { GetCursorPos(&mut cursor) }
+        .map_err(|e| AppError::Ocr(format!("Failed to get cursor position: {}", e)))?;
+
+    let mut windows: Vec<WindowRect> = Vec::new();
+    unsafe { EnumWindows(Some(collect), LPARAM(&mut windows as *mut _ as isize)) }
+        .map_err(|e| AppError::Ocr(format!("Failed to enumerate windows: {}", e)))?;
+
+    windows.retain(|w| {
+        cursor.x >= w.x && cursor.x < w.x + w.width && cursor.y >= w.y && cursor.y < w.y + w.height
+    });
+    Ok(windows)
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn window_rect(hwnd: HWND) -> Option<WindowRect> {
+    if !IsWindowVisible(hwnd).as_bool() || IsIconic(hwnd).as_bool() {
+        return None;
+    }
+
+    let mut pid = 0u32;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32));
+    if pid == std::process::id() {
+        return None;
+    }
+
+    // UWP 后台窗口“可见”但被 DWM 隐藏
+    let mut cloaked = 0u32;
+    if DwmGetWindowAttribute(
+        hwnd,
+        DWMWA_CLOAKED,
+        &mut cloaked as *mut _ as *mut _,
+        std::mem::size_of::<u32>() as u32,
+    )
+    .is_ok()
+        && cloaked != 0
+    {
+        return None;
+    }
+
+    // 扩展边框不含阴影，比 GetWindowRect 更贴合可见区域
+    let mut rect = RECT::default();
+    if DwmGetWindowAttribute(
+        hwnd,
+        DWMWA_EXTENDED_FRAME_BOUNDS,
+        &mut rect as *mut _ as *mut _,
+        std::mem::size_of::<RECT>() as u32,
+    )
+    .is_err()
+    {
+        GetWindowRect(hwnd, &mut rect).ok()?;
+    }
+    if rect.right <= rect.left || rect.bottom <= rect.top {
+        return None;
+    }
+
+    let mut title = [0u16; 256];
+    let len = GetWindowTextW(hwnd, &mut title).max(0) as usize;
+
+    Some(WindowRect {
+        hwnd: hwnd.0,
+        title: String::from_utf16_lossy(&title[..len]),
+        x: rect.left,
+        y: rect.top,
+        width: rect.right - rect.left,
+        height: rect.bottom - rect.top,
+    })
+}
+
+/// 解析窗口句柄：十进制或 `0x` 开头的十六进制
+pub fn parse_handle(target: &str) -> Option<isize> {
+    let target = target.trim();
+    match target.strip_prefix("0x").or_else(|| target.strip_prefix("0X")) {
+        Some(hex) => isize::from_str_radix(hex, 16).ok(),
+        None => target.parse().ok(),
+    }
+}
+
+/// 按句柄或标题查找顶层窗口。标题不区分大小写，完全一致的优先，其次取 Z 序最靠上的包含该文字的窗口
+#[cfg(target_os = "windows")]
+pub fn find_window(target: &str) -> Result<WindowRect> {
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let windows = &mut *(lparam.0 as *mut Vec<WindowRect>);
+        if let Some(rect) = window_rect(hwnd) {
+            windows.push(rect);
+        }
+        BOOL(1)
+    }
+
+    if let Some(handle) = parse_handle(target) {
+        let hwnd = HWND(handle);
+        if !unsafe { IsWindow(hwnd) }.as_bool() {
+            return Err(AppError::InvalidRequest(format!("No window with handle {}", target.trim())));
+        }
+        return unsafe { window_rect(hwnd) }
+            .ok_or_else(|| AppError::InvalidRequest(format!("Window {} is hidden or minimized", target.trim())));
+    }
+
+    let mut windows: Vec<WindowRect> = Vec::new();
+    unsafe { EnumWindows(Some(collect), LPARAM(&mut windows as *mut _ as isize)) }
+        .map_err(|e| AppError::Ocr(format!("Failed to enumerate windows: {}", e)))?;
+
+    let wanted = target.trim().to_lowercase();
+    if wanted.is_empty() {
+        return Err(AppError::InvalidRequest("Window title cannot be empty".to_string()));
+    }
+    let exact = windows.iter().position(|w| w.title.to_lowercase() == wanted);
+    let partial = || windows.iter().position(|w| w.title.to_lowercase().contains(&wanted));
+    exact
+        .or_else(partial)
+        .map(|index| windows.swap_remove(index))
+        .ok_or_else(|| AppError::InvalidRequest(format!("No visible window titled \"{}\"", target.trim())))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn list_window_rects() -> Result<Vec<WindowRect>> {
+    Err(AppError::PlatformNotSupported("Window enumeration is only available on Windows".to_string()))
+}
+
+/// 多边形的外接矩形 `(x, y, w, h)`
+pub fn bounding_box(points: &[Point]) -> Result<(i32, i32, i32, i32)> {
+    if points.len() < 3 {
+        return Err(AppError::InvalidRequest("A freeform region needs at least 3 points".to_string()));
+    }
+    let min_x = points.iter().map(|p| p.x).min().unwrap_or(0);
+    let min_y = points.iter().map(|p| p.y).min().unwrap_or(0);
+    let max_x = points.iter().map(|p| p.x).max().unwrap_or(0);
+    let max_y = points.iter().map(|p| p.y).max().unwrap_or(0);
+    if max_x <= min_x || max_y <= min_y {
+        return Err(AppError::InvalidRequest("Freeform region has no area".to_string()));
+    }
+    Ok((min_x, min_y, max_x - min_x, max_y - min_y))
+}
+
+/// 两个矩形 `(x, y, w, h)` 的交集；不相交时返回 `None`
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn intersect(a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)) -> Option<(i32, i32, i32, i32)> {
+    let (left, top) = (a.0.max(b.0), a.1.max(b.1));
+    let (right, bottom) = ((a.0 + a.2).min(b.0 + b.2), (a.1 + a.3).min(b.1 + b.3));
+    (right > left && bottom > top).then_some((left, top, right - left, bottom - top))
+}
+
+/// 把 `part` 区域的像素（BGRA，行宽为 `part` 的宽度）写入覆盖 `region` 的缓冲区
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn blit(pixels: &mut [u8], region: (i32, i32, i32, i32), part: (i32, i32, i32, i32), source: &[u8]) {
+    let row_bytes = (part.2 * 4) as usize;
+    for row in 0..part.3 {
+        let target = (((part.1 - region.1 + row) * region.2 + (part.0 - region.0)) * 4) as usize;
+        let start = row as usize * row_bytes;
+        pixels[target..target + row_bytes].copy_from_slice(&source[start..start + row_bytes]);
+    }
+}
+
+/// 奇偶规则判断像素中心是否在多边形内
+fn contains(points: &[Point], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    let mut j = points.len() - 1;
+    for i in 0..points.len() {
+        let (xi, yi) = (points[i].x as f64, points[i].y as f64);
+        let (xj, yj) = (points[j].x as f64, points[j].y as f64);
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// 将 BGRA/RGBA 截图中多边形以外的像素涂白，避免选区外的文字被识别。
+/// `origin` 为截图左上角的屏幕坐标
+#[cfg_attr(not(any(target_os = "windows", target_os = "linux")), allow(dead_code))]
+pub fn apply_polygon_mask(pixels: &mut [u8], w: i32, h: i32, origin: (i32, i32), points: &[Point]) {
+    for row in 0..h {
+        let y = (origin.1 + row) as f64 + 0.5;
+        for col in 0..w {
+            let x = (origin.0 + col) as f64 + 0.5;
+            if !contains(points, x, y) {
+                let idx = ((row * w + col) * 4) as usize;
+                pixels[idx..idx + 4].copy_from_slice(&[255, 255, 255, 255]);
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::ocr::mathpix::MathpixResult;
+use base64::{engine::general_purpose, Engine as _};
+
+/// 表单中图像的字段名和文件名
+const FILE_FIELD: &str = "file";
+const FILE_NAME: &str = "formula.png";
+
+/// 构造只含一个文件字段的 multipart/form-data 请求体，返回 (Content-Type, 请求体)
+fn multipart_body(image: &[u8]) -> (String, Vec<u8>) {
+    let boundary = format!("----dict-{}", uuid::Uuid::new_v4().simple());
+    let mut body = Vec::with_capacity(image.len() + 256);
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: image/png\r\n\r\n",
+            boundary, FILE_FIELD, FILE_NAME
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(image);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
+/// 通过 SimpleTex 识别公式。SimpleTex 只接受上传的图像文件，不支持图像 URL
+pub async fn recognize_formula(
+    image_data: Option<String>,
+    image_url: Option<String>,
+    config: Option<&serde_json::Value>,
+) -> crate::error::Result<MathpixResult> {
+    let token = config
+        .and_then(|c| c.get("token"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| s.to_string())
+        .ok_or_else(|| crate::error::AppError::Config("SimpleTex token not configured".to_string()))?;
+
+    let api_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("https://server.simpletex.cn/api/latex_ocr");
+
+    let image = match (image_data, image_url) {
+        (Some(data), _) => general_purpose::STANDARD.decode(data).map_err(|e| {
+            crate::error::AppError::InvalidRequest(format!("Invalid base64 image data: {}", e))
+        })?,
+        (None, Some(_)) => {
+            return Err(crate::error::AppError::InvalidRequest(
+                "SimpleTex does not support image URLs; provide image_data".to_string()
+            ))
+        }
+        (None, None) => {
+            return Err(crate::error::AppError::InvalidRequest(
+                "Either image_data or image_url must be provided".to_string()
+            ))
+        }
+    };
+
+    let client = crate::services::http::client(config, std::time::Duration::from_secs(30))?;
+    let (content_type, body) = multipart_body(&image);
+
+    let response = client
+        .post(api_url)
+        .header("token", &token)
+        .header("Content-Type", content_type)
+        .body(body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(crate::error::AppError::from_status("SimpleTex", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    // 鉴权失败、额度用尽等错误以 HTTP 200 + `status: false` 返回
+    if json["status"].as_bool() != Some(true) {
+        let message = json["err_info"]
+            .as_str()
+            .or_else(|| json["message"].as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| json.to_string());
+        return Err(crate::error::AppError::Api {
+            service: "SimpleTex".to_string(),
+            message,
+        });
+    }
+
+    let latex = json["res"]["latex"].as_str().map(|s| s.to_string()).unwrap_or_default();
+    let confidence = json["res"]["conf"].as_f64();
+
+    Ok(MathpixResult {
+        latex,
+        latex_styled: None,
+        confidence,
+        error: None,
+        mathml: None,
+        asciimath: None,
+        text: None,
+    })
+}
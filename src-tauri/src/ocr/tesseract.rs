@@ -0,0 +1,309 @@
+//! Tesseract OCR 引擎，通过 `tesseract` 命令行调用，各平台可用。
+//! 可执行文件依次查找：设置中指定的路径、随应用附带的 `tesseract/` 目录、
+//! Windows 默认安装位置和 PATH；语言包目录同样优先使用设置和随附的 `tessdata`，
+//! 否则交给 Tesseract 自身的默认位置（`TESSDATA_PREFIX`）。
+
+use crate::error::{AppError, Result};
+use crate::ocr::models::{OcrLine, OcrResult};
+use crate::ocr::paddle::create_command;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// 未指定语言时使用的 Tesseract 语言包
+const DEFAULT_LANGUAGE: &str = "eng";
+
+#[cfg(target_os = "windows")]
+const BINARY_NAME: &str = "tesseract.exe";
+#[cfg(not(target_os = "windows"))]
+const BINARY_NAME: &str = "tesseract";
+
+/// 用户设置；均为空时使用随附或系统安装的 Tesseract
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TesseractOptions {
+    /// `tesseract` 可执行文件路径
+    #[serde(default)]
+    pub binary: Option<String>,
+    /// 语言包（`*.traineddata`）所在目录
+    #[serde(default)]
+    pub tessdata_dir: Option<String>,
+    /// 请求未指定语言或为 `auto` 时使用的语言包，例如 `eng+chi_sim+jpn`
+    #[serde(default)]
+    pub languages: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TesseractStatus {
+    pub available: bool,
+    pub binary: String,
+    pub version: Option<String>,
+    pub tessdata_dir: Option<String>,
+    /// 已安装的语言包
+    pub languages: Vec<String>,
+}
+
+static OPTIONS: Lazy<RwLock<TesseractOptions>> = Lazy::new(|| RwLock::new(TesseractOptions::default()));
+
+pub fn set_options(options: TesseractOptions) {
+    *OPTIONS.write().unwrap() = options;
+}
+
+pub fn options() -> TesseractOptions {
+    OPTIONS.read().unwrap().clone()
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// 随应用附带的 Tesseract 目录（可执行文件旁的 `tesseract/`）
+fn bundled_dir() -> Option<PathBuf> {
+    let dir = std::env::current_exe().ok()?.parent()?.join("tesseract");
+    dir.is_dir().then_some(dir)
+}
+
+fn binary(options: &TesseractOptions) -> String {
+    if let Some(binary) = non_empty(&options.binary) {
+        return binary.to_string();
+    }
+    if let Some(bundled) = bundled_dir().map(|dir| dir.join(BINARY_NAME)).filter(|path| path.is_file()) {
+        return bundled.to_string_lossy().into_owned();
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let installed = PathBuf::from(r"C:\Program Files\Tesseract-OCR").join(BINARY_NAME);
+        if installed.is_file() {
+            return installed.to_string_lossy().into_owned();
+        }
+    }
+    BINARY_NAME.to_string()
+}
+
+fn tessdata_dir(options: &TesseractOptions) -> Option<String> {
+    if let Some(dir) = non_empty(&options.tessdata_dir) {
+        return Some(dir.to_string());
+    }
+    bundled_dir()
+        .map(|dir| dir.join("tessdata"))
+        .filter(|dir| dir.is_dir())
+        .map(|dir| dir.to_string_lossy().into_owned())
+}
+
+fn command(options: &TesseractOptions) -> std::process::Command {
+    let mut cmd = create_command(&binary(options));
+    if let Some(dir) = tessdata_dir(options) {
+        cmd.arg("--tessdata-dir").arg(dir);
+    }
+    cmd
+}
+
+/// 应用语言代码转换为 Tesseract 语言包名；`auto` 同时加载中英文
+pub(crate) fn language_code(language: Option<&str>) -> String {
+    match language {
+        None | Some("") => DEFAULT_LANGUAGE.to_string(),
+        Some("auto") => "eng+chi_sim".to_string(),
+        Some("en") | Some("en-US") => "eng".to_string(),
+        Some("zh") | Some("zh-CN") | Some("zh-Hans") => "chi_sim".to_string(),
+        Some("zh-TW") | Some("zh-Hant") => "chi_tra".to_string(),
+        Some("ja") | Some("ja-JP") => "jpn".to_string(),
+        Some("ko") | Some("ko-KR") => "kor".to_string(),
+        Some("fr") => "fra".to_string(),
+        Some("de") => "deu".to_string(),
+        Some("es") => "spa".to_string(),
+        Some("ru") => "rus".to_string(),
+        // 其余按 Tesseract 语言包名原样传入，例如 `ita` 或 `eng+jpn`
+        Some(other) => other.to_string(),
+    }
+}
+
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c as u32, 0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF)
+}
+
+/// 拼接同一行的单词；中日韩文字之间不加空格
+pub(crate) fn join_words(words: &[&str]) -> String {
+    let mut line = String::new();
+    for word in words {
+        let touching_cjk = line.chars().last().is_some_and(is_cjk) && word.chars().next().is_some_and(is_cjk);
+        if !line.is_empty() && !touching_cjk {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    line
+}
+
+/// 解析 `tesseract ... tsv` 的输出：按块、段落和行号归并单词，置信度取单词平均值
+pub(crate) fn parse_tsv(tsv: &str) -> OcrResult {
+    struct Line<'a> {
+        key: (u32, u32, u32),
+        words: Vec<&'a str>,
+        confidences: Vec<f64>,
+        left: f64,
+        top: f64,
+        right: f64,
+        bottom: f64,
+    }
+
+    let mut lines: Vec<Line> = Vec::new();
+    for row in tsv.lines().skip(1) {
+        let fields: Vec<&str> = row.split('\t').collect();
+        // level 5 为单词；空文本是版面分析留下的占位
+        if fields.len() < 12 || fields[0] != "5" || fields[11].trim().is_empty() {
+            continue;
+        }
+        let number = |i: usize| fields[i].trim().parse::<f64>().unwrap_or(0.0);
+        let key = (number(2) as u32, number(3) as u32, number(4) as u32);
+        let (left, top) = (number(6), number(7));
+        let (right, bottom) = (left + number(8), top + number(9));
+        let confidence = number(10);
+
+        let line = match lines.last_mut() {
+            Some(line) if line.key == key => line,
+            _ => {
+                lines.push(Line {
+                    key,
+                    words: Vec::new(),
+                    confidences: Vec::new(),
+                    left,
+                    top,
+                    right,
+                    bottom,
+                });
+                lines.last_mut().unwrap()
+            }
+        };
+        line.words.push(fields[11].trim());
+        if confidence >= 0.0 {
+            line.confidences.push(confidence / 100.0);
+        }
+        line.left = line.left.min(left);
+        line.top = line.top.min(top);
+        line.right = line.right.max(right);
+        line.bottom = line.bottom.max(bottom);
+    }
+
+    let lines: Vec<OcrLine> = lines
+        .into_iter()
+        .map(|line| OcrLine {
+            text: join_words(&line.words),
+            x: line.left,
+            y: line.top,
+            width: line.right - line.left,
+            height: line.bottom - line.top,
+            confidence: (!line.confidences.is_empty())
+                .then(|| line.confidences.iter().sum::<f64>() / line.confidences.len() as f64),
+        })
+        .collect();
+
+    let scored: Vec<f64> = lines.iter().filter_map(|line| line.confidence).collect();
+    let confidence = if scored.is_empty() { 0.0 } else { scored.iter().sum::<f64>() / scored.len() as f64 };
+
+    OcrResult {
+        text: lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join("\n"),
+        confidence,
+        lines,
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    }
+}
+
+/// 请求语言对应的语言包；未指定或 `auto` 时优先使用设置中的语言包
+pub(crate) fn resolve_languages(language: Option<&str>, options: &TesseractOptions) -> String {
+    match (language, non_empty(&options.languages)) {
+        (None | Some("") | Some("auto"), Some(configured)) => configured.to_string(),
+        _ => language_code(language),
+    }
+}
+
+/// 竖排识别使用的语言包：中日韩语言包换成对应的 `_vert` 版本
+pub(crate) fn vertical_languages(languages: &str) -> String {
+    languages
+        .split('+')
+        .map(|language| match language {
+            "jpn" | "chi_sim" | "chi_tra" | "kor" => format!("{}_vert", language),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// 调用 `tesseract` 命令识别编码后的图像（PNG/BMP 等）
+pub async fn recognize(image_data: &[u8], language: Option<&str>) -> Result<OcrResult> {
+    let options = options();
+    run(&options, image_data, &resolve_languages(language, &options), &[]).await
+}
+
+/// 识别竖排文字：使用竖排语言包，并按单个竖排文本块（`--psm 5`）分析版面
+pub async fn recognize_vertical(image_data: &[u8], language: Option<&str>) -> Result<OcrResult> {
+    let options = options();
+    let languages = vertical_languages(&resolve_languages(language, &options));
+    run(&options, image_data, &languages, &["--psm", "5"]).await
+}
+
+/// 异步运行 `tesseract` 并收集输出，识别期间不占用运行时线程；调用方取消时结束进程
+async fn output(cmd: std::process::Command) -> std::io::Result<std::process::Output> {
+    tokio::process::Command::from(cmd).kill_on_drop(true).output().await
+}
+
+async fn run(options: &TesseractOptions, image_data: &[u8], language: &str, extra_args: &[&str]) -> Result<OcrResult> {
+    let temp_path = std::env::temp_dir().join(format!("dict_tesseract_{}.png", uuid::Uuid::new_v4()));
+    tokio::fs::write(&temp_path, image_data)
+        .await
+        .map_err(|e| AppError::Ocr(format!("Failed to write temp image: {}", e)))?;
+
+    println!("Running Tesseract with languages: {}", language);
+    let mut cmd = command(options);
+    cmd.arg(&temp_path).args(["stdout", "-l", language]).args(extra_args).arg("tsv");
+    let output = output(cmd).await;
+    let _ = tokio::fs::remove_file(&temp_path).await;
+
+    let output = output.map_err(|e| {
+        AppError::Ocr(format!("Failed to run tesseract: {}. Please install Tesseract OCR.", e))
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::Ocr(format!("Tesseract failed: {}", stderr.trim())));
+    }
+
+    Ok(parse_tsv(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 检测 Tesseract 是否可用，并列出已安装的语言包
+pub async fn status() -> TesseractStatus {
+    let options = options();
+    let binary = binary(&options);
+    let tessdata_dir = tessdata_dir(&options);
+    let mut version_cmd = command(&options);
+    version_cmd.arg("--version");
+    let version = output(version_cmd)
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| {
+            // 部分版本把版本信息写到 stderr
+            let text = [output.stdout, output.stderr].concat();
+            String::from_utf8_lossy(&text).lines().next().map(|line| line.trim().to_string())
+        });
+    let mut list_cmd = command(&options);
+    list_cmd.arg("--list-langs");
+    let languages = match version {
+        Some(_) => output(list_cmd)
+            .await
+            .map(|output| {
+                String::from_utf8_lossy(&[output.stdout, output.stderr].concat())
+                    .lines()
+                    .skip_while(|line| !line.starts_with("List of available languages"))
+                    .skip(1)
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    TesseractStatus { available: version.is_some(), binary, version, tessdata_dir, languages }
+}
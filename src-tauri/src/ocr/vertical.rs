@@ -0,0 +1,254 @@
+//! 竖排中日文识别。Tesseract 直接使用竖排语言包；其余引擎只认横排，
+//! 因此先按墨迹投影切出各列（从右到左），再把每列的字逐个横向排成一行，
+//! 识别后把结果映射回原图中的列。
+
+use crate::error::{AppError, Result};
+use crate::ocr::models::{OcrLine, OcrResult};
+use crate::ocr::tesseract;
+use image::{GrayImage, Luma};
+
+/// 与背景亮度相差超过该值的像素视为墨迹
+const INK_CONTRAST: i32 = 64;
+/// 列间空白平均宽度超过行间空白的该倍数时判定为竖排
+const VERTICAL_GAP_RATIO: f64 = 1.5;
+/// 列内小于列宽该比例的空白视为同一个字内部的间隙（如“二”“川”）
+const GLYPH_GAP_RATIO: f64 = 0.25;
+
+/// 竖排图像中的一列，以及它在重排图像中所占的行
+#[derive(Debug, Clone)]
+pub struct Strip {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// 重排图像中该行的上下边界
+    pub top: f64,
+    pub bottom: f64,
+}
+
+/// 是否按竖排处理：请求明确指定时照办，否则仅对中日文按图像自动判断
+pub fn wanted(hint: Option<bool>, language: Option<&str>, image_data: &[u8]) -> bool {
+    match hint {
+        Some(vertical) => vertical,
+        None if is_vertical_language(language) => {
+            image::load_from_memory(image_data).map(|image| detect(&image.to_luma8())).unwrap_or(false)
+        }
+        None => false,
+    }
+}
+
+/// 可能竖排书写的语言：中文和日文（含 Tesseract 语言包名）
+fn is_vertical_language(language: Option<&str>) -> bool {
+    language.is_some_and(|language| {
+        ["ja", "zh", "jpn", "chi_"].iter().any(|prefix| language.starts_with(prefix))
+    })
+}
+
+/// 背景亮度取四条边像素的中位数
+fn background(image: &GrayImage) -> u8 {
+    let (w, h) = image.dimensions();
+    let mut border: Vec<u8> = (0..w)
+        .flat_map(|x| [image.get_pixel(x, 0)[0], image.get_pixel(x, h - 1)[0]])
+        .chain((0..h).flat_map(|y| [image.get_pixel(0, y)[0], image.get_pixel(w - 1, y)[0]]))
+        .collect();
+    border.sort_unstable();
+    border.get(border.len() / 2).copied().unwrap_or(255)
+}
+
+fn is_ink(pixel: u8, background: u8) -> bool {
+    (pixel as i32 - background as i32).abs() > INK_CONTRAST
+}
+
+/// 区域内每一列（`by_column`）或每一行是否含有墨迹
+fn profile(image: &GrayImage, background: u8, (x0, y0, x1, y1): (u32, u32, u32, u32), by_column: bool) -> Vec<bool> {
+    let (outer, inner) = if by_column { (x0..x1, y0..y1) } else { (y0..y1, x0..x1) };
+    outer
+        .map(|i| {
+            inner.clone().any(|j| {
+                let (x, y) = if by_column { (i, j) } else { (j, i) };
+                is_ink(image.get_pixel(x, y)[0], background)
+            })
+        })
+        .collect()
+}
+
+/// 投影中连续含墨迹的区段 `[start, end)`；短于 `min_gap` 的空白并入相邻区段
+fn runs(profile: &[bool], min_gap: usize) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut start = None;
+    for (i, &ink) in profile.iter().chain(std::iter::once(&false)).enumerate() {
+        match (ink, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                match runs.last_mut() {
+                    Some(last) if s - last.1 < min_gap => last.1 = i,
+                    _ => runs.push((s, i)),
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    runs
+}
+
+/// 区段之间空白的平均宽度；只有一个区段时为 0
+fn mean_gap(runs: &[(usize, usize)]) -> f64 {
+    if runs.len() < 2 {
+        return 0.0;
+    }
+    let total: usize = runs.windows(2).map(|pair| pair[1].0 - pair[0].1).sum();
+    total as f64 / (runs.len() - 1) as f64
+}
+
+/// 根据墨迹投影判断是否为竖排：行距通常明显大于字距，竖排时列间空白比行间空白宽；
+/// 只有一列时看外形是否细长
+pub fn detect(image: &GrayImage) -> bool {
+    let (w, h) = image.dimensions();
+    if w == 0 || h == 0 {
+        return false;
+    }
+    let background = background(image);
+    let columns = runs(&profile(image, background, (0, 0, w, h), true), 1);
+    let rows = runs(&profile(image, background, (0, 0, w, h), false), 1);
+    let (Some(first_column), Some(last_column)) = (columns.first(), columns.last()) else {
+        return false;
+    };
+    let (column_gap, row_gap) = (mean_gap(&columns), mean_gap(&rows));
+    if columns.len() >= 2 {
+        return column_gap > row_gap * VERTICAL_GAP_RATIO;
+    }
+    let ink_width = (last_column.1 - first_column.0) as f64;
+    let ink_height = (rows.last().unwrap().1 - rows.first().unwrap().0) as f64;
+    rows.len() >= 3 && ink_height > ink_width * 2.5
+}
+
+/// 把竖排图像重排为横排：每列（从右到左）成为一行，列内的字从左到右排开。
+/// 返回 PNG 数据及各列的位置
+pub fn restack(image_data: &[u8]) -> Result<(Vec<u8>, Vec<Strip>)> {
+    let image = image::load_from_memory(image_data)
+        .map_err(|e| AppError::Ocr(format!("Failed to decode image: {}", e)))?
+        .to_luma8();
+    let (stacked, strips) = restack_image(&image);
+
+    let mut data = Vec::new();
+    stacked
+        .write_to(&mut std::io::Cursor::new(&mut data), image::ImageFormat::Png)
+        .map_err(|e| AppError::Ocr(format!("Failed to encode image: {}", e)))?;
+    Ok((data, strips))
+}
+
+pub(crate) fn restack_image(image: &GrayImage) -> (GrayImage, Vec<Strip>) {
+    let (w, h) = image.dimensions();
+    let background = background(image);
+
+    // 每列内按字切分；列按从右到左的阅读顺序排列
+    let mut columns: Vec<_> = runs(&profile(image, background, (0, 0, w, h), true), 2)
+        .into_iter()
+        .rev()
+        .map(|(x0, x1)| {
+            let (x0, x1) = (x0 as u32, x1 as u32);
+            let min_gap = (((x1 - x0) as f64 * GLYPH_GAP_RATIO) as usize).max(1);
+            let glyphs = runs(&profile(image, background, (x0, 0, x1, h), false), min_gap)
+                .into_iter()
+                .map(|(y0, y1)| (y0 as u32, y1 as u32))
+                .collect::<Vec<_>>();
+            ((x0, x1), glyphs)
+        })
+        .collect();
+    columns.retain(|(_, glyphs)| !glyphs.is_empty());
+
+    let glyph_size = columns
+        .iter()
+        .flat_map(|((x0, x1), glyphs)| glyphs.iter().map(move |(y0, y1)| (x1 - x0).max(y1 - y0)))
+        .max()
+        .unwrap_or(0);
+    let padding = (glyph_size / 2).max(4);
+    let spacing = (glyph_size / 8).max(2);
+    let row_height = glyph_size + padding;
+    let width = columns
+        .iter()
+        .map(|((x0, x1), glyphs)| glyphs.len() as u32 * (x1 - x0 + spacing))
+        .max()
+        .unwrap_or(0)
+        + padding * 2;
+    let height = row_height * columns.len() as u32 + padding;
+
+    let mut stacked = GrayImage::from_pixel(width, height, Luma([background]));
+    let mut strips = Vec::with_capacity(columns.len());
+    for (row, ((x0, x1), glyphs)) in columns.iter().enumerate() {
+        let top = padding + row as u32 * row_height;
+        let mut cursor = padding;
+        for (y0, y1) in glyphs {
+            let offset = top + (glyph_size - (y1 - y0)) / 2;
+            for y in *y0..*y1 {
+                for x in *x0..*x1 {
+                    stacked.put_pixel(cursor + x - x0, offset + y - y0, *image.get_pixel(x, y));
+                }
+            }
+            cursor += x1 - x0 + spacing;
+        }
+        let (y0, y1) = (glyphs[0].0, glyphs[glyphs.len() - 1].1);
+        strips.push(Strip {
+            x: *x0 as f64,
+            y: y0 as f64,
+            width: (x1 - x0) as f64,
+            height: (y1 - y0) as f64,
+            top: (top - padding / 2) as f64,
+            bottom: (top + glyph_size + padding / 2) as f64,
+        });
+    }
+    (stacked, strips)
+}
+
+/// 把重排图像的识别结果映射回原图：每列一行，坐标为原图中该列的外接矩形
+pub fn assemble(result: OcrResult, strips: &[Strip]) -> OcrResult {
+    if result.lines.is_empty() || strips.is_empty() {
+        return result;
+    }
+
+    let mut columns: Vec<Vec<&OcrLine>> = vec![Vec::new(); strips.len()];
+    for line in &result.lines {
+        let center = line.y + line.height / 2.0;
+        let distance = |strip: &Strip| {
+            if center < strip.top {
+                strip.top - center
+            } else if center > strip.bottom {
+                center - strip.bottom
+            } else {
+                0.0
+            }
+        };
+        let nearest = (0..strips.len())
+            .min_by(|&a, &b| distance(&strips[a]).total_cmp(&distance(&strips[b])))
+            .unwrap();
+        columns[nearest].push(line);
+    }
+
+    let lines: Vec<OcrLine> = strips
+        .iter()
+        .zip(columns.iter_mut())
+        .filter(|(_, parts)| !parts.is_empty())
+        .map(|(strip, parts)| {
+            parts.sort_by(|a, b| a.x.total_cmp(&b.x));
+            let words: Vec<&str> = parts.iter().flat_map(|part| part.text.split_whitespace()).collect();
+            let scored: Vec<f64> = parts.iter().filter_map(|part| part.confidence).collect();
+            OcrLine {
+                text: tesseract::join_words(&words),
+                x: strip.x,
+                y: strip.y,
+                width: strip.width,
+                height: strip.height,
+                confidence: (!scored.is_empty()).then(|| scored.iter().sum::<f64>() / scored.len() as f64),
+            }
+        })
+        .collect();
+
+    OcrResult {
+        text: lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join("\n"),
+        confidence: result.confidence,
+        lines,
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    }
+}
@@ -0,0 +1,140 @@
+//! 区域 OCR 监视：按固定间隔截取同一块屏幕区域并识别，文本变化时翻译并通过
+//! `ocr-watch-update` 事件推送，用于实时翻译视频硬字幕和游戏对话。
+//! 同一时间只运行一个监视任务；后台任务暂停或处于节能模式（计费网络、省电模式）时暂停截图。
+
+use crate::error::{AppError, Result};
+use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+use crate::ocr::models::OcrWatchRequest;
+use crate::services::memory::similarity;
+use base64::{engine::general_purpose, Engine as _};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 截图间隔下限
+const MIN_INTERVAL_MS: u64 = 200;
+/// 与上次翻译的文本相似度达到该值时视为识别抖动，不重新翻译
+const SAME_TEXT_SIMILARITY: f64 = 0.9;
+
+/// 每次启动或停止加一；监视循环发现代数变化后退出
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// `ocr-watch-update` 事件的负载；文本为空表示字幕已消失
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchUpdate {
+    pub text: String,
+    pub translation: Option<String>,
+    pub error: Option<String>,
+}
+
+fn is_current(generation: u64) -> bool {
+    GENERATION.load(Ordering::SeqCst) == generation
+}
+
+/// 识别文本相对上次是否有实质变化
+pub(crate) fn text_changed(previous: &str, current: &str) -> bool {
+    let (previous, current) = (previous.trim(), current.trim());
+    if previous.is_empty() || current.is_empty() {
+        return previous != current;
+    }
+    similarity(previous, current) < SAME_TEXT_SIMILARITY
+}
+
+/// 开始监视；已有监视任务时先停止它
+pub fn start(app: AppHandle, request: OcrWatchRequest) -> Result<()> {
+    if request.rect.width <= 0 || request.rect.height <= 0 {
+        return Err(AppError::InvalidRequest("OCR watch region has no area".to_string()));
+    }
+    if request.target_lang.trim().is_empty() {
+        return Err(AppError::InvalidRequest("Target language is required".to_string()));
+    }
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    println!("Starting OCR watch every {} ms", request.interval_ms.max(MIN_INTERVAL_MS));
+    tauri::async_runtime::spawn(run(app, request, generation));
+    Ok(())
+}
+
+pub fn stop() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+async fn run(app: AppHandle, request: OcrWatchRequest, generation: u64) {
+    let interval = Duration::from_millis(request.interval_ms.max(MIN_INTERVAL_MS));
+    let engine = request.engine.clone().unwrap_or_else(|| "windows".to_string());
+    let mut last_frame = String::new();
+    let mut last_text = String::new();
+
+    loop {
+        crate::background::wait_until_resumed().await;
+        if !is_current(generation) {
+            break;
+        }
+        if !crate::background::auto_watch_allowed() {
+            tokio::time::sleep(crate::background::adjusted_interval(interval)).await;
+            continue;
+        }
+
+        let rect = request.rect;
+        let frame = match super::capture_screen(rect.x, rect.y, rect.width, rect.height, None).await {
+            Ok(frame) => frame,
+            Err(e) => {
+                // 截图失败通常不会自行恢复，报告后结束监视
+                let update = WatchUpdate { text: String::new(), translation: None, error: Some(e.to_string()) };
+                let _ = app.emit(&crate::events::event_name("ocr-watch-update"), update);
+                break;
+            }
+        };
+        // 画面完全未变时跳过识别
+        if frame != last_frame {
+            if let Some(update) = recognize_frame(&request, &engine, &frame, &mut last_text).await {
+                if !is_current(generation) {
+                    break;
+                }
+                let _ = app.emit(&crate::events::event_name("ocr-watch-update"), update);
+            }
+            last_frame = frame;
+        }
+
+        tokio::time::sleep(crate::background::adjusted_interval(interval)).await;
+    }
+    println!("OCR watch stopped");
+}
+
+/// 识别一帧；文本有变化时翻译并返回要推送的更新
+async fn recognize_frame(request: &OcrWatchRequest, engine: &str, frame: &str, last_text: &mut String) -> Option<WatchUpdate> {
+    // 监视期间每帧都会识别，不写入 OCR 历史
+    let recognized = match general_purpose::STANDARD.decode(frame) {
+        Ok(image_data) => super::recognize_encoded(image_data, request.language.clone(), engine).await.map(|result| super::postprocess(result, true, false)),
+        Err(e) => Err(AppError::Ocr(format!("Failed to decode screenshot: {}", e))),
+    };
+    let text = match recognized {
+        Ok(result) => result.text.trim().to_string(),
+        Err(e) => return Some(WatchUpdate { text: String::new(), translation: None, error: Some(e.to_string()) }),
+    };
+    if !text_changed(last_text, &text) {
+        return None;
+    }
+    *last_text = text.clone();
+    if text.is_empty() {
+        return Some(WatchUpdate { text, translation: None, error: None });
+    }
+
+    let translation = crate::services::translate(TranslationRequest {
+        text: text.clone(),
+        source_lang: request.language.clone().unwrap_or_else(|| "auto".to_string()),
+        target_lang: request.target_lang.clone(),
+        services: request.services.clone(),
+        config: request.config.clone(),
+        mode: TranslationMode::Fallback,
+        format: TextFormat::Plain,
+    })
+    .await
+    .map(|response| response.results.into_iter().next());
+    Some(match translation {
+        Ok(Some(result)) if result.error.is_none() => WatchUpdate { text, translation: Some(result.text), error: None },
+        Ok(Some(result)) => WatchUpdate { text, translation: None, error: result.error },
+        Ok(None) => WatchUpdate { text, translation: None, error: None },
+        Err(e) => WatchUpdate { text, translation: None, error: Some(e.to_string()) },
+    })
+}
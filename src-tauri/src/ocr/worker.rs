@@ -0,0 +1,204 @@
+//! 常驻的 Python 工作进程：模型只在启动时加载一次，之后每行 JSON 请求对应一行 JSON 响应。
+//! 脚本加载完成后先输出一行就绪消息，处理失败时响应 `{"error": "..."}`；
+//! 脚本应把 stdout 重定向到 stderr，避免模型库的日志混入响应。
+
+use crate::error::{AppError, Result};
+use crate::ocr::paddle::{create_command, find_python};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+struct Worker {
+    name: &'static str,
+    error: fn(String) -> AppError,
+    /// 与读写管道分开加锁，请求阻塞时仍可终止进程
+    child: Arc<Mutex<Child>>,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// 启动参数；参数变化时需要重启
+    args: Vec<String>,
+}
+
+impl Worker {
+    /// 启动工作进程并等待就绪消息
+    fn spawn(name: &'static str, error: fn(String) -> AppError, script: &str, args: &[String]) -> Result<Self> {
+        let python_cmd = find_python()
+            .ok_or_else(|| error("Python not found. Please install Python.".to_string()))?;
+        let mut child = create_command(&python_cmd)
+            .args(["-u", "-c", script])
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| error(format!("Failed to start {} worker: {}", name, e)))?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            return Err(error(format!("{} worker has no pipes", name)));
+        };
+        let mut worker = Worker {
+            name,
+            error,
+            child: Arc::new(Mutex::new(child)),
+            stdin,
+            stdout: BufReader::new(stdout),
+            args: args.to_vec(),
+        };
+        if let Err(e) = worker.read_response() {
+            worker.stop();
+            return Err(e);
+        }
+        println!("{} worker started", name);
+        Ok(worker)
+    }
+
+    fn is_running(&mut self) -> bool {
+        matches!(self.child.lock().unwrap().try_wait(), Ok(None))
+    }
+
+    /// 读取一行响应；工作进程报告的错误转换为 `AppError`
+    fn read_response(&mut self) -> Result<Value> {
+        let mut line = String::new();
+        let read = self
+            .stdout
+            .read_line(&mut line)
+            .map_err(|e| (self.error)(format!("Failed to read from {} worker: {}", self.name, e)))?;
+        if read == 0 {
+            return Err((self.error)(format!("{} worker exited unexpectedly", self.name)));
+        }
+        let response: Value = serde_json::from_str(&line).map_err(|e| {
+            (self.error)(format!("Failed to parse {} output: {} (output was: {})", self.name, e, line.trim()))
+        })?;
+        match response.get("error").and_then(|e| e.as_str()) {
+            Some(error) => Err((self.error)(format!("{} failed: {}", self.name, error))),
+            None => Ok(response),
+        }
+    }
+
+    fn request(&mut self, request: &Value) -> Result<Value> {
+        writeln!(self.stdin, "{}", request)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| (self.error)(format!("Failed to send request to {} worker: {}", self.name, e)))?;
+        self.read_response()
+    }
+
+    fn stop(self) {
+        let mut child = self.child.lock().unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// 一个按需启动的工作进程；同一时间只处理一个请求
+pub struct WorkerSlot {
+    name: &'static str,
+    script: &'static str,
+    /// 工作进程出错时使用的错误类型
+    error: fn(String) -> AppError,
+    /// None 表示工作进程未启动
+    worker: Mutex<Option<Worker>>,
+    /// 当前进程的句柄，供 `abort` 在请求阻塞时终止进程
+    child: Mutex<Option<Arc<Mutex<Child>>>>,
+    /// `abort` 终止了进程，正在等待的请求直接失败而不重启
+    aborted: AtomicBool,
+}
+
+impl WorkerSlot {
+    pub const fn new(name: &'static str, script: &'static str) -> Self {
+        Self::with_error(name, script, AppError::Ocr)
+    }
+
+    pub const fn with_error(name: &'static str, script: &'static str, error: fn(String) -> AppError) -> Self {
+        Self {
+            name,
+            script,
+            error,
+            worker: Mutex::new(None),
+            child: Mutex::new(None),
+            aborted: AtomicBool::new(false),
+        }
+    }
+
+    fn spawn(&self, args: &[String]) -> Result<Worker> {
+        let worker = Worker::spawn(self.name, self.error, self.script, args)?;
+        *self.child.lock().unwrap() = Some(worker.child.clone());
+        Ok(worker)
+    }
+
+    /// 启动工作进程；已在运行时直接返回。`args` 作为脚本的命令行参数
+    pub fn start(&self, args: &[String]) -> Result<()> {
+        let mut guard = self.worker.lock().unwrap();
+        if guard.as_mut().is_some_and(Worker::is_running) {
+            return Ok(());
+        }
+        if let Some(exited) = guard.take() {
+            exited.stop();
+        }
+        *guard = Some(self.spawn(args)?);
+        Ok(())
+    }
+
+    /// 停止工作进程，释放模型占用的内存
+    pub fn stop(&self) {
+        if let Some(worker) = self.worker.lock().unwrap().take() {
+            worker.stop();
+            println!("{} worker stopped", self.name);
+        }
+    }
+
+    /// 终止卡住的工作进程（如请求超时）；不等待正在处理的请求，下次请求时重新启动
+    pub fn abort(&self) {
+        if let Some(child) = self.child.lock().unwrap().take() {
+            self.aborted.store(true, Ordering::SeqCst);
+            let _ = child.lock().unwrap().kill();
+            println!("{} worker aborted", self.name);
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.worker.lock().unwrap().as_mut().is_some_and(Worker::is_running)
+    }
+
+    /// 发送请求；进程未启动或启动参数不同时先（重新）启动，已有进程在处理中异常退出时重启一次
+    pub fn request(&self, args: &[String], request: &Value) -> Result<Value> {
+        let mut guard = self.worker.lock().unwrap();
+        self.aborted.store(false, Ordering::SeqCst);
+        if guard.as_mut().is_some_and(|worker| worker.is_running() && worker.args == args) {
+            let response = guard.as_mut().unwrap().request(request);
+            if response.is_ok() || guard.as_mut().is_some_and(Worker::is_running) {
+                return response;
+            }
+            if self.aborted.swap(false, Ordering::SeqCst) {
+                if let Some(aborted) = guard.take() {
+                    aborted.stop();
+                }
+                return response;
+            }
+            println!("{} worker exited, restarting", self.name);
+        }
+        if let Some(previous) = guard.take() {
+            previous.stop();
+        }
+        let worker = guard.insert(self.spawn(args)?);
+        worker.request(request)
+    }
+}
+
+/// 把图像写入唯一的临时文件供工作进程读取，用完即删
+pub fn with_temp_image<T>(prefix: &str, image_data: &[u8], f: impl FnOnce(&Path) -> Result<T>) -> Result<T> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let temp_path = std::env::temp_dir().join(format!(
+        "{}_{}_{}.png",
+        prefix,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&temp_path, image_data)
+        .map_err(|e| AppError::Ocr(format!("Failed to write temp image: {}", e)))?;
+    let result = f(&temp_path);
+    let _ = std::fs::remove_file(&temp_path);
+    result
+}
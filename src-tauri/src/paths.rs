@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+/// 前端设置的持久化文件（`@tauri-apps/plugin-store`），翻译服务等的密钥也保存在这里
+pub const SETTINGS_STORE: &str = "settings.json";
+
+/// 应用本地数据目录（模型、缓存、持久化状态），不依赖 AppHandle 以便在服务层直接使用
+pub fn data_dir() -> PathBuf {
+    // 测试不写入用户的数据目录
+    if cfg!(test) {
+        return std::env::temp_dir().join("dict-test");
+    }
+    let base = std::env::var_os("LOCALAPPDATA")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+        .unwrap_or_else(std::env::temp_dir);
+    base.join("dict")
+}
@@ -0,0 +1,41 @@
+//! 频繁修改的状态文件延迟写盘：最后一次修改后稍等片刻再在后台线程整体写入，
+//! 连续修改只写一次，写入时不持有调用方的锁。
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+pub(crate) struct DebouncedWrite {
+    name: &'static str,
+    delay: Duration,
+    pending: AtomicBool,
+}
+
+impl DebouncedWrite {
+    pub(crate) const fn new(name: &'static str, delay: Duration) -> Self {
+        DebouncedWrite { name, delay, pending: AtomicBool::new(false) }
+    }
+
+    /// 安排一次写入；已有待写入时合并。`snapshot` 在延迟结束后调用，返回写入路径和内容，
+    /// 返回 None 表示无需写入
+    pub(crate) fn schedule(&'static self, snapshot: fn() -> Option<(PathBuf, String)>) {
+        if self.pending.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        std::thread::spawn(move || {
+            std::thread::sleep(self.delay);
+            self.pending.store(false, Ordering::SeqCst);
+            let Some((path, content)) = snapshot() else {
+                return;
+            };
+            let result = path
+                .parent()
+                .map(std::fs::create_dir_all)
+                .unwrap_or(Ok(()))
+                .and_then(|_| std::fs::write(&path, content));
+            if let Err(e) = result {
+                println!("Failed to persist {}: {}", self.name, e);
+            }
+        });
+    }
+}
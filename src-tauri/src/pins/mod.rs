@@ -0,0 +1,119 @@
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// 固定在对比工作区中的一条结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pin {
+    pub id: String,
+    pub source_text: String,
+    pub translation: String,
+    pub source_lang: Option<String>,
+    pub target_lang: Option<String>,
+    pub service: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPin {
+    pub source_text: String,
+    pub translation: String,
+    #[serde(default)]
+    pub source_lang: Option<String>,
+    #[serde(default)]
+    pub target_lang: Option<String>,
+    #[serde(default)]
+    pub service: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// None 表示尚未从磁盘加载
+static PINS: Lazy<Mutex<Option<Vec<Pin>>>> = Lazy::new(|| Mutex::new(None));
+
+fn pins_file() -> PathBuf {
+    crate::paths::data_dir().join("pins.json")
+}
+
+fn load() -> Vec<Pin> {
+    std::fs::read_to_string(pins_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(pins: &[Pin]) -> Result<()> {
+    let path = pins_file();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(pins)?)?;
+    Ok(())
+}
+
+/// 在加载后的列表上执行修改并写回磁盘
+fn with_pins<T>(f: impl FnOnce(&mut Vec<Pin>) -> Result<T>) -> Result<T> {
+    let mut guard = PINS.lock().unwrap();
+    let pins = guard.get_or_insert_with(load);
+    let value = f(pins)?;
+    save(pins)?;
+    Ok(value)
+}
+
+fn not_found(id: &str) -> AppError {
+    AppError::InvalidRequest(format!("Pin not found: {}", id))
+}
+
+pub fn list() -> Vec<Pin> {
+    PINS.lock().unwrap().get_or_insert_with(load).clone()
+}
+
+pub fn pin(new_pin: NewPin) -> Result<Pin> {
+    let pin = Pin {
+        id: uuid::Uuid::new_v4().to_string(),
+        source_text: new_pin.source_text,
+        translation: new_pin.translation,
+        source_lang: new_pin.source_lang,
+        target_lang: new_pin.target_lang,
+        service: new_pin.service,
+        note: new_pin.note.filter(|n| !n.trim().is_empty()),
+        created_at: Utc::now(),
+    };
+    with_pins(|pins| {
+        pins.push(pin.clone());
+        Ok(pin)
+    })
+}
+
+pub fn unpin(id: &str) -> Result<()> {
+    with_pins(|pins| {
+        let index = pins.iter().position(|p| p.id == id).ok_or_else(|| not_found(id))?;
+        pins.remove(index);
+        Ok(())
+    })
+}
+
+pub fn annotate(id: &str, note: Option<String>) -> Result<Pin> {
+    with_pins(|pins| {
+        let pin = pins.iter_mut().find(|p| p.id == id).ok_or_else(|| not_found(id))?;
+        pin.note = note.filter(|n| !n.trim().is_empty());
+        Ok(pin.clone())
+    })
+}
+
+/// 按给定 id 顺序重排；未列出的固定项保持原有相对顺序并排在最后
+pub fn reorder(ids: &[String]) -> Result<Vec<Pin>> {
+    with_pins(|pins| {
+        if let Some(unknown) = ids.iter().find(|id| !pins.iter().any(|p| &p.id == *id)) {
+            return Err(not_found(unknown));
+        }
+        pins.sort_by_key(|pin| ids.iter().position(|id| *id == pin.id).unwrap_or(usize::MAX));
+        Ok(pins.clone())
+    })
+}
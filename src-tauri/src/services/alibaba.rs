@@ -1,13 +1,17 @@
 use crate::models::TranslationResult;
 use chrono::Utc;
 use hmac::{Hmac, Mac};
-use reqwest::Client;
 use serde::Deserialize;
 use sha1::Sha1;
 use base64::{Engine as _, engine::general_purpose};
 use std::collections::BTreeMap;
 use std::env;
 use uuid::Uuid;
+use crate::error::AppError;
+use crate::translate::TranslationService;
+use crate::translate::languages::{self, LanguageSupport};
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
 
 #[derive(Deserialize)]
 struct AlibabaResponse {
@@ -23,12 +27,16 @@ struct AlibabaData {
     translated: String,
 }
 
+fn api_error(message: impl Into<String>) -> AppError {
+    AppError::Api { service: "Alibaba".to_string(), message: message.into() }
+}
+
 pub async fn translate(
     text: &str,
     source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
-) -> Result<TranslationResult, String> {
+) -> crate::error::Result<TranslationResult> {
     let (access_key_id, access_key_secret) = if let Some(c) = config {
         (
             c.get("accessKeyId").and_then(|v| v.as_str()).map(|s| s.to_string()),
@@ -41,17 +49,14 @@ pub async fn translate(
     let access_key_id = access_key_id
         .or_else(|| env::var("ALIBABA_ACCESS_KEY_ID").ok())
         .or_else(|| read_env("ALIBABA_ACCESS_KEY_ID").ok())
-        .ok_or_else(|| "ALIBABA_ACCESS_KEY_ID not found".to_string())?;
+        .ok_or_else(|| api_error("ALIBABA_ACCESS_KEY_ID not found"))?;
 
     let access_key_secret = access_key_secret
         .or_else(|| env::var("ALIBABA_ACCESS_KEY_SECRET").ok())
         .or_else(|| read_env("ALIBABA_ACCESS_KEY_SECRET").ok())
-        .ok_or_else(|| "ALIBABA_ACCESS_KEY_SECRET not found".to_string())?;
+        .ok_or_else(|| api_error("ALIBABA_ACCESS_KEY_SECRET not found"))?;
 
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let client = super::http::client(config, super::request_timeout(config))?;
     let url = "https://mt.aliyuncs.com/";
 
     let mut params = BTreeMap::new();
@@ -87,26 +92,28 @@ pub async fn translate(
     let response = client
         .post(url)
         .form(&form_params)
-        .send()
+        .send_retrying()
         .await
-        .map_err(|e| format!("Alibaba API request failed: {}", e))?;
+        .map_err(|e| api_error(format!("Alibaba API request failed: {}", e)))?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Alibaba API error: {}", error_text));
+        return Err(AppError::from_status("Alibaba", status, format!("Alibaba API error: {}", error_text)));
     }
 
     let result: AlibabaResponse = response.json().await
-        .map_err(|e| format!("Failed to parse Alibaba response: {}", e))?;
+        .map_err(|e| api_error(format!("Failed to parse Alibaba response: {}", e)))?;
 
     if let Some(data) = result.data {
         Ok(TranslationResult {
             name: "Alibaba".to_string(),
             text: data.translated,
             error: None,
+            alternatives: Vec::new(),
         })
     } else {
-        Err(result.message.unwrap_or_else(|| "Unknown error from Alibaba".to_string()))
+        Err(api_error(result.message.unwrap_or_else(|| "Unknown error from Alibaba".to_string())))
     }
 }
 
@@ -114,7 +121,7 @@ fn calculate_signature(
     params: &BTreeMap<&str, &str>,
     method: &str,
     secret: &str,
-) -> Result<String, String> {
+) -> crate::error::Result<String> {
     let mut canonicalized_query_string = String::new();
     for (key, value) in params {
         if !canonicalized_query_string.is_empty() {
@@ -135,7 +142,7 @@ fn calculate_signature(
     let key = format!("{}&", secret);
     type HmacSha1 = Hmac<Sha1>;
     let mut mac = HmacSha1::new_from_slice(key.as_bytes())
-        .map_err(|_| "Invalid HMAC key".to_string())?;
+        .map_err(|_| api_error("Invalid HMAC key"))?;
     mac.update(string_to_sign.as_bytes());
     let result = mac.finalize();
     let signature = general_purpose::STANDARD.encode(result.into_bytes());
@@ -161,3 +168,34 @@ fn read_env(key: &str) -> Result<String, ()> {
                 .ok_or(())
         })
 }
+
+pub struct Alibaba;
+
+#[async_trait]
+impl TranslationService for Alibaba {
+    fn name(&self) -> &str {
+        "Alibaba"
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(languages::BROAD)
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["alibaba"]
+    }
+
+    fn needs_source_lang(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> crate::error::Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
@@ -0,0 +1,137 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use crate::services::sigv4;
+use chrono::Utc;
+use crate::translate::TranslationService;
+use crate::translate::languages::{self, LanguageSupport};
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let get = |key: &str| {
+        config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+
+    let access_key_id = get("accessKeyId")
+        .ok_or_else(|| AppError::Config("AWS access key ID not configured".to_string()))?;
+    let secret_access_key = get("secretAccessKey")
+        .ok_or_else(|| AppError::Config("AWS secret access key not configured".to_string()))?;
+    let region = get("region").unwrap_or("us-east-1");
+
+    let url = url::Url::parse(&format!("https://translate.{}.amazonaws.com/", region))
+        .map_err(|e| AppError::Config(format!("Invalid AWS region: {}", e)))?;
+
+    let payload = serde_json::to_vec(&serde_json::json!({
+        "Text": text,
+        "SourceLanguageCode": map_lang(source_lang),
+        "TargetLanguageCode": map_lang(target_lang)
+    }))?;
+
+    let content_type = "application/x-amz-json-1.1";
+    let target = "AWSShineFrontendService_20170701.TranslateText";
+
+    let signed_headers = sigv4::sign(
+        &sigv4::AWS_V4,
+        &sigv4::SigningParams {
+            access_key_id,
+            secret_access_key,
+            session_token: get("sessionToken"),
+            region,
+            service: "translate",
+        },
+        "POST",
+        &url,
+        &[("content-type", content_type), ("x-amz-target", target)],
+        &payload,
+        Utc::now(),
+    )?;
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let mut request = client
+        .post(url)
+        .header("Content-Type", content_type)
+        .header("X-Amz-Target", target)
+        .body(payload);
+    for (name, value) in signed_headers {
+        request = request.header(name, value);
+    }
+
+    let response = request.send_retrying().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("AWS", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    let translated_text = json["TranslatedText"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Translation("No translation in AWS response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "AWS".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+fn map_lang(lang: &str) -> &str {
+    match lang {
+        "zh-CN" | "zh-Hans" => "zh",
+        "zh-Hant" => "zh-TW",
+        other => other,
+    }
+}
+
+pub struct Aws;
+
+#[async_trait]
+impl TranslationService for Aws {
+    fn name(&self) -> &str {
+        "AWS"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["aws"]
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(languages::BROAD)
+    }
+
+    /// AWS limits requests to 10,000 UTF-8 bytes; CJK text takes three bytes per char.
+    fn max_text_chars(&self) -> usize {
+        3000
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["accessKeyId", "secretAccessKey"]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "Access key ID and secret access key required"
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
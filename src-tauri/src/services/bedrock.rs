@@ -0,0 +1,347 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use crate::services::generation::GenerationParams;
+use crate::services::prompt::Prompt;
+use crate::services::sigv4;
+use base64::{Engine as _, engine::general_purpose};
+use chrono::Utc;
+use futures_util::StreamExt;
+use crate::translate::TranslationService;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+/// Longest chunk sent per request. A CJK translation takes roughly one token per character,
+/// so a 3000-character chunk stays within the default 4096-token output budget of `apply_claude`,
+/// which is also the most the default Claude 3 Haiku model allows.
+pub(crate) const MAX_TEXT_CHARS: usize = 3000;
+
+struct BedrockConfig<'a> {
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    session_token: Option<&'a str>,
+    region: &'a str,
+    model: &'a str,
+}
+
+fn read_config(config: Option<&serde_json::Value>) -> Result<BedrockConfig<'_>> {
+    let get = |key: &str| {
+        config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+
+    Ok(BedrockConfig {
+        access_key_id: get("accessKeyId")
+            .ok_or_else(|| AppError::Config("AWS access key ID not configured".to_string()))?,
+        secret_access_key: get("secretAccessKey")
+            .ok_or_else(|| AppError::Config("AWS secret access key not configured".to_string()))?,
+        session_token: get("sessionToken"),
+        region: get("region").unwrap_or("us-east-1"),
+        model: get("model").unwrap_or("anthropic.claude-3-haiku-20240307-v1:0"),
+    })
+}
+
+/// Anthropic Messages body for InvokeModel, with the service's `promptTemplate` and generation params.
+pub(crate) fn request_body(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> serde_json::Value {
+    let prompt = Prompt::from_template(config, text, source_lang, target_lang)
+        .unwrap_or_else(|| Prompt::translation_engine(text, target_lang));
+    let mut body = serde_json::json!({
+        "anthropic_version": "bedrock-2023-05-31",
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt.user
+            }
+        ]
+    });
+    if let Some(system) = prompt.system {
+        body["system"] = serde_json::Value::String(system);
+    }
+    GenerationParams::from_config(config).apply_claude(&mut body);
+    body
+}
+
+/// The model stopped because it hit `max_tokens`, so the translation is incomplete.
+fn truncated() -> AppError {
+    AppError::Api {
+        service: "Bedrock".to_string(),
+        message: "Translation was cut off at max_tokens; raise maxTokens or lower maxChars".to_string(),
+    }
+}
+
+/// Sends a signed InvokeModel request. `action` is `invoke` or `invoke-with-response-stream`.
+async fn send(
+    bedrock: &BedrockConfig<'_>,
+    action: &str,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+    timeout: std::time::Duration,
+) -> Result<reqwest::Response> {
+    // Model IDs contain ':' which must be percent-encoded in the path for SigV4
+    let url = url::Url::parse(&format!(
+        "https://bedrock-runtime.{}.amazonaws.com/model/{}/{}",
+        bedrock.region,
+        bedrock.model.replace(':', "%3A"),
+        action
+    ))
+    .map_err(|e| AppError::Config(format!("Invalid Bedrock endpoint: {}", e)))?;
+
+    let payload = serde_json::to_vec(&request_body(text, source_lang, target_lang, config))?;
+
+    let signed_headers = sigv4::sign(
+        &sigv4::AWS_V4,
+        &sigv4::SigningParams {
+            access_key_id: bedrock.access_key_id,
+            secret_access_key: bedrock.secret_access_key,
+            session_token: bedrock.session_token,
+            region: bedrock.region,
+            service: "bedrock",
+        },
+        "POST",
+        &url,
+        &[("content-type", "application/json"), ("accept", "application/json")],
+        &payload,
+        Utc::now(),
+    )?;
+
+    let client = super::http::client(config, timeout)?;
+
+    let mut request = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json")
+        .body(payload);
+    for (name, value) in signed_headers {
+        request = request.header(name, value);
+    }
+
+    let response = request.send_retrying().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Bedrock", status, error_text));
+    }
+
+    Ok(response)
+}
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let bedrock = read_config(config)?;
+    let response = send(&bedrock, "invoke", text, source_lang, target_lang, config, super::request_timeout(config)).await?;
+
+    let json: serde_json::Value = response.json().await?;
+    if json["stop_reason"].as_str() == Some("max_tokens") {
+        return Err(truncated());
+    }
+
+    let translated_text = json["content"][0]["text"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| AppError::Translation("No translation in Bedrock response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "Bedrock".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+pub async fn translate_stream<F>(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+    mut on_delta: F,
+) -> Result<String>
+where
+    F: FnMut(&str),
+{
+    let bedrock = read_config(config)?;
+    let response = send(&bedrock, "invoke-with-response-stream", text, source_lang, target_lang, config, super::stream_timeout(config)).await?;
+
+    let mut full_text = String::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AppError::Network(format!("Stream error: {}", e)))?;
+        buffer.extend_from_slice(&chunk);
+
+        while let Some((message, consumed)) = parse_event_message(&buffer)? {
+            buffer.drain(..consumed);
+
+            if message.message_type.as_deref() == Some("exception") {
+                return Err(AppError::Api {
+                    service: "Bedrock".to_string(),
+                    message: format!(
+                        "{}: {}",
+                        message.exception_type.unwrap_or_default(),
+                        String::from_utf8_lossy(&message.payload)
+                    ),
+                });
+            }
+
+            // Each chunk wraps an Anthropic streaming event as base64 in `bytes`
+            let envelope: serde_json::Value = serde_json::from_slice(&message.payload)?;
+            let Some(encoded) = envelope["bytes"].as_str() else {
+                continue;
+            };
+            let decoded = general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| AppError::Unknown(format!("Invalid Bedrock chunk encoding: {}", e)))?;
+            let event: serde_json::Value = serde_json::from_slice(&decoded)?;
+
+            match event["type"].as_str() {
+                Some("content_block_delta") => {
+                    if let Some(delta) = event["delta"]["text"].as_str() {
+                        on_delta(delta);
+                        full_text.push_str(delta);
+                    }
+                }
+                Some("message_delta") if event["delta"]["stop_reason"].as_str() == Some("max_tokens") => {
+                    return Err(truncated());
+                }
+                Some("message_stop") => return Ok(full_text),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(full_text)
+}
+
+struct EventMessage {
+    message_type: Option<String>,
+    exception_type: Option<String>,
+    payload: Vec<u8>,
+}
+
+/// Parses one frame of the `application/vnd.amazon.eventstream` encoding:
+/// total length (4) | headers length (4) | prelude CRC (4) | headers | payload | message CRC (4).
+/// Returns `None` when the buffer does not yet hold a complete frame.
+fn parse_event_message(buffer: &[u8]) -> Result<Option<(EventMessage, usize)>> {
+    if buffer.len() < 12 {
+        return Ok(None);
+    }
+
+    let total_len = u32::from_be_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as usize;
+    let headers_len = u32::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]) as usize;
+
+    if total_len < 16 + headers_len {
+        return Err(AppError::Unknown("Malformed Bedrock event stream frame".to_string()));
+    }
+    if buffer.len() < total_len {
+        return Ok(None);
+    }
+
+    let headers = &buffer[12..12 + headers_len];
+    let payload = buffer[12 + headers_len..total_len - 4].to_vec();
+
+    let mut message_type = None;
+    let mut exception_type = None;
+    let mut pos = 0;
+    while pos < headers.len() {
+        let name_len = headers[pos] as usize;
+        pos += 1;
+        // name, value type byte and a 2-byte value length must all fit
+        if pos + name_len + 3 > headers.len() {
+            break;
+        }
+        let name = String::from_utf8_lossy(&headers[pos..pos + name_len]).to_string();
+        pos += name_len;
+        let value_type = headers[pos];
+        pos += 1;
+
+        // Bedrock only sends string (type 7) headers; stop on anything else
+        if value_type != 7 {
+            break;
+        }
+        let value_len = u16::from_be_bytes([headers[pos], headers[pos + 1]]) as usize;
+        pos += 2;
+        if pos + value_len > headers.len() {
+            break;
+        }
+        let value = String::from_utf8_lossy(&headers[pos..pos + value_len]).to_string();
+        pos += value_len;
+
+        match name.as_str() {
+            ":message-type" => message_type = Some(value),
+            ":exception-type" => exception_type = Some(value),
+            _ => {}
+        }
+    }
+
+    Ok(Some((
+        EventMessage {
+            message_type,
+            exception_type,
+            payload,
+        },
+        total_len,
+    )))
+}
+
+pub struct Bedrock;
+
+#[async_trait]
+impl TranslationService for Bedrock {
+    fn name(&self) -> &str {
+        "Bedrock"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["bedrock"]
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["accessKeyId", "secretAccessKey"]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "Access key ID and secret access key required"
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn max_text_chars(&self) -> usize {
+        MAX_TEXT_CHARS
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        translate_stream(text, source_lang, target_lang, config, on_delta).await
+    }
+}
@@ -0,0 +1,147 @@
+//! LRU cache of successful translations keyed by service, language pair and
+//! a hash of the source text plus the service's effective settings (model,
+//! endpoint, prompt, glossary, post-processing...), so changing any of them
+//! never serves a stale translation. Opt-in per request via `config.cache`:
+//! `{ "enabled": true, "persist": true }`; persisted entries are written to
+//! the app data dir in the background and reloaded on first use.
+
+use crate::lru::Lru;
+use crate::models::TranslationResult;
+use crate::persist::DebouncedWrite;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const CAPACITY: usize = 1000;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheOptions {
+    pub enabled: bool,
+    pub persist: bool,
+}
+
+impl CacheOptions {
+    /// 缓存默认关闭，需显式开启
+    pub fn from_config(config: Option<&HashMap<String, serde_json::Value>>) -> Self {
+        let cache = config.and_then(|c| c.get("cache"));
+        let flag = |key: &str| cache.and_then(|c| c.get(key)).and_then(|v| v.as_bool());
+        CacheOptions {
+            enabled: flag("enabled").unwrap_or(false),
+            persist: flag("persist").unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedResult {
+    key: String,
+    name: String,
+    text: String,
+    #[serde(default)]
+    alternatives: Vec<String>,
+}
+
+struct TranslationCache {
+    entries: Lru<CachedResult>,
+    loaded: bool,
+}
+
+static CACHE: Lazy<Mutex<TranslationCache>> =
+    Lazy::new(|| Mutex::new(TranslationCache { entries: Lru::with_capacity(CAPACITY), loaded: false }));
+
+static SAVE: DebouncedWrite = DebouncedWrite::new("translation cache", Duration::from_secs(2));
+
+fn cache_file() -> PathBuf {
+    crate::paths::data_dir().join("translation_cache.json")
+}
+
+fn ensure_loaded(cache: &mut TranslationCache) {
+    if cache.loaded {
+        return;
+    }
+    cache.loaded = true;
+    let Ok(content) = std::fs::read_to_string(cache_file()) else {
+        return;
+    };
+    match serde_json::from_str::<Vec<CachedResult>>(&content) {
+        Ok(entries) => entries.into_iter().for_each(|entry| cache.entries.insert(entry.key.clone(), entry)),
+        Err(e) => println!("Ignoring unreadable translation cache: {}", e),
+    }
+}
+
+/// 按最近使用顺序导出，重新加载时保持 LRU 顺序；锁内只复制条目，序列化在锁外进行
+fn snapshot() -> Option<(PathBuf, String)> {
+    let entries: Vec<CachedResult> = CACHE.lock().unwrap().entries.iter().map(|(_, entry)| entry.clone()).collect();
+    match serde_json::to_string(&entries) {
+        Ok(json) => Some((cache_file(), json)),
+        Err(e) => {
+            println!("Failed to serialize translation cache: {}", e);
+            None
+        }
+    }
+}
+
+/// `settings` 为影响译文的有效配置，其 JSON 与原文一起参与哈希
+pub fn cache_key(
+    service: &str,
+    source_lang: &str,
+    target_lang: &str,
+    text: &str,
+    settings: Option<&serde_json::Value>,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text.as_bytes());
+    hasher.update([0]);
+    hasher.update(settings.map(|s| s.to_string()).unwrap_or_default().as_bytes());
+    format!("{}|{}|{}|{}", service.to_lowercase(), source_lang, target_lang, hex::encode(hasher.finalize()))
+}
+
+pub fn get(key: &str) -> Option<TranslationResult> {
+    let mut cache = CACHE.lock().unwrap();
+    ensure_loaded(&mut cache);
+    cache.entries.get(key).cloned().map(|cached| TranslationResult {
+        name: cached.name,
+        text: cached.text,
+        error: None,
+        alternatives: cached.alternatives,
+    })
+}
+
+pub fn put(key: String, result: &TranslationResult, options: CacheOptions) {
+    if result.error.is_some() || result.text.is_empty() {
+        return;
+    }
+    {
+        let mut cache = CACHE.lock().unwrap();
+        ensure_loaded(&mut cache);
+        let cached = CachedResult {
+            key: key.clone(),
+            name: result.name.clone(),
+            text: result.text.clone(),
+            alternatives: result.alternatives.clone(),
+        };
+        cache.entries.insert(key, cached);
+    }
+    if options.persist {
+        SAVE.schedule(snapshot);
+    }
+}
+
+/// 清空内存缓存并删除磁盘文件，返回清除的条目数
+pub fn clear() -> usize {
+    let mut cache = CACHE.lock().unwrap();
+    let count = cache.entries.len();
+    cache.entries.clear();
+    cache.loaded = true;
+    let path = cache_file();
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(path) {
+            println!("Failed to remove translation cache file: {}", e);
+        }
+    }
+    count
+}
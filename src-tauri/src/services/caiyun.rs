@@ -0,0 +1,110 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use crate::translate::TranslationService;
+use crate::translate::languages::LanguageSupport;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let token = config
+        .and_then(|c| c.get("apiKey"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Config("Caiyun token not configured".to_string()))?;
+
+    let api_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("https://api.interpreter.caiyunai.com/v1/translator");
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    // Caiyun encodes the direction as "{source}2{target}", e.g. "auto2zh"
+    let trans_type = format!("{}2{}", map_lang(source_lang), map_lang(target_lang));
+
+    let response = client
+        .post(api_url)
+        .header("x-authorization", format!("token {}", token))
+        .json(&serde_json::json!({
+            "source": [text],
+            "trans_type": trans_type,
+            "request_id": uuid::Uuid::new_v4().to_string(),
+            "detect": true
+        }))
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Caiyun", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    let translated_text = json["target"][0]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Translation("No translation in Caiyun response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "Caiyun".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+fn map_lang(lang: &str) -> &str {
+    match lang {
+        "zh-CN" | "zh-Hans" => "zh",
+        "zh-TW" | "zh-Hant" => "zh-Hant",
+        other => other,
+    }
+}
+
+pub struct Caiyun;
+
+#[async_trait]
+impl TranslationService for Caiyun {
+    fn name(&self) -> &str {
+        "Caiyun"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["caiyun", "彩云小译"]
+    }
+
+    /// Caiyun only translates between Chinese and English or Japanese.
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Pairs(&[
+            ("zh-CN", "en"),
+            ("en", "zh-CN"),
+            ("zh-CN", "ja"),
+            ("ja", "zh-CN"),
+            ("zh-TW", "en"),
+            ("en", "zh-TW"),
+            ("zh-CN", "zh-TW"),
+            ("zh-TW", "zh-CN"),
+        ])
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["apiKey"]
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
@@ -0,0 +1,207 @@
+//! Sentence-aware splitting of long texts so each request stays within the
+//! provider's `max_text_chars`. Chunks keep the whitespace that followed them
+//! in the source, so translations are reassembled in order with the original
+//! paragraph breaks.
+
+use crate::error::Result;
+use crate::models::TranslationResult;
+use crate::translate::TranslationService;
+use futures_util::future::try_join_all;
+
+/// 未配置 `chunkConcurrency` 时单个服务同时翻译的分段数
+const DEFAULT_CHUNK_CONCURRENCY: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    pub text: String,
+    /// 分段后原文中紧跟的空白，重组时原样保留
+    pub separator: String,
+}
+
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | ';' | '。' | '！' | '？' | '；' | '…')
+}
+
+fn is_cjk_sentence_end(c: char) -> bool {
+    matches!(c, '。' | '！' | '？' | '；' | '…')
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(c, '"' | '\'' | ')' | ']' | '”' | '’' | '」' | '』' | '）' | '】')
+}
+
+/// 切成句子，每句带上其后的空白。西文标点后须有空白才算句末，中日文标点直接断句
+fn sentences(text: &str) -> Vec<(String, String)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let mut end = None;
+        if c == '\n' {
+            end = Some(i);
+        } else if is_sentence_end(c) {
+            let mut j = i + 1;
+            while j < chars.len() && (is_sentence_end(chars[j]) || is_closing(chars[j])) {
+                j += 1;
+            }
+            if is_cjk_sentence_end(c) || j == chars.len() || chars[j].is_whitespace() {
+                end = Some(j);
+            }
+        }
+        match end {
+            Some(end) => {
+                let mut next = end;
+                while next < chars.len() && chars[next].is_whitespace() {
+                    next += 1;
+                }
+                let sentence: String = chars[start..end].iter().collect();
+                let separator: String = chars[end..next].iter().collect();
+                if !sentence.is_empty() {
+                    result.push((sentence, separator));
+                } else if let Some(last) = result.last_mut() {
+                    last.1.push_str(&separator);
+                }
+                start = next;
+                i = next;
+            }
+            None => i += 1,
+        }
+    }
+    if start < chars.len() {
+        result.push((chars[start..].iter().collect(), String::new()));
+    }
+    result
+}
+
+/// 超长句子按空白或字符边界硬切
+fn split_long(sentence: &str, separator: &str, max_chars: usize) -> Vec<Chunk> {
+    let chars: Vec<char> = sentence.chars().collect();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while chars.len() - start > max_chars {
+        let limit = start + max_chars;
+        let cut = (start + 1..limit).rev().find(|&i| chars[i].is_whitespace()).unwrap_or(limit);
+        let mut next = cut;
+        while next < chars.len() && chars[next].is_whitespace() {
+            next += 1;
+        }
+        chunks.push(Chunk {
+            text: chars[start..cut].iter().collect(),
+            separator: chars[cut..next].iter().collect(),
+        });
+        start = next;
+    }
+    chunks.push(Chunk {
+        text: chars[start..].iter().collect(),
+        separator: separator.to_string(),
+    });
+    chunks
+}
+
+/// 把文本切成不超过 `max_chars` 个字符的分段，尽量在句末断开
+pub fn split(text: &str, max_chars: usize) -> Vec<Chunk> {
+    let max_chars = max_chars.max(1);
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    let mut pending_separator = String::new();
+
+    for (sentence, separator) in sentences(text) {
+        let len = sentence.chars().count();
+        if len > max_chars {
+            if !current.is_empty() {
+                chunks.push(Chunk { text: std::mem::take(&mut current), separator: std::mem::take(&mut pending_separator) });
+                current_len = 0;
+            }
+            chunks.extend(split_long(&sentence, &separator, max_chars));
+            continue;
+        }
+        let joined_len = current_len + pending_separator.chars().count() + len;
+        if !current.is_empty() && joined_len > max_chars {
+            chunks.push(Chunk { text: std::mem::take(&mut current), separator: std::mem::take(&mut pending_separator) });
+            current_len = 0;
+        }
+        if !current.is_empty() {
+            current_len += pending_separator.chars().count();
+            current.push_str(&std::mem::take(&mut pending_separator));
+        }
+        current.push_str(&sentence);
+        current_len += len;
+        pending_separator = separator;
+    }
+    if !current.is_empty() {
+        chunks.push(Chunk { text: current, separator: pending_separator });
+    }
+    chunks
+}
+
+/// 按原顺序拼接各分段译文
+pub fn reassemble(chunks: &[Chunk], translations: &[String]) -> String {
+    chunks
+        .iter()
+        .zip(translations)
+        .map(|(chunk, translation)| format!("{}{}", translation.trim(), chunk.separator))
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+fn max_chars(provider: &dyn TranslationService, config: Option<&serde_json::Value>) -> usize {
+    config
+        .and_then(|c| c.get("maxChars"))
+        .and_then(|v| v.as_u64())
+        .filter(|n| *n > 0)
+        .map(|n| n as usize)
+        .unwrap_or_else(|| provider.max_text_chars())
+}
+
+/// 读取服务配置中的 `chunkConcurrency`
+pub(crate) fn concurrency(config: Option<&serde_json::Value>) -> usize {
+    config
+        .and_then(|c| c.get("chunkConcurrency"))
+        .and_then(|v| v.as_u64())
+        .filter(|n| *n > 0)
+        .map(|n| n as usize)
+        .unwrap_or(DEFAULT_CHUNK_CONCURRENCY)
+}
+
+/// 文本是否超出服务单次请求的长度限制
+pub fn needs_split(provider: &dyn TranslationService, text: &str, config: Option<&serde_json::Value>) -> bool {
+    text.chars().count() > max_chars(provider, config)
+}
+
+/// 超过服务长度限制的文本分段翻译（并发受限）后按序重组，其余情况直接调用服务
+pub async fn translate(
+    provider: &dyn TranslationService,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    if !needs_split(provider, text, config) {
+        return provider.translate(text, source_lang, target_lang, config).await;
+    }
+
+    let chunks = split(text, max_chars(provider, config));
+    let concurrency = concurrency(config);
+    println!("{}: translating {} chunks", provider.name(), chunks.len());
+
+    // 每批最多 `concurrency` 个分段并发，try_join_all 保持顺序
+    let mut translations = Vec::with_capacity(chunks.len());
+    for batch in chunks.chunks(concurrency) {
+        let results = try_join_all(
+            batch.iter().map(|chunk| provider.translate(&chunk.text, source_lang, target_lang, config)),
+        )
+        .await?;
+        translations.extend(results.into_iter().map(|result| result.text));
+    }
+
+    Ok(TranslationResult {
+        name: provider.name().to_string(),
+        text: reassemble(&chunks, &translations),
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
@@ -1,10 +1,15 @@
 use crate::models::TranslationResult;
+use crate::services::generation::GenerationParams;
+use crate::services::prompt::{split_variants, variant_count, Prompt};
 use crate::error::{AppError, Result};
 use futures_util::StreamExt;
+use crate::translate::TranslationService;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
 
 pub async fn translate(
     text: &str,
-    _source_lang: &str,
+    source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
 ) -> Result<TranslationResult> {
@@ -24,36 +29,39 @@ pub async fn translate(
         .and_then(|v| v.as_str())
         .unwrap_or("claude-3-haiku-20240307");
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| AppError::Network(format!("Failed to create HTTP client: {}", e)))?;
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let variants = variant_count(config);
+    let prompt = Prompt::from_template(config, text, source_lang, target_lang)
+        .unwrap_or_else(|| Prompt::translation_engine(text, target_lang))
+        .with_variants(text, variants);
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt.user
+            }
+        ]
+    });
+    if let Some(system) = prompt.system {
+        body["system"] = serde_json::Value::String(system);
+    }
+    GenerationParams::from_config(config).apply_claude(&mut body);
 
     let response = client
         .post(api_url)
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
-        .json(&serde_json::json!({
-            "model": model,
-            "max_tokens": 1024,
-            "system": format!("You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.", target_lang),
-            "messages": [
-                {
-                    "role": "user",
-                    "content": text
-                }
-            ]
-        }))
-        .send()
+        .json(&body)
+        .send_retrying()
         .await?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(AppError::Api { 
-            service: "Claude".to_string(), 
-            message: error_text 
-        });
+        return Err(AppError::from_status("Claude", status, error_text));
     }
 
     let json: serde_json::Value = response.json().await?;
@@ -62,17 +70,23 @@ pub async fn translate(
         .as_str()
         .map(|s| s.trim().to_string())
         .ok_or_else(|| AppError::Translation("No translation in Claude response".to_string()))?;
+    let (translated_text, alternatives) = if variants > 1 && !text.contains('\n') {
+        split_variants(&translated_text)
+    } else {
+        (translated_text, Vec::new())
+    };
 
     Ok(TranslationResult {
         name: "Claude".to_string(),
         text: translated_text,
         error: None,
+        alternatives,
     })
 }
 
 pub async fn translate_stream<F>(
     text: &str,
-    _source_lang: &str,
+    source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
     mut on_delta: F,
@@ -96,37 +110,38 @@ where
         .and_then(|v| v.as_str())
         .unwrap_or("claude-3-haiku-20240307");
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(60))
-        .build()
-        .map_err(|e| AppError::Network(format!("Failed to create HTTP client: {}", e)))?;
+    let client = super::http::client(config, super::stream_timeout(config))?;
+
+    let prompt = Prompt::from_template(config, text, source_lang, target_lang)
+        .unwrap_or_else(|| Prompt::translation_engine(text, target_lang));
+    let mut body = serde_json::json!({
+        "model": model,
+        "stream": true,
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt.user
+            }
+        ]
+    });
+    if let Some(system) = prompt.system {
+        body["system"] = serde_json::Value::String(system);
+    }
+    GenerationParams::from_config(config).apply_claude(&mut body);
 
     let response = client
         .post(api_url)
         .header("x-api-key", &api_key)
         .header("anthropic-version", "2023-06-01")
         .header("content-type", "application/json")
-        .json(&serde_json::json!({
-            "model": model,
-            "max_tokens": 1024,
-            "stream": true,
-            "system": format!("You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.", target_lang),
-            "messages": [
-                {
-                    "role": "user",
-                    "content": text
-                }
-            ]
-        }))
-        .send()
+        .json(&body)
+        .send_retrying()
         .await?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(AppError::Api { 
-            service: "Claude".to_string(), 
-            message: error_text 
-        });
+        return Err(AppError::from_status("Claude", status, error_text));
     }
 
     let mut full_text = String::new();
@@ -159,3 +174,49 @@ where
 
     Ok(full_text)
 }
+
+pub struct Claude;
+
+#[async_trait]
+impl TranslationService for Claude {
+    fn name(&self) -> &str {
+        "Claude"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["claude"]
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["apiKey"]
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn supports_variants(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        translate_stream(text, source_lang, target_lang, config, on_delta).await
+    }
+}
@@ -1,13 +1,21 @@
 use crate::models::TranslationResult;
-use reqwest;
 use std::env;
+use crate::error::AppError;
+use crate::translate::TranslationService;
+use crate::translate::languages::LanguageSupport;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+fn api_error(message: impl Into<String>) -> AppError {
+    AppError::Api { service: "DeepL".to_string(), message: message.into() }
+}
 
 pub async fn translate(
     text: &str,
-    _source_lang: &str,
+    source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
-) -> Result<TranslationResult, String> {
+) -> crate::error::Result<TranslationResult> {
     let api_key = if let Some(c) = config {
         c.get("apiKey").and_then(|v| v.as_str()).map(|s| s.to_string())
     } else {
@@ -27,52 +35,147 @@ pub async fn translate(
                 })
                 .ok()
         })
-        .ok_or_else(|| "DEEPL_API_KEY not found".to_string())?;
-
-    let target = match target_lang.to_uppercase().as_str() {
-        "ZH" | "ZH-HANS" => "ZH",
-        "EN" => "EN-US",
-        "JA" => "JA",
-        "KO" => "KO",
-        "FR" => "FR",
-        "DE" => "DE",
-        "ES" => "ES",
-        "RU" => "RU",
-        _ => "EN-US",
-    };
+        .ok_or_else(|| api_error("DEEPL_API_KEY not found"))?;
+
+    let api_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| default_endpoint(config, &api_key));
+
+    let target = target_code(target_lang);
+
+    // DeepL 的源语言只接受基础代码（如 ZH、EN），未知时省略由其自动检测
+    let source = source_lang
+        .split('-')
+        .next()
+        .map(|s| s.to_uppercase())
+        .filter(|s| !s.is_empty() && s != "AUTO");
+
+    let mut form = vec![("text", text), ("target_lang", target.as_str())];
+    if let Some(source) = source.as_deref() {
+        form.push(("source_lang", source));
+    }
+    // 可选参数原样透传：formality、split_sentences、glossary_id（术语表要求指定源语言）
+    for (key, param) in [
+        ("formality", "formality"),
+        ("splitSentences", "split_sentences"),
+        ("glossaryId", "glossary_id"),
+    ] {
+        if let Some(value) = option(config, key) {
+            form.push((param, value));
+        }
+    }
+    if form.iter().any(|(param, _)| *param == "glossary_id") && source.is_none() {
+        return Err(api_error("DeepL glossaries require a source language"));
+    }
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let client = super::http::client(config, super::request_timeout(config))?;
         
     let response = client
-        .post("https://api-free.deepl.com/v2/translate")
+        .post(api_url)
         .header("Authorization", format!("DeepL-Auth-Key {}", api_key))
-        .form(&[
-            ("text", text),
-            ("target_lang", target),
-        ])
-        .send()
+        .form(&form)
+        .send_retrying()
         .await
-        .map_err(|e| format!("DeepL API request failed: {}", e))?;
+        .map_err(|e| api_error(format!("DeepL API request failed: {}", e)))?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("DeepL API error: {}", error_text));
+        return Err(AppError::from_status("DeepL", status, format!("DeepL API error: {}", error_text)));
     }
 
     let json: serde_json::Value = response.json().await
-        .map_err(|e| format!("Failed to parse DeepL response: {}", e))?;
+        .map_err(|e| api_error(format!("Failed to parse DeepL response: {}", e)))?;
 
     let translated_text = json["translations"][0]["text"]
         .as_str()
         .map(|s| s.to_string())
-        .ok_or("No translation in response")?;
+        .ok_or_else(|| api_error("No translation in response"))?;
 
     Ok(TranslationResult {
         name: "DeepL".to_string(),
         text: translated_text,
         error: None,
+        alternatives: Vec::new(),
     })
 }
+
+const FREE_ENDPOINT: &str = "https://api-free.deepl.com/v2/translate";
+const PRO_ENDPOINT: &str = "https://api.deepl.com/v2/translate";
+
+/// 未配置 `apiUrl` 时按 `pro` 选择端点；也未配置时按密钥判断，免费版密钥以 `:fx` 结尾
+fn default_endpoint(config: Option<&serde_json::Value>, api_key: &str) -> &'static str {
+    let pro = config
+        .and_then(|c| c.get("pro"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(!api_key.ends_with(":fx"));
+    if pro {
+        PRO_ENDPOINT
+    } else {
+        FREE_ENDPOINT
+    }
+}
+
+/// 字符串或数字形式的配置项（`splitSentences` 可写作 0/1）
+fn option<'a>(config: Option<&'a serde_json::Value>, key: &str) -> Option<&'a str> {
+    let value = config?.get(key)?;
+    let value = match value {
+        serde_json::Value::String(s) => s.as_str(),
+        serde_json::Value::Number(n) if n.as_u64() == Some(0) => "0",
+        serde_json::Value::Number(n) if n.as_u64() == Some(1) => "1",
+        _ => return None,
+    };
+    Some(value).filter(|s| !s.is_empty())
+}
+
+fn target_code(target_lang: &str) -> String {
+    match target_lang.to_uppercase().as_str() {
+        "ZH" | "ZH-CN" | "ZH-HANS" => "ZH".to_string(),
+        "ZH-TW" | "ZH-HANT" => "ZH-HANT".to_string(),
+        "EN" => "EN-US".to_string(),
+        "PT" => "PT-BR".to_string(),
+        "NO" | "NB" => "NB".to_string(),
+        "" | "AUTO" => "EN-US".to_string(),
+        other => other.to_string(),
+    }
+}
+
+pub struct DeepL;
+
+#[async_trait]
+impl TranslationService for DeepL {
+    fn name(&self) -> &str {
+        "DeepL"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["deepl"]
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(&[
+            "ar", "bg", "cs", "da", "de", "el", "en", "es", "et", "fi", "fr", "hu", "id", "it", "ja", "ko",
+            "lt", "lv", "nb", "nl", "pl", "pt", "ro", "ru", "sk", "sl", "sv", "tr", "uk", "zh-CN", "zh-TW",
+        ])
+    }
+
+    fn max_text_chars(&self) -> usize {
+        30000
+    }
+
+    fn needs_source_lang(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> crate::error::Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
@@ -0,0 +1,243 @@
+//! Live "translate as you type" support. Each draft (one input box) keeps a
+//! generation counter: a newer call supersedes older ones both during the
+//! debounce window and while a request is in flight. For LLM providers the
+//! already-translated leading sentences are reused and only the changed tail
+//! is sent again.
+
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+
+const DEFAULT_DEBOUNCE_MS: u64 = 400;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftResult {
+    pub draft_id: String,
+    pub service: String,
+    pub text: String,
+    /// 本次实际发送给服务的原文（前缀复用后的剩余部分）
+    pub translated_source: String,
+    pub reused_chunks: usize,
+}
+
+/// 已翻译的一段原文及其译文；只有以句末标点结尾的块才会被复用
+#[derive(Clone)]
+struct Chunk {
+    source: String,
+    translation: String,
+}
+
+struct DraftState {
+    generation: watch::Sender<u64>,
+    key: (String, String, String),
+    chunks: Vec<Chunk>,
+}
+
+static DRAFTS: Lazy<Mutex<HashMap<String, DraftState>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end()
+        .chars()
+        .last()
+        .map(|c| matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '\n'))
+        .unwrap_or(false)
+}
+
+/// 按句末标点切分，保留标点和随后的空白
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '\n') {
+            let mut end = pos + c.len_utf8();
+            while let Some((next_pos, next)) = chars.peek().copied() {
+                if !next.is_whitespace() {
+                    break;
+                }
+                end = next_pos + next.len_utf8();
+                chars.next();
+            }
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+fn joiner(target_lang: &str) -> &'static str {
+    match target_lang.split('-').next().unwrap_or_default() {
+        "zh" | "ja" | "ko" | "th" => "",
+        _ => " ",
+    }
+}
+
+/// Registers a new generation for `draft_id` and returns a receiver that
+/// changes once a newer call arrives.
+fn begin(draft_id: &str, key: (String, String, String)) -> (u64, watch::Receiver<u64>, Vec<Chunk>) {
+    let mut drafts = DRAFTS.lock().unwrap();
+    let state = drafts.entry(draft_id.to_string()).or_insert_with(|| DraftState {
+        generation: watch::channel(0).0,
+        key: key.clone(),
+        chunks: Vec::new(),
+    });
+    if state.key != key {
+        state.key = key;
+        state.chunks.clear();
+    }
+    let generation = *state.generation.borrow() + 1;
+    state.generation.send_replace(generation);
+    (generation, state.generation.subscribe(), state.chunks.clone())
+}
+
+fn is_current(draft_id: &str, generation: u64) -> bool {
+    DRAFTS
+        .lock()
+        .unwrap()
+        .get(draft_id)
+        .map(|state| *state.generation.borrow() == generation)
+        .unwrap_or(false)
+}
+
+/// Waits for `rx` to observe a newer generation than `generation`.
+async fn superseded(mut rx: watch::Receiver<u64>, generation: u64) {
+    while *rx.borrow_and_update() == generation {
+        if rx.changed().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// 返回 `Ok(None)` 表示该请求已被更新的输入取代
+pub async fn translate_draft(
+    draft_id: &str,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    service: &str,
+    config: Option<&HashMap<String, serde_json::Value>>,
+) -> Result<Option<DraftResult>> {
+    let (provider, service_config) = crate::translate::resolve(service, config)?;
+    let key = (service.to_lowercase(), source_lang.to_string(), target_lang.to_string());
+    let (generation, rx, previous) = begin(draft_id, key);
+
+    let debounce = config
+        .and_then(|c| c.get("draft"))
+        .and_then(|d| d.get("debounceMs"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_DEBOUNCE_MS);
+    tokio::time::sleep(Duration::from_millis(debounce)).await;
+    if !is_current(draft_id, generation) {
+        return Ok(None);
+    }
+
+    // 防抖结束后才占用密钥配额
+    let (service_config, key_lease) = crate::translate::keys::lease(provider.name(), service_config)?;
+    if !super::check_config_keys(service_config.as_ref(), provider.required_keys()) {
+        return Err(AppError::Config(provider.missing_keys_message().to_string()));
+    }
+
+    let text = text.trim_end();
+    let source_lang = if provider.needs_source_lang() {
+        crate::langdetect::resolve_source(text, source_lang)
+    } else {
+        source_lang.to_string()
+    };
+    if text.is_empty() {
+        return Ok(Some(DraftResult {
+            draft_id: draft_id.to_string(),
+            service: provider.name().to_string(),
+            text: String::new(),
+            translated_source: String::new(),
+            reused_chunks: 0,
+        }));
+    }
+
+    // 复用以完整句子结尾、且仍是当前文本前缀的译文块（仅 LLM 服务）
+    let prefix_mode = provider.supports_streaming();
+    let mut reused: Vec<Chunk> = Vec::new();
+    if prefix_mode {
+        let mut offset = 0;
+        for chunk in previous {
+            if !ends_sentence(&chunk.source) || !text[offset..].starts_with(&chunk.source) {
+                break;
+            }
+            offset += chunk.source.len();
+            reused.push(chunk);
+        }
+    }
+    let reused_len: usize = reused.iter().map(|c| c.source.len()).sum();
+    let remaining = &text[reused_len..];
+
+    // 未完成的最后一句单独翻译，使已完成的句子下次可以复用
+    let split_at = if prefix_mode && !ends_sentence(remaining) {
+        let sentences = split_sentences(remaining);
+        remaining.len() - sentences.last().map(|s| s.len()).unwrap_or(0)
+    } else {
+        remaining.len()
+    };
+    let sources: Vec<&str> = [&remaining[..split_at], &remaining[split_at..]]
+        .into_iter()
+        .filter(|s| !s.trim().is_empty())
+        .collect();
+
+    let requests = futures_util::future::try_join_all(sources.iter().map(|source| {
+        provider.translate(source, &source_lang, target_lang, service_config.as_ref())
+    }));
+    let outcome = tokio::select! {
+        result = requests => result,
+        _ = superseded(rx, generation) => return Ok(None),
+    };
+    if let Some(lease) = key_lease {
+        lease.finish(outcome.as_ref().err());
+    }
+    let translations = outcome?;
+
+    let mut chunks = reused;
+    let reused_chunks = chunks.len();
+    for (source, result) in sources.iter().zip(translations) {
+        chunks.push(Chunk {
+            source: source.to_string(),
+            translation: result.text.trim().to_string(),
+        });
+    }
+
+    let full = chunks
+        .iter()
+        .map(|c| c.translation.as_str())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(joiner(target_lang));
+
+    {
+        let mut drafts = DRAFTS.lock().unwrap();
+        let state = drafts
+            .get_mut(draft_id)
+            .ok_or_else(|| AppError::Unknown("Draft state disappeared".to_string()))?;
+        if *state.generation.borrow() != generation {
+            return Ok(None);
+        }
+        state.chunks = chunks;
+    }
+
+    Ok(Some(DraftResult {
+        draft_id: draft_id.to_string(),
+        service: provider.name().to_string(),
+        text: full,
+        translated_source: remaining.to_string(),
+        reused_chunks,
+    }))
+}
+
+/// 输入框关闭时释放草稿状态
+pub fn discard(draft_id: &str) {
+    DRAFTS.lock().unwrap().remove(draft_id);
+}
@@ -1,10 +1,15 @@
 use crate::models::TranslationResult;
+use crate::services::generation::GenerationParams;
+use crate::services::prompt::Prompt;
 use crate::error::{AppError, Result};
 use futures_util::StreamExt;
+use crate::translate::TranslationService;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
 
 pub async fn translate(
     text: &str,
-    _source_lang: &str,
+    source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
 ) -> Result<TranslationResult> {
@@ -25,10 +30,7 @@ pub async fn translate(
         .and_then(|v| v.as_str())
         .unwrap_or("ernie-4.0-8k");
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| AppError::Network(format!("Failed to create HTTP client: {}", e)))?;
+    let client = super::http::client(config, super::request_timeout(config))?;
 
     let token_url = format!(
         "https://aip.baidubce.com/oauth/2.0/token?grant_type=client_credentials&client_id={}&client_secret={}",
@@ -37,15 +39,13 @@ pub async fn translate(
 
     let token_response = client
         .get(&token_url)
-        .send()
+        .send_retrying()
         .await?;
 
     if !token_response.status().is_success() {
+        let status = token_response.status();
         let error_text = token_response.text().await.unwrap_or_default();
-        return Err(AppError::Api { 
-            service: "Ernie".to_string(), 
-            message: format!("Failed to get access token: {}", error_text) 
-        });
+        return Err(AppError::from_status("Ernie", status, format!("Failed to get access token: {}", error_text)));
     }
 
     let token_json: serde_json::Value = token_response.json().await?;
@@ -69,25 +69,34 @@ pub async fn translate(
         model_endpoint, access_token
     );
 
+    // 文心的系统提示通过顶层 `system` 字段传入
+    let prompt = Prompt::from_template(config, text, source_lang, target_lang).unwrap_or_else(|| Prompt {
+        system: None,
+        user: format!("Translate the following text to {}. Output ONLY the translated text, no explanations:\n\n{}", target_lang, text),
+    });
+    let mut body = serde_json::json!({
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt.user
+            }
+        ]
+    });
+    if let Some(system) = prompt.system {
+        body["system"] = serde_json::Value::String(system);
+    }
+    GenerationParams::from_config(config).apply_ernie(&mut body);
+
     let response = client
         .post(&api_url)
-        .json(&serde_json::json!({
-            "messages": [
-                {
-                    "role": "user",
-                    "content": format!("Translate the following text to {}. Output ONLY the translated text, no explanations:\n\n{}", target_lang, text)
-                }
-            ]
-        }))
-        .send()
+        .json(&body)
+        .send_retrying()
         .await?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(AppError::Api { 
-            service: "Ernie".to_string(), 
-            message: error_text 
-        });
+        return Err(AppError::from_status("Ernie", status, error_text));
     }
 
     let json: serde_json::Value = response.json().await?;
@@ -101,12 +110,13 @@ pub async fn translate(
         name: "Ernie".to_string(),
         text: translated_text,
         error: None,
+        alternatives: Vec::new(),
     })
 }
 
 pub async fn translate_stream<F>(
     text: &str,
-    _source_lang: &str,
+    source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
     mut on_delta: F,
@@ -131,10 +141,7 @@ where
         .and_then(|v| v.as_str())
         .unwrap_or("ernie-4.0-8k");
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(60))
-        .build()
-        .map_err(|e| AppError::Network(format!("Failed to create HTTP client: {}", e)))?;
+    let client = super::http::client(config, super::stream_timeout(config))?;
 
     let token_url = format!(
         "https://aip.baidubce.com/oauth/2.0/token?grant_type=client_credentials&client_id={}&client_secret={}",
@@ -143,15 +150,13 @@ where
 
     let token_response = client
         .get(&token_url)
-        .send()
+        .send_retrying()
         .await?;
 
     if !token_response.status().is_success() {
+        let status = token_response.status();
         let error_text = token_response.text().await.unwrap_or_default();
-        return Err(AppError::Api { 
-            service: "Ernie".to_string(), 
-            message: format!("Failed to get access token: {}", error_text) 
-        });
+        return Err(AppError::from_status("Ernie", status, format!("Failed to get access token: {}", error_text)));
     }
 
     let token_json: serde_json::Value = token_response.json().await?;
@@ -175,26 +180,35 @@ where
         model_endpoint, access_token
     );
 
+    // 文心的系统提示通过顶层 `system` 字段传入
+    let prompt = Prompt::from_template(config, text, source_lang, target_lang).unwrap_or_else(|| Prompt {
+        system: None,
+        user: format!("Translate the following text to {}. Output ONLY the translated text, no explanations:\n\n{}", target_lang, text),
+    });
+    let mut body = serde_json::json!({
+        "messages": [
+            {
+                "role": "user",
+                "content": prompt.user
+            }
+        ],
+        "stream": true
+    });
+    if let Some(system) = prompt.system {
+        body["system"] = serde_json::Value::String(system);
+    }
+    GenerationParams::from_config(config).apply_ernie(&mut body);
+
     let response = client
         .post(&api_url)
-        .json(&serde_json::json!({
-            "messages": [
-                {
-                    "role": "user",
-                    "content": format!("Translate the following text to {}. Output ONLY the translated text, no explanations:\n\n{}", target_lang, text)
-                }
-            ],
-            "stream": true
-        }))
-        .send()
+        .json(&body)
+        .send_retrying()
         .await?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(AppError::Api { 
-            service: "Ernie".to_string(), 
-            message: error_text 
-        });
+        return Err(AppError::from_status("Ernie", status, error_text));
     }
 
     let mut full_text = String::new();
@@ -227,3 +241,49 @@ where
 
     Ok(full_text)
 }
+
+pub struct Ernie;
+
+#[async_trait]
+impl TranslationService for Ernie {
+    fn name(&self) -> &str {
+        "Ernie"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ernie", "wenxin", "文心一言"]
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["apiKey", "secretKey"]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "API key and secret key required"
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        translate_stream(text, source_lang, target_lang, config, on_delta).await
+    }
+}
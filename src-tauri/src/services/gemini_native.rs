@@ -0,0 +1,262 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use futures_util::StreamExt;
+use crate::translate::TranslationService;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+const DEFAULT_API_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+const SAFETY_CATEGORIES: [&str; 4] = [
+    "HARM_CATEGORY_HARASSMENT",
+    "HARM_CATEGORY_HATE_SPEECH",
+    "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_DANGEROUS_CONTENT",
+];
+
+struct GeminiConfig<'a> {
+    api_key: &'a str,
+    api_url: &'a str,
+    model: &'a str,
+    system_instruction: Option<&'a str>,
+    safety_settings: Option<serde_json::Value>,
+}
+
+fn read_config(config: Option<&serde_json::Value>) -> Result<GeminiConfig<'_>> {
+    let get = |key: &str| {
+        config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+
+    // An explicit `safetySettings` array wins; `safetyThreshold` applies one threshold to every category
+    let safety_settings = config
+        .and_then(|c| c.get("safetySettings"))
+        .filter(|v| v.is_array())
+        .cloned()
+        .or_else(|| {
+            get("safetyThreshold").map(|threshold| {
+                serde_json::Value::Array(
+                    SAFETY_CATEGORIES
+                        .iter()
+                        .map(|category| serde_json::json!({ "category": category, "threshold": threshold }))
+                        .collect(),
+                )
+            })
+        });
+
+    Ok(GeminiConfig {
+        api_key: get("apiKey").ok_or_else(|| AppError::Config("Gemini API key not configured".to_string()))?,
+        api_url: get("apiUrl").unwrap_or(DEFAULT_API_URL).trim_end_matches('/'),
+        model: get("model").unwrap_or("gemini-1.5-flash"),
+        system_instruction: get("systemInstruction"),
+        safety_settings,
+    })
+}
+
+fn build_request(
+    client: &reqwest::Client,
+    gemini: &GeminiConfig,
+    text: &str,
+    target_lang: &str,
+    stream: bool,
+) -> reqwest::RequestBuilder {
+    let system = gemini.system_instruction.map(|s| s.to_string()).unwrap_or_else(|| {
+        format!("You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.", target_lang)
+    });
+
+    let mut body = serde_json::json!({
+        "systemInstruction": {
+            "parts": [{ "text": system }]
+        },
+        "contents": [
+            {
+                "role": "user",
+                "parts": [{ "text": text }]
+            }
+        ]
+    });
+    if let Some(safety_settings) = &gemini.safety_settings {
+        body["safetySettings"] = safety_settings.clone();
+    }
+
+    let method = if stream { "streamGenerateContent" } else { "generateContent" };
+    let url = format!("{}/models/{}:{}", gemini.api_url, gemini.model, method);
+
+    let mut request = client.post(url).query(&[("key", gemini.api_key)]);
+    if stream {
+        request = request.query(&[("alt", "sse")]);
+    }
+    request.json(&body)
+}
+
+/// Concatenates the text parts of the first candidate, reporting prompt or
+/// response blocks as errors instead of returning an empty translation.
+/// `service` is the provider's display name used in errors.
+fn extract_text(service: &str, json: &serde_json::Value) -> Result<Option<String>> {
+    if let Some(reason) = json["promptFeedback"]["blockReason"].as_str() {
+        return Err(AppError::Api {
+            service: service.to_string(),
+            message: format!("Prompt blocked: {}", reason),
+        });
+    }
+
+    let candidate = &json["candidates"][0];
+    if candidate["finishReason"].as_str() == Some("SAFETY") {
+        return Err(AppError::Api {
+            service: service.to_string(),
+            message: "Response blocked by safety settings".to_string(),
+        });
+    }
+
+    let Some(parts) = candidate["content"]["parts"].as_array() else {
+        return Ok(None);
+    };
+    Ok(Some(
+        parts
+            .iter()
+            .filter_map(|part| part["text"].as_str())
+            .collect::<String>(),
+    ))
+}
+
+/// `service` is the provider's display name, used for the result and errors.
+pub async fn translate(
+    service: &str,
+    text: &str,
+    _source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let gemini = read_config(config)?;
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let response = build_request(&client, &gemini, text, target_lang, false)
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status(service, status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    let translated_text = extract_text(service, &json)?
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| AppError::Translation(format!("No translation in {} response", service)))?;
+
+    Ok(TranslationResult {
+        name: service.to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+pub async fn translate_stream<F>(
+    service: &str,
+    text: &str,
+    _source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+    mut on_delta: F,
+) -> Result<String>
+where
+    F: FnMut(&str),
+{
+    let gemini = read_config(config)?;
+
+    let client = super::http::client(config, super::stream_timeout(config))?;
+
+    let response = build_request(&client, &gemini, text, target_lang, true)
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status(service, status, error_text));
+    }
+
+    let mut full_text = String::new();
+    let mut buffer = String::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AppError::Network(format!("Stream error: {}", e)))?;
+        let chunk_str = std::str::from_utf8(&chunk)
+            .map_err(|e| AppError::Unknown(format!("Invalid UTF-8 in stream: {}", e)))?;
+        buffer.push_str(chunk_str);
+
+        while let Some(pos) = buffer.find('\n') {
+            let line = buffer[..pos].trim().to_string();
+            buffer = buffer[pos + 1..].to_string();
+
+            if line.is_empty() || !line.starts_with("data:") {
+                continue;
+            }
+
+            let data = line.trim_start_matches("data:").trim();
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                if let Some(delta) = extract_text(service, &json)? {
+                    if !delta.is_empty() {
+                        on_delta(&delta);
+                        full_text.push_str(&delta);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(full_text)
+}
+
+pub struct GeminiNative;
+
+#[async_trait]
+impl TranslationService for GeminiNative {
+    fn name(&self) -> &str {
+        "Gemini Native"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["gemini-native", "gemininative"]
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["apiKey"]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "Gemini API key not configured"
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(self.name(), text, source_lang, target_lang, config).await
+    }
+
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        translate_stream(self.name(), text, source_lang, target_lang, config, on_delta).await
+    }
+}
@@ -0,0 +1,51 @@
+/// 服务配置中的 `temperature`、`topP`、`maxTokens`，未配置的参数不发送，由模型使用默认值
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct GenerationParams {
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    pub max_tokens: Option<u64>,
+}
+
+/// Claude 要求必须提供 `max_tokens`
+const CLAUDE_DEFAULT_MAX_TOKENS: u64 = 4096;
+
+impl GenerationParams {
+    pub fn from_config(config: Option<&serde_json::Value>) -> Self {
+        let get = |key: &str| config.and_then(|c| c.get(key));
+        GenerationParams {
+            temperature: get("temperature").and_then(|v| v.as_f64()),
+            top_p: get("topP").and_then(|v| v.as_f64()),
+            max_tokens: get("maxTokens").and_then(|v| v.as_u64()).filter(|n| *n > 0),
+        }
+    }
+
+    fn insert(body: &mut serde_json::Value, key: &str, value: Option<serde_json::Value>) {
+        if let (Some(obj), Some(value)) = (body.as_object_mut(), value) {
+            obj.insert(key.to_string(), value);
+        }
+    }
+
+    /// OpenAI 兼容接口
+    pub fn apply_openai(&self, body: &mut serde_json::Value) {
+        Self::insert(body, "temperature", self.temperature.map(Into::into));
+        Self::insert(body, "top_p", self.top_p.map(Into::into));
+        Self::insert(body, "max_tokens", self.max_tokens.map(Into::into));
+    }
+
+    pub fn apply_claude(&self, body: &mut serde_json::Value) {
+        Self::insert(body, "temperature", self.temperature.map(Into::into));
+        Self::insert(body, "top_p", self.top_p.map(Into::into));
+        Self::insert(
+            body,
+            "max_tokens",
+            Some(self.max_tokens.unwrap_or(CLAUDE_DEFAULT_MAX_TOKENS).into()),
+        );
+    }
+
+    /// 文心的输出长度参数名为 `max_output_tokens`
+    pub fn apply_ernie(&self, body: &mut serde_json::Value) {
+        Self::insert(body, "temperature", self.temperature.map(Into::into));
+        Self::insert(body, "top_p", self.top_p.map(Into::into));
+        Self::insert(body, "max_output_tokens", self.max_tokens.map(Into::into));
+    }
+}
@@ -1,11 +1,20 @@
 use crate::models::TranslationResult;
+use crate::error::AppError;
+use crate::translate::TranslationService;
+use crate::translate::languages::{self, LanguageSupport};
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+fn api_error(message: impl Into<String>) -> AppError {
+    AppError::Api { service: "Google".to_string(), message: message.into() }
+}
 
 pub async fn translate(
     text: &str,
     _source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
-) -> Result<TranslationResult, String> {
+) -> crate::error::Result<TranslationResult> {
      let api_key = if let Some(c) = config {
         c.get("apiKey").and_then(|v| v.as_str()).map(|s| s.to_string())
     } else {
@@ -28,46 +37,73 @@ pub async fn translate(
 
     match api_key {
         Some(key) => {
-            let client = reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(10))
-                .build()
-                .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            let client = super::http::client(config, super::request_timeout(config))?;
                 
-            let url = format!(
-                "https://translation.googleapis.com/language/translate/v2?key={}",
-                key
-            );
+            let url = config
+                .and_then(|c| c.get("apiUrl"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("https://translation.googleapis.com/language/translate/v2");
 
             let response = client
-                .post(&url)
+                .post(url)
+                .query(&[("key", key.as_str())])
                 .json(&serde_json::json!({
                     "q": text,
                     "target": target_lang,
                     "format": "text"
                 }))
-                .send()
+                .send_retrying()
                 .await
-                .map_err(|e| format!("Google Translate API request failed: {}", e))?;
+                .map_err(|e| api_error(format!("Google Translate API request failed: {}", e)))?;
 
             if !response.status().is_success() {
+                let status = response.status();
                 let error_text = response.text().await.unwrap_or_default();
-                return Err(format!("Google Translate API error: {}", error_text));
+                return Err(AppError::from_status("Google", status, format!("Google Translate API error: {}", error_text)));
             }
 
             let json: serde_json::Value = response.json().await
-                .map_err(|e| format!("Failed to parse Google response: {}", e))?;
+                .map_err(|e| api_error(format!("Failed to parse Google response: {}", e)))?;
 
             let translated_text = json["data"]["translations"][0]["translatedText"]
                 .as_str()
                 .map(|s| s.to_string())
-                .ok_or("No translation in response")?;
+                .ok_or_else(|| api_error("No translation in response"))?;
 
             Ok(TranslationResult {
                 name: "Google".to_string(),
                 text: translated_text,
                 error: None,
+                alternatives: Vec::new(),
             })
         }
-        None => Err("Google Translate API key not configured. Set GOOGLE_TRANSLATE_API_KEY in .env file.".to_string())
+        None => Err(api_error("Google Translate API key not configured. Set GOOGLE_TRANSLATE_API_KEY in .env file."))
+    }
+}
+
+pub struct Google;
+
+#[async_trait]
+impl TranslationService for Google {
+    fn name(&self) -> &str {
+        "Google"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["google"]
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(languages::BROAD)
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> crate::error::Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
     }
 }
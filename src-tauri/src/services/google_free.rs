@@ -1,20 +1,27 @@
 use crate::models::TranslationResult;
-use reqwest::Client;
 use serde_json::Value;
+use crate::error::AppError;
+use crate::translate::TranslationService;
+use crate::translate::languages::{self, LanguageSupport};
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+fn api_error(message: impl Into<String>) -> AppError {
+    AppError::Api { service: "GoogleFree".to_string(), message: message.into() }
+}
 
 pub async fn translate(
     text: &str,
     source_lang: &str,
     target_lang: &str,
-    _config: Option<&serde_json::Value>,
-) -> Result<TranslationResult, String> {
-    let client = Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    config: Option<&serde_json::Value>,
+) -> crate::error::Result<TranslationResult> {
+    let client = super::http::client_with_user_agent(config, super::request_timeout(config), "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")?;
 
-    let url = "https://translate.googleapis.com/translate_a/single";
+    let url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("https://translate.googleapis.com/translate_a/single");
     
     let res = client
         .get(url)
@@ -26,11 +33,12 @@ pub async fn translate(
             ("sl", source_lang),
             ("tl", target_lang),
             ("dt", "t"),
+            ("dt", "at"),
             ("q", text),
         ])
-        .send()
+        .send_retrying()
         .await
-        .map_err(|e| format!("Google Free API request failed: {}", e))?;
+        .map_err(|e| api_error(format!("Google Free API request failed: {}", e)))?;
 
     let status = res.status();
     
@@ -38,12 +46,12 @@ pub async fn translate(
     if !status.is_success() {
         // 错误情况：获取错误体
         let error_body = res.text().await.unwrap_or_default();
-        return Err(format!("Google Free API returned error: {} - {}", status, error_body));
+        return Err(AppError::from_status("GoogleFree", status, format!("Google Free API returned error: {}", error_body)));
     }
 
     // 成功情况：解析JSON
     let json: Value = res.json().await
-        .map_err(|e| format!("Failed to parse Google Free response: {}", e))?;
+        .map_err(|e| api_error(format!("Failed to parse Google Free response: {}", e)))?;
 
     // Response structure: [[["translated", "original", ...], ...], ...]
     if let Some(sentences) = json.as_array()
@@ -61,15 +69,69 @@ pub async fn translate(
         }
         
         if translated_text.is_empty() {
-             return Err("No translation found in response".to_string());
+             return Err(api_error("No translation found in response"));
         }
 
+        let alternatives = alternatives(&json, &translated_text);
         Ok(TranslationResult {
             name: "GoogleFree".to_string(),
             text: translated_text,
             error: None,
+            alternatives,
         })
     } else {
-        Err("Invalid response format from Google Free API".to_string())
+        Err(api_error("Invalid response format from Google Free API"))
+    }
+}
+
+/// `dt=at` 返回的候选译文位于下标 5：每个分段为 `[原文, _, [[译文, ...], ...], ...]`。
+/// 多分段时候选无法拼成整句，只在单分段时返回
+fn alternatives(json: &Value, translated_text: &str) -> Vec<String> {
+    let Some(segments) = json.get(5).and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+    if segments.len() != 1 {
+        return Vec::new();
+    }
+    let mut alternatives: Vec<String> = Vec::new();
+    for candidate in segments[0].get(2).and_then(|v| v.as_array()).into_iter().flatten() {
+        if let Some(text) = candidate.get(0).and_then(|v| v.as_str()) {
+            if text != translated_text.trim() && !alternatives.iter().any(|alt| alt == text) {
+                alternatives.push(text.to_string());
+            }
+        }
+    }
+    alternatives
+}
+
+pub struct GoogleFree;
+
+#[async_trait]
+impl TranslationService for GoogleFree {
+    fn name(&self) -> &str {
+        "GoogleFree"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["googlefree", "google native"]
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(languages::BROAD)
+    }
+
+    /// The text travels in the query string of a GET request.
+    fn max_text_chars(&self) -> usize {
+        1800
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> crate::error::Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
     }
-}
\ No newline at end of file
+}
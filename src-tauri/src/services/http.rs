@@ -0,0 +1,144 @@
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 429 自动重试允许等待的最长时间，超出时直接返回限流错误
+const RETRY_BUDGET: Duration = Duration::from_secs(5);
+/// 响应未带 Retry-After 时的重试间隔
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+/// 解析 Retry-After 时的上限，避免异常大的值溢出
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 共享客户端的区分条件：超时、代理设置和 User-Agent
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ClientKey {
+    timeout: Duration,
+    proxy: Option<String>,
+    user_agent: Option<&'static str>,
+}
+
+/// 按配置复用的 reqwest 客户端，保留连接池和 TLS 会话
+static CLIENTS: Lazy<Mutex<HashMap<ClientKey, reqwest::Client>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn shared(config: Option<&serde_json::Value>, timeout: Duration, user_agent: Option<&'static str>) -> Result<reqwest::Client> {
+    let key = ClientKey {
+        timeout,
+        proxy: config.and_then(|c| c.get("proxy")).map(|p| p.to_string()),
+        user_agent,
+    };
+    if let Some(client) = CLIENTS.lock().unwrap().get(&key) {
+        return Ok(client.clone());
+    }
+
+    let mut builder = super::proxy::apply(reqwest::Client::builder(), config)?.timeout(timeout);
+    if let Some(user_agent) = user_agent {
+        builder = builder.user_agent(user_agent);
+    }
+    let client = builder
+        .build()
+        .map_err(|e| AppError::Network(format!("Failed to create HTTP client: {}", e)))?;
+    CLIENTS.lock().unwrap().insert(key, client.clone());
+    Ok(client)
+}
+
+/// 取得与服务配置（代理）和超时匹配的共享客户端
+pub(crate) fn client(config: Option<&serde_json::Value>, timeout: Duration) -> Result<reqwest::Client> {
+    shared(config, timeout, None)
+}
+
+/// 同 `client`，但附带固定的 User-Agent
+pub(crate) fn client_with_user_agent(
+    config: Option<&serde_json::Value>,
+    timeout: Duration,
+    user_agent: &'static str,
+) -> Result<reqwest::Client> {
+    shared(config, timeout, Some(user_agent))
+}
+
+/// 从 429 响应头读取重试间隔：支持 `retry-after-ms`、秒数和 HTTP 日期；超出预算时返回 None
+pub(crate) fn retry_delay(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim);
+    let delay = if let Some(ms) = header("retry-after-ms").and_then(|v| v.parse::<f64>().ok()) {
+        // NaN 无法转换，视为无法重试；无穷大和超大值截断到上限
+        Duration::try_from_secs_f64((ms / 1000.0).clamp(0.0, MAX_RETRY_DELAY.as_secs_f64())).ok()?
+    } else if let Some(value) = header("retry-after") {
+        match value.parse::<u64>() {
+            Ok(secs) => Duration::from_secs(secs).min(MAX_RETRY_DELAY),
+            Err(_) => {
+                let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+                (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().unwrap_or_default()
+            }
+        }
+    } else {
+        DEFAULT_RETRY_DELAY
+    };
+    (delay <= RETRY_BUDGET).then_some(delay)
+}
+
+/// 服务请求占用的并发名额；在 `holding` 内发出的请求等待 429 重试时暂时归还
+pub(crate) struct Permit {
+    semaphore: Arc<Semaphore>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Permit {
+    pub(crate) async fn acquire(semaphore: Arc<Semaphore>) -> Self {
+        // 信号量不会被关闭，acquire 不会失败
+        let permit = semaphore.clone().acquire_owned().await.ok();
+        Permit { semaphore, permit }
+    }
+}
+
+tokio::task_local! {
+    static PERMIT: RefCell<Permit>;
+}
+
+/// 在持有 `permit` 的情况下运行 `work`
+pub(crate) async fn holding<F: Future>(permit: Permit, work: F) -> F::Output {
+    PERMIT.scope(RefCell::new(permit), work).await
+}
+
+/// 等待期间归还当前任务的并发名额，结束后重新排队取回
+async fn sleep_releasing_permit(delay: Duration) {
+    let semaphore = PERMIT
+        .try_with(|permit| {
+            let mut permit = permit.borrow_mut();
+            permit.permit.take().map(|_| permit.semaphore.clone())
+        })
+        .ok()
+        .flatten();
+    tokio::time::sleep(delay).await;
+    if let Some(semaphore) = semaphore {
+        let reacquired = semaphore.acquire_owned().await.ok();
+        let _ = PERMIT.try_with(|permit| permit.borrow_mut().permit = reacquired);
+    }
+}
+
+/// 发送请求；遇到 429 时按 Retry-After 等待后重发一次，第二次的响应原样返回由调用方处理
+pub(crate) trait SendRetrying {
+    fn send_retrying(self) -> impl Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl SendRetrying for reqwest::RequestBuilder {
+    async fn send_retrying(self) -> reqwest::Result<reqwest::Response> {
+        // 流式请求体无法复制，只能放弃重试
+        let retry = self.try_clone();
+        let response = self.send().await?;
+        if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+        let (Some(retry), Some(delay)) = (retry, retry_delay(response.headers())) else {
+            return Ok(response);
+        };
+        println!("{} rate limited, retrying in {}ms", response.url().host_str().unwrap_or_default(), delay.as_millis());
+        sleep_releasing_permit(delay).await;
+        retry.send().await
+    }
+}
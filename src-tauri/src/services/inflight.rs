@@ -0,0 +1,51 @@
+//! Coalescing of identical in-flight requests. When the hotkey fires twice
+//! with the same text and services, the second request waits on the first
+//! one's upstream call instead of issuing (and paying for) its own; requests
+//! are matched by their cache key.
+
+use crate::error::ErrorDetail;
+use crate::models::TranslationResult;
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// 单个服务一次上游请求的结果
+pub(crate) type Outcome = std::result::Result<TranslationResult, ErrorDetail>;
+
+type SharedOutcome = Shared<BoxFuture<'static, Outcome>>;
+
+/// 进行中的上游请求，以缓存键区分；编号用于避免误删后来发起的同键请求
+static INFLIGHT: Lazy<Mutex<HashMap<String, (u64, SharedOutcome)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// 合并相同的进行中请求。已有同键请求时直接等待其结果，`work` 不会被执行；
+/// 否则执行 `work` 并把结果分发给期间加入的所有调用方。返回值的第二项表示是否复用了他人的请求
+pub(crate) async fn coalesce<F>(key: String, work: F) -> (Outcome, bool)
+where
+    F: Future<Output = Outcome> + Send + 'static,
+{
+    let (id, shared, joined) = {
+        let mut inflight = INFLIGHT.lock().unwrap();
+        match inflight.get(&key) {
+            Some((id, shared)) => (*id, shared.clone(), true),
+            None => {
+                let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+                let shared = work.boxed().shared();
+                inflight.insert(key.clone(), (id, shared.clone()));
+                (id, shared, false)
+            }
+        }
+    };
+
+    let outcome = shared.await;
+
+    // 首个完成的调用方负责移除，之后的同键请求重新发往上游（或命中缓存）
+    let mut inflight = INFLIGHT.lock().unwrap();
+    if inflight.get(&key).is_some_and(|(current, _)| *current == id) {
+        inflight.remove(&key);
+    }
+    (outcome, joined)
+}
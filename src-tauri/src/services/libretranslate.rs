@@ -0,0 +1,101 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use crate::translate::TranslationService;
+use crate::translate::languages::LanguageSupport;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let base_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| AppError::Config("LibreTranslate instance URL not configured".to_string()))?;
+
+    let api_key = config
+        .and_then(|c| c.get("apiKey"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty());
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let mut body = serde_json::json!({
+        "q": text,
+        "source": map_lang(source_lang),
+        "target": map_lang(target_lang),
+        "format": "text"
+    });
+    if let Some(key) = api_key {
+        body["api_key"] = serde_json::json!(key);
+    }
+
+    let response = client
+        .post(format!("{}/translate", base_url.trim_end_matches('/')))
+        .json(&body)
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("LibreTranslate", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    let translated_text = json["translatedText"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Translation("No translation in LibreTranslate response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "LibreTranslate".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+fn map_lang(lang: &str) -> &str {
+    match lang {
+        "zh-CN" | "zh-Hans" => "zh",
+        "zh-TW" | "zh-Hant" => "zt",
+        other => other,
+    }
+}
+
+pub struct LibreTranslate;
+
+#[async_trait]
+impl TranslationService for LibreTranslate {
+    fn name(&self) -> &str {
+        "LibreTranslate"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["libretranslate"]
+    }
+
+    /// Languages of the default LibreTranslate (Argos) models; self-hosted instances may install fewer.
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(&[
+            "ar", "cs", "da", "de", "el", "en", "es", "fa", "fi", "fr", "he", "hi", "hu", "id", "it", "ja", "ko",
+            "nl", "pl", "pt", "ru", "sk", "sv", "tr", "uk", "zh-CN", "zh-TW",
+        ])
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
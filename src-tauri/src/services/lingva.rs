@@ -0,0 +1,135 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use crate::translate::TranslationService;
+use crate::translate::languages::{self, LanguageSupport};
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+/// Public Lingva instances tried in order when no instance URL is configured.
+const PUBLIC_INSTANCES: &[&str] = &[
+    "https://lingva.ml",
+    "https://lingva.lunar.icu",
+    "https://translate.plausibility.cloud",
+];
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let base_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty());
+
+    let api_key = config
+        .and_then(|c| c.get("apiKey"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty());
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    // A configured instance is used exclusively; otherwise fall through the public list.
+    let instances: Vec<&str> = match base_url {
+        Some(url) => vec![url],
+        None => PUBLIC_INSTANCES.to_vec(),
+    };
+
+    let mut last_error = AppError::ServiceUnavailable("No Lingva instance available".to_string());
+    for instance in instances {
+        match request_translation(&client, instance, api_key, text, source_lang, target_lang).await {
+            Ok(translated_text) => {
+                return Ok(TranslationResult {
+                    name: "Lingva".to_string(),
+                    text: translated_text,
+                    error: None,
+                    alternatives: Vec::new(),
+                });
+            }
+            Err(e) => {
+                println!("Lingva instance {} failed: {}", instance, e);
+                last_error = e;
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
+async fn request_translation(
+    client: &reqwest::Client,
+    base_url: &str,
+    api_key: Option<&str>,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+) -> Result<String> {
+    // Lingva takes the text as a path segment: /api/v1/{source}/{target}/{query}
+    let mut url = url::Url::parse(base_url)
+        .map_err(|e| AppError::Config(format!("Invalid Lingva instance URL: {}", e)))?;
+    url.path_segments_mut()
+        .map_err(|_| AppError::Config("Invalid Lingva instance URL".to_string()))?
+        .pop_if_empty()
+        .extend(["api", "v1", map_lang(source_lang), map_lang(target_lang), text]);
+
+    let mut request = client.get(url);
+    if let Some(key) = api_key {
+        request = request.header("Authorization", format!("Bearer {}", key));
+    }
+
+    let response = request.send_retrying().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Lingva", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    json["translation"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Translation("No translation in Lingva response".to_string()))
+}
+
+fn map_lang(lang: &str) -> &str {
+    match lang {
+        "zh-CN" | "zh-Hans" => "zh",
+        "zh-TW" | "zh-Hant" => "zh_HANT",
+        other => other,
+    }
+}
+
+pub struct Lingva;
+
+#[async_trait]
+impl TranslationService for Lingva {
+    fn name(&self) -> &str {
+        "Lingva"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["lingva"]
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(languages::BROAD)
+    }
+
+    /// The text travels in the URL path of a GET request.
+    fn max_text_chars(&self) -> usize {
+        1000
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
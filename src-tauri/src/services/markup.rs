@@ -0,0 +1,481 @@
+//! Structure-preserving translation for Markdown and HTML input. The text is
+//! split into segments that are kept verbatim (code blocks, list markers,
+//! block tags) and text runs that are translated. Inside a run, inline
+//! markup such as `code`, link targets and inline tags is replaced with
+//! `⟪n⟫` markers and restored afterwards, so sentences stay whole.
+
+use super::chunk;
+use super::postprocess::PostProcess;
+use crate::error::Result;
+use crate::models::{TextFormat, TranslationResult};
+use crate::translate::TranslationService;
+use futures_util::future::try_join_all;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// 原样保留的结构
+    Keep(String),
+    /// 需要翻译的文本，`inline` 为被标记替换的行内片段
+    Text { text: String, inline: Vec<String> },
+}
+
+/// HTML 中整段保留、内容不翻译的元素
+const RAW_TAGS: &[&str] = &["script", "style", "pre", "textarea"];
+/// 行内元素，替换为标记后与周围文本一起翻译
+const INLINE_TAGS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "cite", "em", "i", "kbd", "mark", "q", "s", "small", "span", "strong", "sub", "sup",
+    "time", "u", "var",
+];
+
+fn marker(index: usize) -> String {
+    format!("⟪{}⟫", index)
+}
+
+struct Builder {
+    segments: Vec<Segment>,
+    text: String,
+    inline: Vec<String>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder { segments: Vec::new(), text: String::new(), inline: Vec::new() }
+    }
+
+    fn keep(&mut self, piece: &str) {
+        self.flush();
+        if let Some(Segment::Keep(last)) = self.segments.last_mut() {
+            last.push_str(piece);
+        } else if !piece.is_empty() {
+            self.segments.push(Segment::Keep(piece.to_string()));
+        }
+    }
+
+    fn mask(&mut self, piece: &str) {
+        self.text.push_str(&marker(self.inline.len()));
+        self.inline.push(piece.to_string());
+    }
+
+    fn push(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    /// 结束当前文本段；没有可翻译字符的段落（空白、纯标记）改为保留
+    fn flush(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        let text = std::mem::take(&mut self.text);
+        let inline = std::mem::take(&mut self.inline);
+        let mut stripped = text.clone();
+        for index in 0..inline.len() {
+            stripped = stripped.replace(&marker(index), "");
+        }
+        if stripped.chars().any(|c| c.is_alphanumeric()) {
+            self.segments.push(Segment::Text { text, inline });
+        } else {
+            let restored = restore(&text, &inline);
+            self.keep(&restored);
+        }
+    }
+
+    fn finish(mut self) -> Vec<Segment> {
+        self.flush();
+        self.segments
+    }
+}
+
+/// 还原行内标记；译文中丢失的标记对应片段补在末尾，避免内容丢失
+fn restore(text: &str, inline: &[String]) -> String {
+    let mut text = text.to_string();
+    let mut missing = Vec::new();
+    for (index, piece) in inline.iter().enumerate() {
+        let marker = marker(index);
+        let spaced = format!("⟪ {} ⟫", index);
+        if text.contains(&marker) {
+            text = text.replace(&marker, piece);
+        } else if text.contains(&spaced) {
+            text = text.replace(&spaced, piece);
+        } else {
+            missing.push(piece.as_str());
+        }
+    }
+    if !missing.is_empty() {
+        text.push(' ');
+        text.push_str(&missing.join(" "));
+    }
+    text
+}
+
+pub fn segment(text: &str, format: TextFormat) -> Vec<Segment> {
+    match format {
+        TextFormat::Plain => vec![Segment::Text { text: text.to_string(), inline: Vec::new() }],
+        TextFormat::Markdown => segment_markdown(text),
+        TextFormat::Html => segment_html(text),
+    }
+}
+
+pub fn reassemble(segments: &[Segment], translations: &[String]) -> String {
+    let mut translations = translations.iter();
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Keep(text) => text.clone(),
+            Segment::Text { text, inline } => {
+                let translated = translations.next().map(String::as_str).unwrap_or(text);
+                // 保留原文段首尾的空白
+                let leading = &text[..text.len() - text.trim_start().len()];
+                let trailing = &text[text.trim_end().len()..];
+                format!("{}{}{}", leading, restore(translated.trim(), inline), trailing)
+            }
+        })
+        .collect()
+}
+
+// ---- Markdown ----
+
+fn fence_marker(line: &str) -> Option<&'static str> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    if trimmed.starts_with("```") {
+        Some("```")
+    } else if trimmed.starts_with("~~~") {
+        Some("~~~")
+    } else {
+        None
+    }
+}
+
+fn is_rule(line: &str) -> bool {
+    let trimmed = line.trim();
+    let symbols: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    symbols.len() >= 3
+        && ["-", "*", "_", "="].iter().any(|s| symbols.chars().all(|c| c.to_string() == *s))
+}
+
+/// 行首的缩进、引用、标题和列表标记
+fn line_prefix_len(line: &str) -> usize {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    loop {
+        while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t') {
+            i += 1;
+        }
+        if i < bytes.len() && bytes[i] == b'>' {
+            i += 1;
+            continue;
+        }
+        break;
+    }
+    let rest = &line[i..];
+    let hashes = rest.bytes().take_while(|b| *b == b'#').count();
+    if (1..=6).contains(&hashes) && rest[hashes..].starts_with(' ') {
+        return i + hashes + 1;
+    }
+    let mut list_len = 0;
+    if rest.starts_with("- ") || rest.starts_with("* ") || rest.starts_with("+ ") {
+        list_len = 2;
+    } else {
+        let digits = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        if digits > 0 && (rest[digits..].starts_with(". ") || rest[digits..].starts_with(") ")) {
+            list_len = digits + 2;
+        }
+    }
+    if list_len > 0 {
+        let after = &rest[list_len..];
+        if after.starts_with("[ ] ") || after.starts_with("[x] ") || after.starts_with("[X] ") {
+            list_len += 4;
+        }
+    }
+    i + list_len
+}
+
+fn find_from(chars: &[char], start: usize, pattern: &[char]) -> Option<usize> {
+    (start..=chars.len().checked_sub(pattern.len())?).find(|&i| chars[i..i + pattern.len()] == *pattern)
+}
+
+/// 行内内容：代码、图片、链接目标、HTML 标签和网址替换为标记
+fn markdown_inline(builder: &mut Builder, text: &str) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..chars.len().min(i + 8)].iter().collect();
+        if c == '`' {
+            let run = chars[i..].iter().take_while(|c| **c == '`').count();
+            let fence = vec!['`'; run];
+            if let Some(end) = find_from(&chars, i + run, &fence) {
+                builder.mask(&chars[i..end + run].iter().collect::<String>());
+                i = end + run;
+                continue;
+            }
+        } else if c == '!' && chars.get(i + 1) == Some(&'[') {
+            if let Some(end) = link_end(&chars, i + 1) {
+                builder.mask(&chars[i..end].iter().collect::<String>());
+                i = end;
+                continue;
+            }
+        } else if c == '[' {
+            if let Some(end) = link_end(&chars, i) {
+                let close = chars[i..end].iter().position(|c| *c == ']').unwrap() + i;
+                builder.mask("[");
+                markdown_inline(builder, &chars[i + 1..close].iter().collect::<String>());
+                builder.mask(&chars[close..end].iter().collect::<String>());
+                i = end;
+                continue;
+            }
+        } else if c == '<' {
+            if let Some(len) = tag_len(&chars[i..]) {
+                builder.mask(&chars[i..i + len].iter().collect::<String>());
+                i += len;
+                continue;
+            }
+        } else if rest.starts_with("http://") || rest.starts_with("https://") {
+            let len = chars[i..].iter().take_while(|c| !c.is_whitespace() && **c != ')').count();
+            builder.mask(&chars[i..i + len].iter().collect::<String>());
+            i += len;
+            continue;
+        }
+        builder.push(c);
+        i += 1;
+    }
+}
+
+/// `[text](url)` 或 `[text][ref]` 的结束位置（不含）
+fn link_end(chars: &[char], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut close = None;
+    for (offset, c) in chars[open..].iter().enumerate() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + offset);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let close = close?;
+    let closer = match chars.get(close + 1)? {
+        '(' => ')',
+        '[' => ']',
+        _ => return None,
+    };
+    let end = chars[close + 2..].iter().position(|c| *c == closer)? + close + 2;
+    Some(end + 1)
+}
+
+fn segment_markdown(text: &str) -> Vec<Segment> {
+    let mut builder = Builder::new();
+    let mut fence: Option<&str> = None;
+    for line in text.split_inclusive('\n') {
+        let (content, newline) = match line.strip_suffix('\n') {
+            Some(content) => (content, "\n"),
+            None => (line, ""),
+        };
+        if let Some(open) = fence {
+            builder.keep(line);
+            if content.trim_start().starts_with(open) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(open) = fence_marker(content) {
+            fence = Some(open);
+            builder.keep(line);
+            continue;
+        }
+        if content.trim().is_empty() || is_rule(content) {
+            builder.keep(line);
+            continue;
+        }
+        if content.trim_start().starts_with('|') {
+            // 表格行按单元格翻译，分隔行整体保留
+            if content.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')) {
+                builder.keep(line);
+                continue;
+            }
+            for (index, cell) in content.split('|').enumerate() {
+                if index > 0 {
+                    builder.keep("|");
+                }
+                markdown_inline(&mut builder, cell);
+                builder.flush();
+            }
+            builder.keep(newline);
+            continue;
+        }
+        let prefix = line_prefix_len(content);
+        builder.keep(&content[..prefix]);
+        markdown_inline(&mut builder, &content[prefix..]);
+        builder.keep(newline);
+    }
+    builder.finish()
+}
+
+// ---- HTML ----
+
+/// `<...>` 标签（含注释、声明）的长度；不像标签时返回 None
+fn tag_len(chars: &[char]) -> Option<usize> {
+    let next = *chars.get(1)?;
+    if !(next.is_ascii_alphabetic() || next == '/' || next == '!' || next == '?') {
+        return None;
+    }
+    if chars[1..].starts_with(&['!', '-', '-']) {
+        return find_from(chars, 4, &['-', '-', '>']).map(|end| end + 3);
+    }
+    let mut quote = None;
+    for (i, c) in chars.iter().enumerate().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if *c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(*c),
+            (None, '>') => return Some(i + 1),
+            (None, '<') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('<')
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn segment_html(text: &str) -> Vec<Segment> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text.to_lowercase().chars().collect();
+    let mut builder = Builder::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '<' {
+            builder.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let Some(len) = tag_len(&chars[i..]) else {
+            builder.push('<');
+            i += 1;
+            continue;
+        };
+        let tag: String = chars[i..i + len].iter().collect();
+        let name = tag_name(&tag);
+        let closing = tag.starts_with("</");
+
+        // 小写化改变字符数时无法对齐位置，退化为逐个标签处理
+        let raw = !closing && (RAW_TAGS.contains(&name.as_str()) || name == "code");
+        if raw && lower.len() == chars.len() {
+            // 原样保留整个元素（含内容）
+            let close: Vec<char> = format!("</{}", name).chars().collect();
+            let end = find_from(&lower, i + len, &close)
+                .and_then(|start| tag_len(&chars[start..]).map(|l| start + l))
+                .unwrap_or(chars.len());
+            let element: String = chars[i..end].iter().collect();
+            if name == "code" {
+                builder.mask(&element);
+            } else {
+                builder.keep(&element);
+            }
+            i = end;
+            continue;
+        }
+
+        if INLINE_TAGS.contains(&name.as_str()) {
+            builder.mask(&tag);
+        } else {
+            builder.keep(&tag);
+        }
+        i += len;
+    }
+    builder.finish()
+}
+
+// ---- translation ----
+
+async fn translate_text(
+    provider: &dyn TranslationService,
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+    postprocess: Option<&PostProcess>,
+) -> Result<TranslationResult> {
+    let (masked, placeholders) = match postprocess {
+        Some(postprocess) => postprocess.protect(text),
+        None => (text.to_string(), Vec::new()),
+    };
+    let mut result = chunk::translate(provider, &masked, source_lang, target_lang, config).await?;
+    if let Some(postprocess) = postprocess {
+        result.text = postprocess.apply(&result.text, text, &placeholders);
+        for alternative in &mut result.alternatives {
+            *alternative = postprocess.apply(alternative, text, &placeholders);
+        }
+    }
+    Ok(result)
+}
+
+/// 按格式翻译：纯文本整体翻译，标记语言逐段翻译后重组
+pub async fn translate(
+    provider: &dyn TranslationService,
+    text: &str,
+    format: TextFormat,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+    postprocess: Option<&PostProcess>,
+) -> Result<TranslationResult> {
+    if format == TextFormat::Plain {
+        return translate_text(provider, text, source_lang, target_lang, config, postprocess).await;
+    }
+
+    let segments = segment(text, format);
+    // 相同文本段只翻译一次
+    let mut unique: Vec<&str> = Vec::new();
+    let mut index_of: HashMap<&str, usize> = HashMap::new();
+    for segment in &segments {
+        if let Segment::Text { text, .. } = segment {
+            let key = text.trim();
+            if !index_of.contains_key(key) {
+                index_of.insert(key, unique.len());
+                unique.push(key);
+            }
+        }
+    }
+
+    let mut translated = Vec::with_capacity(unique.len());
+    for batch in unique.chunks(chunk::concurrency(config)) {
+        let results = try_join_all(
+            batch
+                .iter()
+                .map(|text| translate_text(provider, text, source_lang, target_lang, config, postprocess)),
+        )
+        .await?;
+        translated.extend(results.into_iter().map(|result| result.text));
+    }
+
+    let translations: Vec<String> = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Text { text, .. } => Some(translated[index_of[text.trim()]].clone()),
+            Segment::Keep(_) => None,
+        })
+        .collect();
+
+    Ok(TranslationResult {
+        name: provider.name().to_string(),
+        text: reassemble(&segments, &translations),
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
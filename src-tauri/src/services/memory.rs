@@ -0,0 +1,210 @@
+//! Translation memory: translations the user confirmed, matched against new
+//! requests by normalized Levenshtein similarity. Configured per request via
+//! `config.memory`: `{ "enabled": true, "threshold": 0.85, "limit": 3 }`;
+//! entries are persisted to the app data dir.
+
+use crate::models::MemoryMatch;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const CAPACITY: usize = 5000;
+const DEFAULT_THRESHOLD: f64 = 0.85;
+const DEFAULT_LIMIT: usize = 3;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryOptions {
+    pub enabled: bool,
+    pub threshold: f64,
+    pub limit: usize,
+}
+
+impl MemoryOptions {
+    pub fn from_config(config: Option<&HashMap<String, serde_json::Value>>) -> Self {
+        let memory = config.and_then(|c| c.get("memory"));
+        let get = |key: &str| memory.and_then(|m| m.get(key));
+        MemoryOptions {
+            enabled: get("enabled").and_then(|v| v.as_bool()).unwrap_or(true),
+            threshold: get("threshold")
+                .and_then(|v| v.as_f64())
+                .filter(|t| (0.0..=1.0).contains(t))
+                .unwrap_or(DEFAULT_THRESHOLD),
+            limit: get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|n| n as usize)
+                .unwrap_or(DEFAULT_LIMIT),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemoryEntry {
+    source: String,
+    text: String,
+    source_lang: String,
+    target_lang: String,
+    service: Option<String>,
+}
+
+#[derive(Default)]
+struct Memory {
+    entries: Vec<MemoryEntry>,
+    loaded: bool,
+}
+
+static MEMORY: Lazy<Mutex<Memory>> = Lazy::new(|| Mutex::new(Memory::default()));
+
+fn memory_file() -> PathBuf {
+    crate::paths::data_dir().join("translation_memory.json")
+}
+
+fn ensure_loaded(memory: &mut Memory) {
+    if memory.loaded {
+        return;
+    }
+    memory.loaded = true;
+    let Ok(content) = std::fs::read_to_string(memory_file()) else {
+        return;
+    };
+    match serde_json::from_str::<Vec<MemoryEntry>>(&content) {
+        Ok(entries) => memory.entries = entries,
+        Err(e) => println!("Ignoring unreadable translation memory: {}", e),
+    }
+}
+
+fn save(memory: &Memory) {
+    let path = memory_file();
+    let result = path
+        .parent()
+        .map(std::fs::create_dir_all)
+        .unwrap_or(Ok(()))
+        .and_then(|_| {
+            let json = serde_json::to_string(&memory.entries).map_err(std::io::Error::other)?;
+            std::fs::write(&path, json)
+        });
+    if let Err(e) = result {
+        println!("Failed to persist translation memory: {}", e);
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 字符级编辑距离，两行滚动数组
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// 1 - 编辑距离 / 较长文本长度，取值 0..=1
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = normalize(a).chars().collect();
+    let b: Vec<char> = normalize(b).chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+fn lang_matches(stored: &str, requested: &str) -> bool {
+    requested.is_empty() || requested.eq_ignore_ascii_case("auto") || stored.eq_ignore_ascii_case(requested)
+}
+
+/// 记录用户确认的译文；同一原文和语言对只保留最新的一条
+pub fn confirm(source: &str, text: &str, source_lang: &str, target_lang: &str, service: Option<String>) {
+    let source = normalize(source);
+    let text = text.trim().to_string();
+    if source.is_empty() || text.is_empty() {
+        return;
+    }
+    let mut memory = MEMORY.lock().unwrap();
+    ensure_loaded(&mut memory);
+    memory.entries.retain(|entry| {
+        !(entry.source == source && entry.source_lang == source_lang && entry.target_lang == target_lang)
+    });
+    if memory.entries.len() >= CAPACITY {
+        memory.entries.remove(0);
+    }
+    memory.entries.push(MemoryEntry {
+        source,
+        text,
+        source_lang: source_lang.to_string(),
+        target_lang: target_lang.to_string(),
+        service,
+    });
+    save(&memory);
+}
+
+/// 查找相似度不低于阈值的历史译文，按相似度降序
+pub fn lookup(text: &str, source_lang: &str, target_lang: &str, options: MemoryOptions) -> Vec<MemoryMatch> {
+    if !options.enabled || options.limit == 0 {
+        return Vec::new();
+    }
+    let query = normalize(text);
+    let query_len = query.chars().count();
+    if query_len == 0 {
+        return Vec::new();
+    }
+
+    // 只在锁内筛出候选条目，编辑距离在锁外计算，避免阻塞 confirm 等写操作
+    let candidates: Vec<MemoryEntry> = {
+        let mut memory = MEMORY.lock().unwrap();
+        ensure_loaded(&mut memory);
+        memory
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| lang_matches(&entry.source_lang, source_lang) && lang_matches(&entry.target_lang, target_lang))
+            .filter(|entry| {
+                // 长度差已超过允许的编辑距离时无需计算
+                let len = entry.source.chars().count();
+                let longest = len.max(query_len) as f64;
+                (len.abs_diff(query_len) as f64) <= longest * (1.0 - options.threshold)
+            })
+            .cloned()
+            .collect()
+    };
+    let mut matches: Vec<MemoryMatch> = candidates
+        .into_iter()
+        .filter_map(|entry| {
+            let score = similarity(&entry.source, &query);
+            (score >= options.threshold).then_some(MemoryMatch {
+                source: entry.source,
+                text: entry.text,
+                service: entry.service,
+                similarity: score,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    matches.truncate(options.limit);
+    matches
+}
+
+/// 清空翻译记忆并删除磁盘文件，返回清除的条目数
+pub fn clear() -> usize {
+    let mut memory = MEMORY.lock().unwrap();
+    ensure_loaded(&mut memory);
+    let count = memory.entries.len();
+    memory.entries.clear();
+    let path = memory_file();
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(path) {
+            println!("Failed to remove translation memory file: {}", e);
+        }
+    }
+    count
+}
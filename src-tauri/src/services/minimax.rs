@@ -0,0 +1,214 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use futures_util::StreamExt;
+use crate::translate::TranslationService;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+const DEFAULT_API_URL: &str = "https://api.minimax.chat/v1/text/chatcompletion_v2";
+
+struct MinimaxConfig<'a> {
+    api_key: &'a str,
+    group_id: &'a str,
+    api_url: &'a str,
+    model: &'a str,
+}
+
+fn read_config(config: Option<&serde_json::Value>) -> Result<MinimaxConfig<'_>> {
+    let get = |key: &str| {
+        config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+
+    Ok(MinimaxConfig {
+        api_key: get("apiKey").ok_or_else(|| AppError::Config("MiniMax API key not configured".to_string()))?,
+        group_id: get("groupId").ok_or_else(|| AppError::Config("MiniMax group ID not configured".to_string()))?,
+        api_url: get("apiUrl").unwrap_or(DEFAULT_API_URL),
+        model: get("model").unwrap_or("abab6.5s-chat"),
+    })
+}
+
+fn build_request(
+    client: &reqwest::Client,
+    minimax: &MinimaxConfig,
+    text: &str,
+    target_lang: &str,
+    stream: bool,
+) -> reqwest::RequestBuilder {
+    client
+        .post(minimax.api_url)
+        .query(&[("GroupId", minimax.group_id)])
+        .header("Authorization", format!("Bearer {}", minimax.api_key))
+        .json(&serde_json::json!({
+            "model": minimax.model,
+            "messages": [
+                {
+                    "role": "system",
+                    "content": format!("You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.", target_lang)
+                },
+                {
+                    "role": "user",
+                    "content": text
+                }
+            ],
+            "stream": stream
+        }))
+}
+
+/// MiniMax reports business errors with HTTP 200 and a non-zero `base_resp.status_code`.
+fn check_base_resp(json: &serde_json::Value) -> Result<()> {
+    match json["base_resp"]["status_code"].as_i64() {
+        Some(code) if code != 0 => Err(AppError::Api {
+            service: "MiniMax".to_string(),
+            message: format!("{} ({})", json["base_resp"]["status_msg"].as_str().unwrap_or("Unknown error"), code),
+        }),
+        _ => Ok(()),
+    }
+}
+
+pub async fn translate(
+    text: &str,
+    _source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let minimax = read_config(config)?;
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let response = build_request(&client, &minimax, text, target_lang, false)
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("MiniMax", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+    check_base_resp(&json)?;
+
+    let translated_text = json["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| AppError::Translation("No translation in MiniMax response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "MiniMax".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+pub async fn translate_stream<F>(
+    text: &str,
+    _source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+    mut on_delta: F,
+) -> Result<String>
+where
+    F: FnMut(&str),
+{
+    let minimax = read_config(config)?;
+
+    let client = super::http::client(config, super::stream_timeout(config))?;
+
+    let response = build_request(&client, &minimax, text, target_lang, true)
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("MiniMax", status, error_text));
+    }
+
+    let mut full_text = String::new();
+    let mut buffer = String::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AppError::Network(format!("Stream error: {}", e)))?;
+        let chunk_str = std::str::from_utf8(&chunk)
+            .map_err(|e| AppError::Unknown(format!("Invalid UTF-8 in stream: {}", e)))?;
+        buffer.push_str(chunk_str);
+
+        while let Some(pos) = buffer.find('\n') {
+            let line = buffer[..pos].trim().to_string();
+            buffer = buffer[pos + 1..].to_string();
+
+            if line.is_empty() || !line.starts_with("data:") {
+                continue;
+            }
+
+            let data = line.trim_start_matches("data:").trim();
+            if data == "[DONE]" {
+                return Ok(full_text);
+            }
+
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                check_base_resp(&json)?;
+                // The final chunk repeats the whole message; only deltas are accumulated
+                if let Some(delta) = json["choices"][0]["delta"]["content"].as_str() {
+                    if !delta.is_empty() {
+                        on_delta(delta);
+                        full_text.push_str(delta);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(full_text)
+}
+
+pub struct Minimax;
+
+#[async_trait]
+impl TranslationService for Minimax {
+    fn name(&self) -> &str {
+        "MiniMax"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["minimax"]
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["apiKey", "groupId"]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "API key and group ID required"
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        translate_stream(text, source_lang, target_lang, config, on_delta).await
+    }
+}
@@ -5,18 +5,92 @@ pub mod alibaba;
 pub mod google_free;
 pub mod claude;
 pub mod ernie;
-
-use crate::models::{TranslationRequest, TranslationResponse, TranslationResult};
-use crate::error::{AppError, Result};
+pub mod libretranslate;
+pub mod lingva;
+pub mod caiyun;
+pub mod niutrans;
+pub mod spark;
+pub mod volcengine;
+pub mod aws;
+pub mod sigv4;
+pub mod minimax;
+pub mod bedrock;
+pub mod gemini_native;
+pub mod nllb;
+pub mod cache;
+pub mod inflight;
+pub mod chunk;
+pub mod markup;
+pub mod prompt;
+pub mod generation;
+pub mod proxy;
+pub mod postprocess;
+pub mod http;
+pub mod draft;
+pub mod memory;
+pub mod probe;
+
+use crate::models::{MemoryMatch, TextFormat, TranslationMode, TranslationRequest, TranslationResponse, TranslationResult};
+use crate::error::{AppError, ErrorDetail, Result};
 use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+
+/// 未配置 `timeoutMs` 时的请求超时
+const DEFAULT_TIMEOUT_MS: u64 = 15_000;
+/// 流式输出持续时间较长，默认超时单独放宽
+const DEFAULT_STREAM_TIMEOUT_MS: u64 = 60_000;
+
+fn timeout_from_config(config: Option<&serde_json::Value>, default_ms: u64) -> std::time::Duration {
+    let ms = config
+        .and_then(|c| c.get("timeoutMs"))
+        .and_then(|v| v.as_u64())
+        .filter(|ms| *ms > 0)
+        .unwrap_or(default_ms);
+    std::time::Duration::from_millis(ms)
+}
+
+/// 读取服务配置中的 `timeoutMs`，用于构建 reqwest 客户端
+pub(crate) fn request_timeout(config: Option<&serde_json::Value>) -> std::time::Duration {
+    timeout_from_config(config, DEFAULT_TIMEOUT_MS)
+}
+
+pub(crate) fn stream_timeout(config: Option<&serde_json::Value>) -> std::time::Duration {
+    timeout_from_config(config, DEFAULT_STREAM_TIMEOUT_MS)
+}
+
+fn check_config_keys(service_config: Option<&serde_json::Value>, keys: &[&str]) -> bool {
+    keys.iter().all(|key| {
+        service_config
+            .and_then(|config| config.get(*key))
+            .and_then(|value| value.as_str())
+            .map(|value| !value.is_empty())
+            .unwrap_or(false)
+    })
+}
+
+/// 未指定服务时的默认列表；节能模式下已下载离线模型时只使用本地 NLLB，不再发出网络请求
+fn default_services() -> Vec<String> {
+    if crate::background::power::detect().should_conserve() && nllb::default_model_downloaded() {
+        return vec!["NLLB".to_string()];
+    }
+    vec!["OpenAI".to_string(), "DeepL".to_string(), "Alibaba".to_string(), "GoogleFree".to_string()]
+}
 
-fn check_api_key(service_config: Option<&serde_json::Value>) -> bool {
-    service_config
-        .and_then(|config| config.get("apiKey"))
-        .and_then(|key| key.as_str())
-        .map(|key| !key.is_empty())
-        .unwrap_or(false)
+/// 未配置 `maxConcurrency` 时同时进行的服务请求数上限
+const DEFAULT_MAX_CONCURRENCY: usize = 6;
+
+/// 读取请求配置中的 `maxConcurrency`，限制同时发出的服务请求数
+fn concurrency_limit(config: Option<&HashMap<String, serde_json::Value>>) -> Arc<Semaphore> {
+    let limit = config
+        .and_then(|c| c.get("maxConcurrency"))
+        .and_then(|v| v.as_u64())
+        .filter(|n| *n > 0)
+        .map(|n| n as usize)
+        .unwrap_or(DEFAULT_MAX_CONCURRENCY);
+    Arc::new(Semaphore::new(limit))
 }
 
 fn make_error_result(name: &str, error: impl Into<String>) -> TranslationResult {
@@ -24,239 +98,241 @@ fn make_error_result(name: &str, error: impl Into<String>) -> TranslationResult
         name: name.to_string(),
         text: String::new(),
         error: Some(error.into()),
+        alternatives: Vec::new(),
     }
 }
 
-pub async fn translate(request: TranslationRequest) -> Result<TranslationResponse> {
-    let services = if request.services.is_empty() {
-        vec!["OpenAI".to_string(), "DeepL".to_string(), "Alibaba".to_string(), "GoogleFree".to_string()]
+/// 单个服务请求共用的参数
+#[derive(Clone)]
+struct ServiceJob {
+    text: String,
+    source_lang: String,
+    target_lang: String,
+    config: Option<HashMap<String, serde_json::Value>>,
+    format: TextFormat,
+    cache_options: cache::CacheOptions,
+    semaphore: Arc<Semaphore>,
+}
+
+impl ServiceJob {
+    fn from_request(request: &TranslationRequest) -> Self {
+        ServiceJob {
+            text: request.text.clone(),
+            source_lang: request.source_lang.clone(),
+            target_lang: request.target_lang.clone(),
+            config: request.config.clone(),
+            format: request.format,
+            cache_options: cache::CacheOptions::from_config(request.config.as_ref()),
+            semaphore: concurrency_limit(request.config.as_ref()),
+        }
+    }
+}
+
+/// 缓存键覆盖服务的有效配置（模型、地址、提示词、术语表、候选数等）和请求级后处理设置，
+/// 任何一项变化都不会命中旧译文
+fn job_cache_key(job: &ServiceJob, service_name: &str) -> String {
+    let service_config = crate::translate::resolve(service_name, job.config.as_ref())
+        .ok()
+        .and_then(|(_, service_config)| service_config);
+    let settings = serde_json::json!({
+        "format": format!("{:?}", job.format),
+        "service": service_config,
+        "postprocess": job.config.as_ref().and_then(|c| c.get("postprocess")),
+    });
+    cache::cache_key(service_name, &job.source_lang, &job.target_lang, &job.text, Some(&settings))
+}
+
+/// 流式增量回调；只有实际发起上游请求的调用方会收到增量
+type DeltaSink = Box<dyn for<'a> FnMut(&'a str) + Send>;
+
+/// 向上游请求单个服务：占用并发名额、租用密钥并调用接口，成功时写入缓存。
+/// 提供 `on_delta` 且条件允许时走流式接口
+async fn fetch(job: ServiceJob, service_name: String, cache_key: String, on_delta: Option<DeltaSink>) -> inflight::Outcome {
+    let ServiceJob { text, source_lang, target_lang, config, format, cache_options, semaphore } = job;
+
+    let (provider, service_config) = match crate::translate::resolve(&service_name, config.as_ref()) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            println!("Unknown service: {}", service_name);
+            return Err(e.detail(&service_name));
+        }
+    };
+
+    // 熔断中的服务立即返回，不排队等待并发名额
+    let breaker_options = crate::translate::breaker::BreakerOptions::from_config(config.as_ref());
+    if let Err(e) = crate::translate::breaker::check(provider.name(), breaker_options) {
+        println!("{}", e);
+        return Err(e.detail(provider.name()));
+    }
+
+    // 缓存命中不占用并发名额
+    let permit = http::Permit::acquire(semaphore).await;
+
+    let (service_config, key_lease) = match crate::translate::keys::lease(provider.name(), service_config) {
+        Ok(leased) => leased,
+        Err(e) => return Err(e.detail(provider.name())),
+    };
+
+    if !check_config_keys(service_config.as_ref(), provider.required_keys()) {
+        println!("{} service skipped - credentials not configured", provider.name());
+        return Err(AppError::Config(provider.missing_keys_message().to_string()).detail(provider.name()));
+    }
+
+    let source_lang = if provider.needs_source_lang() {
+        crate::langdetect::resolve_source(&text, &source_lang)
     } else {
-        request.services
+        source_lang
     };
 
-    let mut handles = Vec::new();
-
-    for service in services {
-        let text = request.text.clone();
-        let source_lang = request.source_lang.clone();
-        let target_lang = request.target_lang.clone();
-        let config = request.config.clone();
-        let service_name = service.clone();
-
-        let handle = tokio::spawn(async move {
-            let service_config = config.as_ref().and_then(|c| c.get(&service_name.to_lowercase()));
-            
-            println!("Processing translation service: {}", service_name);
-            
-            let result = match service_name.to_lowercase().as_str() {
-                "openai" => {
-                    if !check_api_key(service_config) {
-                        println!("OpenAI service skipped - no API key configured");
-                        return make_error_result("OpenAI", "No API key configured");
-                    }
-                    
-                    match openai::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("OpenAI translation error: {}", e);
-                            make_error_result("OpenAI", e)
-                        },
-                    }
-                }
-                "claude" => {
-                    if !check_api_key(service_config) {
-                        println!("Claude service skipped - no API key configured");
-                        return make_error_result("Claude", "No API key configured");
-                    }
-                    
-                    match claude::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("Claude translation error: {}", e);
-                            make_error_result("Claude", e)
-                        },
-                    }
-                }
-                "ernie" | "wenxin" | "文心一言" => {
-                    let has_api_key = service_config
-                        .and_then(|c| c.get("apiKey"))
-                        .and_then(|k| k.as_str())
-                        .map(|k| !k.is_empty())
-                        .unwrap_or(false);
-                    let has_secret_key = service_config
-                        .and_then(|c| c.get("secretKey"))
-                        .and_then(|k| k.as_str())
-                        .map(|k| !k.is_empty())
-                        .unwrap_or(false);
-                    
-                    if !has_api_key || !has_secret_key {
-                        println!("Ernie service skipped - API key or secret key not configured");
-                        return make_error_result("Ernie", "API key and secret key required");
-                    }
-                    
-                    match ernie::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("Ernie translation error: {}", e);
-                            make_error_result("Ernie", e)
-                        },
-                    }
-                }
-                "zhipu" => {
-                    if !check_api_key(service_config) {
-                        println!("Zhipu service skipped - no API key configured");
-                        return make_error_result("Zhipu", "No API key configured");
-                    }
-                    
-                    let mut config_obj = service_config.cloned().unwrap_or(serde_json::json!({}));
-                    if let Some(obj) = config_obj.as_object_mut() {
-                        obj.entry("apiUrl".to_string())
-                            .or_insert(serde_json::Value::String("https://open.bigmodel.cn/api/paas/v4/chat/completions".to_string()));
-                        obj.entry("model".to_string())
-                            .or_insert(serde_json::Value::String("glm-4-flash".to_string()));
-                    }
-                    
-                    match openai::translate(&text, &source_lang, &target_lang, Some(&config_obj)).await {
-                        Ok(mut result) => {
-                            result.name = "Zhipu".to_string();
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("Zhipu translation error: {}", e);
-                            make_error_result("Zhipu", e)
-                        },
-                    }
-                }
-                "groq" => {
-                    let mut config_obj = service_config.cloned().unwrap_or(serde_json::json!({}));
-                    if let Some(obj) = config_obj.as_object_mut() {
-                        obj.entry("apiUrl".to_string())
-                            .or_insert(serde_json::Value::String("https://api.groq.com/openai/v1/chat/completions".to_string()));
-                        obj.entry("model".to_string())
-                            .or_insert(serde_json::Value::String("llama3-8b-8192".to_string()));
-                    }
+    // 只有大模型输出需要清理
+    let postprocess = provider
+        .supports_streaming()
+        .then(|| postprocess::PostProcess::from_config(service_config.as_ref(), config.as_ref()));
+
+    // 超长文本、标记语言和多候选请求整体返回，不走流式
+    let on_delta = on_delta.filter(|_| {
+        provider.supports_streaming()
+            && format == TextFormat::Plain
+            && !(provider.supports_variants() && prompt::variant_count(service_config.as_ref()) > 1)
+            && !chunk::needs_split(provider.as_ref(), &text, service_config.as_ref())
+    });
+
+    let outcome = http::holding(permit, async {
+        match on_delta {
+            Some(mut on_delta) => {
+                let (request_text, placeholders) = match &postprocess {
+                    Some(postprocess) => postprocess.protect(&text),
+                    None => (text.clone(), Vec::new()),
+                };
+                provider
+                    .translate_stream(&request_text, &source_lang, &target_lang, service_config.as_ref(), &mut *on_delta)
+                    .await
+                    .map(|final_text| {
+                        // 增量按原样推送，完成时返回清理后的全文
+                        let final_text = match &postprocess {
+                            Some(postprocess) => postprocess.apply(&final_text, &text, &placeholders),
+                            None => final_text,
+                        };
+                        TranslationResult {
+                            name: service_name.clone(),
+                            text: final_text,
+                            error: None,
+                            alternatives: Vec::new(),
+                        }
+                    })
+            }
+            None => {
+                markup::translate(
+                    provider.as_ref(),
+                    &text,
+                    format,
+                    &source_lang,
+                    &target_lang,
+                    service_config.as_ref(),
+                    postprocess.as_ref(),
+                )
+                .await
+            }
+        }
+    })
+    .await;
+    if let Some(lease) = key_lease {
+        lease.finish(outcome.as_ref().err());
+    }
+    crate::translate::breaker::record(provider.name(), outcome.as_ref().err(), breaker_options);
 
-                    match openai::translate(&text, &source_lang, &target_lang, Some(&config_obj)).await {
-                        Ok(mut result) => {
-                            result.name = "Groq".to_string();
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("Groq translation error: {}", e);
-                            make_error_result("Groq", e)
-                        },
-                    }
-                }
-                "gemini" => {
-                    let mut config_obj = service_config.cloned().unwrap_or(serde_json::json!({}));
-                    if let Some(obj) = config_obj.as_object_mut() {
-                        obj.entry("apiUrl".to_string())
-                            .or_insert(serde_json::Value::String("https://generativelanguage.googleapis.com/v1beta/openai/chat/completions".to_string()));
-                        obj.entry("model".to_string())
-                            .or_insert(serde_json::Value::String("gemini-1.5-flash".to_string()));
-                    }
+    match outcome {
+        Ok(mut result) => {
+            result.error = None;
+            if cache_options.enabled {
+                cache::put(cache_key, &result, cache_options);
+            }
+            Ok(result)
+        }
+        Err(e) => {
+            println!("{} translation error: {}", provider.name(), e);
+            Err(e.for_service(provider.name()).detail(provider.name()))
+        }
+    }
+}
 
-                    match openai::translate(&text, &source_lang, &target_lang, Some(&config_obj)).await {
-                        Ok(mut result) => {
-                            result.name = "Gemini".to_string();
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("Gemini translation error: {}", e);
-                            make_error_result("Gemini", e)
-                        },
-                    }
-                }
-                "deepl" => {
-                    match deepl::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("DeepL translation error: {}", e);
-                            make_error_result("DeepL", e)
-                        },
-                    }
-                }
-                "google" => {
-                    match google::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("Google translation error: {}", e);
-                            make_error_result("Google", e)
-                        },
-                    }
-                }
-                "alibaba" => {
-                    match alibaba::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("Alibaba translation error: {}", e);
-                            make_error_result("Alibaba", e)
-                        },
-                    }
-                }
-                "googlefree" | "google native" => {
-                    match google_free::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            result
-                        },
-                        Err(e) => {
-                            println!("GoogleFree translation error: {}", e);
-                            make_error_result("GoogleFree", e)
-                        },
-                    }
-                }
-                _ => {
-                    println!("Unknown service: {}", service_name);
-                    make_error_result(&service_name, "Service not supported")
-                }
-            };
-            
-            println!("Service {} completed with result: {:?}", service_name, result);
-            result
-        });
-        handles.push(handle);
+async fn translate_one(job: ServiceJob, service_name: String) -> TranslationResult {
+    println!("Processing translation service: {}", service_name);
+
+    let cache_key = job_cache_key(&job, &service_name);
+    if job.cache_options.enabled {
+        if let Some(result) = cache::get(&cache_key) {
+            println!("Service {} served from cache", service_name);
+            return result;
+        }
     }
 
-    println!("Waiting for all translation services to complete...");
-    let mut final_results = Vec::new();
+    let work = fetch(job, service_name.clone(), cache_key.clone(), None);
+    let (outcome, joined) = inflight::coalesce(cache_key, work).await;
+    if joined {
+        println!("Service {} joined an identical in-flight request", service_name);
+    }
+
+    let result = match outcome {
+        Ok(result) => result,
+        Err(detail) => make_error_result(&detail.service, detail.message),
+    };
+
+    println!("Service {} completed with result: {:?}", service_name, result);
+    result
+}
 
-    for handle in handles {
-        match handle.await {
-            Ok(result) => {
-                if let Some(error) = &result.error {
-                    println!("Service {} failed with error: {}", result.name, error);
-                } else {
-                    println!("Service {} completed successfully", result.name);
+pub async fn translate(request: TranslationRequest) -> Result<TranslationResponse> {
+    let services = if request.services.is_empty() {
+        default_services()
+    } else {
+        request.services.clone()
+    };
+    let job = ServiceJob::from_request(&request);
+
+    let final_results = match request.mode {
+        TranslationMode::All => {
+            let handles: Vec<_> = services
+                .into_iter()
+                .map(|service| tokio::spawn(translate_one(job.clone(), service)))
+                .collect();
+
+            println!("Waiting for all translation services to complete...");
+            let mut final_results = Vec::new();
+
+            for handle in handles {
+                match handle.await {
+                    Ok(result) => {
+                        if let Some(error) = &result.error {
+                            println!("Service {} failed with error: {}", result.name, error);
+                        } else {
+                            println!("Service {} completed successfully", result.name);
+                        }
+                        final_results.push(result);
+                    }
+                    Err(e) => {
+                        println!("Translation task failed: {}", e);
+                    }
                 }
-                final_results.push(result);
             }
-            Err(e) => {
-                println!("Translation task failed: {}", e);
+            final_results
+        }
+        TranslationMode::Fallback => {
+            // 按优先级依次尝试，返回第一个成功结果；全部失败时返回各服务的错误
+            let mut failures = Vec::new();
+            let mut success = None;
+            for service in services {
+                let result = translate_one(job.clone(), service).await;
+                if result.error.is_none() {
+                    success = Some(result);
+                    break;
+                }
+                println!("Service {} failed, falling back to next service", result.name);
+                failures.push(result);
             }
+            success.map(|result| vec![result]).unwrap_or(failures)
         }
-    }
+    };
 
     println!("Translation completed. Total results: {}", final_results.len());
 
@@ -264,7 +340,14 @@ pub async fn translate(request: TranslationRequest) -> Result<TranslationRespons
         return Err(AppError::Translation("No translation services returned results".to_string()));
     }
 
-    Ok(TranslationResponse { results: final_results })
+    let memory = memory::lookup(
+        &request.text,
+        &request.source_lang,
+        &request.target_lang,
+        memory::MemoryOptions::from_config(request.config.as_ref()),
+    );
+
+    Ok(TranslationResponse { results: final_results, memory })
 }
 
 #[derive(Serialize, Clone)]
@@ -273,299 +356,133 @@ struct StreamPayload {
     service: String,
     delta: Option<String>,
     text: Option<String>,
-    error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    alternatives: Vec<String>,
+    /// 翻译记忆中的相似译文，只随最后的 `all_done` 事件推送
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    memory: Vec<MemoryMatch>,
+    error: Option<ErrorDetail>,
     done: bool,
     all_done: bool,
 }
 
-pub async fn translate_stream(app: AppHandle, request: TranslationRequest, request_id: String) -> Result<()> {
-    let services = if request.services.is_empty() {
-        vec!["OpenAI".to_string(), "DeepL".to_string(), "Alibaba".to_string(), "GoogleFree".to_string()]
-    } else {
-        request.services
+/// 流式翻译单个服务，返回是否成功
+async fn stream_one(app_handle: AppHandle, request_id: String, job: ServiceJob, service_name: String) -> bool {
+    let event = crate::events::event_name("translation-stream");
+    let emit = |payload: StreamPayload| {
+        let _ = app_handle.emit(&event, payload);
     };
 
-    let mut handles = Vec::new();
-
-    for service in services {
-        let text = request.text.clone();
-        let source_lang = request.source_lang.clone();
-        let target_lang = request.target_lang.clone();
-        let config = request.config.clone();
-        let service_name = service.clone();
-        let app_handle = app.clone();
-        let request_id_clone = request_id.clone();
-
-        let handle = tokio::spawn(async move {
-            let service_config = config.as_ref().and_then(|c| c.get(&service_name.to_lowercase()));
-
-            let emit = |payload: StreamPayload| {
-                let _ = app_handle.emit("translation-stream", payload);
-            };
-
-            let emit_error = |error: String| {
-                emit(StreamPayload {
-                    request_id: request_id_clone.clone(),
-                    service: service_name.clone(),
-                    delta: None,
-                    text: None,
-                    error: Some(error),
-                    done: true,
-                    all_done: false,
-                });
-            };
-
-            match service_name.to_lowercase().as_str() {
-                "openai" | "zhipu" | "groq" | "gemini" => {
-                    if !check_api_key(service_config) {
-                        emit_error("No API key configured".to_string());
-                        return;
-                    }
-
-                    let mut config_obj = service_config.cloned().unwrap_or(serde_json::json!({}));
-                    if let Some(obj) = config_obj.as_object_mut() {
-                        match service_name.to_lowercase().as_str() {
-                            "zhipu" => {
-                                obj.entry("apiUrl".to_string())
-                                    .or_insert(serde_json::Value::String("https://open.bigmodel.cn/api/paas/v4/chat/completions".to_string()));
-                                obj.entry("model".to_string())
-                                    .or_insert(serde_json::Value::String("glm-4-flash".to_string()));
-                            }
-                            "groq" => {
-                                obj.entry("apiUrl".to_string())
-                                    .or_insert(serde_json::Value::String("https://api.groq.com/openai/v1/chat/completions".to_string()));
-                                obj.entry("model".to_string())
-                                    .or_insert(serde_json::Value::String("llama3-8b-8192".to_string()));
-                            }
-                            "gemini" => {
-                                obj.entry("apiUrl".to_string())
-                                    .or_insert(serde_json::Value::String("https://generativelanguage.googleapis.com/v1beta/openai/chat/completions".to_string()));
-                                obj.entry("model".to_string())
-                                    .or_insert(serde_json::Value::String("gemini-1.5-flash".to_string()));
-                            }
-                            _ => {}
-                        }
-                    }
-
-                    let result = openai::translate_stream(
-                        &text,
-                        &source_lang,
-                        &target_lang,
-                        Some(&config_obj),
-                        |delta| {
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: service_name.clone(),
-                                delta: Some(delta.to_string()),
-                                text: None,
-                                error: None,
-                                done: false,
-                                all_done: false,
-                            });
+    let cache_key = job_cache_key(&job, &service_name);
+    let cached = if job.cache_options.enabled { cache::get(&cache_key) } else { None };
+
+    let outcome = match cached {
+        Some(result) => Ok(result),
+        None => {
+            let on_delta: DeltaSink = {
+                let app_handle = app_handle.clone();
+                let request_id = request_id.clone();
+                let service_name = service_name.clone();
+                let event = event.clone();
+                Box::new(move |delta: &str| {
+                    let _ = app_handle.emit(
+                        &event,
+                        StreamPayload {
+                            request_id: request_id.clone(),
+                            service: service_name.clone(),
+                            delta: Some(delta.to_string()),
+                            text: None,
+                            alternatives: Vec::new(),
+                            memory: Vec::new(),
+                            error: None,
+                            done: false,
+                            all_done: false,
                         },
-                    )
-                    .await;
-
-                    match result {
-                        Ok(final_text) => {
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: service_name.clone(),
-                                delta: None,
-                                text: Some(final_text),
-                                error: None,
-                                done: true,
-                                all_done: false,
-                            });
-                        }
-                        Err(e) => {
-                            emit_error(e);
-                        }
-                    }
-                }
-                "claude" => {
-                    if !check_api_key(service_config) {
-                        emit_error("No API key configured".to_string());
-                        return;
-                    }
+                    );
+                })
+            };
+            // 复用他人请求时收不到增量，只在完成时收到全文
+            let work = fetch(job, service_name.clone(), cache_key.clone(), Some(on_delta));
+            inflight::coalesce(cache_key, work).await.0
+        }
+    };
 
-                    let result = claude::translate_stream(
-                        &text,
-                        &source_lang,
-                        &target_lang,
-                        service_config,
-                        |delta| {
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: service_name.clone(),
-                                delta: Some(delta.to_string()),
-                                text: None,
-                                error: None,
-                                done: false,
-                                all_done: false,
-                            });
-                        },
-                    )
-                    .await;
-
-                    match result {
-                        Ok(final_text) => {
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: service_name.clone(),
-                                delta: None,
-                                text: Some(final_text),
-                                error: None,
-                                done: true,
-                                all_done: false,
-                            });
-                        }
-                        Err(e) => {
-                            emit_error(e.to_string());
-                        }
-                    }
-                }
-                "ernie" | "wenxin" | "文心一言" => {
-                    let has_api_key = service_config
-                        .and_then(|c| c.get("apiKey"))
-                        .and_then(|k| k.as_str())
-                        .map(|k| !k.is_empty())
-                        .unwrap_or(false);
-                    let has_secret_key = service_config
-                        .and_then(|c| c.get("secretKey"))
-                        .and_then(|k| k.as_str())
-                        .map(|k| !k.is_empty())
-                        .unwrap_or(false);
-                    
-                    if !has_api_key || !has_secret_key {
-                        emit_error("API key and secret key required".to_string());
-                        return;
-                    }
+    match outcome {
+        Ok(result) => {
+            emit(StreamPayload {
+                request_id: request_id.clone(),
+                service: result.name,
+                delta: None,
+                text: Some(result.text),
+                alternatives: result.alternatives,
+                memory: Vec::new(),
+                error: None,
+                done: true,
+                all_done: false,
+            });
+            true
+        }
+        Err(detail) => {
+            emit(StreamPayload {
+                request_id: request_id.clone(),
+                service: service_name.clone(),
+                delta: None,
+                text: None,
+                alternatives: Vec::new(),
+                memory: Vec::new(),
+                error: Some(detail),
+                done: true,
+                all_done: false,
+            });
+            false
+        }
+    }
+}
 
-                    let result = ernie::translate_stream(
-                        &text,
-                        &source_lang,
-                        &target_lang,
-                        service_config,
-                        |delta| {
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: service_name.clone(),
-                                delta: Some(delta.to_string()),
-                                text: None,
-                                error: None,
-                                done: false,
-                                all_done: false,
-                            });
-                        },
-                    )
-                    .await;
-
-                    match result {
-                        Ok(final_text) => {
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: service_name.clone(),
-                                delta: None,
-                                text: Some(final_text),
-                                error: None,
-                                done: true,
-                                all_done: false,
-                            });
-                        }
-                        Err(e) => {
-                            emit_error(e.to_string());
-                        }
-                    }
-                }
-                "deepl" => {
-                    match deepl::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: result.name,
-                                delta: None,
-                                text: Some(result.text),
-                                error: None,
-                                done: true,
-                                all_done: false,
-                            });
-                        }
-                        Err(e) => emit_error(e),
-                    }
-                }
-                "google" => {
-                    match google::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: result.name,
-                                delta: None,
-                                text: Some(result.text),
-                                error: None,
-                                done: true,
-                                all_done: false,
-                            });
-                        }
-                        Err(e) => emit_error(e),
-                    }
-                }
-                "alibaba" => {
-                    match alibaba::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: result.name,
-                                delta: None,
-                                text: Some(result.text),
-                                error: None,
-                                done: true,
-                                all_done: false,
-                            });
-                        }
-                        Err(e) => emit_error(e),
-                    }
-                }
-                "googlefree" | "google native" => {
-                    match google_free::translate(&text, &source_lang, &target_lang, service_config).await {
-                        Ok(mut result) => {
-                            result.error = None;
-                            emit(StreamPayload {
-                                request_id: request_id_clone.clone(),
-                                service: result.name,
-                                delta: None,
-                                text: Some(result.text),
-                                error: None,
-                                done: true,
-                                all_done: false,
-                            });
-                        }
-                        Err(e) => emit_error(e),
-                    }
-                }
-                _ => {
-                    emit_error("Service not supported".to_string());
+pub async fn translate_stream(app: AppHandle, request: TranslationRequest, request_id: String) -> Result<()> {
+    let services = if request.services.is_empty() {
+        default_services()
+    } else {
+        request.services.clone()
+    };
+    let job = ServiceJob::from_request(&request);
+
+    match request.mode {
+        TranslationMode::All => {
+            let handles: Vec<_> = services
+                .into_iter()
+                .map(|service| tokio::spawn(stream_one(app.clone(), request_id.clone(), job.clone(), service)))
+                .collect();
+            for handle in handles {
+                let _ = handle.await;
+            }
+        }
+        TranslationMode::Fallback => {
+            // 失败的服务照常推送错误事件，随后尝试下一个
+            for service in services {
+                if stream_one(app.clone(), request_id.clone(), job.clone(), service).await {
+                    break;
                 }
             }
-        });
-
-        handles.push(handle);
+        }
     }
 
-    for handle in handles {
-        let _ = handle.await;
-    }
+    let memory = memory::lookup(
+        &request.text,
+        &request.source_lang,
+        &request.target_lang,
+        memory::MemoryOptions::from_config(request.config.as_ref()),
+    );
 
     let _ = app.emit(
-        "translation-stream",
+        &crate::events::event_name("translation-stream"),
         StreamPayload {
             request_id,
             service: String::new(),
             delta: None,
             text: None,
+            alternatives: Vec::new(),
+            memory,
             error: None,
             done: true,
             all_done: true,
@@ -0,0 +1,105 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use crate::translate::TranslationService;
+use crate::translate::languages::{self, LanguageSupport};
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let api_key = config
+        .and_then(|c| c.get("apiKey"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Config("NiuTrans API key not configured".to_string()))?;
+
+    let api_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("https://api.niutrans.com/NiuTransServer/translation");
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let response = client
+        .post(api_url)
+        .form(&[
+            ("from", map_lang(source_lang)),
+            ("to", map_lang(target_lang)),
+            ("apikey", api_key.as_str()),
+            ("src_text", text),
+        ])
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("NiuTrans", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    // NiuTrans reports failures with HTTP 200 and an error_code/error_msg pair
+    if let Some(code) = json.get("error_code") {
+        let message = json["error_msg"].as_str().unwrap_or("Unknown error");
+        return Err(AppError::Api {
+            service: "NiuTrans".to_string(),
+            message: format!("{} ({})", message, code),
+        });
+    }
+
+    let translated_text = json["tgt_text"]
+        .as_str()
+        .map(|s| s.trim_end().to_string())
+        .ok_or_else(|| AppError::Translation("No translation in NiuTrans response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "NiuTrans".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+fn map_lang(lang: &str) -> &str {
+    match lang {
+        "zh-CN" | "zh-Hans" => "zh",
+        "zh-TW" | "zh-Hant" => "cht",
+        other => other,
+    }
+}
+
+pub struct NiuTrans;
+
+#[async_trait]
+impl TranslationService for NiuTrans {
+    fn name(&self) -> &str {
+        "NiuTrans"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["niutrans", "小牛翻译"]
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(languages::BROAD)
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["apiKey"]
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
@@ -0,0 +1,363 @@
+//! Offline translation with NLLB-200 or small100 through a local Python
+//! `transformers` runtime, kept loaded in a persistent worker like PaddleOCR.
+//! Weights are downloaded once into the app's model directory and loaded from there.
+
+use crate::error::{AppError, Result};
+use crate::models::TranslationResult;
+use crate::ocr::paddle::{create_command, find_python};
+use crate::ocr::worker::WorkerSlot;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Stdio;
+use crate::translate::TranslationService;
+use crate::translate::languages::LanguageSupport;
+use async_trait::async_trait;
+
+/// 可下载的模型：(名称, HuggingFace 仓库)
+const MODELS: &[(&str, &str)] = &[
+    ("nllb-200-distilled-600M", "facebook/nllb-200-distilled-600M"),
+    ("nllb-200-distilled-1.3B", "facebook/nllb-200-distilled-1.3B"),
+    ("small100", "alirezamsh/small100"),
+];
+
+const DEFAULT_MODEL: &str = "nllb-200-distilled-600M";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NllbModelInfo {
+    pub name: String,
+    pub repo: String,
+    pub downloaded: bool,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NllbStatus {
+    pub runtime_available: bool,
+    pub models: Vec<NllbModelInfo>,
+}
+
+#[derive(Deserialize)]
+struct ScriptOutput {
+    text: Option<String>,
+    error: Option<String>,
+}
+
+fn models_dir() -> PathBuf {
+    crate::paths::data_dir().join("models")
+}
+
+fn resolve_model(name: &str) -> Result<(&'static str, &'static str)> {
+    MODELS
+        .iter()
+        .find(|(model, _)| model.eq_ignore_ascii_case(name))
+        .copied()
+        .ok_or_else(|| AppError::Config(format!("Unknown NLLB model: {}", name)))
+}
+
+fn model_path(name: &str) -> PathBuf {
+    models_dir().join(name)
+}
+
+fn is_downloaded(name: &str) -> bool {
+    model_path(name).join("config.json").exists()
+}
+
+/// 默认模型是否已下载，可用于离线翻译
+pub(crate) fn default_model_downloaded() -> bool {
+    is_downloaded(DEFAULT_MODEL)
+}
+
+/// Runs a Python snippet with `input` on stdin and parses its JSON reply.
+fn run_script(script: &str, args: &[&str], input: &str) -> Result<ScriptOutput> {
+    let python_cmd = find_python()
+        .ok_or_else(|| AppError::Config("Python not found. Please install Python.".to_string()))?;
+
+    let mut child = create_command(&python_cmd)
+        .arg("-c")
+        .arg(script)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::ServiceUnavailable(format!("Failed to start Python: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // The scripts print a single JSON line last; anything before it is library noise
+    let last_line = stdout.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("");
+    serde_json::from_str(last_line).map_err(|_| {
+        AppError::Translation(format!(
+            "NLLB runtime failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    })
+}
+
+/// 常驻工作进程脚本：启动参数为模型路径、是否 small100、是否量化，
+/// 模型只加载一次，每个请求给出 `text`、`src`、`tgt`
+const WORKER_SCRIPT: &str = r#"
+import io
+import json
+import sys
+
+out = sys.stdout
+sys.stdout = sys.stderr
+requests = io.TextIOWrapper(sys.stdin.buffer, encoding='utf-8')
+
+def reply(payload):
+    out.write(json.dumps(payload, ensure_ascii=False) + "\n")
+    out.flush()
+
+try:
+    import torch
+    from transformers import AutoModelForSeq2SeqLM, AutoTokenizer
+    path, small100, quantize = sys.argv[1], sys.argv[2] == "1", sys.argv[3] == "1"
+    if small100:
+        # small100 的分词器随模型以 tokenization_small100.py 分发
+        sys.path.insert(0, path)
+        from tokenization_small100 import SMALL100Tokenizer
+        tokenizer = SMALL100Tokenizer.from_pretrained(path)
+    else:
+        tokenizer = AutoTokenizer.from_pretrained(path)
+    model = AutoModelForSeq2SeqLM.from_pretrained(path)
+    if quantize:
+        model = torch.quantization.quantize_dynamic(model, {torch.nn.Linear}, dtype=torch.qint8)
+except Exception as e:
+    reply({"error": str(e)})
+    sys.exit(1)
+
+reply({"ready": True})
+
+for request in requests:
+    try:
+        request = json.loads(request)
+        if small100:
+            tokenizer.tgt_lang = request["tgt"]
+            inputs = tokenizer(request["text"], return_tensors="pt")
+            forced = None
+        else:
+            if request["src"]:
+                tokenizer.src_lang = request["src"]
+            inputs = tokenizer(request["text"], return_tensors="pt")
+            forced = tokenizer.convert_tokens_to_ids(request["tgt"])
+        with torch.no_grad():
+            tokens = model.generate(**inputs, forced_bos_token_id=forced, max_new_tokens=512)
+        reply({"text": tokenizer.batch_decode(tokens, skip_special_tokens=True)[0]})
+    except Exception as e:
+        reply({"error": str(e)})
+"#;
+
+static WORKER: WorkerSlot = WorkerSlot::with_error("NLLB", WORKER_SCRIPT, AppError::Translation);
+
+/// 单次翻译的默认超时；首次请求包含模型加载时间
+const DEFAULT_TIMEOUT_MS: u64 = 120_000;
+
+/// 停止常驻工作进程，释放模型占用的内存
+pub fn stop_worker() {
+    WORKER.stop()
+}
+
+const DOWNLOAD_SCRIPT: &str = r#"
+import json, sys
+try:
+    from huggingface_hub import snapshot_download
+    snapshot_download(repo_id=sys.argv[1], local_dir=sys.argv[2],
+                      allow_patterns=["*.json", "*.model", "*.bin", "*.safetensors", "*.txt", "*.py"])
+    print(json.dumps({"text": sys.argv[2]}))
+except Exception as e:
+    print(json.dumps({"error": str(e)}))
+"#;
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let get = |key: &str| {
+        config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+    };
+
+    let (model, _) = resolve_model(get("model").unwrap_or(DEFAULT_MODEL))?;
+    if !is_downloaded(model) {
+        return Err(AppError::Config(format!("NLLB model '{}' is not downloaded", model)));
+    }
+
+    let small100 = model == "small100";
+    let (source, target) = if small100 {
+        (String::new(), small100_code(target_lang).to_string())
+    } else {
+        let source = if source_lang == "auto" { String::new() } else { flores_code(source_lang).to_string() };
+        (source, flores_code(target_lang).to_string())
+    };
+    let quantize = config
+        .and_then(|c| c.get("quantize"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    let args = vec![
+        model_path(model).to_string_lossy().to_string(),
+        if small100 { "1" } else { "0" }.to_string(),
+        if quantize { "1" } else { "0" }.to_string(),
+    ];
+    let request = serde_json::json!({ "text": text, "src": source, "tgt": target });
+    let timeout = super::timeout_from_config(config, DEFAULT_TIMEOUT_MS);
+    let task = tokio::task::spawn_blocking(move || WORKER.request(&args, &request));
+    let response = match tokio::time::timeout(timeout, task).await {
+        Ok(joined) => joined.map_err(|e| AppError::Unknown(format!("NLLB task failed: {}", e)))??,
+        Err(_) => {
+            // 卡住的进程会一直占用工作进程，终止后下次请求重新加载模型
+            WORKER.abort();
+            return Err(AppError::Timeout(format!("NLLB did not respond within {}s", timeout.as_secs())));
+        }
+    };
+    let output: ScriptOutput = serde_json::from_value(response)
+        .map_err(|e| AppError::Translation(format!("Failed to parse NLLB output: {}", e)))?;
+
+    Ok(TranslationResult {
+        name: "NLLB".to_string(),
+        text: output.text.unwrap_or_default().trim().to_string(),
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+pub fn status() -> NllbStatus {
+    let runtime_available = find_python()
+        .and_then(|python| {
+            create_command(&python)
+                .args(["-c", "import transformers, torch; print('ok')"])
+                .output()
+                .ok()
+        })
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+
+    NllbStatus {
+        runtime_available,
+        models: MODELS
+            .iter()
+            .map(|(name, repo)| NllbModelInfo {
+                name: name.to_string(),
+                repo: repo.to_string(),
+                downloaded: is_downloaded(name),
+                path: model_path(name).to_string_lossy().to_string(),
+            })
+            .collect(),
+    }
+}
+
+pub async fn download_model(name: &str) -> Result<NllbModelInfo> {
+    let (model, repo) = resolve_model(name)?;
+    let path = model_path(model);
+    std::fs::create_dir_all(&path)?;
+
+    let target = path.to_string_lossy().to_string();
+    let output = tokio::task::spawn_blocking(move || run_script(DOWNLOAD_SCRIPT, &[repo, &target], ""))
+        .await
+        .map_err(|e| AppError::Unknown(format!("NLLB download task failed: {}", e)))??;
+
+    if let Some(error) = output.error {
+        return Err(AppError::Network(format!("Failed to download {}: {}", repo, error)));
+    }
+
+    Ok(NllbModelInfo {
+        name: model.to_string(),
+        repo: repo.to_string(),
+        downloaded: is_downloaded(model),
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+pub fn delete_model(name: &str) -> Result<()> {
+    let (model, _) = resolve_model(name)?;
+    let path = model_path(model);
+    if path.exists() {
+        // 工作进程可能正加载着该模型
+        stop_worker();
+        std::fs::remove_dir_all(path)?;
+    }
+    Ok(())
+}
+
+/// FLORES-200 codes used by NLLB.
+fn flores_code(lang: &str) -> &str {
+    match lang {
+        "en" => "eng_Latn",
+        "zh" | "zh-CN" | "zh-Hans" => "zho_Hans",
+        "zh-TW" | "zh-Hant" => "zho_Hant",
+        "ja" => "jpn_Jpan",
+        "ko" => "kor_Hang",
+        "fr" => "fra_Latn",
+        "de" => "deu_Latn",
+        "es" => "spa_Latn",
+        "it" => "ita_Latn",
+        "pt" => "por_Latn",
+        "ru" => "rus_Cyrl",
+        "ar" => "arb_Arab",
+        "hi" => "hin_Deva",
+        "th" => "tha_Thai",
+        "vi" => "vie_Latn",
+        "id" => "ind_Latn",
+        "tr" => "tur_Latn",
+        "nl" => "nld_Latn",
+        "pl" => "pol_Latn",
+        "uk" => "ukr_Cyrl",
+        other => other,
+    }
+}
+
+fn small100_code(lang: &str) -> &str {
+    match lang {
+        "zh-CN" | "zh-Hans" | "zh-TW" | "zh-Hant" => "zh",
+        other => other,
+    }
+}
+
+pub struct Nllb;
+
+#[async_trait]
+impl TranslationService for Nllb {
+    fn name(&self) -> &str {
+        "NLLB"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["nllb"]
+    }
+
+    /// NLLB cannot detect the source; without `src_lang` the tokenizer assumes English.
+    fn needs_source_lang(&self) -> bool {
+        true
+    }
+
+    /// Languages with a FLORES-200 mapping in `flores_code`.
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(&[
+            "en", "zh-CN", "zh-TW", "ja", "ko", "fr", "de", "es", "it", "pt", "ru", "ar", "hi", "th", "vi", "id",
+            "tr", "nl", "pl", "uk",
+        ])
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
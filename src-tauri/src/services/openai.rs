@@ -1,20 +1,66 @@
+use crate::error::AppError;
 use crate::models::TranslationResult;
+use crate::services::generation::GenerationParams;
+use crate::services::prompt::{split_variants, variant_count, Prompt};
+use crate::translate::TranslationService;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
 use futures_util::StreamExt;
-use reqwest;
 use std::env;
 
+/// Where the API key may come from. Only the official endpoint may fall back to
+/// `OPENAI_API_KEY`; user-defined endpoints must never receive that key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySource {
+    /// `apiKey`, then the `OPENAI_API_KEY` environment variable or `./.env`; required.
+    ConfigOrEnv,
+    /// `apiKey` only; without one the request is sent unauthenticated.
+    ConfigOnly,
+}
+
+fn api_error(message: impl Into<String>) -> AppError {
+    AppError::Api { service: "OpenAI".to_string(), message: message.into() }
+}
+
+fn env_api_key() -> Option<String> {
+    env::var("OPENAI_API_KEY").ok().or_else(|| {
+        std::fs::read_to_string(".env").ok().and_then(|s| {
+            s.lines()
+                .find(|l| l.starts_with("OPENAI_API_KEY="))
+                .map(|l| l.trim_start_matches("OPENAI_API_KEY=").to_string())
+        })
+    })
+}
+
+fn resolve_api_key(config: Option<&serde_json::Value>, key_source: KeySource) -> crate::error::Result<Option<String>> {
+    let configured = config
+        .and_then(|c| c.get("apiKey"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+    match key_source {
+        KeySource::ConfigOnly => Ok(configured),
+        KeySource::ConfigOrEnv => configured
+            .or_else(env_api_key)
+            .map(Some)
+            .ok_or_else(|| api_error("API key not found in config or environment")),
+    }
+}
+
+fn authorize(request: reqwest::RequestBuilder, api_key: Option<&str>) -> reqwest::RequestBuilder {
+    match api_key {
+        Some(key) => request.header("Authorization", format!("Bearer {}", key)),
+        None => request,
+    }
+}
+
 pub async fn translate(
     text: &str,
-    _source_lang: &str,
+    source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
-) -> Result<TranslationResult, String> {
-    let api_key = if let Some(c) = config {
-        c.get("apiKey").and_then(|v| v.as_str()).map(|s| s.to_string())
-    } else {
-        None
-    };
-
+    key_source: KeySource,
+) -> crate::error::Result<TranslationResult> {
     let api_url = config
         .and_then(|c| c.get("apiUrl"))
         .and_then(|v| v.as_str())
@@ -25,83 +71,64 @@ pub async fn translate(
         .and_then(|v| v.as_str())
         .unwrap_or("gpt-3.5-turbo");
 
-    let api_key = api_key
-        .or_else(|| env::var("OPENAI_API_KEY").ok())
-        .or_else(|| {
-            std::fs::read_to_string(".env")
-                .map_err(|_| ())
-                .and_then(|s| {
-                    s.lines()
-                        .find(|l| l.starts_with("OPENAI_API_KEY="))
-                        .map(|l| l.trim_start_matches("OPENAI_API_KEY=").to_string())
-                        .ok_or(())
-                })
-                .ok()
-        })
-        .ok_or_else(|| "API key not found in config or environment".to_string())?;
+    let api_key = resolve_api_key(config, key_source)?;
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let client = super::http::client(config, super::request_timeout(config))?;
     
-    let response = client
-        .post(api_url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .json(&serde_json::json!({
-            "model": model,
-            "messages": [
-                {
-                    "role": "system",
-                    "content": format!("You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.", target_lang)
-                },
-                {
-                    "role": "user",
-                    "content": text
-                }
-            ],
-            "max_tokens": 1000
-        }))
-        .send()
+    let variants = variant_count(config);
+    let prompt = Prompt::from_template(config, text, source_lang, target_lang)
+        .unwrap_or_else(|| Prompt::translation_engine(text, target_lang))
+        .with_variants(text, variants);
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": prompt.messages()
+    });
+    GenerationParams::from_config(config).apply_openai(&mut body);
+
+    let response = authorize(client.post(api_url), api_key.as_deref())
+        .json(&body)
+        .send_retrying()
         .await
-        .map_err(|e| format!("OpenAI API request failed: {}", e))?;
+        .map_err(|e| api_error(format!("OpenAI API request failed: {}", e)))?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("OpenAI API error: {}", error_text));
+        return Err(AppError::from_status("OpenAI", status, format!("OpenAI API error: {}", error_text)));
     }
 
     let json: serde_json::Value = response.json().await
-        .map_err(|e| format!("Failed to parse OpenAI response: {}", e))?;
+        .map_err(|e| api_error(format!("Failed to parse OpenAI response: {}", e)))?;
 
     let translated_text = json["choices"][0]["message"]["content"]
         .as_str()
         .map(|s| s.trim().to_string())
-        .ok_or("No translation in response")?;
+        .ok_or_else(|| api_error("No translation in response"))?;
+    let (translated_text, alternatives) = if variants > 1 && !text.contains('\n') {
+        split_variants(&translated_text)
+    } else {
+        (translated_text, Vec::new())
+    };
 
     Ok(TranslationResult {
         name: "OpenAI".to_string(),
         text: translated_text,
         error: None,
+        alternatives,
     })
 }
 
 pub async fn translate_stream<F>(
     text: &str,
-    _source_lang: &str,
+    source_lang: &str,
     target_lang: &str,
     config: Option<&serde_json::Value>,
+    key_source: KeySource,
     mut on_delta: F,
-) -> Result<String, String>
+) -> crate::error::Result<String>
 where
     F: FnMut(&str),
 {
-    let api_key = if let Some(c) = config {
-        c.get("apiKey").and_then(|v| v.as_str()).map(|s| s.to_string())
-    } else {
-        None
-    };
-
     let api_url = config
         .and_then(|c| c.get("apiUrl"))
         .and_then(|v| v.as_str())
@@ -112,51 +139,29 @@ where
         .and_then(|v| v.as_str())
         .unwrap_or("gpt-3.5-turbo");
 
-    let api_key = api_key
-        .or_else(|| env::var("OPENAI_API_KEY").ok())
-        .or_else(|| {
-            std::fs::read_to_string(".env")
-                .map_err(|_| ())
-                .and_then(|s| {
-                    s.lines()
-                        .find(|l| l.starts_with("OPENAI_API_KEY="))
-                        .map(|l| l.trim_start_matches("OPENAI_API_KEY=").to_string())
-                        .ok_or(())
-                })
-                .ok()
-        })
-        .ok_or_else(|| "API key not found in config or environment".to_string())?;
-
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(60))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let response = client
-        .post(api_url)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .json(&serde_json::json!({
-            "model": model,
-            "messages": [
-                {
-                    "role": "system",
-                    "content": format!("You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.", target_lang)
-                },
-                {
-                    "role": "user",
-                    "content": text
-                }
-            ],
-            "max_tokens": 1000,
-            "stream": true
-        }))
-        .send()
+    let api_key = resolve_api_key(config, key_source)?;
+
+    let client = super::http::client(config, super::stream_timeout(config))?;
+
+    let prompt = Prompt::from_template(config, text, source_lang, target_lang)
+        .unwrap_or_else(|| Prompt::translation_engine(text, target_lang));
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": prompt.messages(),
+        "stream": true
+    });
+    GenerationParams::from_config(config).apply_openai(&mut body);
+
+    let response = authorize(client.post(api_url), api_key.as_deref())
+        .json(&body)
+        .send_retrying()
         .await
-        .map_err(|e| format!("OpenAI API request failed: {}", e))?;
+        .map_err(|e| api_error(format!("OpenAI API request failed: {}", e)))?;
 
     if !response.status().is_success() {
+        let status = response.status();
         let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("OpenAI API error: {}", error_text));
+        return Err(AppError::from_status("OpenAI", status, format!("OpenAI API error: {}", error_text)));
     }
 
     let mut full_text = String::new();
@@ -164,9 +169,9 @@ where
     let mut stream = response.bytes_stream();
 
     while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Stream error: {}", e))?;
+        let chunk = chunk.map_err(|e| api_error(format!("Stream error: {}", e)))?;
         let chunk_str = std::str::from_utf8(&chunk)
-            .map_err(|e| format!("Invalid UTF-8 in stream: {}", e))?;
+            .map_err(|e| api_error(format!("Invalid UTF-8 in stream: {}", e)))?;
         buffer.push_str(chunk_str);
 
         while let Some(pos) = buffer.find('\n') {
@@ -183,7 +188,7 @@ where
             }
 
             let json: serde_json::Value = serde_json::from_str(data)
-                .map_err(|e| format!("Failed to parse stream JSON: {}", e))?;
+                .map_err(|e| api_error(format!("Failed to parse stream JSON: {}", e)))?;
             let delta = json["choices"][0]["delta"]["content"]
                 .as_str()
                 .or_else(|| json["choices"][0]["message"]["content"].as_str())
@@ -197,3 +202,119 @@ where
 
     Ok(full_text)
 }
+
+/// OpenAI-compatible endpoint: the official API, a built-in preset that only
+/// differs in default URL/model, or a user-defined `custom:` service.
+pub struct OpenAiCompatible {
+    name: String,
+    aliases: &'static [&'static str],
+    api_url: Option<&'static str>,
+    model: Option<&'static str>,
+    required_keys: &'static [&'static str],
+    missing_keys_message: &'static str,
+    key_source: KeySource,
+}
+
+impl OpenAiCompatible {
+    pub fn preset(
+        name: &str,
+        aliases: &'static [&'static str],
+        api_url: Option<&'static str>,
+        model: Option<&'static str>,
+    ) -> Self {
+        OpenAiCompatible {
+            name: name.to_string(),
+            aliases,
+            api_url,
+            model,
+            required_keys: &["apiKey"],
+            missing_keys_message: "No API key configured",
+            key_source: KeySource::ConfigOrEnv,
+        }
+    }
+
+    /// Custom services bring their own config, so only the URL is mandatory; the
+    /// key comes from that config alone and is omitted when absent.
+    pub fn custom(name: &str) -> Self {
+        OpenAiCompatible {
+            name: name.to_string(),
+            aliases: &[],
+            api_url: None,
+            model: None,
+            required_keys: &["apiUrl"],
+            missing_keys_message: "No API URL configured",
+            key_source: KeySource::ConfigOnly,
+        }
+    }
+
+    fn with_defaults(&self, config: Option<&serde_json::Value>) -> serde_json::Value {
+        let mut config_obj = config.cloned().unwrap_or(serde_json::json!({}));
+        if let Some(obj) = config_obj.as_object_mut() {
+            if let Some(api_url) = self.api_url {
+                obj.entry("apiUrl".to_string())
+                    .or_insert(serde_json::Value::String(api_url.to_string()));
+            }
+            if let Some(model) = self.model {
+                obj.entry("model".to_string())
+                    .or_insert(serde_json::Value::String(model.to_string()));
+            }
+        }
+        config_obj
+    }
+}
+
+#[async_trait]
+impl TranslationService for OpenAiCompatible {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        self.aliases
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        self.required_keys
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        self.missing_keys_message
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn supports_variants(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> crate::error::Result<TranslationResult> {
+        let config_obj = self.with_defaults(config);
+        let mut result = translate(text, source_lang, target_lang, Some(&config_obj), self.key_source)
+            .await
+            .map_err(|e| e.with_service(&self.name))?;
+        result.name = self.name.clone();
+        Ok(result)
+    }
+
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> crate::error::Result<String> {
+        let config_obj = self.with_defaults(config);
+        translate_stream(text, source_lang, target_lang, Some(&config_obj), self.key_source, on_delta)
+            .await
+            .map_err(|e| e.with_service(&self.name))
+    }
+}
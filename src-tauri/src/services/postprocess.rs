@@ -0,0 +1,191 @@
+//! Cleanup applied to LLM output: models tend to wrap the answer in quotes,
+//! prefix it with "Translation:" or pad it with stray whitespace. Configured
+//! via `postprocess` in the request config, overridable per service:
+//! `{ "stripQuotes": true, "stripPrefix": true, "collapseWhitespace": false,
+//! "preservePlaceholders": false }`. Whitespace collapsing is opt-in since it
+//! would flatten indentation and blank lines the source text relies on. Placeholder preservation swaps
+//! `{name}` / `%s`-style tokens for markers before the request and restores
+//! them afterwards, so the model cannot translate or reformat them.
+
+use std::collections::HashMap;
+
+const PREFIXES: &[&str] = &[
+    "here is the translation:",
+    "translated text:",
+    "translation:",
+    "译文：",
+    "译文:",
+    "翻译：",
+    "翻译:",
+    "翻訳：",
+];
+
+const QUOTE_PAIRS: &[(char, char)] = &[('"', '"'), ('\'', '\''), ('“', '”'), ('‘', '’'), ('「', '」'), ('『', '』')];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostProcess {
+    pub strip_quotes: bool,
+    pub strip_prefix: bool,
+    pub collapse_whitespace: bool,
+    pub preserve_placeholders: bool,
+}
+
+impl Default for PostProcess {
+    fn default() -> Self {
+        PostProcess {
+            strip_quotes: true,
+            strip_prefix: true,
+            collapse_whitespace: false,
+            preserve_placeholders: false,
+        }
+    }
+}
+
+impl PostProcess {
+    /// 服务配置中的 `postprocess` 逐项覆盖请求级配置
+    pub fn from_config(
+        service_config: Option<&serde_json::Value>,
+        config: Option<&HashMap<String, serde_json::Value>>,
+    ) -> Self {
+        let layers = [
+            config.and_then(|c| c.get("postprocess")),
+            service_config.and_then(|c| c.get("postprocess")),
+        ];
+        let flag = |key: &str, default: bool| {
+            layers
+                .iter()
+                .rev()
+                .flatten()
+                .find_map(|layer| layer.get(key).and_then(|v| v.as_bool()))
+                .unwrap_or(default)
+        };
+        let defaults = PostProcess::default();
+        PostProcess {
+            strip_quotes: flag("stripQuotes", defaults.strip_quotes),
+            strip_prefix: flag("stripPrefix", defaults.strip_prefix),
+            collapse_whitespace: flag("collapseWhitespace", defaults.collapse_whitespace),
+            preserve_placeholders: flag("preservePlaceholders", defaults.preserve_placeholders),
+        }
+    }
+
+    /// 把占位符换成 `⟦n⟧` 标记，返回替换后的文本和原占位符
+    pub fn protect(&self, text: &str) -> (String, Vec<String>) {
+        if !self.preserve_placeholders {
+            return (text.to_string(), Vec::new());
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let mut output = String::with_capacity(text.len());
+        let mut placeholders = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match placeholder_len(&chars[i..]) {
+                Some(len) => {
+                    output.push_str(&format!("⟦{}⟧", placeholders.len()));
+                    placeholders.push(chars[i..i + len].iter().collect());
+                    i += len;
+                }
+                None => {
+                    output.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+        (output, placeholders)
+    }
+
+    /// 清理模型输出并还原占位符；`source` 用于判断引号是否原文自带
+    pub fn apply(&self, text: &str, source: &str, placeholders: &[String]) -> String {
+        let mut text = text.trim().to_string();
+        if self.strip_prefix {
+            text = strip_prefix(&text);
+        }
+        if self.strip_quotes {
+            text = strip_quotes(&text, source);
+        }
+        if self.collapse_whitespace {
+            text = collapse_whitespace(&text);
+        }
+        restore(&text, placeholders)
+    }
+}
+
+/// 识别 `{name}`、`{{name}}`、`%s`、`%1$d`、`%(name)s`、`%@` 等格式占位符
+fn placeholder_len(chars: &[char]) -> Option<usize> {
+    match chars.first()? {
+        '{' => {
+            let double = chars.get(1) == Some(&'{');
+            let start = if double { 2 } else { 1 };
+            let close = chars[start..].iter().position(|c| *c == '}')? + start;
+            let name = &chars[start..close];
+            if name.is_empty() || name.len() > 40 || !name.iter().all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '-')) {
+                return None;
+            }
+            if double {
+                (chars.get(close + 1) == Some(&'}')).then_some(close + 2)
+            } else {
+                Some(close + 1)
+            }
+        }
+        '%' => {
+            let mut i = 1;
+            if chars.get(i) == Some(&'(') {
+                i += chars[i..].iter().position(|c| *c == ')')? + 1;
+            }
+            while chars.get(i).is_some_and(|c| c.is_ascii_digit() || matches!(c, '$' | '.' | '-' | '+')) {
+                i += 1;
+            }
+            chars
+                .get(i)
+                .filter(|c| matches!(c, 's' | 'd' | 'i' | 'f' | 'u' | 'x' | 'X' | 'c' | '@'))
+                .map(|_| i + 1)
+        }
+        _ => None,
+    }
+}
+
+fn restore(text: &str, placeholders: &[String]) -> String {
+    let mut text = text.to_string();
+    for (index, placeholder) in placeholders.iter().enumerate() {
+        let marker = format!("⟦{}⟧", index);
+        let spaced = format!("⟦ {} ⟧", index);
+        text = text.replace(&marker, placeholder).replace(&spaced, placeholder);
+    }
+    text
+}
+
+fn strip_prefix(text: &str) -> String {
+    let lower = text.to_lowercase();
+    PREFIXES
+        .iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map(|prefix| text[prefix.len()..].trim_start().to_string())
+        .unwrap_or_else(|| text.to_string())
+}
+
+fn strip_quotes(text: &str, source: &str) -> String {
+    let source = source.trim();
+    for (open, close) in QUOTE_PAIRS {
+        let wrapped = text.starts_with(*open) && text.ends_with(*close) && text.chars().count() >= 2;
+        if wrapped && !source.starts_with(*open) {
+            let inner = &text[open.len_utf8()..text.len() - close.len_utf8()];
+            // 内部还有同种引号时说明引号不是外层包裹
+            if !inner.contains(*open) && !inner.contains(*close) {
+                return inner.trim().to_string();
+            }
+        }
+    }
+    text.to_string()
+}
+
+/// 行内连续空白合并为一个空格，多个空行合并为一个
+fn collapse_whitespace(text: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let collapsed = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.is_empty() && lines.last().is_some_and(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(collapsed);
+    }
+    lines.join("\n").trim().to_string()
+}
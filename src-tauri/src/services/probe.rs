@@ -0,0 +1,136 @@
+use crate::error::AppError;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Instant;
+
+const PROBE_TEXT: &str = "hello";
+
+/// 设置界面保存前验证服务配置的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceTestResult {
+    pub service: String,
+    pub ok: bool,
+    pub latency_ms: u64,
+    /// 失败类别：auth、rate_limit、network、timeout、config 等
+    pub category: Option<&'static str>,
+    pub message: Option<String>,
+    pub sample: Option<String>,
+    /// 配置了 `apiKeys` 时逐个密钥的结果
+    pub keys: Vec<KeyTestResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyTestResult {
+    pub key_hint: String,
+    pub ok: bool,
+    pub category: Option<&'static str>,
+    pub message: Option<String>,
+}
+
+fn key_hint(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    chars[chars.len().saturating_sub(4)..].iter().collect()
+}
+
+/// 用 `config` 作为该服务的配置翻译一次 "hello"，不经过缓存和密钥池。
+/// `proxy` 为全局代理设置，服务未单独配置代理时继承
+pub async fn test_service(
+    service: &str,
+    config: Option<serde_json::Value>,
+    proxy: Option<serde_json::Value>,
+) -> ServiceTestResult {
+    let started = Instant::now();
+    let config = config.unwrap_or_else(|| serde_json::json!({}));
+
+    let keys: Vec<String> = config
+        .get("apiKeys")
+        .and_then(|v| v.as_array())
+        .map(|keys| keys.iter().filter_map(|k| k.as_str()).filter(|k| !k.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default();
+
+    if keys.is_empty() {
+        let outcome = probe(service, config, proxy.as_ref()).await;
+        return finish(service, outcome, started.elapsed().as_millis() as u64, Vec::new());
+    }
+
+    // 多个密钥时逐个测试，任一可用即视为通过
+    let mut outcome = None;
+    let mut key_results = Vec::new();
+    for key in &keys {
+        let mut key_config = config.clone();
+        key_config["apiKey"] = serde_json::Value::String(key.clone());
+        let result = probe(service, key_config, proxy.as_ref()).await;
+        key_results.push(KeyTestResult {
+            key_hint: key_hint(key),
+            ok: result.is_ok(),
+            category: result.as_ref().err().map(AppError::category),
+            message: result.as_ref().err().map(|e| e.to_string()),
+        });
+        if !matches!(outcome, Some(Ok(_))) && (outcome.is_none() || result.is_ok()) {
+            outcome = Some(result);
+        }
+    }
+    let outcome = outcome.unwrap_or_else(|| Ok(String::new()));
+    finish(service, outcome, started.elapsed().as_millis() as u64, key_results)
+}
+
+fn finish(
+    service: &str,
+    outcome: crate::error::Result<String>,
+    latency_ms: u64,
+    keys: Vec<KeyTestResult>,
+) -> ServiceTestResult {
+    match outcome {
+        Ok(sample) => ServiceTestResult {
+            service: service.to_string(),
+            ok: true,
+            latency_ms,
+            category: None,
+            message: None,
+            sample: Some(sample),
+            keys,
+        },
+        Err(e) => ServiceTestResult {
+            service: service.to_string(),
+            ok: false,
+            latency_ms,
+            category: Some(e.category()),
+            message: Some(e.to_string()),
+            sample: None,
+            keys,
+        },
+    }
+}
+
+async fn probe(
+    service: &str,
+    config: serde_json::Value,
+    proxy: Option<&serde_json::Value>,
+) -> crate::error::Result<String> {
+    // custom:<id> 服务从 customServices 中查找，其余按服务名查找
+    let id = service.get("custom:".len()..).filter(|_| service.to_lowercase().starts_with("custom:"));
+    let mut entry = config.clone();
+    if let (Some(id), Some(obj)) = (id, entry.as_object_mut()) {
+        obj.insert("id".to_string(), serde_json::Value::String(id.to_string()));
+    }
+    let mut request_config = HashMap::from([
+        (service.to_lowercase(), config),
+        ("customServices".to_string(), serde_json::json!([entry])),
+    ]);
+    if let Some(proxy) = proxy {
+        request_config.insert("proxy".to_string(), proxy.clone());
+    }
+
+    let (provider, service_config) = crate::translate::resolve(service, Some(&request_config))?;
+    if !super::check_config_keys(service_config.as_ref(), provider.required_keys()) {
+        return Err(AppError::Config(provider.missing_keys_message().to_string()));
+    }
+
+    let result = provider.translate(PROBE_TEXT, "en", "zh", service_config.as_ref()).await?;
+    if result.text.trim().is_empty() {
+        return Err(AppError::Translation("Service returned an empty translation".to_string()));
+    }
+    Ok(result.text)
+}
@@ -0,0 +1,113 @@
+/// 候选译文数量上限
+const MAX_VARIANTS: usize = 5;
+
+/// 服务配置中的 `variants`：大于 1 时要求模型给出多个候选译文
+pub(crate) fn variant_count(config: Option<&serde_json::Value>) -> usize {
+    config
+        .and_then(|c| c.get("variants"))
+        .and_then(|v| v.as_u64())
+        .map(|n| (n as usize).min(MAX_VARIANTS))
+        .unwrap_or(1)
+}
+
+/// 拆分逐行输出的候选译文，去掉模型可能加上的编号，第一条为最佳译文
+pub(crate) fn split_variants(output: &str) -> (String, Vec<String>) {
+    let mut variants: Vec<String> = output
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+            let line = match line[digits..].strip_prefix(['.', ')', '、']) {
+                Some(rest) if digits > 0 => rest,
+                _ => line.trim_start_matches(['-', '*']),
+            };
+            line.trim().to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    variants.dedup();
+    if variants.is_empty() {
+        return (output.trim().to_string(), Vec::new());
+    }
+    let best = variants.remove(0);
+    (best, variants)
+}
+
+/// LLM 服务的提示词。`system` 为空时整段提示作为用户消息发送
+pub(crate) struct Prompt {
+    pub system: Option<String>,
+    pub user: String,
+}
+
+impl Prompt {
+    /// 未配置模板时的默认提示
+    pub fn translation_engine(text: &str, target_lang: &str) -> Self {
+        Prompt {
+            system: Some(format!(
+                "You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.",
+                target_lang
+            )),
+            user: text.to_string(),
+        }
+    }
+
+    /// 读取服务配置中的 `promptTemplate`，支持 `{text}`、`{source}`、`{target}` 占位符。
+    /// 模板包含 `{text}` 时渲染结果即用户消息；否则作为系统提示，原文单独发送
+    pub fn from_template(
+        config: Option<&serde_json::Value>,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Option<Self> {
+        let template = config
+            .and_then(|c| c.get("promptTemplate"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())?;
+
+        let source = if source_lang == "auto" { "the source language" } else { source_lang };
+        let rendered = template
+            .replace("{source}", source)
+            .replace("{target}", target_lang);
+
+        Some(if rendered.contains("{text}") {
+            Prompt {
+                system: None,
+                user: rendered.replace("{text}", text),
+            }
+        } else {
+            Prompt {
+                system: Some(rendered),
+                user: text.to_string(),
+            }
+        })
+    }
+
+    /// 要求模型逐行给出 `count` 个不同译文；原文多行时无法按行区分，保持不变
+    pub fn with_variants(mut self, text: &str, count: usize) -> Self {
+        if count <= 1 || text.contains('\n') {
+            return self;
+        }
+        let instruction = format!(
+            "Give {} different translations, one per line, best first. Output only the translations, without numbering or explanations.",
+            count
+        );
+        match &mut self.system {
+            Some(system) => {
+                system.push(' ');
+                system.push_str(&instruction);
+            }
+            None => self.user = format!("{}\n\n{}", self.user, instruction),
+        }
+        self
+    }
+
+    /// OpenAI 兼容接口的 `messages` 数组
+    pub fn messages(&self) -> Vec<serde_json::Value> {
+        let mut messages = Vec::new();
+        if let Some(system) = &self.system {
+            messages.push(serde_json::json!({ "role": "system", "content": system }));
+        }
+        messages.push(serde_json::json!({ "role": "user", "content": self.user }));
+        messages
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::{AppError, Result};
+use base64::{engine::general_purpose, Engine as _};
+
+/// 代理 CONNECT 响应头的长度上限
+const MAX_CONNECT_RESPONSE: usize = 8 * 1024;
+
+/// 服务配置中的代理设置
+enum Setting<'a> {
+    /// 未配置：沿用系统代理环境变量
+    System,
+    /// 直连
+    Direct,
+    Proxy { url: &'a str, username: Option<&'a str>, password: Option<&'a str> },
+}
+
+fn setting(config: Option<&serde_json::Value>) -> Result<Setting<'_>> {
+    let Some(proxy) = config.and_then(|c| c.get("proxy")) else {
+        return Ok(Setting::System);
+    };
+
+    let (url, username, password) = match proxy {
+        serde_json::Value::Bool(false) | serde_json::Value::Null => return Ok(Setting::Direct),
+        serde_json::Value::String(url) => (url.as_str(), None, None),
+        serde_json::Value::Object(obj) => {
+            if obj.get("enabled").and_then(|v| v.as_bool()) == Some(false) {
+                return Ok(Setting::Direct);
+            }
+            let get = |key: &str| obj.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+            (get("url").unwrap_or_default(), get("username"), get("password"))
+        }
+        _ => return Err(AppError::Config("Invalid proxy setting".to_string())),
+    };
+
+    if url.trim().is_empty() {
+        return Ok(Setting::Direct);
+    }
+    Ok(Setting::Proxy { url: url.trim(), username, password })
+}
+
+/// 按服务配置中的 `proxy` 设置客户端代理。支持以下形式：
+/// - `"http://host:port"` / `"socks5://host:port"`
+/// - `{ "url": "...", "username": "...", "password": "..." }`
+/// - `false` 或空字符串：该服务直连（同时忽略系统代理）
+///
+/// 未配置时保持 reqwest 默认行为（读取系统代理环境变量）
+pub(crate) fn apply(
+    builder: reqwest::ClientBuilder,
+    config: Option<&serde_json::Value>,
+) -> Result<reqwest::ClientBuilder> {
+    match setting(config)? {
+        Setting::System => Ok(builder),
+        Setting::Direct => Ok(builder.no_proxy()),
+        Setting::Proxy { url, username, password } => {
+            let mut proxy = reqwest::Proxy::all(url)
+                .map_err(|e| AppError::Config(format!("Invalid proxy URL: {}", e)))?;
+            if let Some(username) = username {
+                proxy = proxy.basic_auth(username, password.unwrap_or_default());
+            }
+            Ok(builder.proxy(proxy))
+        }
+    }
+}
+
+/// 经 `proxy` 配置的 HTTP 代理（CONNECT）建立到 `host:port` 的隧道，供 reqwest 之外的连接
+/// （如 WebSocket）使用。未配置代理或直连时返回 None；SOCKS 代理不支持
+pub(crate) async fn tunnel(
+    config: Option<&serde_json::Value>,
+    host: &str,
+    port: u16,
+) -> Result<Option<tokio::net::TcpStream>> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let Setting::Proxy { url, username, password } = setting(config)? else {
+        return Ok(None);
+    };
+    let url = url::Url::parse(url).map_err(|e| AppError::Config(format!("Invalid proxy URL: {}", e)))?;
+    if url.scheme() != "http" {
+        return Err(AppError::Config(format!(
+            "Unsupported proxy scheme for this connection: {} (use an HTTP proxy)",
+            url.scheme()
+        )));
+    }
+    let proxy_host = url.host_str().ok_or_else(|| AppError::Config("Proxy URL has no host".to_string()))?;
+    let proxy_port = url.port_or_known_default().unwrap_or(80);
+
+    let username = username.or(Some(url.username()).filter(|s| !s.is_empty()));
+    let password = password.or(url.password());
+
+    let mut stream = tokio::net::TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .map_err(|e| AppError::Network(format!("Failed to connect to proxy: {}", e)))?;
+
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if let Some(username) = username {
+        let credentials = format!("{}:{}", username, password.unwrap_or_default());
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            general_purpose::STANDARD.encode(credentials)
+        ));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // 逐字节读取响应头，避免多读走隧道后的数据
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_CONNECT_RESPONSE {
+            return Err(AppError::Network("Proxy response header too large".to_string()));
+        }
+        let mut byte = [0u8];
+        if stream.read(&mut byte).await? == 0 {
+            return Err(AppError::Network("Proxy closed the connection".to_string()));
+        }
+        head.push(byte[0]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(AppError::Network(format!("Proxy refused the tunnel: {}", status_line)));
+    }
+    Ok(Some(stream))
+}
+
+/// 服务未单独配置时继承全局 `proxy`
+pub(crate) fn inherit_global(
+    service_config: Option<serde_json::Value>,
+    global: Option<&std::collections::HashMap<String, serde_json::Value>>,
+) -> Option<serde_json::Value> {
+    let Some(global_proxy) = global.and_then(|g| g.get("proxy")) else {
+        return service_config;
+    };
+    let mut service_config = service_config.unwrap_or_else(|| serde_json::json!({}));
+    if let Some(obj) = service_config.as_object_mut() {
+        obj.entry("proxy".to_string()).or_insert_with(|| global_proxy.clone());
+    }
+    Some(service_config)
+}
@@ -0,0 +1,163 @@
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Constants that distinguish SigV4-style signing schemes.
+pub struct SigningScheme {
+    pub algorithm: &'static str,
+    pub key_prefix: &'static str,
+    pub terminator: &'static str,
+    pub date_header: &'static str,
+    pub hash_header: &'static str,
+    pub token_header: &'static str,
+}
+
+pub const AWS_V4: SigningScheme = SigningScheme {
+    algorithm: "AWS4-HMAC-SHA256",
+    key_prefix: "AWS4",
+    terminator: "aws4_request",
+    date_header: "x-amz-date",
+    hash_header: "x-amz-content-sha256",
+    token_header: "x-amz-security-token",
+};
+
+/// Volcengine uses the SigV4 algorithm with its own header names and no secret prefix.
+pub const VOLCENGINE: SigningScheme = SigningScheme {
+    algorithm: "HMAC-SHA256",
+    key_prefix: "",
+    terminator: "request",
+    date_header: "x-date",
+    hash_header: "x-content-sha256",
+    token_header: "x-security-token",
+};
+
+pub struct SigningParams<'a> {
+    pub access_key_id: &'a str,
+    pub secret_access_key: &'a str,
+    pub session_token: Option<&'a str>,
+    pub region: &'a str,
+    pub service: &'a str,
+}
+
+/// Signs a request and returns the headers that must be sent with it,
+/// including `authorization`. `headers` are the caller's own headers that
+/// should be covered by the signature (`host` is added automatically).
+pub fn sign(
+    scheme: &SigningScheme,
+    params: &SigningParams,
+    method: &str,
+    url: &url::Url,
+    headers: &[(&str, &str)],
+    payload: &[u8],
+    now: DateTime<Utc>,
+) -> Result<Vec<(String, String)>> {
+    let host = url.host_str()
+        .ok_or_else(|| AppError::Config("Request URL has no host".to_string()))?;
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let short_date = now.format("%Y%m%d").to_string();
+    let payload_hash = hex::encode(Sha256::digest(payload));
+
+    let mut extra_headers = vec![
+        (scheme.date_header.to_string(), amz_date.clone()),
+        (scheme.hash_header.to_string(), payload_hash.clone()),
+    ];
+    if let Some(token) = params.session_token.filter(|t| !t.is_empty()) {
+        extra_headers.push((scheme.token_header.to_string(), token.to_string()));
+    }
+
+    let mut canonical: Vec<(String, String)> = headers
+        .iter()
+        .map(|(k, v)| (k.to_lowercase(), v.trim().to_string()))
+        .chain(std::iter::once(("host".to_string(), host.to_string())))
+        .chain(extra_headers.iter().cloned())
+        .collect();
+    canonical.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let canonical_headers: String = canonical
+        .iter()
+        .map(|(k, v)| format!("{}:{}\n", k, v))
+        .collect();
+    let signed_headers = canonical
+        .iter()
+        .map(|(k, _)| k.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method,
+        canonical_uri(url.path()),
+        canonical_query(url),
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+
+    let credential_scope = format!("{}/{}/{}/{}", short_date, params.region, params.service, scheme.terminator);
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        scheme.algorithm,
+        amz_date,
+        credential_scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let secret = format!("{}{}", scheme.key_prefix, params.secret_access_key);
+    let k_date = hmac_sha256(secret.as_bytes(), short_date.as_bytes())?;
+    let k_region = hmac_sha256(&k_date, params.region.as_bytes())?;
+    let k_service = hmac_sha256(&k_region, params.service.as_bytes())?;
+    let k_signing = hmac_sha256(&k_service, scheme.terminator.as_bytes())?;
+    let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes())?);
+
+    extra_headers.push((
+        "authorization".to_string(),
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            scheme.algorithm, params.access_key_id, credential_scope, signed_headers, signature
+        ),
+    ));
+
+    Ok(extra_headers)
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = HmacSha256::new_from_slice(key)
+        .map_err(|_| AppError::Config("Invalid HMAC key".to_string()))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// Non-S3 services expect each path segment to be URI-encoded a second time.
+fn canonical_uri(path: &str) -> String {
+    if path.is_empty() {
+        return "/".to_string();
+    }
+    path.split('/')
+        .map(uri_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn canonical_query(url: &url::Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| (uri_encode(&k), uri_encode(&v)))
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn uri_encode(input: &str) -> String {
+    url::form_urlencoded::byte_serialize(input.as_bytes())
+        .collect::<String>()
+        .replace("+", "%20")
+        .replace("*", "%2A")
+        .replace("%7E", "~")
+}
@@ -0,0 +1,224 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use futures_util::StreamExt;
+use crate::translate::TranslationService;
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+const DEFAULT_API_URL: &str = "https://spark-api-open.xf-yun.com/v1/chat/completions";
+
+/// The HTTP chat-completions API authenticates with the console's APIPassword
+/// as a bearer token (the HMAC-signed URL scheme is only for the WebSocket API).
+fn api_password(config: Option<&serde_json::Value>) -> Result<String> {
+    let get = |key: &str| config.and_then(|c| c.get(key)).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+    if let Some(password) = get("apiPassword") {
+        return Ok(password.to_string());
+    }
+    // WebSocket credentials don't work here; say so instead of a bare "not configured"
+    let message = if get("apiKey").is_some() || get("apiSecret").is_some() {
+        "Spark HTTP API needs the APIPassword from the console; apiKey/apiSecret are only for the WebSocket API"
+    } else {
+        "Spark API password not configured"
+    };
+    Err(AppError::Config(message.to_string()))
+}
+
+fn app_id(config: Option<&serde_json::Value>) -> Option<&str> {
+    config.and_then(|c| c.get("appId")).and_then(|v| v.as_str()).filter(|s| !s.is_empty())
+}
+
+fn build_body(text: &str, target_lang: &str, model: &str, app_id: Option<&str>, stream: bool) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "system",
+                "content": format!("You are a translation engine. Translate the following text to {}. Output ONLY the translated text, no explanations.", target_lang)
+            },
+            {
+                "role": "user",
+                "content": text
+            }
+        ],
+        "stream": stream
+    });
+    if let Some(app_id) = app_id {
+        body["user"] = serde_json::json!(app_id);
+    }
+    body
+}
+
+pub async fn translate(
+    text: &str,
+    _source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let api_password = api_password(config)?;
+
+    let api_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_API_URL);
+
+    let model = config
+        .and_then(|c| c.get("model"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("generalv3.5");
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let response = client
+        .post(api_url)
+        .header("Authorization", format!("Bearer {}", api_password))
+        .json(&build_body(text, target_lang, model, app_id(config), false))
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Spark", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    if json["code"].as_i64().map(|c| c != 0).unwrap_or(false) {
+        return Err(AppError::Api {
+            service: "Spark".to_string(),
+            message: json["message"].as_str().unwrap_or("Unknown error").to_string(),
+        });
+    }
+
+    let translated_text = json["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| AppError::Translation("No translation in Spark response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "Spark".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+pub async fn translate_stream<F>(
+    text: &str,
+    _source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+    mut on_delta: F,
+) -> Result<String>
+where
+    F: FnMut(&str),
+{
+    let api_password = api_password(config)?;
+
+    let api_url = config
+        .and_then(|c| c.get("apiUrl"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(DEFAULT_API_URL);
+
+    let model = config
+        .and_then(|c| c.get("model"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("generalv3.5");
+
+    let client = super::http::client(config, super::stream_timeout(config))?;
+
+    let response = client
+        .post(api_url)
+        .header("Authorization", format!("Bearer {}", api_password))
+        .json(&build_body(text, target_lang, model, app_id(config), true))
+        .send_retrying()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Spark", status, error_text));
+    }
+
+    let mut full_text = String::new();
+    let mut buffer = String::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AppError::Network(format!("Stream error: {}", e)))?;
+        let chunk_str = std::str::from_utf8(&chunk)
+            .map_err(|e| AppError::Unknown(format!("Invalid UTF-8 in stream: {}", e)))?;
+        buffer.push_str(chunk_str);
+
+        while let Some(pos) = buffer.find('\n') {
+            let line = buffer[..pos].trim().to_string();
+            buffer = buffer[pos + 1..].to_string();
+
+            if line.is_empty() || !line.starts_with("data:") {
+                continue;
+            }
+
+            let data = line.trim_start_matches("data:").trim();
+            if data == "[DONE]" {
+                return Ok(full_text);
+            }
+
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(data) {
+                if let Some(delta) = json["choices"][0]["delta"]["content"].as_str() {
+                    if !delta.is_empty() {
+                        on_delta(delta);
+                        full_text.push_str(delta);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(full_text)
+}
+
+pub struct Spark;
+
+#[async_trait]
+impl TranslationService for Spark {
+    fn name(&self) -> &str {
+        "Spark"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["spark", "讯飞星火"]
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["apiPassword"]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "API password required"
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        translate_stream(text, source_lang, target_lang, config, on_delta).await
+    }
+}
@@ -0,0 +1,147 @@
+use crate::models::TranslationResult;
+use crate::error::{AppError, Result};
+use crate::services::sigv4;
+use chrono::Utc;
+use crate::translate::TranslationService;
+use crate::translate::languages::{self, LanguageSupport};
+use crate::services::http::SendRetrying;
+use async_trait::async_trait;
+
+const HOST: &str = "translate.volcengineapi.com";
+const QUERY: &str = "Action=TranslateText&Version=2020-06-01";
+const SERVICE: &str = "translate";
+
+pub async fn translate(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    config: Option<&serde_json::Value>,
+) -> Result<TranslationResult> {
+    let access_key_id = config
+        .and_then(|c| c.get("accessKeyId"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Config("Volcengine access key ID not configured".to_string()))?;
+
+    let access_key_secret = config
+        .and_then(|c| c.get("accessKeySecret"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Config("Volcengine access key secret not configured".to_string()))?;
+
+    let region = config
+        .and_then(|c| c.get("region"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("cn-north-1");
+
+    let mut body = serde_json::json!({
+        "TargetLanguage": map_lang(target_lang),
+        "TextList": [text]
+    });
+    if source_lang != "auto" {
+        body["SourceLanguage"] = serde_json::json!(map_lang(source_lang));
+    }
+    let payload = serde_json::to_vec(&body)?;
+
+    let url = url::Url::parse(&format!("https://{}/?{}", HOST, QUERY))
+        .map_err(|e| AppError::Config(format!("Invalid Volcengine URL: {}", e)))?;
+
+    let signed_headers = sigv4::sign(
+        &sigv4::VOLCENGINE,
+        &sigv4::SigningParams {
+            access_key_id: &access_key_id,
+            secret_access_key: &access_key_secret,
+            session_token: None,
+            region,
+            service: SERVICE,
+        },
+        "POST",
+        &url,
+        &[("content-type", "application/json")],
+        &payload,
+        Utc::now(),
+    )?;
+
+    let client = super::http::client(config, super::request_timeout(config))?;
+
+    let mut request = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(payload);
+    for (name, value) in signed_headers {
+        request = request.header(name, value);
+    }
+
+    let response = request.send_retrying().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Volcengine", status, error_text));
+    }
+
+    let json: serde_json::Value = response.json().await?;
+
+    if let Some(error) = json["ResponseMetadata"]["Error"].as_object() {
+        let message = error.get("Message").and_then(|v| v.as_str()).unwrap_or("Unknown error");
+        return Err(AppError::Api {
+            service: "Volcengine".to_string(),
+            message: message.to_string(),
+        });
+    }
+
+    let translated_text = json["TranslationList"][0]["Translation"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::Translation("No translation in Volcengine response".to_string()))?;
+
+    Ok(TranslationResult {
+        name: "Volcengine".to_string(),
+        text: translated_text,
+        error: None,
+        alternatives: Vec::new(),
+    })
+}
+
+fn map_lang(lang: &str) -> &str {
+    match lang {
+        "zh-CN" | "zh-Hans" => "zh",
+        "zh-TW" | "zh-Hant" => "zh-Hant",
+        other => other,
+    }
+}
+
+pub struct Volcengine;
+
+#[async_trait]
+impl TranslationService for Volcengine {
+    fn name(&self) -> &str {
+        "Volcengine"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["volcengine", "火山翻译"]
+    }
+
+    fn languages(&self) -> LanguageSupport {
+        LanguageSupport::Among(languages::BROAD)
+    }
+
+    fn required_keys(&self) -> &'static [&'static str] {
+        &["accessKeyId", "accessKeySecret"]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "Access key ID and secret required"
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult> {
+        translate(text, source_lang, target_lang, config).await
+    }
+}
@@ -0,0 +1,70 @@
+pub mod reading;
+pub mod report;
+
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 一次学习/工作会话中翻译过的所有内容，用于导出报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub id: String,
+    pub title: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub entries: Vec<SessionEntry>,
+}
+
+/// 会话中的一条记录：原文、选定译文、笔记和可选截图（base64 PNG）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEntry {
+    pub source_text: String,
+    pub translation: String,
+    #[serde(default)]
+    pub source_lang: Option<String>,
+    #[serde(default)]
+    pub target_lang: Option<String>,
+    #[serde(default)]
+    pub service: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub screenshot: Option<String>,
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+}
+
+static SESSIONS: Lazy<Mutex<HashMap<String, Session>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn start(title: Option<String>) -> Session {
+    let session = Session {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: title.filter(|t| !t.trim().is_empty()),
+        started_at: Utc::now(),
+        entries: Vec::new(),
+    };
+    SESSIONS.lock().unwrap().insert(session.id.clone(), session.clone());
+    session
+}
+
+pub fn add_entry(session_id: &str, entry: SessionEntry) -> Result<usize> {
+    let mut sessions = SESSIONS.lock().unwrap();
+    let session = sessions
+        .get_mut(session_id)
+        .ok_or_else(|| AppError::InvalidRequest(format!("Unknown session: {}", session_id)))?;
+    session.entries.push(entry);
+    Ok(session.entries.len() - 1)
+}
+
+pub fn get(session_id: &str) -> Result<Session> {
+    SESSIONS
+        .lock()
+        .unwrap()
+        .get(session_id)
+        .cloned()
+        .ok_or_else(|| AppError::InvalidRequest(format!("Unknown session: {}", session_id)))
+}
@@ -0,0 +1,187 @@
+use crate::dictionary::{self, DictionaryEntry};
+use crate::error::{AppError, Result};
+use crate::phonetic::{self, PhoneticResult};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// 阅读视图中的一个词元及其在原文中的位置（UTF-16 偏移，与前端字符串下标一致）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Token {
+    pub index: usize,
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    pub sentence_index: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingSession {
+    pub id: String,
+    pub tokens: Vec<Token>,
+}
+
+/// `lookup_token` 的返回值，只包含预取好的数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub token: Token,
+    pub sentence: String,
+    pub phonetic: Option<PhoneticResult>,
+    pub entry: Option<DictionaryEntry>,
+    /// 预取是否已完成；为 false 时前端可稍后重试
+    pub prefetched: bool,
+}
+
+struct ReadingState {
+    tokens: Vec<Token>,
+    sentences: Vec<String>,
+    /// 小写单词 → 词典条目（None 表示已查询但无结果）
+    entries: Mutex<HashMap<String, Option<DictionaryEntry>>>,
+}
+
+static READING: Lazy<Mutex<HashMap<String, Arc<ReadingState>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '\n')
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '\'' || c == '’'
+}
+
+/// 按句子和单词切分原文
+fn tokenize(text: &str) -> (Vec<Token>, Vec<String>) {
+    let mut tokens = Vec::new();
+    let mut sentences = Vec::new();
+    let mut sentence_start = 0;
+    // 单词起点：(字节偏移, UTF-16 偏移)
+    let mut word_start: Option<(usize, usize)> = None;
+    let mut utf16_pos = 0;
+
+    for (pos, c) in text.char_indices() {
+        let utf16_start = utf16_pos;
+        utf16_pos += c.len_utf16();
+        if is_word_char(c) {
+            word_start.get_or_insert((pos, utf16_start));
+            continue;
+        }
+        if let Some((start, utf16_word_start)) = word_start.take() {
+            tokens.push(Token {
+                index: tokens.len(),
+                text: text[start..pos].to_string(),
+                start: utf16_word_start,
+                end: utf16_start,
+                sentence_index: sentences.len(),
+            });
+        }
+        if is_sentence_end(c) {
+            let end = pos + c.len_utf8();
+            sentences.push(text[sentence_start..end].trim().to_string());
+            sentence_start = end;
+        }
+    }
+    if let Some((start, utf16_word_start)) = word_start {
+        tokens.push(Token {
+            index: tokens.len(),
+            text: text[start..].to_string(),
+            start: utf16_word_start,
+            end: utf16_pos,
+            sentence_index: sentences.len(),
+        });
+    }
+    if sentence_start < text.len() {
+        sentences.push(text[sentence_start..].trim().to_string());
+    }
+
+    (tokens, sentences)
+}
+
+/// 打开阅读会话并在后台从离线词典预取每个英文单词的条目
+pub fn open(text: &str) -> ReadingSession {
+    let (tokens, sentences) = tokenize(text);
+    let id = uuid::Uuid::new_v4().to_string();
+    let state = Arc::new(ReadingState {
+        tokens: tokens.clone(),
+        sentences,
+        entries: Mutex::new(HashMap::new()),
+    });
+    READING.lock().unwrap().insert(id.clone(), state.clone());
+
+    tauri::async_runtime::spawn(prefetch(state));
+
+    ReadingSession { id, tokens }
+}
+
+pub fn close(session_id: &str) {
+    READING.lock().unwrap().remove(session_id);
+}
+
+async fn prefetch(state: Arc<ReadingState>) {
+    let mut words: Vec<String> = state
+        .tokens
+        .iter()
+        .filter(|token| phonetic::is_single_english_word(&token.text))
+        .map(|token| token.text.to_lowercase())
+        .collect();
+    words.sort();
+    words.dedup();
+
+    for word in words {
+        crate::background::wait_until_resumed().await;
+        // 会话已关闭时停止预取
+        if Arc::strong_count(&state) == 1 {
+            return;
+        }
+        // 只查离线词典：整篇文章的每个词都查网络来源会发出大量请求
+        let entry = match dictionary::lookup_offline(&word).await {
+            Ok(entries) => entries.into_iter().next(),
+            Err(e) => {
+                println!("Prefetch for '{}' failed: {}", word, e);
+                None
+            }
+        };
+        state.entries.lock().unwrap().insert(word, entry);
+    }
+}
+
+/// 零网络查询：只返回预取结果和所在句子，供悬停释义和“结合上下文解释”使用
+pub fn lookup_token(session_id: &str, token_index: usize) -> Result<TokenInfo> {
+    let state = READING
+        .lock()
+        .unwrap()
+        .get(session_id)
+        .cloned()
+        .ok_or_else(|| AppError::InvalidRequest(format!("Unknown reading session: {}", session_id)))?;
+
+    let token = state
+        .tokens
+        .get(token_index)
+        .cloned()
+        .ok_or_else(|| AppError::InvalidRequest(format!("Token index out of range: {}", token_index)))?;
+
+    let key = token.text.to_lowercase();
+    let (prefetched, entry) = match state.entries.lock().unwrap().get(&key) {
+        Some(entry) => (true, entry.clone()),
+        None => (!phonetic::is_single_english_word(&token.text), None),
+    };
+
+    // 离线音标表优先，其次使用词典条目中的音标
+    let phonetic = phonetic::get_phonetic_both(&token.text).or_else(|| {
+        entry.as_ref().and_then(|entry| {
+            let (uk, us) = dictionary::get_phonetics(entry);
+            (uk.is_some() || us.is_some()).then_some(PhoneticResult { uk, us })
+        })
+    });
+
+    Ok(TokenInfo {
+        sentence: state.sentences.get(token.sentence_index).cloned().unwrap_or_default(),
+        token,
+        phonetic,
+        entry,
+        prefetched,
+    })
+}
@@ -0,0 +1,109 @@
+use super::Session;
+use crate::worksheet::template::escape_html;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[serde(alias = "md")]
+    Markdown,
+    Html,
+}
+
+fn title(session: &Session) -> String {
+    session
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Translation session {}", session.started_at.format("%Y-%m-%d %H:%M")))
+}
+
+fn lang_pair(source: Option<&str>, target: Option<&str>) -> Option<String> {
+    match (source, target) {
+        (None, None) => None,
+        (source, target) => Some(format!("{} → {}", source.unwrap_or("auto"), target.unwrap_or("?"))),
+    }
+}
+
+pub fn render(session: &Session, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Markdown => render_markdown(session),
+        ReportFormat::Html => render_html(session),
+    }
+}
+
+fn render_markdown(session: &Session) -> String {
+    let mut out = format!("# {}\n\n", title(session));
+    out.push_str(&format!("_{} entries, started {}_\n", session.entries.len(), session.started_at.to_rfc3339()));
+
+    for (index, entry) in session.entries.iter().enumerate() {
+        out.push_str(&format!("\n## {}\n\n", index + 1));
+
+        let mut meta = Vec::new();
+        if let Some(pair) = lang_pair(entry.source_lang.as_deref(), entry.target_lang.as_deref()) {
+            meta.push(pair);
+        }
+        if let Some(service) = &entry.service {
+            meta.push(service.clone());
+        }
+        if !meta.is_empty() {
+            out.push_str(&format!("_{}_\n\n", meta.join(" · ")));
+        }
+
+        // Quote each source line so multi-line text stays inside the blockquote
+        for line in entry.source_text.lines() {
+            out.push_str(&format!("> {}\n", line));
+        }
+        out.push('\n');
+        out.push_str(entry.translation.trim());
+        out.push('\n');
+
+        if let Some(notes) = entry.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+            out.push_str(&format!("\n**Notes:** {}\n", notes.trim()));
+        }
+        if let Some(screenshot) = &entry.screenshot {
+            out.push_str(&format!("\n![screenshot {}](data:image/png;base64,{})\n", index + 1, screenshot));
+        }
+    }
+
+    out
+}
+
+fn render_html(session: &Session) -> String {
+    let title = escape_html(&title(session));
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>\n\
+         body {{ font-family: system-ui, sans-serif; max-width: 960px; margin: 2em auto; }}\n\
+         .entry {{ display: grid; grid-template-columns: 1fr 1fr; gap: 1em; border-top: 1px solid #ddd; padding: 1em 0; }}\n\
+         .meta, .notes {{ grid-column: 1 / -1; color: #666; }}\n\
+         .source, .translation {{ white-space: pre-wrap; }}\n\
+         img {{ grid-column: 1 / -1; max-width: 100%; }}\n\
+         </style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        title
+    );
+
+    for entry in &session.entries {
+        out.push_str("<section class=\"entry\">\n");
+        let mut meta = Vec::new();
+        if let Some(pair) = lang_pair(entry.source_lang.as_deref(), entry.target_lang.as_deref()) {
+            meta.push(pair);
+        }
+        if let Some(service) = &entry.service {
+            meta.push(service.clone());
+        }
+        if !meta.is_empty() {
+            out.push_str(&format!("<div class=\"meta\">{}</div>\n", escape_html(&meta.join(" · "))));
+        }
+        out.push_str(&format!("<div class=\"source\">{}</div>\n", escape_html(&entry.source_text)));
+        out.push_str(&format!("<div class=\"translation\">{}</div>\n", escape_html(entry.translation.trim())));
+        if let Some(notes) = entry.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+            out.push_str(&format!("<div class=\"notes\">{}</div>\n", escape_html(notes.trim())));
+        }
+        if let Some(screenshot) = &entry.screenshot {
+            out.push_str(&format!("<img src=\"data:image/png;base64,{}\" alt=\"screenshot\">\n", escape_html(screenshot)));
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
@@ -0,0 +1,65 @@
+use crate::api;
+use serde_json::json;
+
+#[tokio::test]
+async fn every_schema_command_is_dispatchable() {
+    // Invalid arguments are rejected before any work happens, so this only
+    // checks that the dispatcher knows each name the schema advertises.
+    for command in api::schema().commands {
+        let envelope = api::call(command.name, json!({ "request": 0, "text": 0, "word": 0, "x": "" })).await;
+        let error = envelope.error.unwrap_or_default();
+        assert!(!error.contains("Unknown command"), "{} is not dispatched", command.name);
+    }
+}
+
+/// Checks that a serialized struct has exactly the fields the schema lists;
+/// `value` must have every optional field set.
+fn assert_schema_fields(type_name: &str, value: serde_json::Value) {
+    let spec = api::schema().types.iter().find(|spec| spec.name == type_name).unwrap();
+    let mut schema: Vec<&str> = spec.fields.iter().map(|field| field.name).collect();
+    let mut actual: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+    schema.sort_unstable();
+    actual.sort_unstable();
+    assert_eq!(schema, actual, "schema fields of {} do not match the struct", type_name);
+}
+
+#[test]
+fn dictionary_entry_schema_matches_the_struct() {
+    use crate::dictionary::level::WordLevel;
+    use crate::dictionary::DictionaryEntry;
+
+    let entry = DictionaryEntry {
+        word: "hello".to_string(),
+        phonetic: Some("/həˈləʊ/".to_string()),
+        phonetics: Vec::new(),
+        meanings: Vec::new(),
+        examples: vec!["Hello, world.".to_string()],
+        level: Some(WordLevel::new(1, Some("A1".to_string()))),
+        stale: true,
+    };
+    assert_schema_fields("DictionaryEntry", serde_json::to_value(entry).unwrap());
+}
+
+#[tokio::test]
+async fn unknown_command_returns_error_envelope() {
+    let envelope = api::call("no_such_command", json!({})).await;
+
+    assert_eq!(envelope.api_version, api::API_VERSION);
+    assert!(envelope.data.is_none());
+    assert!(envelope.error.unwrap().contains("Unknown command"));
+}
+
+#[tokio::test]
+async fn ocr_files_reports_each_file_in_order() {
+    let paths = ["missing-page-1.png", "missing-page-2.png"];
+    let envelope = api::call("ocr_files", json!({ "paths": paths, "engine": "tesseract" })).await;
+
+    let results = envelope.data.unwrap();
+    let results = results.as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    for (result, path) in results.iter().zip(paths) {
+        assert_eq!(result["path"], path);
+        assert!(result["result"].is_null());
+        assert!(result["error"].is_string());
+    }
+}
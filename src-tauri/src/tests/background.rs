@@ -0,0 +1,21 @@
+use crate::background::power;
+
+#[test]
+fn linux_battery_and_metered_state_are_parsed() {
+    assert!(power::on_battery_from_supplies([("Mains\n", "Unknown\n"), ("Battery\n", "Discharging\n")]));
+    assert!(!power::on_battery_from_supplies([("Battery\n", "Charging\n")]));
+    assert!(!power::on_battery_from_supplies([]));
+
+    assert!(power::metered_from_nmcli("unknown\nyes (guessed)\n"));
+    assert!(!power::metered_from_nmcli("no\nunknown\n"));
+}
+
+#[test]
+fn macos_pmset_output_is_parsed() {
+    let batt = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1)\t80%; discharging; 4:00 remaining\n";
+    assert!(power::on_battery_from_pmset(batt));
+    assert!(!power::on_battery_from_pmset("Now drawing from 'AC Power'\n"));
+
+    assert!(power::low_power_from_pmset("System-wide power settings:\n lowpowermode         1\n sleep 1\n"));
+    assert!(!power::low_power_from_pmset(" lowpowermode         0\n"));
+}
@@ -0,0 +1,50 @@
+use crate::ocr::barcode::{detect_bgra, detect_encoded};
+use crate::ocr::models::OcrResult;
+
+#[test]
+fn images_without_codes_yield_nothing() {
+    assert!(detect_bgra(&[255u8; 32 * 32 * 4], 32, 32).is_empty());
+    assert!(detect_encoded(b"not an image").is_empty());
+}
+
+/// 内容为 "rqrr" 的二维码，取自 rqrr 的测试数据（MIT/Apache-2.0），放大三倍后转为 PNG
+const QR_FIXTURE: &[u8] = include_bytes!("fixtures/qr_rqrr.png");
+
+#[test]
+fn decodes_qr_code_from_encoded_image() {
+    let barcodes = detect_encoded(QR_FIXTURE);
+
+    assert_eq!(barcodes.len(), 1);
+    assert_eq!(barcodes[0].format, "qr");
+    assert_eq!(barcodes[0].text, "rqrr");
+    assert_eq!((barcodes[0].x, barcodes[0].y), (24.0, 24.0));
+    assert_eq!((barcodes[0].width, barcodes[0].height), (264.0, 264.0));
+}
+
+#[test]
+fn decodes_qr_code_from_bgra_screenshot() {
+    let image = image::load_from_memory(QR_FIXTURE).unwrap().to_rgba8();
+    let (w, h) = image.dimensions();
+    let pixels: Vec<u8> = image.pixels().flat_map(|p| [p[2], p[1], p[0], p[3]]).collect();
+
+    let barcodes = detect_bgra(&pixels, w as i32, h as i32);
+
+    assert_eq!(barcodes.len(), 1);
+    assert_eq!(barcodes[0].text, "rqrr");
+}
+
+#[test]
+fn barcodes_are_omitted_from_results_without_codes() {
+    let result = OcrResult {
+        text: "text".to_string(),
+        confidence: 1.0,
+        lines: Vec::new(),
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    };
+
+    let json = serde_json::to_value(&result).unwrap();
+    assert!(json.get("barcodes").is_none());
+    let parsed: OcrResult = serde_json::from_value(json).unwrap();
+    assert!(parsed.barcodes.is_empty());
+}
@@ -0,0 +1,52 @@
+use crate::services::chunk::{reassemble, split};
+
+#[test]
+fn short_text_is_a_single_chunk() {
+    let chunks = split("One sentence. Two sentences.", 100);
+
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].text, "One sentence. Two sentences.");
+}
+
+#[test]
+fn splits_on_sentence_boundaries() {
+    let chunks = split("First one here. Second one here. Third one here.", 20);
+    let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+
+    assert_eq!(texts, ["First one here.", "Second one here.", "Third one here."]);
+    assert!(chunks.iter().all(|c| c.text.chars().count() <= 20));
+}
+
+#[test]
+fn cjk_punctuation_ends_sentences_without_spaces() {
+    let chunks = split("今天天气很好。我们去公园吧！你觉得怎么样？", 8);
+    let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+
+    assert_eq!(texts, ["今天天气很好。", "我们去公园吧！", "你觉得怎么样？"]);
+}
+
+#[test]
+fn decimals_do_not_split_sentences() {
+    let chunks = split("Version 2.5 is out. Update now.", 22);
+
+    assert_eq!(chunks[0].text, "Version 2.5 is out.");
+}
+
+#[test]
+fn overlong_sentence_is_hard_split() {
+    let sentence = "word ".repeat(30);
+    let chunks = split(sentence.trim(), 24);
+
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|c| c.text.chars().count() <= 24));
+    assert!(chunks.iter().all(|c| !c.text.starts_with(' ') && !c.text.ends_with(' ')));
+}
+
+#[test]
+fn reassembly_keeps_order_and_paragraph_breaks() {
+    let chunks = split("Alpha beta.\n\nGamma delta.", 12);
+    let translations = vec!["阿尔法贝塔。".to_string(), "伽马德尔塔。".to_string()];
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(reassemble(&chunks, &translations), "阿尔法贝塔。\n\n伽马德尔塔。");
+}
@@ -0,0 +1,64 @@
+use crate::ocr::cleanup::clean;
+use crate::ocr::models::{OcrLine, OcrResult};
+use crate::ocr::postprocess;
+
+#[test]
+fn wrapped_lines_become_one_sentence() {
+    assert_eq!(
+        clean("The quick brown fox jumps\nover the lazy dog and runs\ninto the forest."),
+        "The quick brown fox jumps over the lazy dog and runs into the forest."
+    );
+}
+
+#[test]
+fn hyphenated_words_are_joined() {
+    assert_eq!(clean("a good exam-\nple of text"), "a good example of text");
+    // 大写开头的下一行多为复合词或专有名词，保留连字符
+    assert_eq!(clean("Jean-\nPaul"), "Jean-Paul");
+}
+
+#[test]
+fn cjk_lines_join_without_spaces() {
+    assert_eq!(clean("今 天 天 气\n很 好 ， 我 们\n去 公 园"), "今天天气很好，我们去公园");
+    assert_eq!(clean("使用 Rust 编写"), "使用 Rust 编写");
+}
+
+#[test]
+fn sentence_ends_lists_and_paragraphs_keep_their_breaks() {
+    assert_eq!(clean("First sentence.\nSecond one"), "First sentence.\nSecond one");
+    assert_eq!(clean("Steps\n1. open\n2. close"), "Steps\n1. open\n2. close");
+    assert_eq!(clean("Items\n• one\n• two"), "Items\n• one\n• two");
+    assert_eq!(clean("para one\nwraps\n\npara two"), "para one wraps\n\npara two");
+}
+
+fn wrapped_result() -> OcrResult {
+    let line = |text: &str, y: f64, width: f64| OcrLine {
+        text: text.to_string(),
+        x: 0.0,
+        y,
+        width,
+        height: 12.0,
+        confidence: None,
+    };
+    let lines = vec![line("a good exam-", 0.0, 300.0), line("ple of text", 14.0, 290.0)];
+    OcrResult {
+        text: "a good exam-\nple of text".to_string(),
+        confidence: 1.0,
+        lines,
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    }
+}
+
+#[test]
+fn postprocess_cleans_text_and_paragraphs_on_request() {
+    let result = postprocess(wrapped_result(), true, true);
+
+    assert_eq!(result.text, "a good example of text");
+    assert_eq!(result.paragraphs.len(), 1);
+    assert_eq!(result.paragraphs[0].text, "a good example of text");
+
+    let result = postprocess(wrapped_result(), true, false);
+    assert_eq!(result.text, "a good exam-\nple of text");
+    assert_eq!(result.paragraphs[0].text, "a good exam-\nple of text");
+}
@@ -0,0 +1,22 @@
+use crate::ocr::confidence::estimate;
+
+#[test]
+fn clean_text_scores_high() {
+    assert!(estimate(&["The", "quick", "brown", "fox."]) > 0.95);
+    assert!(estimate(&["今", "天", "天", "气", "很", "好", "。", "你", "好"]) > 0.85);
+}
+
+#[test]
+fn noise_and_symbols_score_low() {
+    let clean = estimate(&["Hello", "world"]);
+    let noisy = estimate(&["H¤ll§", "~", "|", "w@r^d"]);
+    assert!(noisy < clean);
+    assert!(noisy < 0.7);
+}
+
+#[test]
+fn short_or_empty_results_are_less_certain() {
+    assert!(estimate(&["a"]) < estimate(&["alphabet"]));
+    assert_eq!(estimate(&[]), 0.0);
+    assert_eq!(estimate(&["  "]), 0.0);
+}
@@ -0,0 +1,56 @@
+use crate::ocr::correct::{correct_low_confidence, correct_text};
+use crate::ocr::models::{OcrLine, OcrResult};
+
+fn result(lines: &[(&str, Option<f64>)]) -> OcrResult {
+    OcrResult {
+        text: lines.iter().map(|(text, _)| *text).collect::<Vec<_>>().join("\n"),
+        confidence: 0.0,
+        lines: lines
+            .iter()
+            .map(|(text, confidence)| OcrLine {
+                text: text.to_string(),
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 20.0,
+                confidence: *confidence,
+            })
+            .collect(),
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    }
+}
+
+#[test]
+fn glyph_confusions_are_fixed() {
+    assert_eq!(correct_text("rnake the vvater"), "make the water");
+    assert_eq!(correct_text("Tirne"), "Time");
+}
+
+#[test]
+fn numeric_tokens_restore_digits() {
+    assert_eq!(correct_text("in 2O23 at l0"), "in 2023 at 10");
+}
+
+#[test]
+fn known_words_and_punctuation_are_kept() {
+    assert_eq!(correct_text("Read the BOOK, then stop."), "Read the BOOK, then stop.");
+    assert_eq!(correct_text("xyzzyq"), "xyzzyq");
+}
+
+#[test]
+fn only_low_confidence_lines_are_corrected() {
+    let corrected = correct_low_confidence(result(&[("rnake it", Some(0.5)), ("rnake it", Some(0.95))]));
+
+    assert_eq!(corrected.lines[0].text, "make it");
+    assert_eq!(corrected.lines[1].text, "rnake it");
+    assert_eq!(corrected.text, "make it\nrnake it");
+}
+
+#[test]
+fn results_without_confidence_are_left_alone() {
+    let untouched = correct_low_confidence(result(&[("rnake the vvater", None)]));
+
+    assert_eq!(untouched.text, "rnake the vvater");
+    assert_eq!(untouched.lines[0].text, "rnake the vvater");
+}
@@ -0,0 +1,655 @@
+use super::{json_response, malformed_response, mount};
+use crate::dictionary;
+use serde_json::json;
+use wiremock::MockServer;
+
+#[tokio::test]
+async fn lookup_parses_entries() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/hello", json_response(200, json!([{
+        "word": "hello",
+        "phonetic": "/həˈləʊ/",
+        "phonetics": [
+            { "text": "/həˈləʊ/", "audio": "https://example.com/hello-uk.mp3" },
+            { "text": "/həˈloʊ/", "audio": "https://example.com/hello-us.mp3" }
+        ],
+        "meanings": [{
+            "partOfSpeech": "noun",
+            "definitions": [{ "definition": "A greeting.", "example": "Hello, everyone." }]
+        }]
+    }]))).await;
+
+    let entries = dictionary::lookup_word_from(&server.uri(), "hello").await.unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].meanings[0].part_of_speech, "noun");
+    assert_eq!(
+        dictionary::get_phonetics(&entries[0]),
+        (Some("/həˈləʊ/".to_string()), Some("/həˈloʊ/".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn lookup_reports_unknown_word() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/qwzx", json_response(404, json!({
+        "title": "No Definitions Found"
+    }))).await;
+
+    assert!(dictionary::lookup_word_from(&server.uri(), "qwzx").await.is_err());
+}
+
+#[tokio::test]
+async fn lookup_reports_rate_limit() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/hello", json_response(429, json!({}))).await;
+
+    assert!(dictionary::lookup_word_from(&server.uri(), "hello").await.is_err());
+}
+
+#[tokio::test]
+async fn lookup_rejects_malformed_response() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/hello", malformed_response()).await;
+
+    assert!(dictionary::lookup_word_from(&server.uri(), "hello").await.is_err());
+}
+
+#[tokio::test]
+async fn quick_lookup_serves_second_hit_from_cache() {
+    use crate::dictionary::quick::{quick_lookup, QuickSource};
+
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/single", json_response(200, json!([[["快速", "quickly"]]]))).await;
+    let config = json!({ "apiUrl": format!("{}/single", server.uri()) });
+
+    let first = quick_lookup("quickly", "zh", None, Some(config.clone())).await.unwrap();
+    assert_eq!(first.source, QuickSource::Provider);
+    assert_eq!(first.translation.as_deref(), Some("快速"));
+
+    let second = quick_lookup("Quickly", "zh", None, Some(config)).await.unwrap();
+    assert_eq!(second.source, QuickSource::Cache);
+    assert!(!second.stale);
+}
+
+#[tokio::test]
+async fn quick_lookup_finishes_a_slow_request_in_the_background() {
+    use crate::dictionary::quick::{quick_lookup, QuickSource};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/single"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([[["缓慢", "slowly"]]]))
+                .set_delay(std::time::Duration::from_millis(600)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    let config = json!({ "apiUrl": format!("{}/single", server.uri()) });
+
+    let first = quick_lookup("slowly", "zh", None, Some(config.clone())).await.unwrap();
+    assert_eq!(first.source, QuickSource::Pending);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+    let second = quick_lookup("slowly", "zh", None, Some(config)).await.unwrap();
+    assert_eq!(second.source, QuickSource::Cache);
+    assert_eq!(second.translation.as_deref(), Some("缓慢"));
+}
+
+#[tokio::test]
+async fn wiktionary_returns_the_requested_language_section() {
+    use crate::dictionary::wiktionary;
+
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/definition/ice_cream", json_response(200, json!({
+        "en": [{
+            "partOfSpeech": "Noun",
+            "language": "English",
+            "definitions": [
+                {
+                    "definition": "A <a href=\"/wiki/frozen\">frozen</a> dessert.",
+                    "parsedExamples": [{ "example": "<i>Ice cream</i> melts fast." }]
+                },
+                { "definition": "" }
+            ]
+        }],
+        "fr": [{
+            "partOfSpeech": "Verb",
+            "language": "French",
+            "definitions": [{ "definition": "to scream", "examples": ["ice cream"] }]
+        }]
+    }))).await;
+    let base = format!("{}/definition/", server.uri());
+
+    let entry = wiktionary::lookup_from(&base, "ice cream", None).await.unwrap().unwrap();
+    assert_eq!(entry.meanings.len(), 1);
+    assert_eq!(entry.meanings[0].part_of_speech, "noun");
+    assert_eq!(entry.meanings[0].definitions.len(), 1);
+    assert_eq!(entry.meanings[0].definitions[0].definition, "A frozen dessert.");
+    assert_eq!(entry.meanings[0].definitions[0].example.as_deref(), Some("Ice cream melts fast."));
+
+    let french = wiktionary::lookup_from(&base, "ice cream", Some("fr")).await.unwrap().unwrap();
+    assert_eq!(french.meanings[0].definitions[0].example.as_deref(), Some("ice cream"));
+    assert!(wiktionary::lookup_from(&base, "ice cream", Some("de")).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn wiktionary_treats_missing_pages_as_no_entry() {
+    use crate::dictionary::wiktionary;
+
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/definition/qwzx", json_response(404, json!({ "type": "not_found" }))).await;
+    mount(&server, "GET", "/definition/broken", malformed_response()).await;
+    let base = format!("{}/definition", server.uri());
+
+    assert!(wiktionary::lookup_from(&base, "qwzx", None).await.unwrap().is_none());
+    assert!(wiktionary::lookup_from(&base, "broken", None).await.is_err());
+}
+
+fn merriam_webster_config(server: &MockServer) -> crate::dictionary::merriam_webster::MerriamWebsterConfig {
+    serde_json::from_value(json!({ "api_key": "test-key", "api_url": server.uri() })).unwrap()
+}
+
+#[tokio::test]
+async fn merriam_webster_returns_definitions_with_audio() {
+    use crate::dictionary::merriam_webster;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::Mock;
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/collegiate/json/ran"))
+        .and(query_param("key", "test-key"))
+        .respond_with(json_response(200, json!([
+            {
+                "meta": { "id": "run:1", "stems": ["run", "ran", "running"] },
+                "hwi": { "hw": "run", "prs": [{ "mw": "ˈrən", "sound": { "audio": "run00001" } }] },
+                "fl": "verb",
+                "shortdef": ["to go faster than a walk"]
+            },
+            {
+                "meta": { "id": "Rann of Kutch", "stems": ["Rann of Kutch"] },
+                "hwi": { "hw": "Rann of Kutch" },
+                "fl": "geographical name",
+                "shortdef": ["salt marsh in India"]
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let entries = merriam_webster::lookup("ran", &merriam_webster_config(&server)).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].word, "run");
+    assert_eq!(entries[0].phonetic.as_deref(), Some("/ˈrən/"));
+    assert_eq!(
+        entries[0].phonetics[0].audio.as_deref(),
+        Some("https://media.merriam-webster.com/audio/prons/en/us/mp3/r/run00001.mp3")
+    );
+    assert_eq!(entries[0].meanings[0].part_of_speech, "verb");
+}
+
+#[tokio::test]
+async fn merriam_webster_handles_suggestions_and_bad_keys() {
+    use crate::dictionary::merriam_webster;
+    use crate::error::AppError;
+
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/collegiate/json/helo", json_response(200, json!(["hello", "halo"]))).await;
+    mount(&server, "GET", "/collegiate/json/denied", wiremock::ResponseTemplate::new(200)
+        .set_body_string("Invalid API key. Not subscribed for this reference.")).await;
+    let config = merriam_webster_config(&server);
+
+    assert!(merriam_webster::lookup("helo", &config).await.unwrap().is_empty());
+    let err = merriam_webster::lookup("denied", &config).await.unwrap_err();
+    assert!(matches!(err, AppError::AuthFailed { .. }));
+}
+
+#[test]
+fn merriam_webster_audio_urls_use_subdirectories() {
+    use crate::dictionary::merriam_webster::audio_url;
+
+    assert!(audio_url("bixby01").ends_with("/bix/bixby01.mp3"));
+    assert!(audio_url("ggame01").ends_with("/gg/ggame01.mp3"));
+    assert!(audio_url("3d000001").ends_with("/number/3d000001.mp3"));
+}
+
+#[test]
+fn dictionary_settings_reject_unknown_duplicate_and_disabled_sources() {
+    use crate::dictionary::settings::DictionarySettings;
+
+    let settings: DictionarySettings = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(settings, DictionarySettings::default());
+    assert!(settings.validate().is_ok());
+
+    assert_eq!(settings.enabled_sources(), vec!["stardict", "jmdict", "free_dictionary", "ecdict"]);
+
+    let unknown: DictionarySettings = serde_json::from_value(json!({ "sources": [{ "name": "oed" }] })).unwrap();
+    assert!(unknown.validate().is_err());
+    let duplicate: DictionarySettings =
+        serde_json::from_value(json!({ "sources": [{ "name": "youdao" }, { "name": "youdao" }] })).unwrap();
+    assert!(duplicate.validate().is_err());
+    let none_enabled: DictionarySettings =
+        serde_json::from_value(json!({ "sources": [{ "name": "youdao", "enabled": false }] })).unwrap();
+    assert!(none_enabled.validate().is_err());
+
+    let no_key: DictionarySettings = serde_json::from_value(json!({
+        "sources": [{ "name": "merriam_webster" }],
+        "merriam_webster": { "api_key": " ", "dictionary": "learners" }
+    }))
+    .unwrap();
+    assert!(no_key.validate().is_err());
+}
+
+#[test]
+fn enabled_sources_are_ordered_by_priority() {
+    use crate::dictionary::settings::DictionarySettings;
+
+    let settings: DictionarySettings = serde_json::from_value(json!({
+        "sources": [
+            { "name": "ecdict", "priority": 2 },
+            { "name": "youdao", "priority": 0 },
+            { "name": "wiktionary", "enabled": false, "priority": 1 },
+            { "name": "stardict", "priority": 2 }
+        ]
+    }))
+    .unwrap();
+    assert!(settings.validate().is_ok());
+    assert_eq!(settings.enabled_sources(), vec!["youdao", "ecdict", "stardict"]);
+}
+
+#[test]
+fn dictionary_settings_are_saved_without_the_api_key() {
+    use crate::dictionary::settings::{self, DictionarySettings};
+
+    let custom: DictionarySettings = serde_json::from_value(json!({
+        "sources": [{ "name": "merriam_webster", "priority": 0 }, { "name": "ecdict", "priority": 1 }],
+        "merriam_webster": { "api_key": "secret-key" }
+    }))
+    .unwrap();
+    settings::set_settings(custom.clone()).unwrap();
+    assert_eq!(settings::settings(), custom);
+
+    let saved = std::fs::read_to_string(crate::paths::data_dir().join("dictionary_settings.json")).unwrap();
+    assert!(!saved.contains("secret-key"));
+    let saved: DictionarySettings = serde_json::from_str(&saved).unwrap();
+    assert_eq!(saved.enabled_sources(), vec!["merriam_webster", "ecdict"]);
+    assert!(saved.merriam_webster.is_none());
+
+    settings::set_settings(DictionarySettings::default()).unwrap();
+}
+
+#[tokio::test]
+async fn grouped_lookup_rejects_empty_words() {
+    assert!(crate::dictionary::lookup_grouped("  ").await.is_err());
+}
+
+/// 延迟 `delay` 后返回固定结果的来源
+struct MockSource {
+    name: &'static str,
+    delay: std::time::Duration,
+    entries: usize,
+    fails: bool,
+}
+
+impl MockSource {
+    fn returning(name: &'static str, delay_ms: u64, entries: usize) -> std::sync::Arc<dyn crate::dictionary::DictionarySource> {
+        std::sync::Arc::new(MockSource { name, delay: std::time::Duration::from_millis(delay_ms), entries, fails: false })
+    }
+
+    fn failing(name: &'static str, delay_ms: u64) -> std::sync::Arc<dyn crate::dictionary::DictionarySource> {
+        std::sync::Arc::new(MockSource { name, delay: std::time::Duration::from_millis(delay_ms), entries: 0, fails: true })
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::dictionary::DictionarySource for MockSource {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn lookup(
+        &self,
+        word: &str,
+        _settings: &crate::dictionary::settings::DictionarySettings,
+    ) -> crate::error::Result<Vec<crate::dictionary::DictionaryEntry>> {
+        tokio::time::sleep(self.delay).await;
+        if self.fails {
+            return Err(crate::error::AppError::Network("offline".to_string()));
+        }
+        Ok((0..self.entries).map(|_| cached_entry(word)).collect())
+    }
+}
+
+#[tokio::test]
+async fn first_source_with_entries_does_not_wait_for_slower_sources() {
+    use crate::dictionary::query_first;
+    use std::time::{Duration, Instant};
+    let settings = crate::dictionary::settings::DictionarySettings::default();
+    let timeout = Duration::from_secs(30);
+
+    // 优先级最高的来源有结果时不等待慢速来源
+    let sources = [MockSource::returning("local", 0, 1), MockSource::returning("slow", 20_000, 1)];
+    let started = Instant::now();
+    let (source, entries) = query_first(&sources, "hello", &settings, timeout).await.unwrap().unwrap();
+    assert_eq!((source, entries.len()), ("local", 1));
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // 优先级更高的来源仍在查询时，先返回的低优先级结果要等它没有结果才采用
+    let sources = [MockSource::returning("first", 200, 2), MockSource::returning("second", 0, 1)];
+    assert_eq!(query_first(&sources, "hello", &settings, timeout).await.unwrap().unwrap().0, "first");
+    let sources = [MockSource::returning("empty", 200, 0), MockSource::failing("broken", 0), MockSource::returning("third", 0, 1)];
+    assert_eq!(query_first(&sources, "hello", &settings, timeout).await.unwrap().unwrap().0, "third");
+
+    // 都没有结果时返回错误，超时的来源按失败处理
+    let sources = [MockSource::returning("empty", 0, 0), MockSource::returning("hung", 20_000, 1)];
+    let started = Instant::now();
+    let result = query_first(&sources, "hello", &settings, Duration::from_millis(100)).await;
+    assert!(matches!(result, Err(crate::error::AppError::Timeout(_))));
+    assert!(started.elapsed() < Duration::from_secs(5));
+    let sources = [MockSource::returning("empty", 0, 0)];
+    assert!(query_first(&sources, "hello", &settings, timeout).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn grouped_query_keeps_priority_order_and_times_out_slow_sources() {
+    use std::time::Duration;
+    let settings = crate::dictionary::settings::DictionarySettings::default();
+    let sources = [
+        MockSource::returning("slow", 100, 1),
+        MockSource::returning("hung", 20_000, 1),
+        MockSource::failing("broken", 0),
+        MockSource::returning("fast", 0, 2),
+    ];
+
+    let results = crate::dictionary::query_all(&sources, "hello", &settings, Duration::from_millis(500)).await;
+
+    let names: Vec<_> = results.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, vec!["slow", "hung", "broken", "fast"]);
+    assert_eq!(results[0].1.as_ref().unwrap().len(), 1);
+    assert!(matches!(results[1].1, Err(crate::error::AppError::Timeout(_))));
+    assert!(matches!(results[2].1, Err(crate::error::AppError::Network(_))));
+    assert_eq!(results[3].1.as_ref().unwrap().len(), 2);
+}
+
+fn youdao_response() -> serde_json::Value {
+    json!({
+        "ec": {
+            "exam_type": ["初中", "CET4"],
+            "word": [{
+                "ukphone": "həˈləʊ",
+                "usphone": "həˈloʊ",
+                "ukspeech": "hello&type=1",
+                "usspeech": "hello&type=2",
+                "trs": [
+                    { "tr": [{ "l": { "i": ["int. 喂；哈罗"] } }] },
+                    { "tr": [{ "l": { "i": ["n. 表示问候"] } }] }
+                ],
+                "return-phrase": { "l": { "i": "hello" } }
+            }]
+        },
+        "collins": {
+            "collins_entries": [{
+                "star": "★★★★★",
+                "entries": { "entry": [{ "tran_entry": [{
+                    "pos_entry": { "pos": "CONVENTION" },
+                    "tran": "You say 'Hello' to someone when you meet them. <b>你好</b>",
+                    "exam_sents": { "sent": [{ "eng_sent": "Hello, Trish.", "chn_sent": "你好，特里什。" }] }
+                }]}]}
+            }]
+        },
+        "blng_sents_part": {
+            "sentence-pair": [
+                { "sentence": "<b>Hello</b>, who is it?", "sentence-translation": "喂，是谁啊？" }
+            ]
+        }
+    })
+}
+
+#[test]
+fn youdao_parses_chinese_definitions_collins_and_examples() {
+    use crate::dictionary::youdao::{parse_response, BilingualExample};
+
+    let entry = parse_response("HELLO", youdao_response()).unwrap().unwrap();
+    assert_eq!(entry.word, "hello");
+    assert_eq!(entry.translations, vec!["int. 喂；哈罗", "n. 表示问候"]);
+    assert_eq!(entry.exam_types, vec!["初中", "CET4"]);
+    assert_eq!(entry.us_audio.as_deref(), Some("https://dict.youdao.com/dictvoice?audio=hello&type=2"));
+    assert_eq!(entry.collins_star, Some(5));
+    assert_eq!(entry.collins[0].part_of_speech, "CONVENTION");
+    assert_eq!(entry.collins[0].definition, "You say 'Hello' to someone when you meet them. 你好");
+    assert_eq!(
+        entry.examples,
+        vec![BilingualExample { english: "Hello, who is it?".to_string(), chinese: "喂，是谁啊？".to_string() }]
+    );
+
+    let general = entry.to_entry();
+    assert_eq!(general.phonetic.as_deref(), Some("/həˈləʊ/"));
+    assert_eq!(general.meanings.len(), 2);
+    assert!(general.meanings.iter().flat_map(|m| &m.definitions).all(|d| d.example.is_none()));
+    assert_eq!(general.examples, vec!["Hello, who is it? 喂，是谁啊？"]);
+}
+
+#[test]
+fn youdao_voice_urls_are_encoded() {
+    use crate::dictionary::youdao::voice_url;
+
+    assert_eq!(voice_url("hello&type=2"), "https://dict.youdao.com/dictvoice?audio=hello&type=2");
+    assert_eq!(
+        voice_url("rock & roll&type=1"),
+        "https://dict.youdao.com/dictvoice?audio=rock+%26+roll&type=1"
+    );
+    assert_eq!(voice_url("C#"), "https://dict.youdao.com/dictvoice?audio=C%23");
+}
+
+#[test]
+fn youdao_star_levels_accept_numbers_and_symbols() {
+    use crate::dictionary::youdao::parse_star;
+
+    assert_eq!(parse_star(&json!(3)), Some(3));
+    assert_eq!(parse_star(&json!("4")), Some(4));
+    assert_eq!(parse_star(&json!("★★")), Some(2));
+    assert_eq!(parse_star(&json!("")), None);
+    assert_eq!(parse_star(&json!(null)), None);
+}
+
+#[tokio::test]
+async fn youdao_lookup_returns_none_for_unknown_words() {
+    use crate::dictionary::youdao;
+
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/jsonapi", json_response(200, json!({ "input": "qwzx", "lang": "eng" }))).await;
+
+    let base_url = format!("{}/jsonapi", server.uri());
+    assert!(youdao::lookup_from(&base_url, "qwzx").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn free_dictionary_keeps_synonyms_and_antonyms() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/happy", json_response(200, json!([{
+        "word": "happy",
+        "phonetics": [],
+        "meanings": [{
+            "partOfSpeech": "adjective",
+            "definitions": [{ "definition": "Feeling pleasure.", "synonyms": ["glad"], "antonyms": [] }],
+            "synonyms": ["cheerful", "content"],
+            "antonyms": ["sad"]
+        }]
+    }]))).await;
+
+    let entries = dictionary::lookup_word_from(&server.uri(), "happy").await.unwrap();
+    let meaning = &entries[0].meanings[0];
+    assert_eq!(meaning.synonyms, vec!["cheerful", "content"]);
+    assert_eq!(meaning.antonyms, vec!["sad"]);
+    assert_eq!(meaning.definitions[0].synonyms, vec!["glad"]);
+}
+
+#[tokio::test]
+async fn datamuse_related_words_fill_matching_parts_of_speech() {
+    use crate::dictionary::datamuse;
+    use crate::dictionary::{Definition, DictionaryEntry, Meaning};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::Mock;
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/words"))
+        .and(query_param("rel_syn", "fast"))
+        .respond_with(json_response(200, json!([
+            { "word": "quick", "score": 100, "tags": ["adj"] },
+            { "word": "firm", "score": 90, "tags": ["adj", "adv"] },
+            { "word": "abstain", "score": 80, "tags": ["v"] }
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/words"))
+        .and(query_param("rel_ant", "fast"))
+        .respond_with(json_response(200, json!([{ "word": "slow", "score": 100, "tags": ["adj"] }])))
+        .mount(&server)
+        .await;
+
+    let related = datamuse::related_from(&format!("{}/words", server.uri()), "fast").await.unwrap();
+    let meaning = |pos: &str| Meaning {
+        part_of_speech: pos.to_string(),
+        definitions: vec![Definition { definition: "...".to_string(), ..Default::default() }],
+        ..Default::default()
+    };
+    let mut entries = vec![DictionaryEntry {
+        word: "fast".to_string(),
+        phonetic: None,
+        phonetics: Vec::new(),
+        meanings: vec![meaning("adjective"), meaning("v."), meaning("noun")],
+        examples: Vec::new(),
+        level: None,
+        stale: false,
+    }];
+    assert!(datamuse::needs_related_words("fast", &entries));
+
+    datamuse::supplement(&mut entries, &related);
+    assert_eq!(entries[0].meanings[0].synonyms, vec!["quick", "firm"]);
+    assert_eq!(entries[0].meanings[0].antonyms, vec!["slow"]);
+    assert_eq!(entries[0].meanings[1].synonyms, vec!["abstain"]);
+    assert!(entries[0].meanings[2].synonyms.is_empty());
+    assert!(!datamuse::needs_related_words("fast", &entries));
+
+    // 词性对不上或未知时不补充，名词义项不会得到形容词、动词的近义词
+    let mut nouns = vec![DictionaryEntry {
+        meanings: vec![meaning("noun"), meaning("")],
+        ..entries[0].clone()
+    }];
+    datamuse::supplement(&mut nouns, &related);
+    assert!(nouns[0].meanings.iter().all(|m| m.synonyms.is_empty() && m.antonyms.is_empty()));
+}
+
+#[test]
+fn level_tables_accept_explicit_and_implicit_ranks() {
+    use crate::dictionary::level::{LevelTable, WordLevel};
+
+    let table = LevelTable::parse("# comment\nthe\nbe A1\nabandon 4123 b2\nstudy 1500 A1\n");
+    assert_eq!(table.get("The"), Some(WordLevel { rank: 1, cefr: None, coca: 1, oxford: false }));
+    assert_eq!(table.get("be"), Some(WordLevel { rank: 2, cefr: Some("A1".to_string()), coca: 1, oxford: false }));
+    assert_eq!(table.get("abandoned"), Some(WordLevel { rank: 4123, cefr: Some("B2".to_string()), coca: 5, oxford: false }));
+    assert_eq!(table.get("studies").map(|level| level.rank), Some(1500));
+    assert!(table.get("zyzzyva").is_none());
+}
+
+fn cached_entry(word: &str) -> crate::dictionary::DictionaryEntry {
+    crate::dictionary::DictionaryEntry {
+        word: word.to_string(),
+        phonetic: Some("/həˈləʊ/".to_string()),
+        phonetics: Vec::new(),
+        meanings: Vec::new(),
+        examples: Vec::new(),
+        level: None,
+        stale: false,
+    }
+}
+
+#[test]
+fn dictionary_cache_serves_entries_as_stale_across_reloads() {
+    use crate::dictionary::cache::DictionaryCache;
+
+    let mut cache = DictionaryCache::default();
+    cache.insert("ecdict", " Hello ", &[cached_entry("hello")], 100);
+    cache.insert("ecdict", "nothing", &[], 100);
+    assert_eq!(cache.len(), 1);
+
+    let mut reloaded = DictionaryCache::parse(&cache.to_json().unwrap()).unwrap();
+    let entries = reloaded.get("ecdict", "HELLO").unwrap();
+    assert_eq!(entries[0].word, "hello");
+    assert!(entries[0].stale);
+    assert!(reloaded.get("ecdict", "nothing").is_none());
+    // 按来源分别缓存
+    assert!(reloaded.get("youdao", "hello").is_none());
+
+    let json = serde_json::to_value(&entries[0]).unwrap();
+    assert_eq!(json["stale"], true);
+    assert!(serde_json::to_value(cached_entry("hello")).unwrap().get("stale").is_none());
+}
+
+#[test]
+fn dictionary_cache_evicts_least_recently_used_word_when_full() {
+    use crate::dictionary::cache::DictionaryCache;
+
+    let mut cache = DictionaryCache::with_capacity(2);
+    cache.insert("ecdict", "one", &[cached_entry("one")], 1);
+    cache.insert("ecdict", "two", &[cached_entry("two")], 2);
+    assert!(cache.get("ecdict", "one").is_some());
+    cache.insert("ecdict", "three", &[cached_entry("three")], 3);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("ecdict", "two").is_none());
+
+    // 重新加载后保持使用顺序
+    let mut reloaded = DictionaryCache::parse(&cache.to_json().unwrap()).unwrap();
+    assert!(reloaded.get("ecdict", "three").is_some());
+    reloaded.insert("ecdict", "four", &[cached_entry("four")], 4);
+    assert!(reloaded.get("ecdict", "one").is_none());
+    assert!(reloaded.get("ecdict", "three").is_some());
+}
+
+#[tokio::test]
+async fn stale_entries_are_served_when_sources_fail() {
+    use crate::dictionary::{group_sources, lookup_sources};
+    use std::time::Duration;
+    let settings = crate::dictionary::settings::DictionarySettings::default();
+    let timeout = Duration::from_secs(30);
+    // 非英文单词，不会查询 Datamuse
+    let word = "离线缓存";
+
+    let online = [MockSource::returning("cache_test", 0, 1)];
+    let entries = lookup_sources(&online, word, &settings, timeout).await.unwrap();
+    assert!(!entries[0].stale);
+
+    // 网络失败时返回过期的缓存
+    let offline = [MockSource::failing("cache_test", 0)];
+    let entries = lookup_sources(&offline, word, &settings, timeout).await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].stale);
+    assert!(lookup_sources(&offline, "没有缓存", &settings, timeout).await.is_err());
+
+    // 分组查询同样回退到缓存，并保留错误信息
+    let groups = group_sources(&offline, word, &settings, timeout).await;
+    assert_eq!(groups.len(), 1);
+    assert!(groups[0].error.is_some());
+    assert!(groups[0].entries[0].stale);
+
+    // 分组查询成功时也写入缓存
+    let grouped = [MockSource::returning("cache_test_grouped", 0, 2)];
+    assert!(group_sources(&grouped, word, &settings, timeout).await[0].error.is_none());
+    let groups = group_sources(&[MockSource::failing("cache_test_grouped", 0)], word, &settings, timeout).await;
+    assert_eq!(groups[0].entries.len(), 2);
+}
+
+#[test]
+fn only_local_dictionaries_are_offline_sources() {
+    let offline: Vec<_> = dictionary::source_names()
+        .into_iter()
+        .filter(|name| dictionary::find(name).unwrap().offline())
+        .collect();
+    assert_eq!(offline, ["stardict", "jmdict", "ecdict"]);
+}
@@ -0,0 +1,75 @@
+use super::service_config;
+use crate::services::draft;
+use serde_json::json;
+use std::collections::HashMap;
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const CHAT_PATH: &str = "/v1/chat/completions";
+
+fn chat_reply(content: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "choices": [{ "message": { "content": content } }]
+    }))
+}
+
+fn draft_config(server: &MockServer, debounce_ms: u64) -> HashMap<String, serde_json::Value> {
+    HashMap::from([
+        ("openai".to_string(), service_config(server, CHAT_PATH)),
+        ("draft".to_string(), json!({ "debounceMs": debounce_ms })),
+    ])
+}
+
+#[tokio::test]
+async fn newer_draft_supersedes_pending_one() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(chat_reply("你好"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let config = draft_config(&server, 50);
+
+    let (first, second) = tokio::join!(
+        draft::translate_draft("supersede", "Hel", "en", "zh", "openai", Some(&config)),
+        async {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            draft::translate_draft("supersede", "Hello", "en", "zh", "openai", Some(&config)).await
+        }
+    );
+
+    assert!(first.unwrap().is_none());
+    assert_eq!(second.unwrap().unwrap().text, "你好");
+}
+
+#[tokio::test]
+async fn completed_sentences_are_not_retranslated() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .and(body_string_contains("Good morning."))
+        .respond_with(chat_reply("早上好。"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .and(body_string_contains("How"))
+        .respond_with(chat_reply("你好吗"))
+        .mount(&server)
+        .await;
+    let config = draft_config(&server, 0);
+
+    draft::translate_draft("prefix", "Good morning. How", "en", "zh", "openai", Some(&config))
+        .await
+        .unwrap();
+    let result = draft::translate_draft("prefix", "Good morning. How are", "en", "zh", "openai", Some(&config))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result.reused_chunks, 1);
+    assert_eq!(result.translated_source, "How are");
+    assert_eq!(result.text, "早上好。你好吗");
+}
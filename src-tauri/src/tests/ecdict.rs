@@ -0,0 +1,85 @@
+use super::TempDb;
+use crate::dictionary::ecdict::{import_csv, parse_csv_record, parse_exchange, query, ExchangeForm};
+
+const CSV: &str = "word,phonetic,definition,translation,pos,collins,oxford,tag,bnc,frq,exchange,detail,audio\r\n\
+apple,'æpl,\"n. fruit with red or yellow or green skin\",\"n. 苹果, 家伙\\nn. [计] 苹果公司\",,3,1,zk gk cet4,2446,1863,s:apples,,\r\n\
+perceive,pə'si:v,\"v. to become aware of, \"\"know\"\"\",vt. 察觉，感知,,3,1,cet6 toefl,3064,2911,p:perceived/d:perceived/i:perceiving/3:perceives,,\r\n";
+
+#[test]
+fn csv_records_keep_quoted_commas_and_quotes() {
+    let (header, rest) = parse_csv_record(CSV).unwrap();
+    assert_eq!(header.len(), 13);
+    let (apple, rest) = parse_csv_record(rest).unwrap();
+    assert_eq!(apple[3], "n. 苹果, 家伙\\nn. [计] 苹果公司");
+    let (perceive, rest) = parse_csv_record(rest).unwrap();
+    assert_eq!(perceive[2], "v. to become aware of, \"know\"");
+    assert_eq!(perceive[12], "");
+    assert!(parse_csv_record(rest).is_none());
+}
+
+#[test]
+fn exchange_forms_are_named() {
+    assert_eq!(
+        parse_exchange("p:perceived/i:perceiving/x:bogus/s:"),
+        vec![
+            ExchangeForm { kind: "past".to_string(), word: "perceived".to_string() },
+            ExchangeForm { kind: "present_participle".to_string(), word: "perceiving".to_string() },
+        ]
+    );
+}
+
+#[test]
+fn imported_csv_is_queried_case_insensitively() {
+    let db = TempDb::new("ecdict");
+    assert_eq!(import_csv(CSV, &db.0).unwrap(), 2);
+    let conn = db.open();
+    let entry = query(&conn, "Apple").unwrap().unwrap();
+    assert_eq!(entry.word, "apple");
+    assert_eq!(entry.translation, vec!["n. 苹果, 家伙", "n. [计] 苹果公司"]);
+    assert_eq!(entry.tags, vec!["zk", "gk", "cet4"]);
+    assert_eq!((entry.collins, entry.oxford, entry.bnc, entry.frq), (3, true, 2446, 1863));
+    assert_eq!(entry.exchanges[0].kind, "plural");
+
+    let general = entry.to_entry();
+    assert_eq!(general.phonetic.as_deref(), Some("/'æpl/"));
+    assert_eq!(general.meanings.len(), 2);
+    assert_eq!(general.meanings[1].definitions[0].definition, "[计] 苹果公司");
+    // 词频等级来自 frq（COCA 词频顺序）和 oxford 列
+    let level = general.level.unwrap();
+    assert_eq!((level.rank, level.coca, level.oxford), (1863, 2, true));
+
+    assert_eq!(query(&conn, "perceive").unwrap().unwrap().exchanges.len(), 4);
+    assert!(query(&conn, "missing").unwrap().is_none());
+}
+
+#[test]
+fn word_levels_only_strip_suffixes_for_words_missing_from_ecdict() {
+    use crate::dictionary::ecdict::EcdictEntry;
+    use crate::dictionary::level::ecdict_level;
+
+    let entry = |word: &str, frq: i64| EcdictEntry {
+        word: word.to_string(),
+        phonetic: None,
+        definition: Vec::new(),
+        translation: Vec::new(),
+        tags: Vec::new(),
+        collins: 0,
+        oxford: false,
+        bnc: 0,
+        frq,
+        exchanges: Vec::new(),
+    };
+    let lookup = |word: &str| {
+        Ok(match word {
+            "new" => Some(entry("new", 100)),
+            "news" => Some(entry("news", 0)),
+            "walk" => Some(entry("walk", 900)),
+            _ => None,
+        })
+    };
+
+    // 收录但没有词频的词不取词根的等级
+    assert!(ecdict_level("news", lookup).is_none());
+    assert_eq!(ecdict_level("walks", lookup).map(|level| level.rank), Some(900));
+    assert_eq!(ecdict_level("new", lookup).map(|level| level.rank), Some(100));
+}
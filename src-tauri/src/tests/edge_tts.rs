@@ -0,0 +1,83 @@
+use crate::tts::edge::{audio_payload, parse_voices, resolve_voice, sec_ms_gec, ssml, word_boundaries};
+use serde_json::json;
+
+#[test]
+fn voices_resolve_from_accents_locales_and_names() {
+    assert_eq!(resolve_voice(None), "en-US-AriaNeural");
+    assert_eq!(resolve_voice(Some("uk")), "en-GB-SoniaNeural");
+    assert_eq!(resolve_voice(Some("ja-JP")), "ja-JP-NanamiNeural");
+    assert_eq!(resolve_voice(Some("zh")), "zh-CN-XiaoxiaoNeural");
+    assert_eq!(resolve_voice(Some("zh-TW")), "zh-TW-HsiaoChenNeural");
+    assert_eq!(resolve_voice(Some("de-DE-ConradNeural")), "de-DE-ConradNeural");
+    assert_eq!(resolve_voice(Some("xx-YY")), "en-US-AriaNeural");
+}
+
+#[test]
+fn ssml_escapes_text_and_sets_the_voice_language() {
+    let ssml = ssml("Tom & Jerry <3", "ja-JP-NanamiNeural");
+    assert!(ssml.contains("xml:lang='ja-JP'"));
+    assert!(ssml.contains("<voice name='ja-JP-NanamiNeural'>"));
+    assert!(ssml.contains("Tom &amp; Jerry &lt;3"));
+}
+
+#[test]
+fn sec_ms_gec_changes_every_five_minutes() {
+    let token = sec_ms_gec(1_700_000_100);
+    assert_eq!(token.len(), 64);
+    assert!(token.chars().all(|c| c.is_ascii_digit() || c.is_ascii_uppercase()));
+    assert_eq!(token, sec_ms_gec(1_700_000_299));
+    assert_ne!(token, sec_ms_gec(1_700_000_400));
+}
+
+#[test]
+fn audio_is_extracted_from_binary_frames() {
+    let header = b"X-RequestId:1\r\nContent-Type:audio/mpeg\r\nPath:audio\r\n";
+    let mut frame = (header.len() as u16).to_be_bytes().to_vec();
+    frame.extend_from_slice(header);
+    frame.extend_from_slice(&[0xFF, 0xF3]);
+    assert_eq!(audio_payload(&frame), Some(&[0xFF, 0xF3][..]));
+
+    let metadata = b"Path:audio.metadata\r\n";
+    let mut frame = (metadata.len() as u16).to_be_bytes().to_vec();
+    frame.extend_from_slice(metadata);
+    assert_eq!(audio_payload(&frame), None);
+    assert_eq!(audio_payload(&[0]), None);
+}
+
+#[test]
+fn voice_list_entries_become_tts_voices() {
+    let voices = parse_voices(json!([{
+        "Name": "Microsoft Server Speech Text to Speech Voice (ja-JP, NanamiNeural)",
+        "ShortName": "ja-JP-NanamiNeural",
+        "Gender": "Female",
+        "Locale": "ja-JP",
+        "FriendlyName": "Microsoft Nanami Online (Natural) - Japanese (Japan)",
+        "Status": "GA"
+    }]))
+    .unwrap();
+
+    assert_eq!(voices.len(), 1);
+    assert_eq!(voices[0].id, "ja-JP-NanamiNeural");
+    assert_eq!(voices[0].language, "ja-JP");
+    assert_eq!(voices[0].gender, "female");
+    assert_eq!(voices[0].engine, "edge");
+}
+
+#[test]
+fn word_boundaries_are_located_in_the_source_text() {
+    let text = "Hello 世界, hello again";
+    let mut cursor = 0;
+    let message = "X-RequestId:abc\r\nContent-Type:application/json\r\nPath:audio.metadata\r\n\r\n\
+{\"Metadata\":[{\"Type\":\"WordBoundary\",\"Data\":{\"Offset\":1000000,\"Duration\":3000000,\"text\":{\"Text\":\"Hello\",\"Length\":5}}},\
+{\"Type\":\"SessionEnd\",\"Data\":{\"Offset\":0}}]}";
+    let first = word_boundaries(message, text, &mut cursor);
+    assert_eq!(first.len(), 1);
+    assert_eq!((first[0].offset, first[0].length), (0, 5));
+    assert_eq!(first[0].audio_offset, std::time::Duration::from_millis(100));
+
+    let message = "Path:audio.metadata\r\n\r\n\
+{\"Metadata\":[{\"Type\":\"WordBoundary\",\"Data\":{\"Offset\":5000000,\"text\":{\"Text\":\"hello\"}}}]}";
+    let second = word_boundaries(message, text, &mut cursor);
+    assert_eq!((second[0].offset, second[0].length), (10, 5));
+    assert!(word_boundaries("Path:audio.metadata\r\n\r\nnot json", text, &mut cursor).is_empty());
+}
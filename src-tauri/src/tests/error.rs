@@ -0,0 +1,56 @@
+use crate::error::AppError;
+use reqwest::StatusCode;
+
+#[test]
+fn detail_classifies_rate_limits_as_retryable() {
+    let detail = AppError::RateLimitExceeded { service: "DeepL".to_string() }.detail("DeepL");
+
+    assert_eq!(detail.category, "rate_limit");
+    assert_eq!(detail.status, Some(429));
+    assert!(detail.retryable);
+}
+
+#[test]
+fn detail_reads_status_from_http_error() {
+    let auth = AppError::from_status("OpenAI", StatusCode::UNAUTHORIZED, "Incorrect API key provided");
+    let detail = auth.detail("OpenAI");
+    assert_eq!(detail.category, "auth");
+    assert_eq!(detail.status, Some(401));
+    assert!(!detail.retryable);
+
+    let server = AppError::from_status("OpenAI", StatusCode::SERVICE_UNAVAILABLE, "upstream");
+    assert_eq!(server.http_status(), Some(503));
+    assert!(server.is_retryable());
+
+    assert_eq!(AppError::Config("No API key configured".to_string()).detail("Caiyun").status, None);
+}
+
+#[test]
+fn for_service_names_rate_limits() {
+    let unknown = AppError::RateLimitExceeded { service: "unknown".to_string() }.for_service("DeepL");
+    assert!(matches!(unknown, AppError::RateLimitExceeded { ref service } if service == "DeepL"));
+
+    let wrapped = AppError::Api { service: "OpenAI".to_string(), message: "Rate limit reached".to_string() };
+    assert_eq!(wrapped.for_service("OpenAI").to_string(), "Rate limit exceeded for OpenAI");
+
+    let other = AppError::Timeout("slow".to_string()).for_service("OpenAI");
+    assert!(matches!(other, AppError::Timeout(_)));
+}
+
+#[test]
+fn status_comes_from_response_not_message_text() {
+    // 业务错误以 HTTP 200 返回，消息中的数字不是状态码
+    let business = AppError::Api {
+        service: "Caiyun".to_string(),
+        message: "error 401: invalid token for request 500123".to_string(),
+    };
+    assert_eq!(business.http_status(), None);
+    assert!(!business.is_auth_failure());
+    assert!(!business.is_retryable());
+
+    let bad_request = AppError::from_status("OpenAI", StatusCode::BAD_REQUEST, "model returned 401 and 500 before");
+    assert_eq!(bad_request.http_status(), Some(400));
+    assert!(!bad_request.is_auth_failure());
+    assert!(!bad_request.is_retryable());
+    assert_eq!(bad_request.detail("OpenAI").category, "api");
+}
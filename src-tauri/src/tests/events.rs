@@ -0,0 +1,31 @@
+use crate::events;
+use std::collections::HashMap;
+
+#[test]
+fn event_names_follow_tauri_rules() {
+    assert!(events::is_valid_event_name("trigger-screenshot-v2"));
+    assert!(events::is_valid_event_name("app:ocr/done_1"));
+    assert!(!events::is_valid_event_name(""));
+    assert!(!events::is_valid_event_name("has space"));
+    assert!(!events::is_valid_event_name("翻译"));
+}
+
+#[test]
+fn configured_event_names_replace_builtin_names() {
+    events::set_event_names(HashMap::from([
+        ("focus-input".to_string(), "my-app:focus".to_string()),
+        ("global-shortcut".to_string(), "global-shortcut".to_string()),
+    ]))
+    .unwrap();
+
+    assert_eq!(events::event_name("focus-input"), "my-app:focus");
+    assert_eq!(events::event_name("selection-translation"), "selection-translation");
+    assert!(!events::event_names().contains_key("global-shortcut"));
+
+    let invalid = HashMap::from([("focus-input".to_string(), "bad name".to_string())]);
+    assert!(events::set_event_names(invalid).is_err());
+    assert_eq!(events::event_name("focus-input"), "my-app:focus");
+
+    events::set_event_names(HashMap::new()).unwrap();
+    assert_eq!(events::event_name("focus-input"), "focus-input");
+}
@@ -0,0 +1,34 @@
+use crate::error::AppError;
+use crate::ocr::frames;
+use crate::ocr::presets::RegionRect;
+
+fn frame(width: u32, height: u32) -> Vec<u8> {
+    let image = image::RgbaImage::from_fn(width, height, |x, _| image::Rgba([x as u8, 0, 0, 255]));
+    let mut data = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut data), image::ImageFormat::Png).unwrap();
+    data
+}
+
+#[test]
+fn retained_frames_are_found_until_released() {
+    frames::retain("frames-test-a", vec![1, 2, 3]);
+    assert_eq!(frames::get("frames-test-a").unwrap(), vec![1, 2, 3]);
+
+    frames::release("frames-test-a");
+    assert!(matches!(frames::get("frames-test-a"), Err(AppError::InvalidRequest(_))));
+}
+
+#[test]
+fn crop_clamps_the_selection_to_the_frame() {
+    let rect = RegionRect { x: 30, y: -5, width: 50, height: 10 };
+    let cropped = image::load_from_memory(&frames::crop(&frame(40, 20), rect).unwrap()).unwrap();
+
+    assert_eq!((cropped.width(), cropped.height()), (10, 5));
+    assert_eq!(cropped.to_rgba8().get_pixel(0, 0)[0], 30);
+}
+
+#[test]
+fn crop_rejects_selections_outside_the_frame() {
+    let rect = RegionRect { x: 50, y: 0, width: 10, height: 10 };
+    assert!(matches!(frames::crop(&frame(40, 20), rect), Err(AppError::InvalidRequest(_))));
+}
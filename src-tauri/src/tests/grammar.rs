@@ -0,0 +1,37 @@
+use super::{json_response, mount};
+use crate::grammar;
+use serde_json::json;
+use wiremock::MockServer;
+
+#[tokio::test]
+async fn check_text_parses_matches_and_auto_corrects() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v2/check", json_response(200, json!({
+        "matches": [
+            {
+                "message": "Possible spelling mistake found.",
+                "shortMessage": "Spelling mistake",
+                "offset": 3,
+                "length": 4,
+                "replacements": [{ "value": "cafe" }, { "value": "café" }],
+                "rule": { "id": "MORFOLOGIK_RULE_EN_US", "category": { "name": "Possible Typo" } }
+            },
+            {
+                "message": "Use \"an\" instead of \"a\".",
+                "offset": 11,
+                "length": 1,
+                "replacements": [{ "value": "an" }],
+                "rule": { "id": "EN_A_VS_AN", "category": { "name": "Grammar" } }
+            }
+        ]
+    }))).await;
+
+    let config = json!({ "apiUrl": format!("{}/v2/check", server.uri()) });
+    let result = grammar::check_text("😀 cafr is a apple", "en-US", Some(&config), true).await.unwrap();
+
+    assert_eq!(result.issues.len(), 2);
+    assert_eq!(result.issues[0].replacements, vec!["cafe", "café"]);
+    assert_eq!(result.issues[1].category.as_deref(), Some("Grammar"));
+    // The emoji is two UTF-16 units, so offsets must not be treated as bytes or chars
+    assert_eq!(result.corrected.as_deref(), Some("😀 cafe is an apple"));
+}
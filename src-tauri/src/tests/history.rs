@@ -0,0 +1,21 @@
+use crate::ocr::history;
+use image::{DynamicImage, RgbaImage};
+
+#[test]
+fn recorded_results_are_listed_and_deleted() {
+    let image = DynamicImage::ImageRgba8(RgbaImage::new(400, 200));
+    history::record("history test text", "history-test-engine", Some(image));
+    history::record("   ", "history-test-engine", None);
+
+    let entries: Vec<_> = history::list(None, None)
+        .unwrap()
+        .into_iter()
+        .filter(|entry| entry.engine == "history-test-engine")
+        .collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].text, "history test text");
+    assert!(entries[0].thumbnail.is_some());
+
+    assert_eq!(history::delete(Some(&[entries[0].id])).unwrap(), 1);
+    assert!(history::list(None, None).unwrap().iter().all(|entry| entry.id != entries[0].id));
+}
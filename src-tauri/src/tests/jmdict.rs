@@ -0,0 +1,135 @@
+use super::TempDb;
+use crate::dictionary::jmdict::{deinflect, import_from, is_japanese, read_simplified, scan, JmdictEntry};
+use crate::error::AppError;
+
+const JMDICT: &str = r#"{
+  "version": "3.5.0",
+  "words": [
+    {
+      "id": "1358280",
+      "kanji": [{ "common": true, "text": "食べる", "tags": [] }],
+      "kana": [{ "common": true, "text": "たべる", "tags": [], "appliesToKanji": ["*"] }],
+      "sense": [
+        { "partOfSpeech": ["v1", "vt"], "misc": [], "gloss": [{ "lang": "eng", "text": "to eat" }, { "lang": "ger", "text": "essen" }] },
+        { "partOfSpeech": [], "misc": [], "gloss": [{ "lang": "eng", "text": "to live on (e.g. a salary)" }] }
+      ]
+    },
+    {
+      "id": "1578850",
+      "kanji": [{ "common": true, "text": "行く", "tags": [] }],
+      "kana": [{ "common": true, "text": "いく", "tags": [], "appliesToKanji": ["*"] }],
+      "sense": [{ "partOfSpeech": ["v5k-s", "vi"], "misc": [], "gloss": [{ "lang": "eng", "text": "to go" }] }]
+    },
+    {
+      "id": "1221900",
+      "kanji": [{ "common": true, "text": "勉強", "tags": [] }],
+      "kana": [{ "common": true, "text": "べんきょう", "tags": [], "appliesToKanji": ["*"] }],
+      "sense": [{ "partOfSpeech": ["n", "vs", "vt"], "misc": [], "gloss": [{ "lang": "eng", "text": "study" }] }]
+    }
+  ]
+}"#;
+
+const JMNEDICT: &str = r#"{
+  "words": [
+    {
+      "id": "5000001",
+      "kanji": [{ "text": "東京", "tags": [] }],
+      "kana": [{ "text": "とうきょう", "tags": [], "appliesToKanji": ["*"] }],
+      "translation": [{ "type": ["place"], "related": [], "translation": [{ "lang": "eng", "text": "Tokyo" }] }]
+    }
+  ]
+}"#;
+
+/// 解析出全部词条，返回来源和词条
+fn parse(json: &str) -> (String, Vec<JmdictEntry>) {
+    let mut source = String::new();
+    let mut entries = Vec::new();
+    read_simplified(json.as_bytes(), |kind, entry| {
+        source = kind.to_string();
+        entries.push(entry);
+        Ok(())
+    })
+    .unwrap();
+    (source, entries)
+}
+
+fn dictionary_forms(text: &str) -> Vec<(String, Vec<&'static str>)> {
+    deinflect(text).into_iter().map(|d| (d.term, d.reasons)).collect()
+}
+
+#[test]
+fn simplified_json_keeps_english_glosses_and_inherits_part_of_speech() {
+    let (source, entries) = parse(JMDICT);
+    assert_eq!(source, "jmdict");
+    assert_eq!(entries.len(), 3);
+    let taberu = &entries[0];
+    assert_eq!(taberu.id, 1358280);
+    assert_eq!(taberu.readings, vec!["たべる"]);
+    assert!(taberu.common);
+    assert_eq!(taberu.senses[0].glosses, vec!["to eat"]);
+    assert_eq!(taberu.senses[1].part_of_speech, vec!["v1", "vt"]);
+
+    let entry = taberu.to_entry();
+    assert_eq!(entry.word, "食べる");
+    assert_eq!(entry.phonetic.as_deref(), Some("たべる"));
+
+    let (source, names) = parse(JMNEDICT);
+    assert_eq!(source, "jmnedict");
+    assert_eq!(names[0].senses[0].part_of_speech, vec!["place"]);
+    assert_eq!(names[0].senses[0].glosses, vec!["Tokyo"]);
+}
+
+#[test]
+fn invalid_files_and_callback_errors_stop_the_import() {
+    assert!(matches!(read_simplified(&b"{\"version\": \"3.5.0\"}"[..], |_, _| Ok(())), Err(AppError::InvalidRequest(_))));
+    assert!(matches!(read_simplified(&b"[1, 2]"[..], |_, _| Ok(())), Err(AppError::InvalidRequest(_))));
+
+    let mut seen = 0;
+    let result = read_simplified(JMDICT.as_bytes(), |_, _| {
+        seen += 1;
+        Err(AppError::Unknown("disk full".to_string()))
+    });
+    assert!(matches!(result, Err(AppError::Unknown(ref message)) if message == "disk full"));
+    assert_eq!(seen, 1);
+}
+
+#[test]
+fn conjugated_verbs_and_adjectives_are_deinflected() {
+    assert!(dictionary_forms("食べなかった").contains(&("食べる".to_string(), vec!["negative", "past"])));
+    assert!(dictionary_forms("書きました").contains(&("書く".to_string(), vec!["polite past"])));
+    assert!(dictionary_forms("読んでいる").contains(&("読む".to_string(), vec!["te", "progressive"])));
+    assert!(dictionary_forms("行った").contains(&("行く".to_string(), vec!["past"])));
+    assert!(dictionary_forms("来なかった").contains(&("来る".to_string(), vec!["negative", "past"])));
+    assert!(dictionary_forms("勉強しました").contains(&("勉強".to_string(), vec!["suru", "polite past"])));
+    assert!(dictionary_forms("高くない").contains(&("高い".to_string(), vec!["negative"])));
+}
+
+#[test]
+fn japanese_text_is_detected() {
+    assert!(is_japanese("たべる"));
+    assert!(is_japanese("カタカナ"));
+    assert!(is_japanese("勉強する"));
+    assert!(!is_japanese("hello"));
+    // 只有汉字时无法与中文区分
+    assert!(!is_japanese("勉強"));
+    assert!(!is_japanese("学习"));
+}
+
+#[test]
+fn imported_entries_are_found_by_longest_prefix() {
+    let db = TempDb::new("jmdict");
+    let mut conn = db.open();
+    assert_eq!(import_from(&mut conn, JMDICT.as_bytes()).unwrap(), ("jmdict".to_string(), 3));
+    // 重新导入替换同一来源的旧词条
+    assert_eq!(import_from(&mut conn, JMDICT.as_bytes()).unwrap().1, 3);
+    assert_eq!(scan(&conn, "たべる").unwrap().len(), 1);
+
+    let matches = scan(&conn, "食べなかったよ。").unwrap();
+    assert_eq!(matches[0].matched, "食べなかった");
+    assert_eq!(matches[0].term, "食べる");
+    assert_eq!(matches[0].inflections, vec!["negative", "past"]);
+
+    // 辞书形按假名读音也能查到
+    assert_eq!(scan(&conn, "いきました").unwrap()[0].entry.id, 1578850);
+    assert!(scan(&conn, "hello").unwrap().is_empty());
+}
@@ -0,0 +1,96 @@
+use crate::error::AppError;
+use crate::translate::keys;
+use reqwest::StatusCode;
+use serde_json::json;
+
+fn leased_key(service: &str, config: &serde_json::Value) -> (String, keys::KeyLease) {
+    let (config, lease) = keys::lease(service, Some(config.clone())).unwrap();
+    let key = config.unwrap()["apiKey"].as_str().unwrap().to_string();
+    (key, lease.unwrap())
+}
+
+#[test]
+fn keys_rotate_round_robin() {
+    let config = json!({ "apiKeys": ["a", "b", "c"] });
+    let picked: Vec<String> = (0..4)
+        .map(|_| {
+            let (key, lease) = leased_key("PoolRotate", &config);
+            lease.finish(None);
+            key
+        })
+        .collect();
+
+    assert_eq!(picked, ["a", "b", "c", "a"]);
+}
+
+#[test]
+fn rejected_key_leaves_rotation() {
+    let config = json!({ "apiKeys": ["bad", "good"] });
+    let (key, lease) = leased_key("PoolAuth", &config);
+    assert_eq!(key, "bad");
+    lease.finish(Some(&AppError::from_status(
+        "PoolAuth",
+        StatusCode::UNAUTHORIZED,
+        "Incorrect API key provided",
+    )));
+
+    for _ in 0..3 {
+        let (key, lease) = leased_key("PoolAuth", &config);
+        assert_eq!(key, "good");
+        lease.finish(None);
+    }
+}
+
+#[test]
+fn status_like_numbers_in_message_do_not_disable_key() {
+    let config = json!({ "apiKeys": ["kept"] });
+    let (_, lease) = leased_key("PoolMessage", &config);
+    lease.finish(Some(&AppError::from_status(
+        "PoolMessage",
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "upstream said 401 Unauthorized",
+    )));
+
+    let (key, lease) = leased_key("PoolMessage", &config);
+    assert_eq!(key, "kept");
+    lease.finish(None);
+}
+
+#[test]
+fn exhausted_quota_cools_down_instead_of_disabling() {
+    let config = json!({ "apiKeys": ["spent", "fresh"] });
+    let (key, lease) = leased_key("PoolSpent", &config);
+    assert_eq!(key, "spent");
+    lease.finish(Some(&AppError::from_status(
+        "PoolSpent",
+        StatusCode::FORBIDDEN,
+        r#"{"error":{"errors":[{"reason":"quotaExceeded"}]}}"#,
+    )));
+
+    let status = keys::status().into_iter().find(|s| s.service == "PoolSpent" && s.key_hint == "pent").unwrap();
+    assert!(!status.disabled);
+    assert!(status.cooling_down);
+
+    let (key, lease) = leased_key("PoolSpent", &config);
+    assert_eq!(key, "fresh");
+    lease.finish(None);
+}
+
+#[test]
+fn per_key_quota_is_enforced() {
+    let config = json!({ "apiKeys": ["only"], "keyRequestsPerMinute": 1 });
+    let (_, lease) = leased_key("PoolQuota", &config);
+    lease.finish(None);
+
+    let err = keys::lease("PoolQuota", Some(config)).err().unwrap();
+    assert!(matches!(err, AppError::RateLimitExceeded { .. }));
+}
+
+#[test]
+fn single_key_config_passes_through() {
+    let config = json!({ "apiKey": "solo" });
+    let (config, lease) = keys::lease("PoolNone", Some(config)).unwrap();
+
+    assert!(lease.is_none());
+    assert_eq!(config.unwrap()["apiKey"], "solo");
+}
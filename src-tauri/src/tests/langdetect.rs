@@ -0,0 +1,36 @@
+use crate::langdetect::{detect, resolve_source};
+
+fn lang(text: &str) -> String {
+    detect(text).map(|d| d.lang).unwrap_or_default()
+}
+
+#[test]
+fn detects_by_script() {
+    assert_eq!(lang("今天天气很好"), "zh");
+    assert_eq!(lang("今日はいい天気ですね"), "ja");
+    assert_eq!(lang("안녕하세요"), "ko");
+    assert_eq!(lang("Привет, как дела?"), "ru");
+    assert_eq!(lang("Привіт, як справи? Це її дім"), "uk");
+}
+
+#[test]
+fn detects_latin_languages_by_stopwords() {
+    assert_eq!(lang("The weather is nice and it is sunny"), "en");
+    assert_eq!(lang("Le chat est sur la table et il dort"), "fr");
+    assert_eq!(lang("Der Hund ist nicht in das Haus"), "de");
+    assert_eq!(lang("El perro está en la casa con los niños"), "es");
+}
+
+#[test]
+fn resolve_source_keeps_explicit_language() {
+    assert_eq!(resolve_source("Bonjour le monde", "de"), "de");
+    assert_eq!(resolve_source("你好世界", "auto"), "zh");
+    assert_eq!(resolve_source("12345", "auto"), "auto");
+}
+
+#[test]
+fn resolve_source_keeps_auto_for_low_confidence_guesses() {
+    // 没有常用词的拉丁文本只能猜测为英文
+    assert_eq!(resolve_source("Schadenfreude", "auto"), "auto");
+    assert_eq!(resolve_source("The weather is nice and it is sunny", "auto"), "en");
+}
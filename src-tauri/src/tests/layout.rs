@@ -0,0 +1,89 @@
+use crate::ocr::layout::analyze;
+use crate::ocr::models::OcrLine;
+
+fn line(text: &str, x: f64, y: f64, width: f64) -> OcrLine {
+    OcrLine {
+        text: text.to_string(),
+        x,
+        y,
+        width,
+        height: 10.0,
+        confidence: None,
+    }
+}
+
+fn paragraph_texts(lines: &[OcrLine]) -> Vec<String> {
+    analyze(lines).unwrap().paragraphs.into_iter().map(|p| p.text).collect()
+}
+
+#[test]
+fn two_columns_read_left_then_right() {
+    let lines = vec![
+        line("Title spanning both columns", 0.0, 0.0, 400.0),
+        line("L1", 0.0, 20.0, 180.0),
+        line("R1", 220.0, 20.0, 180.0),
+        line("L2", 0.0, 35.0, 170.0),
+        line("R2", 220.0, 35.0, 175.0),
+    ];
+
+    let layout = analyze(&lines).unwrap();
+
+    assert_eq!(layout.columns, 2);
+    assert_eq!(layout.text(), "Title spanning both columns\n\nL1\nL2\n\nR1\nR2");
+    assert_eq!(layout.paragraphs[2].column, 1);
+}
+
+#[test]
+fn single_column_keeps_engine_order() {
+    let lines = vec![
+        line("a", 0.0, 0.0, 300.0),
+        line("b", 0.0, 15.0, 280.0),
+        line("c", 10.0, 30.0, 150.0),
+        line("d", 0.0, 45.0, 290.0),
+    ];
+
+    let layout = analyze(&lines).unwrap();
+
+    assert_eq!(layout.columns, 1);
+    assert_eq!(layout.text(), "a\nb\nc\nd");
+}
+
+#[test]
+fn wide_line_spacing_starts_a_paragraph() {
+    let lines = vec![
+        line("first paragraph line one", 0.0, 0.0, 300.0),
+        line("first paragraph line two", 0.0, 14.0, 295.0),
+        line("second paragraph line one", 0.0, 40.0, 300.0),
+        line("second paragraph line two", 0.0, 54.0, 290.0),
+    ];
+
+    assert_eq!(
+        paragraph_texts(&lines),
+        [
+            "first paragraph line one\nfirst paragraph line two",
+            "second paragraph line one\nsecond paragraph line two"
+        ]
+    );
+}
+
+#[test]
+fn short_last_line_and_indent_end_a_paragraph() {
+    let lines = vec![
+        line("a full line of text", 0.0, 0.0, 300.0),
+        line("short end.", 0.0, 14.0, 120.0),
+        line("indented next start", 20.0, 28.0, 280.0),
+        line("and its continuation", 0.0, 42.0, 300.0),
+    ];
+
+    let layout = analyze(&lines).unwrap();
+
+    assert_eq!(layout.paragraphs.len(), 2);
+    assert_eq!(layout.paragraphs[0].text, "a full line of text\nshort end.");
+    let second = &layout.paragraphs[1];
+    assert_eq!((second.x, second.y, second.width, second.height), (0.0, 28.0, 300.0, 24.0));
+}
+
+#[test]
+fn lines_without_geometry_are_left_alone() {
+    assert!(analyze(&[]).is_none());
+}
@@ -0,0 +1,116 @@
+use super::{json_response, mount, service_config};
+use crate::error::AppError;
+use crate::ocr::llm_vision::{request_gemini, request_openai, set_config, strip_code_fence};
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer};
+
+#[test]
+fn code_fences_around_the_transcription_are_removed() {
+    assert_eq!(strip_code_fence("```\nHello\nWorld\n```"), "Hello\nWorld");
+    assert_eq!(strip_code_fence("```text\n你好\n```"), "你好");
+    assert_eq!(strip_code_fence("  plain text \n"), "plain text");
+}
+
+#[test]
+fn incomplete_configs_are_rejected() {
+    assert!(matches!(set_config(json!({ "model": "gpt-4o" })), Err(AppError::Config(_))));
+    assert!(matches!(set_config(json!({ "apiKey": "key" })), Err(AppError::Config(_))));
+    assert!(matches!(
+        set_config(json!({ "apiKey": "key", "model": "m", "provider": "unknown" })),
+        Err(AppError::InvalidRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn openai_request_sends_the_image_as_a_data_url() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(header("Authorization", "Bearer test-key"))
+        .and(body_partial_json(json!({
+            "model": "gpt-4o",
+            "temperature": 0,
+            "messages": [{
+                "role": "user",
+                "content": [
+                    { "type": "text", "text": "Transcribe" },
+                    { "type": "image_url", "image_url": { "url": "data:image/png;base64,aGVsbG8=" } }
+                ]
+            }]
+        })))
+        .respond_with(json_response(200, json!({
+            "choices": [{ "message": { "role": "assistant", "content": "Hello\nWorld" } }]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = service_config(&server, "/v1/chat/completions");
+    let text = request_openai(&config, "test-key", "gpt-4o", "Transcribe", "image/png", "aGVsbG8=")
+        .await
+        .unwrap();
+
+    assert_eq!(text, "Hello\nWorld");
+}
+
+#[tokio::test]
+async fn openai_request_without_content_is_an_error() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v1/chat/completions", json_response(200, json!({ "choices": [] }))).await;
+
+    let config = service_config(&server, "/v1/chat/completions");
+    let err = request_openai(&config, "test-key", "gpt-4o", "Transcribe", "image/png", "aGVsbG8=")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AppError::Ocr(_)));
+}
+
+#[tokio::test]
+async fn gemini_request_sends_inline_image_data() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1beta/models/gemini-1.5-flash:generateContent"))
+        .and(query_param("key", "test-key"))
+        .and(body_partial_json(json!({
+            "contents": [{
+                "role": "user",
+                "parts": [
+                    { "text": "Transcribe" },
+                    { "inline_data": { "mime_type": "image/jpeg", "data": "aGVsbG8=" } }
+                ]
+            }],
+            "generationConfig": { "temperature": 0 }
+        })))
+        .respond_with(json_response(200, json!({
+            "candidates": [{ "content": { "parts": [{ "text": "你好" }, { "text": "世界" }] } }]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = service_config(&server, "/v1beta/");
+    let text = request_gemini(&config, "test-key", "gemini-1.5-flash", "Transcribe", "image/jpeg", "aGVsbG8=")
+        .await
+        .unwrap();
+
+    assert_eq!(text, "你好世界");
+}
+
+#[tokio::test]
+async fn gemini_request_reports_http_errors() {
+    let server = MockServer::start().await;
+    mount(
+        &server,
+        "POST",
+        "/v1beta/models/gemini-1.5-flash:generateContent",
+        json_response(403, json!({ "error": { "message": "API key invalid" } })),
+    )
+    .await;
+
+    let config = service_config(&server, "/v1beta");
+    let err = request_gemini(&config, "test-key", "gemini-1.5-flash", "Transcribe", "image/png", "aGVsbG8=")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AppError::HttpStatus { ref service, status, .. } if service == "Vision model" && status.as_u16() == 403));
+}
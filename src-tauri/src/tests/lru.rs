@@ -0,0 +1,40 @@
+use crate::lru::Lru;
+
+#[test]
+fn evicts_least_recently_used_entry() {
+    let mut lru = Lru::with_capacity(2);
+    lru.insert("a".to_string(), 1);
+    lru.insert("b".to_string(), 2);
+    // 访问 a 后 b 成为最久未使用
+    assert_eq!(lru.get("a"), Some(&1));
+    lru.insert("c".to_string(), 3);
+
+    assert_eq!(lru.len(), 2);
+    assert_eq!(lru.get("b"), None);
+    assert_eq!(lru.get("a"), Some(&1));
+    assert_eq!(lru.get("c"), Some(&3));
+}
+
+#[test]
+fn reinserting_a_key_replaces_it_without_evicting() {
+    let mut lru = Lru::with_capacity(2);
+    lru.insert("a".to_string(), 1);
+    lru.insert("b".to_string(), 2);
+    lru.insert("a".to_string(), 10);
+
+    assert_eq!(lru.len(), 2);
+    assert_eq!(lru.get("a"), Some(&10));
+    assert_eq!(lru.get("b"), Some(&2));
+}
+
+#[test]
+fn iterates_from_oldest_to_newest() {
+    let mut lru = Lru::with_capacity(3);
+    lru.insert("a".to_string(), 1);
+    lru.insert("b".to_string(), 2);
+    lru.insert("c".to_string(), 3);
+    lru.get("a");
+
+    let keys: Vec<&str> = lru.iter().map(|(key, _)| key.as_str()).collect();
+    assert_eq!(keys, ["b", "c", "a"]);
+}
@@ -0,0 +1,70 @@
+use crate::models::TextFormat;
+use crate::services::markup::{reassemble, segment, Segment};
+
+/// Stand-in translator: upper-cases the text but leaves markers alone.
+fn fake_translate(segments: &[Segment]) -> Vec<String> {
+    segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Text { text, .. } => Some(text.trim().to_uppercase()),
+            Segment::Keep(_) => None,
+        })
+        .collect()
+}
+
+fn round_trip(text: &str, format: TextFormat) -> String {
+    let segments = segment(text, format);
+    reassemble(&segments, &fake_translate(&segments))
+}
+
+#[test]
+fn markdown_keeps_code_blocks_and_structure() {
+    let source = "# Getting started\n\nRun `cargo build` first.\n\n```rust\nfn main() {}\n```\n- list item\n";
+
+    assert_eq!(
+        round_trip(source, TextFormat::Markdown),
+        "# GETTING STARTED\n\nRUN `cargo build` FIRST.\n\n```rust\nfn main() {}\n```\n- LIST ITEM\n"
+    );
+}
+
+#[test]
+fn markdown_link_targets_survive() {
+    let source = "See [the guide](https://example.com/guide) and ![logo](img/logo.png).";
+
+    assert_eq!(
+        round_trip(source, TextFormat::Markdown),
+        "SEE [THE GUIDE](https://example.com/guide) AND ![logo](img/logo.png)."
+    );
+}
+
+#[test]
+fn markdown_table_cells_are_translated_separately() {
+    let source = "| name | value |\n|------|-------|\n| size | large |\n";
+
+    assert_eq!(
+        round_trip(source, TextFormat::Markdown),
+        "| NAME | VALUE |\n|------|-------|\n| SIZE | LARGE |\n"
+    );
+}
+
+#[test]
+fn html_translates_text_nodes_only() {
+    let source = "<p class=\"intro\">Hello <b>bold</b> world</p><script>var x = 'hi';</script><div>Bye</div>";
+
+    assert_eq!(
+        round_trip(source, TextFormat::Html),
+        "<p class=\"intro\">HELLO <b>BOLD</b> WORLD</p><script>var x = 'hi';</script><div>BYE</div>"
+    );
+}
+
+#[test]
+fn dropped_markers_are_appended_not_lost() {
+    let segments = segment("Use `x` here", TextFormat::Markdown);
+
+    assert_eq!(reassemble(&segments, &["在这里使用".to_string()]), "在这里使用 `x`");
+}
+
+#[test]
+fn plain_text_is_a_single_segment() {
+    assert_eq!(segment("a `b` c", TextFormat::Plain).len(), 1);
+}
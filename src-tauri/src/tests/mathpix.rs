@@ -0,0 +1,127 @@
+use super::{json_response, malformed_response, mount};
+use crate::error::AppError;
+use crate::ocr::mathpix;
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer};
+
+fn mathpix_config(server: &MockServer) -> serde_json::Value {
+    json!({
+        "appId": "test-app",
+        "appKey": "test-key",
+        "apiUrl": format!("{}/v3/text", server.uri())
+    })
+}
+
+#[tokio::test]
+async fn recognize_formula_returns_latex() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/text"))
+        .and(header("app_id", "test-app"))
+        .and(header("app_key", "test-key"))
+        .respond_with(json_response(200, json!({
+            "latex_simplified": "x^2",
+            "latex_styled": "x^{2}",
+            "confidence": 0.98
+        })))
+        .mount(&server)
+        .await;
+
+    let config = mathpix_config(&server);
+    let result = mathpix::recognize_formula(Some("aGVsbG8=".to_string()), None, Some(&config))
+        .await
+        .unwrap();
+
+    assert_eq!(result.latex, "x^2");
+    assert_eq!(result.latex_styled.as_deref(), Some("x^{2}"));
+    assert_eq!(result.confidence, Some(0.98));
+}
+
+#[tokio::test]
+async fn recognize_formula_reports_auth_failure() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v3/text", json_response(401, json!({
+        "error": "Invalid credentials"
+    }))).await;
+
+    let config = mathpix_config(&server);
+    let err = mathpix::recognize_formula(Some("aGVsbG8=".to_string()), None, Some(&config))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AppError::HttpStatus { ref service, status, .. } if service == "Mathpix" && status.as_u16() == 401));
+}
+
+#[tokio::test]
+async fn recognize_formula_reports_rate_limit() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v3/text", json_response(429, json!({
+        "error": "Too many requests"
+    }))).await;
+
+    let config = mathpix_config(&server);
+    assert!(mathpix::recognize_formula(Some("aGVsbG8=".to_string()), None, Some(&config)).await.is_err());
+}
+
+#[tokio::test]
+async fn recognize_formula_rejects_malformed_response() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v3/text", malformed_response()).await;
+
+    let config = mathpix_config(&server);
+    assert!(mathpix::recognize_formula(Some("aGVsbG8=".to_string()), None, Some(&config)).await.is_err());
+}
+
+#[tokio::test]
+async fn recognize_formula_requires_an_image() {
+    let server = MockServer::start().await;
+
+    let config = mathpix_config(&server);
+    let err = mathpix::recognize_formula(None, None, Some(&config)).await.unwrap_err();
+
+    assert!(matches!(err, AppError::InvalidRequest(_)));
+}
+
+#[tokio::test]
+async fn recognize_formula_returns_requested_formats() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v3/text"))
+        .and(body_partial_json(json!({
+            "formats": ["latex_simplified", "latex_styled", "text", "data"],
+            "data_options": { "include_mathml": true, "include_asciimath": true },
+            "rm_spaces": false,
+            "math_inline_delimiters": ["$", "$"]
+        })))
+        .respond_with(json_response(200, json!({
+            "latex_simplified": "x^2",
+            "text": "$x^2$",
+            "data": [
+                { "type": "asciimath", "value": "x^(2)" },
+                { "type": "mathml", "value": "<math><msup><mi>x</mi><mn>2</mn></msup></math>" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let mut config = mathpix_config(&server);
+    config["formats"] = json!(["mathml", "asciimath", "text", "svg"]);
+    config["rmSpaces"] = json!(false);
+    config["mathInlineDelimiters"] = json!(["$", "$"]);
+    let result = mathpix::recognize_formula(Some("aGVsbG8=".to_string()), None, Some(&config))
+        .await
+        .unwrap();
+
+    assert_eq!(result.text.as_deref(), Some("$x^2$"));
+    assert_eq!(result.asciimath.as_deref(), Some("x^(2)"));
+    assert!(result.mathml.unwrap().starts_with("<math>"));
+}
+
+#[test]
+fn request_body_only_asks_for_latex_by_default() {
+    let body = mathpix::request_body(None);
+    assert_eq!(body["formats"], json!(["latex_simplified", "latex_styled"]));
+    assert_eq!(body["data_options"]["include_mathml"], json!(false));
+    assert!(body.get("rm_spaces").is_none());
+}
@@ -0,0 +1,43 @@
+use crate::services::memory::{self, MemoryOptions};
+
+fn options(threshold: f64) -> MemoryOptions {
+    MemoryOptions { enabled: true, threshold, limit: 3 }
+}
+
+#[test]
+fn similarity_is_normalized_edit_distance() {
+    assert_eq!(memory::similarity("kitten", "kitten"), 1.0);
+    assert!((memory::similarity("kitten", "sitting") - (1.0 - 3.0 / 7.0)).abs() < 1e-9);
+    assert_eq!(memory::similarity("  same   text ", "same text"), 1.0);
+}
+
+#[test]
+fn near_duplicate_sentence_is_recalled() {
+    memory::confirm(
+        "Restart the service after changing the memory tuning configuration file.",
+        "修改内存调优配置文件后重启服务。",
+        "en",
+        "tm-zh",
+        Some("DeepL".to_string()),
+    );
+
+    let matches = memory::lookup(
+        "Restart the service after changing the memory tuning configuration files.",
+        "en",
+        "tm-zh",
+        options(0.85),
+    );
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].text, "修改内存调优配置文件后重启服务。");
+    assert!(matches[0].similarity > 0.95);
+}
+
+#[test]
+fn dissimilar_or_other_language_pair_is_ignored() {
+    memory::confirm("Open the settings window.", "打开设置窗口。", "en", "tm-zh2", None);
+
+    assert!(memory::lookup("Close every window now.", "en", "tm-zh2", options(0.85)).is_empty());
+    assert!(memory::lookup("Open the settings window.", "en", "tm-ja", options(0.85)).is_empty());
+    assert!(memory::lookup("Open the settings window.", "en", "tm-zh2", MemoryOptions { enabled: false, ..options(0.85) }).is_empty());
+}
@@ -0,0 +1,98 @@
+//! Integration tests for the HTTP-backed code paths. Each test spins up a
+//! wiremock server and points the provider at it through its `apiUrl`
+//! (or base URL) override, so no real network access is needed.
+
+mod api;
+mod background;
+mod barcode;
+mod chunk;
+mod cleanup;
+mod confidence;
+mod correct;
+mod dictionary;
+mod draft;
+mod ecdict;
+mod edge_tts;
+mod error;
+mod events;
+mod frames;
+mod grammar;
+mod history;
+mod jmdict;
+mod keys;
+mod langdetect;
+mod layout;
+mod llm_vision;
+mod lru;
+mod markup;
+mod mathpix;
+mod memory;
+mod overlay;
+mod paddle;
+mod postprocess;
+mod presets;
+mod probe;
+mod proxy;
+mod rapid;
+mod region;
+mod registry;
+mod session;
+mod simpletex;
+mod stardict;
+mod tatoeba;
+mod tesseract;
+mod translation;
+mod tts;
+mod vertical;
+mod watch;
+mod worksheet;
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Mounts a responder for `http_method path` on the mock server.
+pub(crate) async fn mount(server: &MockServer, http_method: &str, route: &str, response: ResponseTemplate) {
+    Mock::given(method(http_method))
+        .and(path(route))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+/// Service config pointing `apiUrl` at `route` on the mock server, with a dummy key.
+pub(crate) fn service_config(server: &MockServer, route: &str) -> serde_json::Value {
+    serde_json::json!({
+        "apiUrl": format!("{}{}", server.uri(), route),
+        "apiKey": "test-key"
+    })
+}
+
+pub(crate) fn json_response(status: u16, body: serde_json::Value) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(body)
+}
+
+pub(crate) fn malformed_response() -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "application/json")
+        .set_body_string("{not valid json")
+}
+
+/// A uniquely named SQLite file in the temp dir, removed on drop. Bind it
+/// before any connection to it so the connection is closed first.
+pub(crate) struct TempDb(pub(crate) std::path::PathBuf);
+
+impl TempDb {
+    pub(crate) fn new(name: &str) -> Self {
+        TempDb(std::env::temp_dir().join(format!("dict_{}_{}.db", name, uuid::Uuid::new_v4().simple())))
+    }
+
+    pub(crate) fn open(&self) -> rusqlite::Connection {
+        rusqlite::Connection::open(&self.0).unwrap()
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
@@ -0,0 +1,24 @@
+use crate::ocr::models::OcrLine;
+use crate::ocr::overlay::{pair_lines, LineBox};
+
+fn line(text: &str, y: f64) -> OcrLine {
+    OcrLine { text: text.to_string(), x: 10.0, y, width: 120.0, height: 18.0, confidence: None }
+}
+
+#[test]
+fn translations_are_paired_with_line_boxes() {
+    let lines = [line("Hello world ", 0.0), line("Good morning", 24.0)];
+    let paired = pair_lines(&lines, "你好世界\n 早上好\n").unwrap();
+
+    assert_eq!(paired.len(), 2);
+    assert_eq!(paired[0].source, "Hello world");
+    assert_eq!(paired[0].translation, "你好世界");
+    assert_eq!(paired[1].translation, "早上好");
+    assert_eq!(paired[1].bbox, LineBox { x: 10.0, y: 24.0, width: 120.0, height: 18.0 });
+}
+
+#[test]
+fn mismatched_line_counts_are_rejected() {
+    let lines = [line("The quick brown fox", 0.0), line("jumps over the dog", 24.0)];
+    assert!(pair_lines(&lines, "敏捷的棕色狐狸跳过了狗").is_none());
+}
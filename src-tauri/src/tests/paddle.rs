@@ -0,0 +1,19 @@
+use crate::ocr::paddle::PaddleOptions;
+
+#[test]
+fn only_specified_options_are_passed_to_paddle() {
+    let options = PaddleOptions { use_gpu: true, ocr_version: Some("PP-OCRv4".to_string()), ..Default::default() };
+    assert!(options.validate().is_ok());
+    assert_eq!(
+        serde_json::to_value(&options).unwrap(),
+        serde_json::json!({ "use_gpu": true, "ocr_version": "PP-OCRv4" })
+    );
+}
+
+#[test]
+fn unknown_versions_and_out_of_range_thresholds_are_rejected() {
+    let version = PaddleOptions { ocr_version: Some("PP-OCRv9".to_string()), ..Default::default() };
+    assert!(version.validate().is_err());
+    let threshold = PaddleOptions { det_db_box_thresh: Some(1.5), ..Default::default() };
+    assert!(threshold.validate().is_err());
+}
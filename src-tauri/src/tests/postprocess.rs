@@ -0,0 +1,55 @@
+use crate::services::postprocess::PostProcess;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[test]
+fn strips_prefix_quotes_and_extra_whitespace() {
+    let postprocess = PostProcess { collapse_whitespace: true, ..PostProcess::default() };
+
+    assert_eq!(postprocess.apply("Translation: \"你好，世界\"", "Hello, world", &[]), "你好，世界");
+    assert_eq!(postprocess.apply("译文：「早上好」", "Good morning", &[]), "早上好");
+    assert_eq!(postprocess.apply("第一行   有  空格\n\n\n\n第二行", "x", &[]), "第一行 有 空格\n\n第二行");
+}
+
+#[test]
+fn whitespace_is_kept_by_default() {
+    let postprocess = PostProcess::default();
+
+    assert_eq!(postprocess.apply("def f():\n    return 1\n\n\nf()", "x", &[]), "def f():\n    return 1\n\n\nf()");
+}
+
+#[test]
+fn quotes_present_in_source_are_kept() {
+    let postprocess = PostProcess::default();
+
+    assert_eq!(postprocess.apply("\"引用\"", "\"quoted\"", &[]), "\"引用\"");
+    assert_eq!(postprocess.apply("\"a\" and \"b\"", "a and b", &[]), "\"a\" and \"b\"");
+}
+
+#[test]
+fn placeholders_survive_round_trip() {
+    let postprocess = PostProcess { preserve_placeholders: true, ..PostProcess::default() };
+    let source = "Hello {name}, you have %d new messages from %(sender)s and {{count}} more";
+
+    let (masked, placeholders) = postprocess.protect(source);
+    assert_eq!(placeholders, ["{name}", "%d", "%(sender)s", "{{count}}"]);
+    assert!(!masked.contains('{') && !masked.contains('%'));
+
+    let translated = "你好⟦0⟧，你有 ⟦1⟧ 条来自 ⟦ 2 ⟧ 的新消息，另有 ⟦3⟧ 条";
+    assert_eq!(
+        postprocess.apply(translated, source, &placeholders),
+        "你好{name}，你有 %d 条来自 %(sender)s 的新消息，另有 {{count}} 条"
+    );
+}
+
+#[test]
+fn service_config_overrides_request_config() {
+    let config = HashMap::from([("postprocess".to_string(), json!({ "stripQuotes": false, "preservePlaceholders": true }))]);
+    let service = json!({ "postprocess": { "stripQuotes": true } });
+
+    let postprocess = PostProcess::from_config(Some(&service), Some(&config));
+
+    assert!(postprocess.strip_quotes);
+    assert!(postprocess.preserve_placeholders);
+    assert!(!PostProcess::from_config(None, Some(&config)).strip_quotes);
+}
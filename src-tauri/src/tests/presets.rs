@@ -0,0 +1,23 @@
+use crate::ocr::presets::{self, RegionRect};
+
+#[test]
+fn saved_regions_are_recalled_replaced_and_deleted() {
+    let rect = RegionRect { x: -1920, y: 900, width: 1280, height: 120 };
+    presets::save("  preset test bar ", rect).unwrap();
+    let preset = presets::get("preset test bar").unwrap();
+    assert_eq!((preset.rect.x, preset.rect.y, preset.rect.width, preset.rect.height), (-1920, 900, 1280, 120));
+
+    presets::save("preset test bar", RegionRect { x: 0, y: 0, width: 10, height: 10 }).unwrap();
+    assert_eq!(presets::list().iter().filter(|p| p.name == "preset test bar").count(), 1);
+    assert_eq!(presets::get("preset test bar").unwrap().rect.width, 10);
+
+    presets::delete("preset test bar").unwrap();
+    assert!(presets::get("preset test bar").is_err());
+    assert!(presets::delete("preset test bar").is_err());
+}
+
+#[test]
+fn regions_need_a_name_and_an_area() {
+    assert!(presets::save(" ", RegionRect { x: 0, y: 0, width: 10, height: 10 }).is_err());
+    assert!(presets::save("preset test empty", RegionRect { x: 0, y: 0, width: 0, height: 10 }).is_err());
+}
@@ -0,0 +1,60 @@
+use super::{json_response, mount, service_config};
+use crate::services::probe::test_service;
+use serde_json::json;
+use wiremock::MockServer;
+
+const CHAT_PATH: &str = "/v1/chat/completions";
+
+#[tokio::test]
+async fn probe_reports_sample_on_success() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(200, json!({
+        "choices": [{ "message": { "content": "你好" } }]
+    }))).await;
+
+    let result = test_service("OpenAI", Some(service_config(&server, CHAT_PATH)), None).await;
+
+    assert!(result.ok);
+    assert_eq!(result.sample.as_deref(), Some("你好"));
+    assert!(result.category.is_none());
+}
+
+#[tokio::test]
+async fn probe_classifies_rejected_key() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(401, json!({
+        "error": { "message": "Incorrect API key provided" }
+    }))).await;
+
+    let result = test_service("OpenAI", Some(service_config(&server, CHAT_PATH)), None).await;
+
+    assert!(!result.ok);
+    assert_eq!(result.category, Some("auth"));
+}
+
+#[tokio::test]
+async fn probe_flags_missing_key_as_config() {
+    let result = test_service("Caiyun", Some(json!({})), None).await;
+
+    assert!(!result.ok);
+    assert_eq!(result.category, Some("config"));
+}
+
+#[tokio::test]
+async fn probe_inherits_global_proxy() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(200, json!({
+        "choices": [{ "message": { "content": "你好" } }]
+    }))).await;
+
+    // 无效的全局代理设置会在构建客户端时报错，说明探测请求使用了全局代理
+    let result = test_service("OpenAI", Some(service_config(&server, CHAT_PATH)), Some(json!(42))).await;
+    assert!(!result.ok);
+    assert_eq!(result.category, Some("config"));
+
+    // 服务自身的代理设置优先于全局代理
+    let mut config = service_config(&server, CHAT_PATH);
+    config["proxy"] = json!(false);
+    let result = test_service("OpenAI", Some(config), Some(json!(42))).await;
+    assert!(result.ok);
+}
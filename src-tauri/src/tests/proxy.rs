@@ -0,0 +1,65 @@
+use crate::error::AppError;
+use crate::services::proxy::tunnel;
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// 本地模拟的 HTTP 代理：读取 CONNECT 请求头后回复 `response`，隧道建立时再写入 "tunneled"
+async fn fake_proxy(response: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut byte = [0u8];
+        while !head.ends_with(b"\r\n\r\n") {
+            socket.read_exact(&mut byte).await.unwrap();
+            head.push(byte[0]);
+        }
+        socket.write_all(response.as_bytes()).await.unwrap();
+        if response.contains(" 200 ") {
+            socket.write_all(b"tunneled").await.unwrap();
+        }
+        String::from_utf8(head).unwrap()
+    });
+    (url, handle)
+}
+
+#[tokio::test]
+async fn tunnel_is_skipped_without_a_proxy() {
+    assert!(tunnel(None, "example.com", 443).await.unwrap().is_none());
+    assert!(tunnel(Some(&json!({ "proxy": false })), "example.com", 443).await.unwrap().is_none());
+    assert!(tunnel(Some(&json!({ "proxy": "" })), "example.com", 443).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn tunnel_connects_through_http_proxy() {
+    let (url, proxy) = fake_proxy("HTTP/1.1 200 Connection established\r\n\r\n").await;
+    let config = json!({ "proxy": { "url": url, "username": "user", "password": "pass" } });
+
+    let mut stream = tunnel(Some(&config), "speech.platform.bing.com", 443).await.unwrap().unwrap();
+    let mut data = [0u8; 8];
+    stream.read_exact(&mut data).await.unwrap();
+    assert_eq!(&data, b"tunneled");
+
+    let request = proxy.await.unwrap();
+    assert!(request.starts_with("CONNECT speech.platform.bing.com:443 HTTP/1.1\r\n"));
+    // "user:pass" 的 base64
+    assert!(request.contains("Proxy-Authorization: Basic dXNlcjpwYXNz\r\n"));
+}
+
+#[tokio::test]
+async fn tunnel_reports_refused_connect() {
+    let (url, _proxy) = fake_proxy("HTTP/1.1 407 Proxy Authentication Required\r\n\r\n").await;
+
+    let err = tunnel(Some(&json!({ "proxy": url })), "example.com", 443).await.unwrap_err();
+    assert!(matches!(err, AppError::Network(ref message) if message.contains("407")));
+}
+
+#[tokio::test]
+async fn tunnel_rejects_socks_proxies() {
+    let err = tunnel(Some(&json!({ "proxy": "socks5://127.0.0.1:1080" })), "example.com", 443)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Config(_)));
+}
@@ -0,0 +1,77 @@
+use crate::ocr::rapid::{boxes_from_map, ctc_decode, det_input_size, parse_keys, rec_tensor};
+
+/// 在 `width` 宽的概率图上填充一个矩形区域
+fn fill(map: &mut [f32], width: usize, (x, y, w, h): (usize, usize, usize, usize), value: f32) {
+    for row in y..y + h {
+        for col in x..x + w {
+            map[row * width + col] = value;
+        }
+    }
+}
+
+#[test]
+fn det_input_is_limited_and_aligned_to_32() {
+    assert_eq!(det_input_size(100, 50), (96, 64));
+    assert_eq!(det_input_size(1920, 1080), (960, 544));
+    assert_eq!(det_input_size(10, 10), (32, 32));
+}
+
+#[test]
+fn boxes_are_expanded_and_sorted_in_reading_order() {
+    let (width, height) = (100, 60);
+    let mut map = vec![0.0; width * height];
+    fill(&mut map, width, (60, 10, 30, 6), 0.9);
+    fill(&mut map, width, (10, 12, 30, 6), 0.9);
+    fill(&mut map, width, (10, 40, 50, 6), 0.8);
+
+    let boxes = boxes_from_map(&map, width, height);
+
+    assert_eq!(boxes.len(), 3);
+    // 同一行的两个框按横坐标排序
+    assert!(boxes[0].x < boxes[1].x);
+    assert!(boxes[2].y > boxes[1].y);
+    // 文本核向外扩展
+    assert!(boxes[0].x < 10.0 && boxes[0].width > 30.0);
+    assert!((boxes[2].score - 0.8).abs() < 1e-6);
+}
+
+#[test]
+fn tiny_or_faint_regions_are_dropped() {
+    let (width, height) = (40, 40);
+    let mut map = vec![0.0; width * height];
+    fill(&mut map, width, (2, 2, 2, 10), 0.9);
+    fill(&mut map, width, (10, 10, 20, 8), 0.35);
+
+    assert!(boxes_from_map(&map, width, height).is_empty());
+}
+
+#[test]
+fn ctc_decoding_skips_blanks_and_repeats() {
+    let keys = parse_keys("你\n好\n");
+    // 类别：0 空白、1 你、2 好、3 空格
+    let probs = [
+        0.1, 0.8, 0.05, 0.05, //
+        0.1, 0.7, 0.1, 0.1, //
+        0.9, 0.05, 0.03, 0.02, //
+        0.1, 0.1, 0.6, 0.2, //
+        0.1, 0.1, 0.1, 0.7, //
+    ];
+
+    let (text, confidence) = ctc_decode(&probs, 5, 4, &keys);
+
+    assert_eq!(text, "你好 ");
+    assert!((confidence - 0.7).abs() < 1e-6);
+}
+
+#[test]
+fn rec_input_keeps_minimum_width() {
+    let image = image::RgbImage::from_pixel(20, 24, image::Rgb([255, 255, 255]));
+
+    let (data, width) = rec_tensor(&image);
+
+    assert_eq!(width, 320);
+    assert_eq!(data.len(), 3 * 48 * 320);
+    // 白色像素归一化为 1，补齐部分为 0
+    assert!((data[0] - 1.0).abs() < 1e-6);
+    assert_eq!(data[319], 0.0);
+}
@@ -0,0 +1,57 @@
+use crate::ocr::region::{apply_polygon_mask, blit, bounding_box, intersect, parse_handle, Point};
+
+fn triangle() -> Vec<Point> {
+    vec![Point { x: 10, y: 10 }, Point { x: 14, y: 10 }, Point { x: 10, y: 14 }]
+}
+
+#[test]
+fn bounding_box_covers_all_points() {
+    assert_eq!(bounding_box(&triangle()).unwrap(), (10, 10, 4, 4));
+}
+
+#[test]
+fn degenerate_region_is_rejected() {
+    let line = vec![Point { x: 0, y: 0 }, Point { x: 5, y: 0 }, Point { x: 9, y: 0 }];
+    assert!(bounding_box(&line).is_err());
+    assert!(bounding_box(&line[..2]).is_err());
+}
+
+#[test]
+fn pixels_outside_polygon_are_whitened() {
+    let (w, h) = (4, 4);
+    let mut pixels = vec![0u8; (w * h * 4) as usize];
+    apply_polygon_mask(&mut pixels, w, h, (10, 10), &triangle());
+
+    let pixel = |col: i32, row: i32| &pixels[((row * w + col) * 4) as usize..((row * w + col) * 4 + 4) as usize];
+    assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+    assert_eq!(pixel(3, 3), [255, 255, 255, 255]);
+    assert_eq!(pixel(2, 3), [255, 255, 255, 255]);
+    assert_eq!(pixel(1, 1), [0, 0, 0, 0]);
+}
+
+#[test]
+fn regions_left_of_the_primary_monitor_intersect() {
+    let virtual_screen = (-1920, 0, 3840, 1080);
+    assert_eq!(intersect((-100, 500, 300, 100), virtual_screen), Some((-100, 500, 300, 100)));
+    assert_eq!(intersect((-100, 500, 300, 100), (0, 0, 1920, 1080)), Some((0, 500, 200, 100)));
+    assert_eq!(intersect((-2000, -50, 100, 100), virtual_screen), Some((-1920, 0, 20, 50)));
+    assert_eq!(intersect((0, 0, 10, 10), (10, 0, 10, 10)), None);
+}
+
+#[test]
+fn parts_are_copied_into_place() {
+    let region = (-2, 0, 4, 2);
+    let mut pixels = vec![255u8; 4 * 2 * 4];
+    blit(&mut pixels, region, (0, 1, 2, 1), &[1u8; 8]);
+
+    assert_eq!(&pixels[..24], &[255u8; 24]);
+    assert_eq!(&pixels[24..], &[1u8; 8]);
+}
+
+#[test]
+fn window_handles_parse_as_decimal_or_hex() {
+    assert_eq!(parse_handle("132456"), Some(132456));
+    assert_eq!(parse_handle(" 0x2A0B4 "), Some(0x2A0B4));
+    assert_eq!(parse_handle("Notepad"), None);
+    assert_eq!(parse_handle("0xZZ"), None);
+}
@@ -0,0 +1,110 @@
+use crate::translate;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[test]
+fn aliases_resolve_case_insensitively() {
+    assert_eq!(translate::find("DeepL").unwrap().name(), "DeepL");
+    assert_eq!(translate::find("文心一言").unwrap().name(), "Ernie");
+    assert_eq!(translate::find("Google Native").unwrap().name(), "GoogleFree");
+    assert!(translate::find("nope").is_none());
+}
+
+#[test]
+fn custom_services_resolve_from_config() {
+    let config: HashMap<String, serde_json::Value> = HashMap::from([(
+        "customServices".to_string(),
+        json!([
+            { "id": "local", "apiUrl": "http://localhost:8080/v1/chat/completions" },
+            { "id": "ollama", "name": "Ollama", "apiUrl": "http://localhost:11434/v1/chat/completions" },
+        ]),
+    )]);
+
+    let (provider, service_config) = translate::resolve("custom:LOCAL", Some(&config)).unwrap();
+    assert_eq!(provider.name(), "custom:LOCAL");
+    assert!(provider.supports_streaming());
+    assert_eq!(service_config.unwrap()["apiUrl"], "http://localhost:8080/v1/chat/completions");
+
+    // The configured name is used for results and errors
+    let (named, _) = translate::resolve("custom:ollama", Some(&config)).unwrap();
+    assert_eq!(named.name(), "Ollama");
+
+    assert!(translate::resolve("custom:missing", Some(&config)).is_err());
+}
+
+#[test]
+fn global_proxy_is_inherited_unless_overridden() {
+    let config: HashMap<String, serde_json::Value> = HashMap::from([
+        ("proxy".to_string(), json!("http://127.0.0.1:7890")),
+        ("alibaba".to_string(), json!({ "accessKeyId": "id", "proxy": false })),
+    ]);
+
+    let (_, openai_config) = translate::resolve("openai", Some(&config)).unwrap();
+    assert_eq!(openai_config.unwrap()["proxy"], "http://127.0.0.1:7890");
+
+    let (_, alibaba_config) = translate::resolve("alibaba", Some(&config)).unwrap();
+    assert_eq!(alibaba_config.unwrap()["proxy"], false);
+}
+
+#[test]
+fn invalid_proxy_url_is_a_config_error() {
+    let config = json!({ "proxy": { "url": "not a url" } });
+    let err = crate::services::proxy::apply(reqwest::Client::builder(), Some(&config)).err().unwrap();
+    assert!(err.to_string().contains("Invalid proxy URL"));
+}
+
+#[test]
+fn service_languages_describe_supported_pairs() {
+    let openai = translate::service_languages("OpenAI").unwrap();
+    assert!(openai.any);
+
+    let caiyun = translate::service_languages("caiyun").unwrap();
+    assert!(!caiyun.any);
+    assert!(caiyun.pairs.contains(&("en", "zh-CN")));
+    assert!(!caiyun.pairs.contains(&("en", "ja")));
+    assert!(caiyun.pairs.contains(&("auto", "ja")));
+    assert!(!caiyun.languages.contains(&"auto"));
+
+    let deepl = translate::service_languages("DeepL").unwrap();
+    assert!(deepl.pairs.contains(&("en", "zh-CN")));
+    assert!(!deepl.pairs.contains(&("en", "en")));
+    assert!(deepl.pairs.contains(&("auto", "en")));
+
+    for service in ["Google", "GoogleFree", "AWS", "Alibaba", "Lingva", "NiuTrans", "Volcengine", "LibreTranslate"] {
+        let languages = translate::service_languages(service).unwrap();
+        assert!(!languages.any, "{} should declare its languages", service);
+        assert!(languages.pairs.contains(&("auto", "zh-CN")), "{} should accept auto", service);
+    }
+
+    assert!(translate::service_languages("custom:anything").unwrap().any);
+    assert!(translate::service_languages("no-such-service").is_err());
+}
+
+#[test]
+fn list_services_reports_credentials_and_streaming() {
+    let config: HashMap<String, serde_json::Value> = HashMap::from([
+        ("deepl".to_string(), json!({ "apiKey": "k" })),
+        ("openai".to_string(), json!({ "apiKeys": ["a", "b"] })),
+        (
+            "customServices".to_string(),
+            json!([
+                { "id": "LOCAL", "apiUrl": "http://localhost:8080" },
+                { "id": "ollama", "name": "Ollama", "apiUrl": "http://localhost:11434" },
+            ]),
+        ),
+    ]);
+
+    let services = translate::list_services(Some(&config));
+    let get = |name: &str| services.iter().find(|s| s.name == name).unwrap();
+
+    assert!(get("OpenAI").configured);
+    assert!(get("OpenAI").streaming);
+    assert!(!get("Claude").configured);
+    assert!(!get("DeepL").streaming);
+    assert!(get("GoogleFree").configured);
+    assert!(get("custom:LOCAL").custom);
+    assert!(get("custom:LOCAL").configured);
+    assert_eq!(get("custom:LOCAL").id, "custom:LOCAL");
+    assert_eq!(get("Ollama").id, "custom:ollama");
+    assert_eq!(get("OpenAI").id, "OpenAI");
+}
@@ -0,0 +1,66 @@
+use crate::session::{self, report, SessionEntry};
+use chrono::Utc;
+
+fn entry(source: &str, translation: &str) -> SessionEntry {
+    SessionEntry {
+        source_text: source.to_string(),
+        translation: translation.to_string(),
+        source_lang: Some("en".to_string()),
+        target_lang: Some("zh".to_string()),
+        service: Some("DeepL".to_string()),
+        notes: Some("<b>remember</b>".to_string()),
+        screenshot: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn markdown_report_is_bilingual() {
+    let s = session::start(Some("Reading".to_string()));
+    session::add_entry(&s.id, entry("first line\nsecond line", "第一行\n第二行")).unwrap();
+
+    let markdown = report::render(&session::get(&s.id).unwrap(), report::ReportFormat::Markdown);
+
+    assert!(markdown.starts_with("# Reading"));
+    assert!(markdown.contains("> first line\n> second line"));
+    assert!(markdown.contains("第一行"));
+    assert!(markdown.contains("en → zh · DeepL"));
+}
+
+#[test]
+fn html_report_escapes_content() {
+    let s = session::start(None);
+    session::add_entry(&s.id, entry("a < b", "a 小于 b")).unwrap();
+
+    let html = report::render(&session::get(&s.id).unwrap(), report::ReportFormat::Html);
+
+    assert!(html.contains("a &lt; b"));
+    assert!(html.contains("&lt;b&gt;remember&lt;/b&gt;"));
+    assert!(!html.contains("<b>remember"));
+}
+
+#[test]
+fn unknown_session_is_rejected() {
+    assert!(session::add_entry("missing", entry("x", "y")).is_err());
+}
+
+#[test]
+fn lookup_token_returns_sentence_context() {
+    // Non-English tokens are never prefetched, so this stays off the network
+    let reading = session::reading::open("你好 世界。再见！");
+    assert_eq!(reading.tokens.len(), 3);
+
+    // Offsets count UTF-16 code units, matching JavaScript string indices
+    assert_eq!((reading.tokens[1].start, reading.tokens[1].end), (3, 5));
+    assert_eq!((reading.tokens[2].start, reading.tokens[2].end), (6, 8));
+
+    let info = session::reading::lookup_token(&reading.id, 2).unwrap();
+    assert_eq!(info.token.text, "再见");
+    assert_eq!(info.sentence, "再见！");
+    assert!(info.prefetched);
+    assert!(info.entry.is_none());
+
+    assert!(session::reading::lookup_token(&reading.id, 3).is_err());
+    session::reading::close(&reading.id);
+    assert!(session::reading::lookup_token(&reading.id, 0).is_err());
+}
@@ -0,0 +1,64 @@
+use super::{json_response, mount};
+use crate::error::AppError;
+use crate::ocr::simpletex;
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer};
+
+fn simpletex_config(server: &MockServer) -> serde_json::Value {
+    json!({
+        "token": "test-token",
+        "apiUrl": format!("{}/api/latex_ocr", server.uri())
+    })
+}
+
+#[tokio::test]
+async fn recognize_formula_returns_latex() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/latex_ocr"))
+        .and(header("token", "test-token"))
+        .respond_with(json_response(200, json!({
+            "status": true,
+            "res": { "latex": "\\frac{a}{b}", "conf": 0.93 },
+            "request_id": "req"
+        })))
+        .mount(&server)
+        .await;
+
+    let config = simpletex_config(&server);
+    let result = simpletex::recognize_formula(Some("aGVsbG8=".to_string()), None, Some(&config))
+        .await
+        .unwrap();
+
+    assert_eq!(result.latex, "\\frac{a}{b}");
+    assert_eq!(result.confidence, Some(0.93));
+}
+
+#[tokio::test]
+async fn recognize_formula_reports_status_false() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/api/latex_ocr", json_response(200, json!({
+        "status": false,
+        "err_info": "invalid token"
+    }))).await;
+
+    let config = simpletex_config(&server);
+    let err = simpletex::recognize_formula(Some("aGVsbG8=".to_string()), None, Some(&config))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AppError::Api { ref service, ref message } if service == "SimpleTex" && message == "invalid token"));
+}
+
+#[tokio::test]
+async fn recognize_formula_needs_image_data() {
+    let server = MockServer::start().await;
+
+    let config = simpletex_config(&server);
+    let err = simpletex::recognize_formula(None, Some("https://example.com/f.png".to_string()), Some(&config))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, AppError::InvalidRequest(_)));
+}
@@ -0,0 +1,165 @@
+use crate::dictionary::stardict::{self, parse_article, parse_dictzip_header, parse_idx, parse_ifo, to_entry};
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::Write;
+use std::sync::Mutex;
+
+/// 已加载的词典是全局的；加载词典的测试依次运行
+static LOADED: Mutex<()> = Mutex::new(());
+
+fn idx_entry(word: &str, offset: u32, size: u32) -> Vec<u8> {
+    let mut bytes = word.as_bytes().to_vec();
+    bytes.push(0);
+    bytes.extend_from_slice(&offset.to_be_bytes());
+    bytes.extend_from_slice(&size.to_be_bytes());
+    bytes
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// 按 `chunk_len` 分块压缩成 dictzip
+fn dictzip(data: &[u8], chunk_len: usize) -> Vec<u8> {
+    let chunks: Vec<Vec<u8>> = data.chunks(chunk_len).map(deflate).collect();
+    let mut extra = b"RA".to_vec();
+    extra.extend_from_slice(&((6 + chunks.len() * 2) as u16).to_le_bytes());
+    extra.extend_from_slice(&1u16.to_le_bytes());
+    extra.extend_from_slice(&(chunk_len as u16).to_le_bytes());
+    extra.extend_from_slice(&(chunks.len() as u16).to_le_bytes());
+    for chunk in &chunks {
+        extra.extend_from_slice(&(chunk.len() as u16).to_le_bytes());
+    }
+
+    let mut bytes = vec![0x1f, 0x8b, 0x08, 0x04 | 0x08, 0, 0, 0, 0, 0, 3];
+    bytes.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&extra);
+    bytes.extend_from_slice(b"test.dict\0");
+    for chunk in chunks {
+        bytes.extend_from_slice(&chunk);
+    }
+    bytes
+}
+
+#[test]
+fn ifo_and_index_are_parsed() {
+    let ifo = parse_ifo("StarDict's dict ifo file\nversion=3.0.0\nwordcount=2\nbookname=Test\nsametypesequence=tm\n").unwrap();
+    assert_eq!(ifo.name, "Test");
+    assert_eq!(ifo.same_type_sequence.as_deref(), Some("tm"));
+    assert_eq!(ifo.offset_bits, 32);
+    assert!(parse_ifo("not a dictionary").is_none());
+
+    let mut idx = idx_entry("apple", 0, 10);
+    idx.extend(idx_entry("Apple", 10, 5));
+    idx.extend(idx_entry("ant", 15, 3));
+    let index = parse_idx(&idx, 32).unwrap();
+    assert_eq!(index.iter().map(|e| e.word.as_str()).collect::<Vec<_>>(), ["ant", "apple", "Apple"]);
+    assert!(parse_idx(&idx[..idx.len() - 2], 32).is_none());
+}
+
+#[test]
+fn articles_are_split_into_typed_fields() {
+    let fields = parse_article(b"h\xc9\x99\x00n. a greeting\nint. hi there", Some("tm"));
+    assert_eq!(fields, vec![('t', "h\u{259}".to_string()), ('m', "n. a greeting\nint. hi there".to_string())]);
+
+    let fields = parse_article(b"mfirst\0h<b>bold</b><br>second\0", None);
+    assert_eq!(fields.len(), 2);
+
+    let entry = to_entry("hello", &fields);
+    assert_eq!(entry.meanings[0].definitions[0].definition, "first");
+    let html: Vec<_> = entry.meanings[1].definitions.iter().map(|d| d.definition.as_str()).collect();
+    assert_eq!(html, ["bold", "second"]);
+
+    let entry = to_entry("hello", &parse_article(b"h\xc9\x99\x00n. a greeting\nint. hi there", Some("tm")));
+    assert_eq!(entry.phonetic.as_deref(), Some("/h\u{259}/"));
+    assert_eq!(entry.meanings.len(), 2);
+    assert_eq!((entry.meanings[1].part_of_speech.as_str(), entry.meanings[1].definitions[0].definition.as_str()), ("int", "hi there"));
+}
+
+#[test]
+fn dictzip_header_locates_chunks() {
+    let bytes = dictzip(b"0123456789", 4);
+    let header = parse_dictzip_header(&bytes).unwrap();
+    assert_eq!(header.chunk_len, 4);
+    assert_eq!(header.chunk_sizes.len(), 3);
+    assert_eq!(&bytes[header.data_start as usize - 10..header.data_start as usize], b"test.dict\0");
+    assert!(parse_dictzip_header(b"plain text").is_none());
+}
+
+#[test]
+fn dictionaries_in_a_folder_are_loaded_and_queried() {
+    let _loaded = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = std::env::temp_dir().join(format!("dict_stardict_{}", uuid::Uuid::new_v4().simple()));
+    std::fs::create_dir_all(dir.join("nested")).unwrap();
+
+    let first = "n. a round fruit";
+    let second = "v. to request";
+    let data = format!("{}{}", first, second);
+    let mut idx = idx_entry("apple", 0, first.len() as u32);
+    idx.extend(idx_entry("ask", first.len() as u32, second.len() as u32));
+    let ifo = "StarDict's dict ifo file\nversion=2.4.2\nwordcount=2\nbookname=Plain\nsametypesequence=m\n";
+    std::fs::write(dir.join("plain.ifo"), ifo).unwrap();
+    std::fs::write(dir.join("plain.idx"), &idx).unwrap();
+    std::fs::write(dir.join("plain.dict"), &data).unwrap();
+
+    // 词条跨越两个压缩块
+    std::fs::write(dir.join("nested/zipped.ifo"), ifo.replace("Plain", "Zipped")).unwrap();
+    std::fs::write(dir.join("nested/zipped.idx"), &idx).unwrap();
+    std::fs::write(dir.join("nested/zipped.dict.dz"), dictzip(data.as_bytes(), 8)).unwrap();
+    std::fs::write(dir.join("broken.ifo"), "garbage").unwrap();
+
+    let infos = stardict::load_dir(dir.to_str().unwrap()).unwrap();
+    assert_eq!(infos.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), ["Zipped", "Plain"]);
+
+    let entries = stardict::lookup("ASK").unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.meanings[0].part_of_speech == "v" && e.meanings[0].definitions[0].definition == "to request"));
+    assert!(stardict::lookup("missing").unwrap().is_empty());
+
+    assert!(stardict::load_dir(dir.join("plain.ifo").to_str().unwrap()).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// 写入只有 "ask" 一个词条的 dictzip 词典
+fn write_zipped_dictionary(dir: &std::path::Path, dict_dz: &[u8]) {
+    std::fs::create_dir_all(dir).unwrap();
+    let ifo = "StarDict's dict ifo file\nversion=2.4.2\nwordcount=1\nbookname=Zipped\nsametypesequence=m\n";
+    std::fs::write(dir.join("zipped.ifo"), ifo).unwrap();
+    std::fs::write(dir.join("zipped.idx"), idx_entry("ask", 0, 13)).unwrap();
+    std::fs::write(dir.join("zipped.dict.dz"), dict_dz).unwrap();
+}
+
+#[test]
+fn corrupt_compressed_chunks_are_reported() {
+    let _loaded = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = std::env::temp_dir().join(format!("dict_stardict_{}", uuid::Uuid::new_v4().simple()));
+    let mut bytes = dictzip(b"v. to request", 8);
+    // 0xff 是保留的块类型，解压必然失败
+    let data_start = parse_dictzip_header(&bytes).unwrap().data_start as usize;
+    bytes[data_start] = 0xff;
+    write_zipped_dictionary(&dir, &bytes);
+
+    stardict::load_dir(dir.to_str().unwrap()).unwrap();
+    assert!(stardict::lookup("ask").is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn dictionary_folder_is_remembered() {
+    let _loaded = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = std::env::temp_dir().join(format!("dict_stardict_{}", uuid::Uuid::new_v4().simple()));
+    let empty = dir.join("empty");
+    std::fs::create_dir_all(&empty).unwrap();
+    write_zipped_dictionary(&dir.join("saved"), &dictzip(b"v. to request", 8));
+
+    assert_eq!(stardict::set_dir(dir.join("saved").to_str().unwrap()).unwrap().len(), 1);
+    // 模拟重启：清空已加载的词典后从记住的文件夹重新加载
+    stardict::load_dir(empty.to_str().unwrap()).unwrap();
+    assert!(stardict::loaded().is_empty());
+    let restored = stardict::restore().unwrap();
+    assert_eq!(restored.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), ["Zipped"]);
+    assert_eq!(stardict::lookup("ask").unwrap().len(), 1);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,72 @@
+use super::{json_response, mount, TempDb};
+use crate::dictionary::tatoeba::{insert_pairs, parse_pairs_tsv, query, search_from, tatoeba_code, tokenize, ExampleSentence};
+use serde_json::json;
+use wiremock::MockServer;
+
+const TSV: &str = "1276\tLet's try something.\t2\t我们试试看！\r\n\
+1277\tI have to go to sleep.\t5\t我该去睡觉了。\n\
+broken line\n\
+1280\tToday is June 18th.\t9\t今天是6月18号。\n";
+
+#[test]
+fn sentence_pair_tsv_skips_malformed_lines() {
+    let pairs = parse_pairs_tsv(TSV);
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(pairs[0], ("Let's try something.".to_string(), "我们试试看！".to_string()));
+}
+
+#[test]
+fn sentences_are_tokenized_for_the_index() {
+    assert_eq!(tokenize("Let's try, let's TRY!"), vec!["let's", "try"]);
+    assert_eq!(tokenize("'Quoted' words"), vec!["quoted", "words"]);
+}
+
+#[test]
+fn language_codes_map_to_iso_639_3() {
+    assert_eq!(tatoeba_code("zh-CN"), "cmn");
+    assert_eq!(tatoeba_code("en"), "eng");
+    assert_eq!(tatoeba_code("deu"), "deu");
+}
+
+#[tokio::test]
+async fn search_returns_direct_translations_in_the_target_language() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/search", json_response(200, json!({
+        "paging": {},
+        "results": [
+            {
+                "id": 1277,
+                "text": "I have to go to sleep.",
+                "lang": "eng",
+                "translations": [
+                    [{ "id": 4, "text": "Je dois aller dormir.", "lang": "fra" }, { "id": 5, "text": "我该去睡觉了。", "lang": "cmn" }],
+                    []
+                ]
+            },
+            { "id": 3, "text": "Sleep well.", "lang": "eng", "translations": [[], [{ "id": 7, "text": "Schlaf gut.", "lang": "deu" }]] }
+        ]
+    }))).await;
+
+    let examples = search_from(&format!("{}/search", server.uri()), "sleep", "eng", "cmn", 10).await.unwrap();
+    assert_eq!(
+        examples,
+        vec![ExampleSentence {
+            sentence: "I have to go to sleep.".to_string(),
+            translation: "我该去睡觉了。".to_string(),
+            source_lang: "eng".to_string(),
+            target_lang: "cmn".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn imported_pairs_are_found_by_word() {
+    let db = TempDb::new("tatoeba");
+    let mut conn = db.open();
+    assert_eq!(insert_pairs(&mut conn, "eng", "cmn", &parse_pairs_tsv(TSV)).unwrap(), 3);
+
+    let examples = query(&conn, "Sleep", "eng", "cmn", 5).unwrap();
+    assert_eq!(examples.len(), 1);
+    assert_eq!(examples[0].translation, "我该去睡觉了。");
+    assert!(query(&conn, "sleep", "eng", "jpn", 5).unwrap().is_empty());
+}
@@ -0,0 +1,69 @@
+use crate::ocr::tesseract::{language_code, parse_tsv, resolve_languages, TesseractOptions};
+
+const HEADER: &str = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext";
+
+fn tsv(rows: &[&str]) -> String {
+    std::iter::once(HEADER).chain(rows.iter().copied()).collect::<Vec<_>>().join("\n")
+}
+
+#[test]
+fn words_are_grouped_into_lines_with_boxes() {
+    let output = tsv(&[
+        "1\t1\t0\t0\t0\t0\t0\t0\t400\t100\t-1\t",
+        "4\t1\t1\t1\t1\t0\t10\t10\t120\t20\t-1\t",
+        "5\t1\t1\t1\t1\t1\t10\t10\t50\t20\t90\tHello",
+        "5\t1\t1\t1\t1\t2\t70\t12\t60\t18\t80\tworld",
+        "5\t1\t1\t1\t2\t1\t10\t40\t40\t20\t70\tagain",
+    ]);
+
+    let result = parse_tsv(&output);
+
+    assert_eq!(result.text, "Hello world\nagain");
+    assert_eq!(result.lines.len(), 2);
+    let first = &result.lines[0];
+    assert_eq!((first.x, first.y, first.width, first.height), (10.0, 10.0, 120.0, 20.0));
+    assert!((first.confidence.unwrap() - 0.85).abs() < 1e-9);
+    assert!((result.confidence - 0.775).abs() < 1e-9);
+}
+
+#[test]
+fn cjk_words_are_joined_without_spaces() {
+    let output = tsv(&[
+        "5\t1\t1\t1\t1\t1\t0\t0\t20\t20\t95\t你",
+        "5\t1\t1\t1\t1\t2\t20\t0\t20\t20\t95\t好",
+        "5\t1\t1\t1\t1\t3\t45\t0\t40\t20\t95\tOCR",
+    ]);
+
+    assert_eq!(parse_tsv(&output).text, "你好 OCR");
+}
+
+#[test]
+fn empty_output_has_no_lines() {
+    let result = parse_tsv(HEADER);
+
+    assert!(result.text.is_empty());
+    assert!(result.lines.is_empty());
+    assert_eq!(result.confidence, 0.0);
+}
+
+#[test]
+fn app_languages_map_to_tesseract_packs() {
+    assert_eq!(language_code(None), "eng");
+    assert_eq!(language_code(Some("zh-CN")), "chi_sim");
+    assert_eq!(language_code(Some("zh-TW")), "chi_tra");
+    assert_eq!(language_code(Some("ja")), "jpn");
+    assert_eq!(language_code(Some("eng+deu")), "eng+deu");
+}
+
+#[test]
+fn configured_languages_apply_only_without_explicit_language() {
+    let options = TesseractOptions {
+        languages: Some("eng+jpn".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(resolve_languages(None, &options), "eng+jpn");
+    assert_eq!(resolve_languages(Some("auto"), &options), "eng+jpn");
+    assert_eq!(resolve_languages(Some("ko"), &options), "kor");
+    assert_eq!(resolve_languages(Some("auto"), &TesseractOptions::default()), "eng+chi_sim");
+}
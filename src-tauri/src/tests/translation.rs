@@ -0,0 +1,952 @@
+use super::{json_response, malformed_response, mount, service_config};
+use crate::error::AppError;
+use crate::services::openai::KeySource;
+use crate::services::{claude, deepl, google_free, libretranslate, lingva, openai};
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const CHAT_PATH: &str = "/v1/chat/completions";
+
+#[tokio::test]
+async fn openai_returns_trimmed_translation() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(200, json!({
+        "choices": [{ "message": { "content": "  你好  " } }]
+    }))).await;
+
+    let config = service_config(&server, CHAT_PATH);
+    let result = openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.unwrap();
+
+    assert_eq!(result.name, "OpenAI");
+    assert_eq!(result.text, "你好");
+}
+
+#[tokio::test]
+async fn openai_variants_become_alternatives() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(200, json!({
+        "choices": [{ "message": { "content": "1. 你好\n2. 您好\n3. 嗨" } }]
+    }))).await;
+
+    let mut config = service_config(&server, CHAT_PATH);
+    config["variants"] = json!(3);
+    let result = openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.unwrap();
+
+    assert_eq!(result.text, "你好");
+    assert_eq!(result.alternatives, ["您好", "嗨"]);
+}
+
+#[tokio::test]
+async fn openai_sends_bearer_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .and(header("Authorization", "Bearer test-key"))
+        .respond_with(json_response(200, json!({
+            "choices": [{ "message": { "content": "ok" } }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = service_config(&server, CHAT_PATH);
+    assert!(openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.is_ok());
+}
+
+#[tokio::test]
+async fn custom_service_never_sends_the_environment_key() {
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(200, json!({
+        "choices": [{ "message": { "content": "ok" } }]
+    }))).await;
+    std::env::set_var("OPENAI_API_KEY", "env-secret");
+
+    let config: HashMap<String, serde_json::Value> = HashMap::from([(
+        "customServices".to_string(),
+        json!([{ "id": "local", "apiUrl": format!("{}{}", server.uri(), CHAT_PATH) }]),
+    )]);
+    let (provider, service_config) = crate::translate::resolve("custom:local", Some(&config)).unwrap();
+    provider.translate("hello", "en", "zh", service_config.as_ref()).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(!requests[0].headers.contains_key("authorization"));
+}
+
+#[tokio::test]
+async fn openai_reports_auth_failure() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(401, json!({
+        "error": { "message": "Incorrect API key provided" }
+    }))).await;
+
+    let config = service_config(&server, CHAT_PATH);
+    let err = openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.unwrap_err();
+
+    assert!(err.to_string().contains("OpenAI API error"));
+    assert!(err.to_string().contains("Incorrect API key"));
+}
+
+#[tokio::test]
+async fn openai_reports_rate_limit() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, json_response(429, json!({
+        "error": { "message": "Rate limit reached" }
+    }))).await;
+
+    let config = service_config(&server, CHAT_PATH);
+    let err = openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.unwrap_err();
+
+    assert!(err.to_string().contains("Rate limit reached"));
+}
+
+#[tokio::test]
+async fn openai_rejects_malformed_response() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", CHAT_PATH, malformed_response()).await;
+
+    let config = service_config(&server, CHAT_PATH);
+    let err = openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.unwrap_err();
+
+    assert!(err.to_string().contains("Failed to parse OpenAI response"));
+}
+
+#[tokio::test]
+async fn openai_stream_accumulates_deltas() {
+    let server = MockServer::start().await;
+    let body = concat!(
+        "data: {\"choices\":[{\"delta\":{\"content\":\"你\"}}]}\n\n",
+        "data: {\"choices\":[{\"delta\":{\"content\":\"好\"}}]}\n\n",
+        "data: [DONE]\n\n",
+    );
+    mount(&server, "POST", CHAT_PATH, ResponseTemplate::new(200)
+        .insert_header("content-type", "text/event-stream")
+        .set_body_string(body)).await;
+
+    let config = service_config(&server, CHAT_PATH);
+    let mut deltas = Vec::new();
+    let text = openai::translate_stream("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv, |d| deltas.push(d.to_string()))
+        .await
+        .unwrap();
+
+    assert_eq!(text, "你好");
+    assert_eq!(deltas, vec!["你", "好"]);
+}
+
+#[tokio::test]
+async fn openai_renders_prompt_template() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .and(body_partial_json(json!({
+            "messages": [{ "role": "user", "content": "Casually translate en to zh: hello" }]
+        })))
+        .respond_with(json_response(200, json!({
+            "choices": [{ "message": { "content": "嗨" } }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = service_config(&server, CHAT_PATH);
+    config["promptTemplate"] = json!("Casually translate {source} to {target}: {text}");
+    let result = openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.unwrap();
+
+    assert_eq!(result.text, "嗨");
+}
+
+#[tokio::test]
+async fn openai_forwards_generation_params() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .and(body_partial_json(json!({ "temperature": 0.2, "top_p": 0.9, "max_tokens": 4000 })))
+        .respond_with(json_response(200, json!({
+            "choices": [{ "message": { "content": "你好" } }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = service_config(&server, CHAT_PATH);
+    config["temperature"] = json!(0.2);
+    config["topP"] = json!(0.9);
+    config["maxTokens"] = json!(4000);
+    assert!(openai::translate("hello", "en", "zh", Some(&config), KeySource::ConfigOrEnv).await.is_ok());
+}
+
+#[tokio::test]
+async fn claude_uses_template_as_system_prompt() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(json!({
+            "system": "Translate into technical zh.",
+            "messages": [{ "role": "user", "content": "hello" }]
+        })))
+        .respond_with(json_response(200, json!({
+            "content": [{ "type": "text", "text": "你好" }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = service_config(&server, "/v1/messages");
+    config["promptTemplate"] = json!("Translate into technical {target}.");
+    assert!(claude::translate("hello", "en", "zh", Some(&config)).await.is_ok());
+}
+
+#[tokio::test]
+async fn claude_returns_translation() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v1/messages", json_response(200, json!({
+        "content": [{ "type": "text", "text": "你好" }]
+    }))).await;
+
+    let config = service_config(&server, "/v1/messages");
+    let result = claude::translate("hello", "en", "zh", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "你好");
+}
+
+#[tokio::test]
+async fn claude_reports_auth_failure_as_api_error() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v1/messages", json_response(401, json!({
+        "type": "error",
+        "error": { "type": "authentication_error", "message": "invalid x-api-key" }
+    }))).await;
+
+    let config = service_config(&server, "/v1/messages");
+    let err = claude::translate("hello", "en", "zh", Some(&config)).await.unwrap_err();
+
+    match err {
+        AppError::HttpStatus { service, status, message } => {
+            assert_eq!(service, "Claude");
+            assert_eq!(status.as_u16(), 401);
+            assert!(message.contains("invalid x-api-key"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn claude_reports_rate_limit() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v1/messages", json_response(429, json!({
+        "type": "error",
+        "error": { "type": "rate_limit_error", "message": "rate limited" }
+    }))).await;
+
+    let config = service_config(&server, "/v1/messages");
+    assert!(claude::translate("hello", "en", "zh", Some(&config)).await.is_err());
+}
+
+#[tokio::test]
+async fn claude_rejects_malformed_response() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v1/messages", malformed_response()).await;
+
+    let config = service_config(&server, "/v1/messages");
+    assert!(claude::translate("hello", "en", "zh", Some(&config)).await.is_err());
+}
+
+#[test]
+fn bedrock_takes_max_tokens_from_config() {
+    use crate::services::bedrock::{request_body, MAX_TEXT_CHARS};
+
+    let body = request_body("Hello", "en", "zh", None);
+    assert_eq!(body["max_tokens"], 4096);
+    // 默认分段译成中日韩文字时不超过默认输出上限
+    assert!(MAX_TEXT_CHARS as u64 <= body["max_tokens"].as_u64().unwrap());
+
+    let body = request_body("Hello", "en", "zh", Some(&json!({ "maxTokens": 8000 })));
+    assert_eq!(body["max_tokens"], 8000);
+}
+
+#[test]
+fn bedrock_uses_prompt_template_and_generation_params() {
+    use crate::services::bedrock::request_body;
+
+    let config = json!({
+        "promptTemplate": "Translate {source} to {target}: {text}",
+        "temperature": 0.3,
+        "topP": 0.8
+    });
+    let body = request_body("Hello", "en", "ja", Some(&config));
+    assert_eq!(body["messages"][0]["content"], "Translate en to ja: Hello");
+    assert!(body.get("system").is_none());
+    assert_eq!((body["temperature"].as_f64(), body["top_p"].as_f64()), (Some(0.3), Some(0.8)));
+
+    let body = request_body("Hello", "en", "ja", None);
+    assert_eq!(body["messages"][0]["content"], "Hello");
+    assert!(body["system"].as_str().unwrap().contains("ja"));
+    assert!(body.get("temperature").is_none());
+}
+
+#[tokio::test]
+async fn gemini_native_results_and_errors_use_its_own_name() {
+    use crate::translate::TranslationService;
+    let provider = crate::services::gemini_native::GeminiNative;
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/models/gemini-1.5-flash:generateContent", json_response(200, json!({
+        "candidates": [{ "content": { "parts": [{ "text": " 你好 " }] } }]
+    }))).await;
+    mount(&server, "POST", "/models/blocked:generateContent", json_response(200, json!({
+        "promptFeedback": { "blockReason": "SAFETY" }
+    }))).await;
+    let config = json!({ "apiUrl": server.uri(), "apiKey": "test-key" });
+
+    let result = provider.translate("Hello", "en", "zh", Some(&config)).await.unwrap();
+    assert_eq!((result.name.as_str(), result.text.as_str()), ("Gemini Native", "你好"));
+
+    let mut blocked = config.clone();
+    blocked["model"] = json!("blocked");
+    match provider.translate("Hello", "en", "zh", Some(&blocked)).await {
+        Err(AppError::Api { service, .. }) => assert_eq!(service, "Gemini Native"),
+        other => panic!("expected API error, got {:?}", other.map(|r| r.text)),
+    }
+}
+
+#[tokio::test]
+async fn deepl_maps_target_language_and_auth_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v2/translate"))
+        .and(header("Authorization", "DeepL-Auth-Key test-key"))
+        .respond_with(json_response(200, json!({
+            "translations": [{ "detected_source_language": "EN", "text": "你好" }]
+        })))
+        .mount(&server)
+        .await;
+
+    let config = service_config(&server, "/v2/translate");
+    let result = deepl::translate("hello", "en", "zh", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "你好");
+}
+
+#[tokio::test]
+async fn deepl_forwards_formality_and_glossary() {
+    use wiremock::matchers::body_string_contains;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v2/translate"))
+        .and(body_string_contains("formality=prefer_less"))
+        .and(body_string_contains("split_sentences=0"))
+        .and(body_string_contains("glossary_id=g-123"))
+        .and(body_string_contains("target_lang=ZH-HANT"))
+        .respond_with(json_response(200, json!({ "translations": [{ "text": "哈囉" }] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut config = service_config(&server, "/v2/translate");
+    config["formality"] = json!("prefer_less");
+    config["splitSentences"] = json!(0);
+    config["glossaryId"] = json!("g-123");
+    let result = deepl::translate("hello", "en", "zh-TW", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "哈囉");
+}
+
+#[tokio::test]
+async fn deepl_glossary_requires_source_language() {
+    let mut config = json!({ "apiUrl": "http://127.0.0.1:9/v2/translate", "apiKey": "k:fx" });
+    config["glossaryId"] = json!("g-123");
+
+    let err = deepl::translate("hello", "auto", "zh", Some(&config)).await.unwrap_err();
+
+    assert!(err.to_string().contains("source language"));
+}
+
+#[tokio::test]
+async fn deepl_reports_auth_failure() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/v2/translate", json_response(403, json!({
+        "message": "Wrong endpoint"
+    }))).await;
+
+    let config = service_config(&server, "/v2/translate");
+    let err = deepl::translate("hello", "en", "zh", Some(&config)).await.unwrap_err();
+
+    assert!(err.to_string().contains("DeepL API error"));
+    assert_eq!(err.http_status(), Some(403));
+}
+
+#[tokio::test]
+async fn google_free_joins_sentences() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/translate_a/single", json_response(200, json!([
+        [["你好。", "Hello.", null, null], ["世界", "World", null, null]],
+        null,
+        "en"
+    ]))).await;
+
+    let config = service_config(&server, "/translate_a/single");
+    let result = google_free::translate("Hello. World", "en", "zh", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "你好。世界");
+}
+
+#[tokio::test]
+async fn google_free_returns_alternatives_for_single_segment() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/translate_a/single", json_response(200, json!([
+        [["你好", "Hello", null, null]],
+        null,
+        "en",
+        null,
+        null,
+        [["Hello", null, [["你好", 1000, true, false], ["哈罗", 0, true, false], ["喂", 0, true, false]], [[0, 5]], "Hello", 0, 0]]
+    ]))).await;
+
+    let config = service_config(&server, "/translate_a/single");
+    let result = google_free::translate("Hello", "en", "zh", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "你好");
+    assert_eq!(result.alternatives, ["哈罗", "喂"]);
+}
+
+#[tokio::test]
+async fn google_free_rejects_unexpected_shape() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/translate_a/single", json_response(200, json!({ "error": "nope" }))).await;
+
+    let config = service_config(&server, "/translate_a/single");
+    let err = google_free::translate("hello", "en", "zh", Some(&config)).await.unwrap_err();
+
+    assert!(err.to_string().contains("Invalid response format"));
+}
+
+#[tokio::test]
+async fn google_free_reports_rate_limit() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/translate_a/single", ResponseTemplate::new(429)).await;
+
+    let config = service_config(&server, "/translate_a/single");
+    let err = google_free::translate("hello", "en", "zh", Some(&config)).await.unwrap_err();
+
+    assert_eq!(err.http_status(), Some(429));
+}
+
+#[tokio::test]
+async fn rate_limited_request_is_retried_after_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/translate"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/translate"))
+        .respond_with(json_response(200, json!({ "translatedText": "hola" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = service_config(&server, "");
+    let result = libretranslate::translate("hello", "en", "es", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "hola");
+}
+
+#[tokio::test]
+async fn persistent_rate_limit_names_the_service() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(
+            json_response(429, json!({ "error": { "message": "Rate limit reached for requests" } }))
+                .insert_header("Retry-After", "0"),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let request = TranslationRequest {
+        text: "still limited".to_string(),
+        source_lang: "en".to_string(),
+        target_lang: "zh".to_string(),
+        services: vec!["Hunyuan".to_string()],
+        config: Some(HashMap::from([
+            ("hunyuan".to_string(), service_config(&server, CHAT_PATH)),
+            ("cache".to_string(), json!({ "enabled": false })),
+        ])),
+        mode: TranslationMode::All,
+        format: TextFormat::Plain,
+    };
+
+    let response = crate::services::translate(request).await.unwrap();
+
+    assert_eq!(response.results[0].error.as_deref(), Some("Rate limit exceeded for Hunyuan"));
+}
+
+#[test]
+fn retry_delay_respects_budget() {
+    use crate::services::http::retry_delay;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use std::time::Duration;
+
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_delay(&headers), Some(Duration::from_secs(1)));
+
+    headers.insert("retry-after", HeaderValue::from_static("2"));
+    assert_eq!(retry_delay(&headers), Some(Duration::from_secs(2)));
+
+    headers.insert("retry-after-ms", HeaderValue::from_static("250"));
+    assert_eq!(retry_delay(&headers), Some(Duration::from_millis(250)));
+
+    headers.remove("retry-after-ms");
+    headers.insert("retry-after", HeaderValue::from_static("120"));
+    assert_eq!(retry_delay(&headers), None);
+
+    // 异常值不会 panic
+    for value in ["inf", "1e300", "NaN", "-5"] {
+        headers.insert("retry-after-ms", HeaderValue::from_static(value));
+        let delay = retry_delay(&headers);
+        assert!(delay.is_none() || delay == Some(Duration::ZERO), "{}: {:?}", value, delay);
+    }
+}
+
+#[tokio::test]
+async fn retry_wait_releases_concurrency_permit() {
+    use crate::services::http::{holding, Permit, SendRetrying};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tokio::sync::Semaphore;
+
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/limited", ResponseTemplate::new(429).insert_header("retry-after-ms", "300")).await;
+
+    let semaphore = Arc::new(Semaphore::new(1));
+    let permit = Permit::acquire(semaphore.clone()).await;
+    let client = reqwest::Client::new();
+    let url = format!("{}/limited", server.uri());
+
+    let limited = async {
+        let response = holding(permit, client.get(&url).send_retrying()).await.unwrap();
+        assert_eq!(response.status(), 429);
+        Instant::now()
+    };
+    // 另一个请求在等待重试期间拿到名额
+    let other = async {
+        let _permit = semaphore.acquire().await.unwrap();
+        Instant::now()
+    };
+    let (limited_done, other_started) = tokio::time::timeout(Duration::from_secs(5), async { tokio::join!(limited, other) })
+        .await
+        .unwrap();
+
+    assert!(other_started < limited_done);
+}
+
+#[tokio::test]
+async fn libretranslate_uses_instance_base_url() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/translate", json_response(200, json!({
+        "translatedText": "hola"
+    }))).await;
+
+    let config = service_config(&server, "");
+    let result = libretranslate::translate("hello", "en", "es", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "hola");
+}
+
+#[tokio::test]
+async fn libretranslate_sends_key_and_mapped_languages() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/translate"))
+        .and(body_partial_json(json!({
+            "q": "hello",
+            "source": "en",
+            "target": "zt",
+            "format": "text",
+            "api_key": "test-key"
+        })))
+        .respond_with(json_response(200, json!({ "translatedText": "哈囉" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = service_config(&server, "");
+    let result = libretranslate::translate("hello", "en", "zh-TW", Some(&config)).await.unwrap();
+
+    assert_eq!(result.name, "LibreTranslate");
+    assert_eq!(result.text, "哈囉");
+}
+
+#[tokio::test]
+async fn libretranslate_reports_api_error() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/translate", json_response(400, json!({
+        "error": "zz is not supported"
+    }))).await;
+
+    let config = service_config(&server, "");
+    let err = libretranslate::translate("hello", "en", "zz", Some(&config)).await.unwrap_err();
+
+    assert!(matches!(err, AppError::HttpStatus { ref service, status, .. } if service == "LibreTranslate" && status.as_u16() == 400), "{}", err);
+}
+
+#[tokio::test]
+async fn libretranslate_rejects_malformed_response() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/translate", malformed_response()).await;
+
+    let config = service_config(&server, "");
+    assert!(libretranslate::translate("hello", "en", "es", Some(&config)).await.is_err());
+}
+
+#[tokio::test]
+async fn spark_authenticates_with_bearer_api_password() {
+    use crate::services::spark;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .and(header("Authorization", "Bearer key:secret"))
+        .and(body_partial_json(json!({ "model": "generalv3.5", "stream": false })))
+        .respond_with(json_response(200, json!({
+            "code": 0,
+            "choices": [{ "message": { "content": " 你好 " } }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = json!({ "apiUrl": format!("{}{}", server.uri(), CHAT_PATH), "apiPassword": "key:secret" });
+    let result = spark::translate("hello", "en", "zh", Some(&config)).await.unwrap();
+
+    assert_eq!(result.text, "你好");
+
+    // WebSocket credentials are rejected with a message naming the right key
+    let websocket = json!({ "appId": "app", "apiKey": "key", "apiSecret": "secret" });
+    let err = spark::translate("hello", "en", "zh", Some(&websocket)).await.unwrap_err();
+    assert!(err.to_string().contains("APIPassword"));
+}
+
+#[tokio::test]
+async fn lingva_puts_languages_and_text_in_path() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/en/zh_HANT/hello%20world"))
+        .and(header("Authorization", "Bearer test-key"))
+        .respond_with(json_response(200, json!({ "translation": "哈囉世界" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = service_config(&server, "");
+    let result = lingva::translate("hello world", "en", "zh-TW", Some(&config)).await.unwrap();
+
+    assert_eq!(result.name, "Lingva");
+    assert_eq!(result.text, "哈囉世界");
+}
+
+#[tokio::test]
+async fn lingva_configured_instance_failure_is_reported() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/api/v1/en/es/hello", json_response(500, json!({
+        "error": "upstream failed"
+    }))).await;
+
+    let config = service_config(&server, "");
+    let err = lingva::translate("hello", "en", "es", Some(&config)).await.unwrap_err();
+
+    assert!(matches!(err, AppError::HttpStatus { ref service, status, .. } if service == "Lingva" && status.as_u16() == 500), "{}", err);
+}
+
+#[tokio::test]
+async fn lingva_rejects_response_without_translation() {
+    let server = MockServer::start().await;
+    mount(&server, "GET", "/api/v1/en/es/hello", json_response(200, json!({ "info": {} }))).await;
+
+    let config = service_config(&server, "");
+    let err = lingva::translate("hello", "en", "es", Some(&config)).await.unwrap_err();
+
+    assert!(matches!(err, AppError::Translation(_)), "{}", err);
+}
+
+#[tokio::test]
+async fn timeout_ms_from_config_is_applied() {
+    let server = MockServer::start().await;
+    mount(&server, "POST", "/translate", json_response(200, json!({ "translatedText": "hola" }))
+        .set_delay(std::time::Duration::from_millis(500))).await;
+
+    let mut config = service_config(&server, "");
+    config["timeoutMs"] = json!(50);
+    let err = libretranslate::translate("hello", "en", "es", Some(&config)).await.unwrap_err();
+
+    assert!(matches!(err, AppError::Timeout(_)), "{}", err);
+}
+
+#[tokio::test]
+async fn repeated_translation_is_served_from_cache() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/translate"))
+        .respond_with(json_response(200, json!({ "translatedText": "caché" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = TranslationRequest {
+        text: "cache me once".to_string(),
+        source_lang: "en".to_string(),
+        target_lang: "fr".to_string(),
+        services: vec!["LibreTranslate".to_string()],
+        config: Some(HashMap::from([
+            ("libretranslate".to_string(), service_config(&server, "")),
+            ("cache".to_string(), json!({ "enabled": true })),
+        ])),
+        mode: TranslationMode::All,
+        format: TextFormat::Plain,
+    };
+
+    let first = crate::services::translate(request.clone()).await.unwrap();
+    let second = crate::services::translate(request).await.unwrap();
+
+    assert_eq!(first.results[0].text, "caché");
+    assert_eq!(second.results[0].text, "caché");
+}
+
+#[tokio::test]
+async fn changing_service_settings_bypasses_cache() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(json_response(200, json!({ "choices": [{ "message": { "content": "你好" } }] })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let request_with_model = |model: &str| {
+        let mut openai = service_config(&server, CHAT_PATH);
+        openai["model"] = json!(model);
+        TranslationRequest {
+            text: "cache per model".to_string(),
+            source_lang: "en".to_string(),
+            target_lang: "zh".to_string(),
+            services: vec!["OpenAI".to_string()],
+            config: Some(HashMap::from([
+                ("openai".to_string(), openai),
+                ("cache".to_string(), json!({ "enabled": true })),
+            ])),
+            mode: TranslationMode::All,
+            format: TextFormat::Plain,
+        }
+    };
+
+    crate::services::translate(request_with_model("gpt-4o-mini")).await.unwrap();
+    crate::services::translate(request_with_model("gpt-4o")).await.unwrap();
+    // 相同配置命中缓存，不再请求
+    crate::services::translate(request_with_model("gpt-4o")).await.unwrap();
+}
+
+#[tokio::test]
+async fn max_concurrency_serializes_service_requests() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(
+            json_response(200, json!({ "choices": [{ "message": { "content": "ok" } }] }))
+                .set_delay(std::time::Duration::from_millis(200)),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let request = TranslationRequest {
+        text: "one at a time".to_string(),
+        source_lang: "en".to_string(),
+        target_lang: "zh".to_string(),
+        services: vec!["OpenAI".to_string(), "Groq".to_string()],
+        config: Some(HashMap::from([
+            ("openai".to_string(), service_config(&server, CHAT_PATH)),
+            ("groq".to_string(), service_config(&server, CHAT_PATH)),
+            ("maxConcurrency".to_string(), json!(1)),
+            ("cache".to_string(), json!({ "enabled": false })),
+        ])),
+        mode: TranslationMode::All,
+        format: TextFormat::Plain,
+    };
+
+    let started = std::time::Instant::now();
+    let response = crate::services::translate(request).await.unwrap();
+
+    assert_eq!(response.results.len(), 2);
+    assert!(response.results.iter().all(|r| r.error.is_none()));
+    assert!(started.elapsed() >= std::time::Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn identical_in_flight_requests_share_one_upstream_call() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(
+            json_response(200, json!({ "choices": [{ "message": { "content": "一次" } }] }))
+                .set_delay(std::time::Duration::from_millis(200)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = TranslationRequest {
+        text: "pressed twice".to_string(),
+        source_lang: "en".to_string(),
+        target_lang: "zh".to_string(),
+        services: vec!["OpenAI".to_string()],
+        config: Some(HashMap::from([
+            ("openai".to_string(), service_config(&server, CHAT_PATH)),
+            ("cache".to_string(), json!({ "enabled": false })),
+        ])),
+        mode: TranslationMode::All,
+        format: TextFormat::Plain,
+    };
+
+    let (first, second) = tokio::join!(
+        crate::services::translate(request.clone()),
+        crate::services::translate(request),
+    );
+
+    assert_eq!(first.unwrap().results[0].text, "一次");
+    assert_eq!(second.unwrap().results[0].text, "一次");
+}
+
+#[tokio::test]
+async fn repeated_failures_open_the_circuit() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(json_response(503, json!({ "error": { "message": "overloaded" } })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let request = TranslationRequest {
+        text: "dead provider".to_string(),
+        source_lang: "en".to_string(),
+        target_lang: "zh".to_string(),
+        services: vec!["Grok".to_string()],
+        config: Some(HashMap::from([
+            ("grok".to_string(), service_config(&server, CHAT_PATH)),
+            ("cache".to_string(), json!({ "enabled": false })),
+            ("circuitBreaker".to_string(), json!({ "threshold": 2 })),
+        ])),
+        mode: TranslationMode::All,
+        format: TextFormat::Plain,
+    };
+
+    for _ in 0..2 {
+        let response = crate::services::translate(request.clone()).await.unwrap();
+        assert!(response.results[0].error.is_some());
+    }
+    let response = crate::services::translate(request).await.unwrap();
+
+    let error = response.results[0].error.as_deref().unwrap();
+    assert!(error.contains("temporarily disabled"), "{}", error);
+}
+
+#[tokio::test]
+async fn fallback_mode_stops_at_first_success() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let failing = MockServer::start().await;
+    mount(&failing, "POST", CHAT_PATH, json_response(500, json!({ "error": { "message": "down" } }))).await;
+    let working = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(json_response(200, json!({ "choices": [{ "message": { "content": "备用" } }] })))
+        .expect(1)
+        .mount(&working)
+        .await;
+    let unused = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(CHAT_PATH))
+        .respond_with(json_response(200, json!({ "choices": [{ "message": { "content": "unused" } }] })))
+        .expect(0)
+        .mount(&unused)
+        .await;
+
+    let request = TranslationRequest {
+        text: "try in order".to_string(),
+        source_lang: "en".to_string(),
+        target_lang: "zh".to_string(),
+        services: vec!["OpenAI".to_string(), "Groq".to_string(), "Zhipu".to_string()],
+        config: Some(HashMap::from([
+            ("openai".to_string(), service_config(&failing, CHAT_PATH)),
+            ("groq".to_string(), service_config(&working, CHAT_PATH)),
+            ("zhipu".to_string(), service_config(&unused, CHAT_PATH)),
+            ("cache".to_string(), json!({ "enabled": false })),
+        ])),
+        mode: TranslationMode::Fallback,
+        format: TextFormat::Plain,
+    };
+
+    let response = crate::services::translate(request).await.unwrap();
+
+    assert_eq!(response.results.len(), 1);
+    assert_eq!(response.results[0].text, "备用");
+}
+
+#[tokio::test]
+async fn long_text_is_chunked_and_reassembled() {
+    use crate::models::{TextFormat, TranslationMode, TranslationRequest};
+    use std::collections::HashMap;
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/translate"))
+        .respond_with(json_response(200, json!({ "translatedText": "hola." })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let mut config = service_config(&server, "");
+    config["maxChars"] = json!(20);
+    let request = TranslationRequest {
+        text: "First sentence here. Second sentence.".to_string(),
+        source_lang: "en".to_string(),
+        target_lang: "es".to_string(),
+        services: vec!["LibreTranslate".to_string()],
+        config: Some(HashMap::from([
+            ("libretranslate".to_string(), config),
+            ("cache".to_string(), json!({ "enabled": false })),
+        ])),
+        mode: TranslationMode::All,
+        format: TextFormat::Plain,
+    };
+
+    let response = crate::services::translate(request).await.unwrap();
+
+    assert_eq!(response.results[0].text, "hola. hola.");
+}
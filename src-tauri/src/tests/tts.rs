@@ -0,0 +1,150 @@
+use crate::error::AppError;
+use crate::tts::models::{TtsRequest, TtsVoice};
+use crate::tts::playback;
+use crate::tts::queue::{self, QueuePolicy};
+use std::sync::Mutex;
+
+/// 播放状态是全局的；改动它的测试依次运行，避免互相停止对方的播放
+static PLAYBACK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn playback_controls_without_audio_are_no_ops() {
+    let _playback = PLAYBACK.lock().unwrap_or_else(|e| e.into_inner());
+    assert!(playback::pause().is_ok());
+    assert!(playback::resume().is_ok());
+    assert!(playback::stop().is_ok());
+}
+
+#[test]
+fn stopping_clears_queued_speech() {
+    let _playback = PLAYBACK.lock().unwrap_or_else(|e| e.into_inner());
+    let request = TtsRequest { text: "queued".to_string(), voice: None, engine: None, proxy: None };
+    queue::clear();
+    assert_eq!(queue::push(request.clone()), 0);
+    assert_eq!(queue::push(request), 1);
+    assert!(crate::tts::stop().is_ok());
+    assert!(queue::pop().is_none());
+}
+
+#[test]
+fn queue_policy_is_parsed_from_lowercase_names() {
+    let policy: QueuePolicy = serde_json::from_str("\"queue\"").unwrap();
+    assert_eq!(policy, QueuePolicy::Queue);
+    assert_eq!(QueuePolicy::default(), QueuePolicy::Interrupt);
+}
+
+#[test]
+fn queue_policy_is_saved_to_disk() {
+    queue::set_policy(QueuePolicy::Queue).unwrap();
+    assert_eq!(queue::policy(), QueuePolicy::Queue);
+    let saved = std::fs::read_to_string(crate::paths::data_dir().join("tts_queue.json")).unwrap();
+    assert_eq!(serde_json::from_str::<QueuePolicy>(&saved).unwrap(), QueuePolicy::Queue);
+
+    queue::set_policy(QueuePolicy::Interrupt).unwrap();
+    assert_eq!(queue::policy(), QueuePolicy::Interrupt);
+}
+
+#[test]
+fn voices_are_picked_for_the_detected_language() {
+    assert!(crate::tts::wants_auto_voice(None));
+    assert!(crate::tts::wants_auto_voice(Some("UK")));
+    assert!(!crate::tts::wants_auto_voice(Some("en-US-AriaNeural")));
+
+    assert_eq!(crate::tts::detected_language("今天天气很好").as_deref(), Some("zh"));
+    assert_eq!(crate::tts::detected_language("こんにちは、世界").as_deref(), Some("ja"));
+    assert_eq!(crate::tts::detected_language("The weather is nice today"), None);
+
+    let voice = |id: &str, language: &str| TtsVoice {
+        id: id.to_string(),
+        name: id.to_string(),
+        language: language.to_string(),
+        gender: String::new(),
+        engine: "system".to_string(),
+    };
+    let voices = [voice("en", "en-US"), voice("cmn", "cmn"), voice("ja", "ja-JP")];
+    assert_eq!(crate::tts::voice_for_language(&voices, "zh").unwrap().id, "cmn");
+    assert_eq!(crate::tts::voice_for_language(&voices, "ja").unwrap().id, "ja");
+    assert!(crate::tts::voice_for_language(&voices, "ko").is_none());
+}
+
+#[tokio::test]
+async fn speak_to_file_rejects_empty_text_and_unknown_engines() {
+    let request = |text: &str, engine: &str| TtsRequest {
+        text: text.to_string(),
+        voice: None,
+        engine: Some(engine.to_string()),
+        proxy: None,
+    };
+
+    assert!(crate::tts::speak_to_file(request("  ", "edge"), None).await.is_err());
+    let err = crate::tts::speak_to_file(request("hello", "robot"), None).await.unwrap_err();
+    assert!(matches!(err, AppError::InvalidRequest(_)));
+}
+
+#[cfg(target_os = "macos")]
+#[test]
+fn say_voice_list_is_parsed() {
+    let voices = crate::tts::macos::parse_voices(
+        "Daniel              en_GB    # Hello! My name is Daniel.\nEddy (English (US)) en_US    # Hello! My name is Eddy.\n",
+    );
+    assert_eq!(voices.len(), 2);
+    assert_eq!((voices[0].id.as_str(), voices[0].language.as_str()), ("Daniel", "en-GB"));
+    assert_eq!(voices[1].name, "Eddy (English (US))");
+    assert_eq!(voices[1].language, "en-US");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn espeak_voice_list_is_parsed() {
+    let voices = crate::tts::linux::parse_espeak_voices(
+        "Pty Language       Age/Gender VoiceName          File                 Other Languages\n \
+ 5  en-gb           --/M      English_(Great_Britain) gmw/en               (en 2)\n \
+ 5  ja              --/F      Japanese           jpx/ja\n",
+    );
+    assert_eq!(voices.len(), 2);
+    assert_eq!((voices[0].id.as_str(), voices[0].gender.as_str()), ("en-gb", "male"));
+    assert_eq!(voices[0].name, "eSpeak English (Great Britain)");
+    assert_eq!(voices[1].gender, "female");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn piper_models_are_listed_by_file_name() {
+    let voice = crate::tts::linux::piper_voice(std::path::Path::new("/voices/en_US-lessac-medium.onnx")).unwrap();
+    assert_eq!(voice.id, "piper:en_US-lessac-medium");
+    assert_eq!(voice.language, "en-US");
+    assert!(crate::tts::linux::piper_voice(std::path::Path::new("/voices/en_US-lessac-medium.onnx.json")).is_none());
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn failed_playback_resets_state_and_emits_error() {
+    use crate::tts::events::EMITTED;
+
+    let _playback = PLAYBACK.lock().unwrap_or_else(|e| e.into_inner());
+    queue::clear();
+    EMITTED.lock().unwrap().clear();
+
+    // 以非 0 退出码结束的播放进程视为播放失败
+    let child = std::process::Command::new("false").spawn().unwrap();
+    playback::track(child, None);
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    let error = loop {
+        let error = EMITTED
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(event, _)| event == "tts-error")
+            .map(|(_, payload)| payload.clone());
+        if let Some(error) = error {
+            break error;
+        }
+        assert!(std::time::Instant::now() < deadline, "tts-error was not emitted");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    };
+
+    assert!(!playback::is_playing());
+    assert!(error["reason"].as_str().unwrap().contains("exited"));
+    assert!(!EMITTED.lock().unwrap().iter().any(|(event, _)| event == "tts-finished"));
+}
@@ -0,0 +1,72 @@
+use crate::ocr::models::{OcrLine, OcrResult};
+use crate::ocr::tesseract::vertical_languages;
+use crate::ocr::vertical::{assemble, detect, restack_image};
+use image::{GrayImage, Luma};
+
+const GLYPH: u32 = 20;
+
+/// 白底上画黑色方块代替文字；`cells` 为 (列, 行) 网格坐标
+fn page(cells: &[(u32, u32)], column_pitch: u32, row_pitch: u32) -> GrayImage {
+    let mut image = GrayImage::from_pixel(200, 200, Luma([255]));
+    for (column, row) in cells {
+        let (left, top) = (10 + column * column_pitch, 10 + row * row_pitch);
+        for y in top..top + GLYPH {
+            for x in left..left + GLYPH {
+                image.put_pixel(x, y, Luma([0]));
+            }
+        }
+    }
+    image
+}
+
+fn grid(columns: u32, rows: u32) -> Vec<(u32, u32)> {
+    (0..columns).flat_map(|c| (0..rows).map(move |r| (c, r))).collect()
+}
+
+fn line(text: &str, y: f64) -> OcrLine {
+    OcrLine { text: text.to_string(), x: 10.0, y, width: 40.0, height: 20.0, confidence: Some(0.9) }
+}
+
+#[test]
+fn wide_column_gaps_mean_vertical_text() {
+    assert!(detect(&page(&grid(3, 4), GLYPH + 16, GLYPH + 4)));
+    assert!(!detect(&page(&grid(4, 3), GLYPH + 4, GLYPH + 16)));
+}
+
+#[test]
+fn columns_are_restacked_right_to_left() {
+    // 右列三个字，左列两个字
+    let image = page(&[(0, 0), (0, 1), (1, 0), (1, 1), (1, 2)], GLYPH + 16, GLYPH + 8);
+    let (stacked, strips) = restack_image(&image);
+
+    assert_eq!(strips.len(), 2);
+    assert_eq!((strips[0].x, strips[0].y, strips[0].height), (46.0, 10.0, 76.0));
+    assert_eq!((strips[1].x, strips[1].height), (10.0, 48.0));
+    assert!(stacked.width() > stacked.height());
+    assert!(strips[0].bottom <= strips[1].top);
+}
+
+#[test]
+fn recognized_rows_map_back_to_columns() {
+    let image = page(&[(0, 0), (0, 1), (1, 0), (1, 1)], GLYPH + 16, GLYPH + 8);
+    let (_, strips) = restack_image(&image);
+    let center = |i: usize| (strips[i].top + strips[i].bottom) / 2.0 - 10.0;
+    let result = OcrResult {
+        text: String::new(),
+        confidence: 0.9,
+        lines: vec![line("吾輩は", center(0)), line("猫で ある", center(1))],
+        paragraphs: Vec::new(),
+        barcodes: Vec::new(),
+    };
+
+    let result = assemble(result, &strips);
+
+    assert_eq!(result.text, "吾輩は\n猫である");
+    assert_eq!((result.lines[0].x, result.lines[1].x), (46.0, 10.0));
+}
+
+#[test]
+fn cjk_packs_switch_to_vertical_variants() {
+    assert_eq!(vertical_languages("jpn+eng"), "jpn_vert+eng");
+    assert_eq!(vertical_languages("chi_tra"), "chi_tra_vert");
+}
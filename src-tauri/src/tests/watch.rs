@@ -0,0 +1,15 @@
+use crate::ocr::watch::text_changed;
+
+#[test]
+fn recognition_jitter_is_not_a_change() {
+    assert!(!text_changed("I never said that to you.", "I never said that to you,"));
+    assert!(!text_changed("  same line ", "same line"));
+}
+
+#[test]
+fn new_or_cleared_subtitles_are_changes() {
+    assert!(text_changed("Where are you going?", "Home."));
+    assert!(text_changed("", "Hello"));
+    assert!(text_changed("Hello", ""));
+    assert!(!text_changed("", "  "));
+}
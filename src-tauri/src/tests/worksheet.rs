@@ -0,0 +1,71 @@
+use crate::worksheet::{render_html, template, ExerciseKind, VocabEntry, WorksheetOptions};
+use std::collections::HashMap;
+
+fn entry(word: &str, definition: &str, example: Option<&str>) -> VocabEntry {
+    VocabEntry {
+        word: word.to_string(),
+        definition: definition.to_string(),
+        example: example.map(str::to_string),
+        translation: None,
+    }
+}
+
+fn options(exercises: Vec<ExerciseKind>, answer_key: bool) -> WorksheetOptions {
+    WorksheetOptions {
+        title: Some("Unit 3 <review>".to_string()),
+        exercises,
+        answer_key,
+        seed: Some(7),
+    }
+}
+
+#[test]
+fn template_escapes_unless_triple_braced() {
+    let values = HashMap::from([("a", "<b>".to_string())]);
+    assert_eq!(template::render("{{a}}|{{{a}}}|{{missing}}", &values), "&lt;b&gt;|<b>|");
+}
+
+#[test]
+fn cloze_blanks_whole_words_only() {
+    let entries = vec![
+        entry("cat", "a small pet", Some("The Cat sat near the category list.")),
+        entry("dog", "a loyal pet", None),
+    ];
+    let html = render_html(&entries, &options(vec![ExerciseKind::Cloze], false)).unwrap();
+
+    assert!(html.contains("The <span class=\"blank\"></span> sat near the category list."));
+    assert!(!html.contains("Answer key"));
+    assert!(html.contains("Unit 3 &lt;review&gt;"));
+}
+
+#[test]
+fn cloze_handles_case_changes_that_alter_byte_length() {
+    // `İ` 小写后变长，按小写文本的偏移切原句会错位甚至越界
+    let entries = vec![
+        entry("cat", "a small pet", Some("İİİ the CAT naps")),
+        entry("dog", "a loyal pet", None),
+    ];
+    let html = render_html(&entries, &options(vec![ExerciseKind::Cloze], false)).unwrap();
+
+    assert!(html.contains("İİİ the <span class=\"blank\"></span> naps"));
+}
+
+#[test]
+fn same_seed_gives_same_worksheet_with_answer_key() {
+    let entries = vec![
+        entry("brisk", "quick and active", None),
+        entry("candid", "truthful and straightforward", None),
+        entry("diligent", "showing care in one's work", None),
+    ];
+    let opts = options(vec![ExerciseKind::Matching, ExerciseKind::Definitions], true);
+
+    let first = render_html(&entries, &opts).unwrap();
+    assert_eq!(first, render_html(&entries, &opts).unwrap());
+    assert!(first.contains("Answer key"));
+    assert!(first.contains("candid: truthful and straightforward"));
+}
+
+#[test]
+fn empty_selection_is_rejected() {
+    assert!(render_html(&[], &options(vec![ExerciseKind::Cloze], true)).is_err());
+}
@@ -0,0 +1,142 @@
+//! Per-service circuit breaker. After `threshold` consecutive failures the
+//! service is skipped for a cooldown window and requests fail immediately
+//! instead of waiting out the full timeout. Once the window passes the next
+//! request goes through as a trial: success closes the circuit, failure opens
+//! it again. Configured per request via `config.circuitBreaker`:
+//! `{ "enabled": true, "threshold": 3, "cooldownMs": 60000 }`.
+
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const DEFAULT_THRESHOLD: u32 = 3;
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy)]
+pub struct BreakerOptions {
+    pub enabled: bool,
+    pub threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for BreakerOptions {
+    fn default() -> Self {
+        BreakerOptions { enabled: true, threshold: DEFAULT_THRESHOLD, cooldown: DEFAULT_COOLDOWN }
+    }
+}
+
+impl BreakerOptions {
+    pub fn from_config(config: Option<&HashMap<String, serde_json::Value>>) -> Self {
+        let defaults = BreakerOptions::default();
+        let Some(options) = config.and_then(|c| c.get("circuitBreaker")) else {
+            return defaults;
+        };
+        BreakerOptions {
+            enabled: options.get("enabled").and_then(|v| v.as_bool()).unwrap_or(defaults.enabled),
+            threshold: options
+                .get("threshold")
+                .and_then(|v| v.as_u64())
+                .filter(|n| *n > 0)
+                .map(|n| n as u32)
+                .unwrap_or(defaults.threshold),
+            cooldown: options
+                .get("cooldownMs")
+                .and_then(|v| v.as_u64())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.cooldown),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Circuit {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+static CIRCUITS: Lazy<Mutex<HashMap<String, Circuit>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitStatus {
+    pub service: String,
+    pub consecutive_failures: u32,
+    pub open: bool,
+    /// Milliseconds until the next trial request is let through.
+    pub retry_in_ms: u64,
+}
+
+/// Fails fast while the circuit for `service` is open.
+pub fn check(service: &str, options: BreakerOptions) -> Result<()> {
+    if !options.enabled {
+        return Ok(());
+    }
+    let circuits = CIRCUITS.lock().unwrap();
+    let Some(until) = circuits.get(service).and_then(|circuit| circuit.open_until) else {
+        return Ok(());
+    };
+    let now = Instant::now();
+    if now >= until {
+        return Ok(());
+    }
+    Err(AppError::ServiceUnavailable(format!(
+        "{} temporarily disabled after repeated failures, retrying in {}s",
+        service,
+        (until - now).as_secs().max(1)
+    )))
+}
+
+/// Records the outcome of a request that went upstream.
+pub fn record(service: &str, error: Option<&AppError>, options: BreakerOptions) {
+    if !options.enabled {
+        return;
+    }
+    let mut circuits = CIRCUITS.lock().unwrap();
+    let circuit = circuits.entry(service.to_string()).or_default();
+    match error {
+        None => *circuit = Circuit::default(),
+        // Bad keys and bad config are the user's to fix and fail instantly anyway; they say
+        // nothing about whether the provider is up. Several providers wrap network errors
+        // and timeouts as `Api`, so everything else counts.
+        Some(error) if error.is_auth_failure() || error.category() == "config" => {}
+        Some(_) => {
+            circuit.consecutive_failures += 1;
+            if circuit.consecutive_failures >= options.threshold {
+                println!(
+                    "{} failed {} times in a row, disabling for {}s",
+                    service,
+                    circuit.consecutive_failures,
+                    options.cooldown.as_secs()
+                );
+                circuit.open_until = Some(Instant::now() + options.cooldown);
+            }
+        }
+    }
+}
+
+pub fn status() -> Vec<CircuitStatus> {
+    let now = Instant::now();
+    let circuits = CIRCUITS.lock().unwrap();
+    let mut statuses: Vec<CircuitStatus> = circuits
+        .iter()
+        .map(|(service, circuit)| {
+            let remaining = circuit.open_until.map(|until| until.saturating_duration_since(now)).unwrap_or_default();
+            CircuitStatus {
+                service: service.clone(),
+                consecutive_failures: circuit.consecutive_failures,
+                open: !remaining.is_zero(),
+                retry_in_ms: remaining.as_millis() as u64,
+            }
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.service.cmp(&b.service));
+    statuses
+}
+
+/// Closes every circuit, e.g. after the user fixes their network or proxy settings.
+pub fn reset() {
+    CIRCUITS.lock().unwrap().clear();
+}
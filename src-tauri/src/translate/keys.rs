@@ -0,0 +1,183 @@
+//! API-key pools. A service config may carry `apiKeys: [..]` instead of (or
+//! alongside) a single `apiKey`; each request leases the next usable key in
+//! round-robin order and reports the outcome back. Keys rejected with an
+//! HTTP 401/403 are dropped from rotation for the rest of the session;
+//! rate-limited keys cool down briefly and keys whose quota is exhausted cool
+//! down for longer before they are offered again. An optional
+//! `keyRequestsPerMinute` caps how often any single key is used.
+
+use crate::error::{AppError, Result};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+/// 额度通常按小时或按天恢复，用尽后较长时间内不再使用该密钥
+const QUOTA_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+const QUOTA_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct KeyState {
+    requests: u64,
+    failures: u64,
+    disabled: bool,
+    cooldown_until: Option<Instant>,
+    recent: VecDeque<Instant>,
+}
+
+impl KeyState {
+    fn available(&mut self, now: Instant, per_minute: Option<usize>) -> bool {
+        while self.recent.front().is_some_and(|t| now.duration_since(*t) >= QUOTA_WINDOW) {
+            self.recent.pop_front();
+        }
+        !self.disabled
+            && self.cooldown_until.is_none_or(|until| now >= until)
+            && per_minute.is_none_or(|limit| self.recent.len() < limit)
+    }
+}
+
+#[derive(Default)]
+struct Pool {
+    next: usize,
+    keys: HashMap<String, KeyState>,
+}
+
+static POOLS: Lazy<Mutex<HashMap<String, Pool>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A key handed out for one request; report the outcome with [`KeyLease::finish`].
+pub struct KeyLease {
+    service: String,
+    key: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyStatus {
+    pub service: String,
+    /// Only the last four characters, so the UI can tell keys apart.
+    pub key_hint: String,
+    pub requests: u64,
+    pub failures: u64,
+    pub disabled: bool,
+    pub cooling_down: bool,
+}
+
+fn pool_keys(config: &serde_json::Value) -> Vec<String> {
+    config
+        .get("apiKeys")
+        .and_then(|v| v.as_array())
+        .map(|keys| {
+            keys.iter()
+                .filter_map(|k| k.as_str())
+                .filter(|k| !k.is_empty())
+                .map(|k| k.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Picks the next usable key for `service` and writes it into `apiKey`.
+/// Configs without `apiKeys` pass through untouched.
+pub fn lease(
+    service: &str,
+    config: Option<serde_json::Value>,
+) -> Result<(Option<serde_json::Value>, Option<KeyLease>)> {
+    let Some(mut config) = config else {
+        return Ok((None, None));
+    };
+    let keys = pool_keys(&config);
+    if keys.is_empty() {
+        return Ok((Some(config), None));
+    }
+
+    let per_minute = config
+        .get("keyRequestsPerMinute")
+        .and_then(|v| v.as_u64())
+        .filter(|n| *n > 0)
+        .map(|n| n as usize);
+
+    let now = Instant::now();
+    let mut pools = POOLS.lock().unwrap();
+    let pool = pools.entry(service.to_string()).or_default();
+    // Forget keys that were removed from the config
+    pool.keys.retain(|key, _| keys.contains(key));
+
+    let start = pool.next % keys.len();
+    let chosen = (0..keys.len())
+        .map(|offset| &keys[(start + offset) % keys.len()])
+        .find(|key| pool.keys.entry(key.to_string()).or_default().available(now, per_minute))
+        .cloned();
+
+    let Some(key) = chosen else {
+        return if keys.iter().all(|key| pool.keys.get(key).is_some_and(|state| state.disabled)) {
+            Err(AppError::AuthFailed { service: service.to_string() })
+        } else {
+            Err(AppError::RateLimitExceeded { service: service.to_string() })
+        };
+    };
+
+    let position = keys.iter().position(|k| *k == key).unwrap_or(0);
+    pool.next = position + 1;
+    let state = pool.keys.entry(key.clone()).or_default();
+    state.requests += 1;
+    state.recent.push_back(now);
+
+    if let Some(obj) = config.as_object_mut() {
+        obj.insert("apiKey".to_string(), serde_json::Value::String(key.clone()));
+    }
+    Ok((Some(config), Some(KeyLease { service: service.to_string(), key })))
+}
+
+impl KeyLease {
+    pub fn finish(self, error: Option<&AppError>) {
+        let Some(error) = error else {
+            return;
+        };
+        let mut pools = POOLS.lock().unwrap();
+        let Some(state) = pools.get_mut(&self.service).and_then(|pool| pool.keys.get_mut(&self.key)) else {
+            return;
+        };
+        state.failures += 1;
+        if error.is_auth_failure() {
+            println!("{} API key ...{} rejected, removing from rotation", self.service, hint(&self.key));
+            state.disabled = true;
+        } else if error.is_quota_exhausted() {
+            println!("{} API key ...{} is out of quota, cooling down", self.service, hint(&self.key));
+            state.cooldown_until = Some(Instant::now() + QUOTA_COOLDOWN);
+        } else if error.is_rate_limited() {
+            state.cooldown_until = Some(Instant::now() + RATE_LIMIT_COOLDOWN);
+        }
+    }
+}
+
+fn hint(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    chars[chars.len().saturating_sub(4)..].iter().collect()
+}
+
+pub fn status() -> Vec<KeyStatus> {
+    let now = Instant::now();
+    let pools = POOLS.lock().unwrap();
+    let mut statuses: Vec<KeyStatus> = pools
+        .iter()
+        .flat_map(|(service, pool)| {
+            pool.keys.iter().map(move |(key, state)| KeyStatus {
+                service: service.clone(),
+                key_hint: hint(key),
+                requests: state.requests,
+                failures: state.failures,
+                disabled: state.disabled,
+                cooling_down: state.cooldown_until.is_some_and(|until| now < until),
+            })
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.service.cmp(&b.service).then(a.key_hint.cmp(&b.key_hint)));
+    statuses
+}
+
+/// Re-enables every key, e.g. after the user edits their keys in settings.
+pub fn reset() {
+    POOLS.lock().unwrap().clear();
+}
@@ -0,0 +1,74 @@
+//! Per-provider language metadata, in the language codes the frontend sends
+//! (`zh-CN`, `zh-TW`, `en`, ...), so impossible pairs can be disabled up front.
+
+use super::TranslationService;
+use serde::Serialize;
+
+/// Languages every broad MT service (Google, AWS, Alibaba, ...) covers, in frontend codes.
+pub const BROAD: &[&str] = &[
+    "ar", "bg", "cs", "da", "de", "el", "en", "es", "et", "fi", "fr", "he", "hi", "hu", "id", "it", "ja", "ko",
+    "lt", "lv", "ms", "nl", "pl", "pt", "ro", "ru", "sk", "sl", "sv", "th", "tr", "uk", "vi", "zh-CN", "zh-TW",
+];
+
+/// Source code meaning "detect it"; providers that cannot detect get a locally detected language.
+pub const AUTO: &str = "auto";
+
+/// Which language pairs a provider accepts.
+pub enum LanguageSupport {
+    /// Any pair the caller asks for (LLMs).
+    Any,
+    /// Any ordered pair of distinct languages from the list.
+    Among(&'static [&'static str]),
+    /// Only the listed `(source, target)` pairs.
+    Pairs(&'static [(&'static str, &'static str)]),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceLanguages {
+    pub service: String,
+    /// True when the provider accepts any pair; `languages` and `pairs` are then empty.
+    /// Otherwise `pairs` also lists `("auto", target)` for every target.
+    pub any: bool,
+    pub languages: Vec<&'static str>,
+    pub pairs: Vec<(&'static str, &'static str)>,
+}
+
+pub fn describe(provider: &dyn TranslationService) -> ServiceLanguages {
+    let (any, languages, mut pairs) = match provider.languages() {
+        LanguageSupport::Any => (true, Vec::new(), Vec::new()),
+        LanguageSupport::Among(languages) => {
+            let pairs = languages
+                .iter()
+                .flat_map(|source| languages.iter().map(move |target| (*source, *target)))
+                .filter(|(source, target)| source != target)
+                .collect();
+            (false, languages.to_vec(), pairs)
+        }
+        LanguageSupport::Pairs(pairs) => {
+            let mut languages: Vec<&'static str> = Vec::new();
+            for (source, target) in pairs {
+                for lang in [*source, *target] {
+                    if !languages.contains(&lang) {
+                        languages.push(lang);
+                    }
+                }
+            }
+            (false, languages, pairs.to_vec())
+        }
+    };
+    let mut targets: Vec<&'static str> = Vec::new();
+    for (_, target) in &pairs {
+        if !targets.contains(target) {
+            targets.push(target);
+        }
+    }
+    pairs.extend(targets.into_iter().map(|target| (AUTO, target)));
+    ServiceLanguages {
+        service: provider.name().to_string(),
+        any,
+        languages,
+        pairs,
+    }
+}
+
@@ -0,0 +1,289 @@
+//! Provider abstraction shared by `services::translate` and
+//! `services::translate_stream`. Each provider implements
+//! `TranslationService` next to its HTTP client and is listed once in
+//! `REGISTRY`; the dispatchers only resolve a name and call through the trait.
+
+pub mod breaker;
+pub mod keys;
+pub mod languages;
+
+use crate::error::{AppError, Result};
+use crate::models::TranslationResult;
+use crate::services::openai::OpenAiCompatible;
+use crate::services::{
+    alibaba, aws, bedrock, caiyun, claude, deepl, ernie, gemini_native, google, google_free,
+    libretranslate, lingva, minimax, niutrans, nllb, proxy, spark, volcengine,
+};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait TranslationService: Send + Sync {
+    /// Display name reported in results and stream events.
+    fn name(&self) -> &str;
+
+    /// Lowercase names (and localized aliases) this provider answers to.
+    fn aliases(&self) -> &'static [&'static str];
+
+    /// Config keys that must be non-empty before the provider is called.
+    fn required_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn missing_keys_message(&self) -> &str {
+        "No API key configured"
+    }
+
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    /// Whether the provider honours `variants` in its config and returns several candidates.
+    fn supports_variants(&self) -> bool {
+        false
+    }
+
+    /// Providers that cannot auto-detect get a locally detected source language instead of "auto".
+    fn needs_source_lang(&self) -> bool {
+        false
+    }
+
+    /// Language pairs the provider can translate; see `languages::describe`.
+    fn languages(&self) -> languages::LanguageSupport {
+        languages::LanguageSupport::Any
+    }
+
+    /// Longest text sent in one request; longer input is split by `services::chunk`.
+    /// LLM providers accept much larger inputs than classic MT APIs.
+    fn max_text_chars(&self) -> usize {
+        if self.supports_streaming() {
+            8000
+        } else {
+            5000
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+    ) -> Result<TranslationResult>;
+
+    /// Streams deltas to `on_delta` and returns the full text. Providers
+    /// without a streaming API fall back to a single non-streaming call.
+    /// The explicit `for<'a>` keeps `async_trait` from pinning the delta's lifetime.
+    async fn translate_stream(
+        &self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        config: Option<&serde_json::Value>,
+        _on_delta: &mut (dyn for<'a> FnMut(&'a str) + Send),
+    ) -> Result<String> {
+        self.translate(text, source_lang, target_lang, config)
+            .await
+            .map(|result| result.text)
+    }
+}
+
+static REGISTRY: Lazy<Vec<Arc<dyn TranslationService>>> = Lazy::new(|| {
+    vec![
+        Arc::new(OpenAiCompatible::preset("OpenAI", &["openai"], None, None)),
+        Arc::new(OpenAiCompatible::preset(
+            "Zhipu",
+            &["zhipu"],
+            Some("https://open.bigmodel.cn/api/paas/v4/chat/completions"),
+            Some("glm-4-flash"),
+        )),
+        Arc::new(OpenAiCompatible::preset(
+            "Groq",
+            &["groq"],
+            Some("https://api.groq.com/openai/v1/chat/completions"),
+            Some("llama3-8b-8192"),
+        )),
+        Arc::new(OpenAiCompatible::preset(
+            "Gemini",
+            &["gemini"],
+            Some("https://generativelanguage.googleapis.com/v1beta/openai/chat/completions"),
+            Some("gemini-1.5-flash"),
+        )),
+        Arc::new(OpenAiCompatible::preset(
+            "Hunyuan",
+            &["hunyuan"],
+            Some("https://api.hunyuan.cloud.tencent.com/v1/chat/completions"),
+            Some("hunyuan-turbo"),
+        )),
+        Arc::new(OpenAiCompatible::preset(
+            "Grok",
+            &["grok"],
+            Some("https://api.x.ai/v1/chat/completions"),
+            Some("grok-beta"),
+        )),
+        Arc::new(claude::Claude),
+        Arc::new(ernie::Ernie),
+        Arc::new(spark::Spark),
+        Arc::new(minimax::Minimax),
+        Arc::new(bedrock::Bedrock),
+        Arc::new(gemini_native::GeminiNative),
+        Arc::new(deepl::DeepL),
+        Arc::new(google::Google),
+        Arc::new(alibaba::Alibaba),
+        Arc::new(google_free::GoogleFree),
+        Arc::new(libretranslate::LibreTranslate),
+        Arc::new(lingva::Lingva),
+        Arc::new(caiyun::Caiyun),
+        Arc::new(niutrans::NiuTrans),
+        Arc::new(volcengine::Volcengine),
+        Arc::new(aws::Aws),
+        Arc::new(nllb::Nllb),
+    ]
+});
+
+/// Looks up a built-in provider by any of its aliases (case-insensitive).
+pub fn find(name: &str) -> Option<Arc<dyn TranslationService>> {
+    let key = name.to_lowercase();
+    REGISTRY
+        .iter()
+        .find(|service| service.aliases().contains(&key.as_str()))
+        .cloned()
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceInfo {
+    /// The service name to request it by; `custom:<id>` for custom services.
+    pub id: String,
+    /// Display name; a custom service's configured `name` when it has one.
+    pub name: String,
+    pub aliases: Vec<&'static str>,
+    /// Whether every required credential is present in the supplied config.
+    pub configured: bool,
+    pub required_keys: Vec<&'static str>,
+    pub streaming: bool,
+    pub custom: bool,
+}
+
+fn describe_service(
+    id: String,
+    service: &dyn TranslationService,
+    service_config: Option<&serde_json::Value>,
+    custom: bool,
+) -> ServiceInfo {
+    let has_key = |key: &str| {
+        let present = service_config
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| !v.is_empty());
+        // A key pool (`apiKeys`) satisfies `apiKey`.
+        present
+            || key == "apiKey"
+                && service_config
+                    .and_then(|c| c.get("apiKeys"))
+                    .and_then(|v| v.as_array())
+                    .is_some_and(|keys| keys.iter().any(|k| k.as_str().is_some_and(|k| !k.is_empty())))
+    };
+    ServiceInfo {
+        id,
+        name: service.name().to_string(),
+        aliases: service.aliases().to_vec(),
+        configured: service.required_keys().iter().all(|key| has_key(key)),
+        required_keys: service.required_keys().to_vec(),
+        streaming: service.supports_streaming(),
+        custom,
+    }
+}
+
+/// Every built-in provider plus the `customServices` from `config`, with
+/// whether each one has its credentials configured.
+pub fn list_services(config: Option<&HashMap<String, serde_json::Value>>) -> Vec<ServiceInfo> {
+    let mut services: Vec<ServiceInfo> = REGISTRY
+        .iter()
+        .map(|service| {
+            let service_config = config.and_then(|c| {
+                std::iter::once(service.name().to_lowercase())
+                    .chain(service.aliases().iter().map(|alias| alias.to_string()))
+                    .find_map(|key| c.get(&key))
+            });
+            describe_service(service.name().to_string(), service.as_ref(), service_config, false)
+        })
+        .collect();
+
+    let custom = config
+        .and_then(|c| c.get("customServices"))
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten();
+    for entry in custom {
+        if let Some(id) = entry.get("id").and_then(|v| v.as_str()) {
+            let id = format!("custom:{}", id);
+            let service = OpenAiCompatible::custom(&custom_display_name(entry, &id));
+            services.push(describe_service(id, &service, Some(entry), true));
+        }
+    }
+    services
+}
+
+/// Looks up a user-defined OpenAI-compatible service from the `customServices` array.
+fn find_custom_service(
+    config: Option<&HashMap<String, serde_json::Value>>,
+    id: &str,
+) -> Option<serde_json::Value> {
+    config?
+        .get("customServices")?
+        .as_array()?
+        .iter()
+        .find(|entry| {
+            entry.get("id")
+                .and_then(|v| v.as_str())
+                .map(|v| v.eq_ignore_ascii_case(id))
+                .unwrap_or(false)
+        })
+        .cloned()
+}
+
+/// A custom service's configured `name`, falling back to its `custom:<id>` service name.
+fn custom_display_name(entry: &serde_json::Value, service_name: &str) -> String {
+    entry
+        .get("name")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(service_name)
+        .to_string()
+}
+
+/// Supported languages for a service; `custom:<id>` services accept any pair.
+pub fn service_languages(service_name: &str) -> Result<languages::ServiceLanguages> {
+    if service_name.to_lowercase().starts_with("custom:") {
+        return Ok(languages::describe(&OpenAiCompatible::custom(service_name)));
+    }
+    let service = find(service_name)
+        .ok_or_else(|| AppError::InvalidRequest("Service not supported".to_string()))?;
+    Ok(languages::describe(service.as_ref()))
+}
+
+/// Resolves a requested service name to its provider and per-service config.
+/// `custom:<id>` names resolve to an OpenAI-compatible client configured from `customServices`.
+pub fn resolve(
+    service_name: &str,
+    config: Option<&HashMap<String, serde_json::Value>>,
+) -> Result<(Arc<dyn TranslationService>, Option<serde_json::Value>)> {
+    if service_name.to_lowercase().starts_with("custom:") {
+        let id = &service_name["custom:".len()..];
+        let custom_config = find_custom_service(config, id)
+            .ok_or_else(|| AppError::InvalidRequest("Custom service not found".to_string()))?;
+        let name = custom_display_name(&custom_config, service_name);
+        let custom_config = proxy::inherit_global(Some(custom_config), config);
+        return Ok((Arc::new(OpenAiCompatible::custom(&name)), custom_config));
+    }
+
+    let service = find(service_name)
+        .ok_or_else(|| AppError::InvalidRequest("Service not supported".to_string()))?;
+    let service_config = config.and_then(|c| c.get(&service_name.to_lowercase())).cloned();
+    Ok((service, proxy::inherit_global(service_config, config)))
+}
@@ -0,0 +1,267 @@
+//! Edge 在线神经网络语音：通过 Microsoft Edge“大声朗读”使用的 WebSocket 接口合成 MP3，
+//! 无需密钥，也不依赖系统安装的语音包。
+
+use crate::error::{AppError, Result};
+use crate::tts::events::{self, WordBoundary};
+use crate::tts::models::TtsVoice;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+const TRUSTED_CLIENT_TOKEN: &str = "6A5AA1D4EAFF4E9FB37E23D68491D6F4";
+const WSS_URL: &str = "wss://speech.platform.bing.com/consumer/speech/synthesize/readaloud/edge/v1";
+const VOICES_URL: &str = "https://speech.platform.bing.com/consumer/speech/synthesize/readaloud/voices/list";
+const SEC_MS_GEC_VERSION: &str = "1-130.0.2849.68";
+const ORIGIN: &str = "chrome-extension://jdiccldimpdaibmpdkjnbmckianbfold";
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) \
+Chrome/130.0.0.0 Safari/537.36 Edg/130.0.0.0";
+const OUTPUT_FORMAT: &str = "audio-24khz-48kbitrate-mono-mp3";
+/// 两条 WebSocket 消息之间的最长等待，超时视为连接卡死
+const RECEIVE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+/// Windows 纪元（1601-01-01）与 Unix 纪元之间的秒数
+const WINDOWS_EPOCH_OFFSET: u64 = 11_644_473_600;
+
+/// 各语言的默认语音
+const DEFAULT_VOICES: &[(&str, &str)] = &[
+    ("en-US", "en-US-AriaNeural"),
+    ("en-GB", "en-GB-SoniaNeural"),
+    ("zh-CN", "zh-CN-XiaoxiaoNeural"),
+    ("zh-TW", "zh-TW-HsiaoChenNeural"),
+    ("zh-HK", "zh-HK-HiuMaanNeural"),
+    ("ja-JP", "ja-JP-NanamiNeural"),
+    ("ko-KR", "ko-KR-SunHiNeural"),
+    ("fr-FR", "fr-FR-DeniseNeural"),
+    ("de-DE", "de-DE-KatjaNeural"),
+    ("es-ES", "es-ES-ElviraNeural"),
+    ("it-IT", "it-IT-ElsaNeural"),
+    ("pt-BR", "pt-BR-FranciscaNeural"),
+    ("ru-RU", "ru-RU-SvetlanaNeural"),
+    ("ar-SA", "ar-SA-ZariyahNeural"),
+    ("th-TH", "th-TH-PremwadeeNeural"),
+    ("vi-VN", "vi-VN-HoaiMyNeural"),
+];
+
+/// 语音列表接口返回的一项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EdgeVoice {
+    short_name: String,
+    friendly_name: String,
+    locale: String,
+    gender: String,
+}
+
+impl From<EdgeVoice> for TtsVoice {
+    fn from(voice: EdgeVoice) -> Self {
+        TtsVoice {
+            id: voice.short_name,
+            name: voice.friendly_name,
+            language: voice.locale,
+            gender: voice.gender.to_lowercase(),
+            engine: "edge".to_string(),
+        }
+    }
+}
+
+/// 把请求中的语音解析为 Edge 语音名：完整语音名原样使用，`uk` / `us` 和语言代码（`ja`、`zh-TW`）
+/// 取该语言的默认语音，未指定时使用美式英语
+pub fn resolve_voice(voice: Option<&str>) -> String {
+    let voice = voice.map(str::trim).filter(|v| !v.is_empty()).unwrap_or("us");
+    if voice.ends_with("Neural") {
+        return voice.to_string();
+    }
+    let locale = match voice.to_ascii_lowercase().as_str() {
+        "us" => "en-US".to_string(),
+        "uk" | "gb" => "en-GB".to_string(),
+        _ => voice.to_string(),
+    };
+    DEFAULT_VOICES
+        .iter()
+        .find(|(l, _)| l.eq_ignore_ascii_case(&locale))
+        .or_else(|| {
+            // 只给出语言（`ja`、`zh`）时取该语言的第一个语音
+            let language = locale.split(['-', '_']).next().unwrap_or_default();
+            DEFAULT_VOICES.iter().find(|(l, _)| l.split('-').next().is_some_and(|p| p.eq_ignore_ascii_case(language)))
+        })
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| DEFAULT_VOICES[0].1.to_string())
+}
+
+/// 语音名中的语言代码，如 `ja-JP-NanamiNeural` → `ja-JP`
+fn voice_locale(voice: &str) -> String {
+    voice.splitn(3, '-').take(2).collect::<Vec<_>>().join("-")
+}
+
+/// 服务端要求的 `Sec-MS-GEC` 令牌：按 5 分钟取整的 Windows 时间刻度与客户端令牌拼接后的 SHA-256
+pub(crate) fn sec_ms_gec(unix_secs: u64) -> String {
+    let secs = unix_secs + WINDOWS_EPOCH_OFFSET;
+    let ticks = (secs - secs % 300) as u128 * 10_000_000;
+    hex::encode_upper(Sha256::digest(format!("{}{}", ticks, TRUSTED_CLIENT_TOKEN).as_bytes()))
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub(crate) fn ssml(text: &str, voice: &str) -> String {
+    format!(
+        "<speak version='1.0' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='{}'><voice name='{}'>\
+<prosody pitch='+0Hz' rate='+0%' volume='+0%'>{}</prosody></voice></speak>",
+        voice_locale(voice),
+        voice,
+        escape_xml(text)
+    )
+}
+
+/// 二进制消息：前两个字节是大端序的头部长度，头部之后是音频数据；返回 `Path:audio` 消息中的音频
+pub(crate) fn audio_payload(frame: &[u8]) -> Option<&[u8]> {
+    let header_len = u16::from_be_bytes([*frame.first()?, *frame.get(1)?]) as usize;
+    let header = frame.get(2..2 + header_len)?;
+    let is_audio = std::str::from_utf8(header).ok()?.lines().any(|line| line.trim() == "Path:audio");
+    is_audio.then(|| &frame[2 + header_len..])
+}
+
+/// 解析 `Path:audio.metadata` 文本消息中的 WordBoundary，定位到原文 `text` 中（`cursor` 为已定位到的字节下标）。
+/// 消息正文形如 `{"Metadata":[{"Type":"WordBoundary","Data":{"Offset":1000000,"text":{"Text":"Hello"}}}]}`，
+/// Offset 以 100 纳秒为单位
+pub(crate) fn word_boundaries(message: &str, text: &str, cursor: &mut usize) -> Vec<WordBoundary> {
+    let Some((_, body)) = message.split_once("\r\n\r\n") else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+    json["Metadata"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item["Type"] == "WordBoundary")
+        .filter_map(|item| {
+            let word = item["Data"]["text"]["Text"].as_str()?;
+            let ticks = item["Data"]["Offset"].as_u64()?;
+            events::locate(text, word, cursor, std::time::Duration::from_nanos(ticks * 100))
+        })
+        .collect()
+}
+
+fn timestamp() -> String {
+    chrono::Utc::now().format("%a %b %d %Y %H:%M:%S GMT+0000 (Coordinated Universal Time)").to_string()
+}
+
+fn ws_error(e: impl std::fmt::Display) -> AppError {
+    AppError::Network(format!("Edge TTS connection failed: {}", e))
+}
+
+/// 代理设置（与服务配置中的 `proxy` 同格式）包装成连接配置
+fn proxy_config(proxy: Option<&serde_json::Value>) -> Option<serde_json::Value> {
+    proxy.map(|proxy| serde_json::json!({ "proxy": proxy }))
+}
+
+/// 合成语音，返回 MP3 数据
+pub async fn synthesize(text: &str, voice: &str, proxy: Option<&serde_json::Value>) -> Result<Vec<u8>> {
+    Ok(synthesize_with_boundaries(text, voice, proxy).await?.0)
+}
+
+/// 合成语音，返回 MP3 数据和每个词的位置
+pub async fn synthesize_with_boundaries(
+    text: &str,
+    voice: &str,
+    proxy: Option<&serde_json::Value>,
+) -> Result<(Vec<u8>, Vec<WordBoundary>)> {
+    let connection_id = uuid::Uuid::new_v4().simple().to_string();
+    let url = format!(
+        "{}?TrustedClientToken={}&ConnectionId={}&Sec-MS-GEC={}&Sec-MS-GEC-Version={}",
+        WSS_URL,
+        TRUSTED_CLIENT_TOKEN,
+        connection_id,
+        sec_ms_gec(chrono::Utc::now().timestamp().max(0) as u64),
+        SEC_MS_GEC_VERSION
+    );
+    let mut request = url.into_client_request().map_err(ws_error)?;
+    request.headers_mut().insert("Origin", HeaderValue::from_static(ORIGIN));
+    request.headers_mut().insert("User-Agent", HeaderValue::from_static(USER_AGENT));
+    request.headers_mut().insert("Pragma", HeaderValue::from_static("no-cache"));
+
+    let host = request.uri().host().unwrap_or_default().to_string();
+    let tunnel = crate::services::proxy::tunnel(proxy_config(proxy).as_ref(), &host, 443).await?;
+    let (mut socket, _) = match tunnel {
+        Some(stream) => tokio_tungstenite::client_async_tls(request, stream).await,
+        None => tokio_tungstenite::connect_async(request).await,
+    }
+    .map_err(ws_error)?;
+
+    let config = format!(
+        "X-Timestamp:{}\r\nContent-Type:application/json; charset=utf-8\r\nPath:speech.config\r\n\r\n\
+{{\"context\":{{\"synthesis\":{{\"audio\":{{\"metadataoptions\":{{\"sentenceBoundaryEnabled\":\"false\",\
+\"wordBoundaryEnabled\":\"true\"}},\"outputFormat\":\"{}\"}}}}}}}}",
+        timestamp(),
+        OUTPUT_FORMAT
+    );
+    socket.send(Message::Text(config)).await.map_err(ws_error)?;
+
+    let ssml_message = format!(
+        "X-RequestId:{}\r\nContent-Type:application/ssml+xml\r\nX-Timestamp:{}Z\r\nPath:ssml\r\n\r\n{}",
+        uuid::Uuid::new_v4().simple(),
+        timestamp(),
+        ssml(text, voice)
+    );
+    socket.send(Message::Text(ssml_message)).await.map_err(ws_error)?;
+
+    let mut audio = Vec::new();
+    let mut boundaries = Vec::new();
+    let mut cursor = 0;
+    loop {
+        let message = tokio::time::timeout(RECEIVE_TIMEOUT, socket.next())
+            .await
+            .map_err(|_| AppError::Timeout("Edge TTS stopped responding".to_string()))?;
+        let Some(message) = message else { break };
+        match message.map_err(ws_error)? {
+            Message::Binary(frame) => {
+                if let Some(payload) = audio_payload(&frame) {
+                    audio.extend_from_slice(payload);
+                }
+            }
+            Message::Text(message) if message.contains("Path:audio.metadata") => {
+                boundaries.extend(word_boundaries(&message, text, &mut cursor));
+            }
+            Message::Text(message) if message.contains("Path:turn.end") => break,
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+    let _ = socket.close(None).await;
+
+    if audio.is_empty() {
+        return Err(AppError::ServiceUnavailable(format!("Edge TTS returned no audio for voice {}", voice)));
+    }
+    Ok((audio, boundaries))
+}
+
+/// 列出 Edge 支持的全部在线语音
+pub async fn list_voices(proxy: Option<&serde_json::Value>) -> Result<Vec<TtsVoice>> {
+    let client = crate::services::http::client(proxy_config(proxy).as_ref(), std::time::Duration::from_secs(15))?;
+    let response = client
+        .get(VOICES_URL)
+        .query(&[("trustedclienttoken", TRUSTED_CLIENT_TOKEN)])
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_status("Edge TTS", status, error_text));
+    }
+    parse_voices(response.json().await?)
+}
+
+pub(crate) fn parse_voices(json: serde_json::Value) -> Result<Vec<TtsVoice>> {
+    let voices: Vec<EdgeVoice> = serde_json::from_value(json)?;
+    Ok(voices.into_iter().map(TtsVoice::from).collect())
+}
@@ -0,0 +1,132 @@
+//! 朗读事件。`tts-progress` 在读到每个词时发出，供跟读模式高亮当前词；
+//! 按播放时间（扣除暂停）调度，停止或开始新的朗读后不再发出旧朗读的事件。
+//! 朗读结束时发出 `tts-finished`，播放失败时发出 `tts-error`。
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+/// 当前朗读的编号；每次开始、停止朗读时递增
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 当前朗读的播放时钟
+struct Clock {
+    started: Instant,
+    paused_at: Option<Instant>,
+    paused_total: Duration,
+}
+
+static CLOCK: Mutex<Option<Clock>> = Mutex::new(None);
+
+/// `tts-progress` 事件的负载。`offset` / `length` 是该词在原文中的位置，按 JavaScript 字符串的 UTF-16 下标计
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WordBoundary {
+    pub offset: usize,
+    pub length: usize,
+    pub word: String,
+    /// 该词在音频中的开始时间
+    #[serde(skip)]
+    pub audio_offset: Duration,
+}
+
+/// `tts-finished` / `tts-error` 事件的负载。`tts-finished` 的 `reason` 为 `ended`（播放完毕）或
+/// `stopped`（被停止或打断），`tts-error` 的 `reason` 为错误信息
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybackStatus {
+    pub reason: String,
+}
+
+/// 应用启动时保存 AppHandle，之后朗读模块即可发出事件
+pub fn init(app: AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// 测试中没有 AppHandle，改为记录发出的事件名称和负载
+#[cfg(test)]
+pub(crate) static EMITTED: Mutex<Vec<(String, serde_json::Value)>> = Mutex::new(Vec::new());
+
+pub(crate) fn emit<S: Serialize + Clone>(event: &str, payload: S) {
+    #[cfg(test)]
+    EMITTED
+        .lock()
+        .unwrap()
+        .push((event.to_string(), serde_json::to_value(&payload).unwrap_or_default()));
+    if let Some(app) = APP.get() {
+        let _ = app.emit(&crate::events::event_name(event), payload);
+    }
+}
+
+pub(crate) fn finished(reason: &str) {
+    emit("tts-finished", PlaybackStatus { reason: reason.to_string() });
+}
+
+pub(crate) fn failed(reason: impl Into<String>) {
+    emit("tts-error", PlaybackStatus { reason: reason.into() });
+}
+
+/// 把 `word` 定位到原文中 `cursor`（字节下标）之后，返回 UTF-16 下标的位置并前移 `cursor`；找不到时返回 None
+pub(crate) fn locate(text: &str, word: &str, cursor: &mut usize, audio_offset: Duration) -> Option<WordBoundary> {
+    let start = *cursor + text.get(*cursor..)?.find(word)?;
+    *cursor = start + word.len();
+    Some(WordBoundary {
+        offset: text[..start].encode_utf16().count(),
+        length: word.encode_utf16().count(),
+        word: word.to_string(),
+        audio_offset,
+    })
+}
+
+fn elapsed() -> Option<Duration> {
+    let clock = CLOCK.lock().unwrap();
+    let clock = clock.as_ref()?;
+    let now = clock.paused_at.unwrap_or_else(Instant::now);
+    Some(now.duration_since(clock.started).saturating_sub(clock.paused_total))
+}
+
+/// 播放开始后调用：按时间依次发出 `tts-progress`
+pub(crate) fn follow(boundaries: Vec<WordBoundary>) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    *CLOCK.lock().unwrap() = Some(Clock { started: Instant::now(), paused_at: None, paused_total: Duration::ZERO });
+    if boundaries.is_empty() {
+        return;
+    }
+    std::thread::spawn(move || {
+        for boundary in boundaries {
+            loop {
+                if GENERATION.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                match elapsed() {
+                    Some(elapsed) if elapsed >= boundary.audio_offset => break,
+                    Some(elapsed) => std::thread::sleep((boundary.audio_offset - elapsed).min(Duration::from_millis(50))),
+                    None => return,
+                }
+            }
+            emit("tts-progress", boundary);
+        }
+    });
+}
+
+pub(crate) fn pause() {
+    if let Some(clock) = CLOCK.lock().unwrap().as_mut() {
+        clock.paused_at.get_or_insert_with(Instant::now);
+    }
+}
+
+pub(crate) fn resume() {
+    if let Some(clock) = CLOCK.lock().unwrap().as_mut() {
+        if let Some(paused_at) = clock.paused_at.take() {
+            clock.paused_total += paused_at.elapsed();
+        }
+    }
+}
+
+/// 朗读停止：不再发出进度事件
+pub(crate) fn cancel() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    CLOCK.lock().unwrap().take();
+}
@@ -0,0 +1,187 @@
+//! Linux 系统语音：优先使用 Piper 神经网络语音（需安装 `piper` 并把 `.onnx` 语音模型放到数据目录的
+//! `piper-voices` 下），其次使用 espeak-ng，两者都没有时通过 speech-dispatcher（`spd-say`）朗读。
+
+use crate::error::{AppError, Result};
+use crate::tts::models::TtsVoice;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Piper 语音 ID 的前缀，后接模型文件名（不含扩展名）
+const PIPER_PREFIX: &str = "piper:";
+
+fn command_error(program: &str, e: std::io::Error) -> AppError {
+    if e.kind() == std::io::ErrorKind::NotFound {
+        AppError::PlatformNotSupported(format!("{} is not installed", program))
+    } else {
+        AppError::Unknown(format!("Failed to run {}: {}", program, e))
+    }
+}
+
+fn piper_dir() -> PathBuf {
+    crate::paths::data_dir().join("piper-voices")
+}
+
+/// 模型文件名形如 `en_US-lessac-medium.onnx`，开头是语言代码
+pub(crate) fn piper_voice(model: &Path) -> Option<TtsVoice> {
+    if model.extension()? != "onnx" {
+        return None;
+    }
+    let stem = model.file_stem()?.to_str()?;
+    Some(TtsVoice {
+        id: format!("{}{}", PIPER_PREFIX, stem),
+        name: format!("Piper {}", stem),
+        language: stem.split('-').next().unwrap_or_default().replace('_', "-"),
+        gender: String::new(),
+        engine: "system".to_string(),
+    })
+}
+
+fn piper_voices() -> Vec<TtsVoice> {
+    let Ok(entries) = std::fs::read_dir(piper_dir()) else {
+        return Vec::new();
+    };
+    let mut voices: Vec<TtsVoice> = entries.flatten().filter_map(|entry| piper_voice(&entry.path())).collect();
+    voices.sort_by(|a, b| a.id.cmp(&b.id));
+    voices
+}
+
+/// 解析 `espeak-ng --voices` 的输出：
+/// `Pty Language Age/Gender VoiceName File Other Languages`，如 ` 5  en-us  --/M  English_(America)  gmw/en-US`
+pub(crate) fn parse_espeak_voices(output: &str) -> Vec<TtsVoice> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            let language = columns.nth(1)?;
+            let gender = match columns.next()?.rsplit('/').next()? {
+                "M" => "male",
+                "F" => "female",
+                _ => "",
+            };
+            let name = columns.next()?.replace('_', " ");
+            Some(TtsVoice {
+                id: language.to_string(),
+                name: format!("eSpeak {}", name),
+                language: language.to_string(),
+                gender: gender.to_string(),
+                engine: "system".to_string(),
+            })
+        })
+        .collect()
+}
+
+pub async fn voices() -> Result<Vec<TtsVoice>> {
+    let mut voices = piper_voices();
+    match Command::new("espeak-ng").arg("--voices").output().await {
+        Ok(output) => voices.extend(parse_espeak_voices(&String::from_utf8_lossy(&output.stdout))),
+        Err(e) => println!("TTS: espeak-ng unavailable: {}", e),
+    }
+    Ok(voices)
+}
+
+/// 选用的语音：显式指定的 Piper 语音，否则交给 espeak-ng / speech-dispatcher 的语言代码
+enum Voice {
+    Piper(PathBuf),
+    Language(Option<String>),
+}
+
+fn resolve_voice(voice: Option<&str>) -> Result<Voice> {
+    let voice = voice.map(str::trim).filter(|v| !v.is_empty());
+    if let Some(name) = voice.and_then(|v| v.strip_prefix(PIPER_PREFIX)) {
+        let model = piper_dir().join(format!("{}.onnx", name));
+        if !model.exists() {
+            return Err(AppError::InvalidRequest(format!("Piper voice not found: {}", name)));
+        }
+        return Ok(Voice::Piper(model));
+    }
+    let language = voice.map(|v| match v.to_ascii_lowercase().as_str() {
+        "uk" => "en-gb".to_string(),
+        "us" => "en-us".to_string(),
+        other => other.to_string(),
+    });
+    Ok(Voice::Language(language))
+}
+
+/// 用 Piper 合成 WAV；文本从标准输入传入
+async fn piper_synthesize(model: &Path, text: &str) -> Result<Vec<u8>> {
+    let path = std::env::temp_dir().join(format!("dict_tts_{}.wav", uuid::Uuid::new_v4().simple()));
+    let mut child = Command::new("piper")
+        .arg("--model")
+        .arg(model)
+        .arg("--output_file")
+        .arg(&path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| command_error("piper", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes()).await?;
+    }
+    let status = child.wait().await?;
+    let audio = if status.success() { tokio::fs::read(&path).await.ok() } else { None };
+    let _ = tokio::fs::remove_file(&path).await;
+    audio.ok_or_else(|| AppError::Unknown("Piper failed to synthesize audio".to_string()))
+}
+
+fn espeak_command(text: &str, language: Option<&str>, output: Option<&Path>) -> std::process::Command {
+    let mut command = std::process::Command::new("espeak-ng");
+    if let Some(language) = language {
+        command.args(["-v", language]);
+    }
+    if let Some(output) = output {
+        command.arg("-w").arg(output);
+    }
+    command.arg("--").arg(text);
+    command
+}
+
+/// 在后台朗读，可通过 `playback::stop` 等中断
+pub async fn speak(text: &str, voice: Option<&str>) -> Result<()> {
+    let language = match resolve_voice(voice)? {
+        Voice::Piper(model) => {
+            let audio = piper_synthesize(&model, text).await?;
+            return crate::tts::playback::play_audio(audio, "audio/wav").await;
+        }
+        Voice::Language(language) => language,
+    };
+
+    match espeak_command(text, language.as_deref(), None).stdout(Stdio::null()).spawn() {
+        Ok(child) => {
+            crate::tts::playback::track(child, None);
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("TTS: espeak-ng not found, falling back to speech-dispatcher");
+            let mut command = std::process::Command::new("spd-say");
+            // -w 等到朗读结束才退出，停止播放时结束该进程
+            command.arg("-w");
+            if let Some(language) = &language {
+                command.args(["-l", language]);
+            }
+            let child = command.arg("--").arg(text).spawn().map_err(|e| command_error("spd-say", e))?;
+            crate::tts::playback::track(child, None);
+            Ok(())
+        }
+        Err(e) => Err(command_error("espeak-ng", e)),
+    }
+}
+
+/// 合成 WAV 数据；speech-dispatcher 不支持导出，需要 Piper 或 espeak-ng
+pub async fn synthesize(text: &str, voice: Option<&str>) -> Result<Vec<u8>> {
+    let language = match resolve_voice(voice)? {
+        Voice::Piper(model) => return piper_synthesize(&model, text).await,
+        Voice::Language(language) => language,
+    };
+    let path = std::env::temp_dir().join(format!("dict_tts_{}.wav", uuid::Uuid::new_v4().simple()));
+    let status = Command::from(espeak_command(text, language.as_deref(), Some(&path)))
+        .status()
+        .await
+        .map_err(|e| command_error("espeak-ng", e))?;
+    let audio = if status.success() { tokio::fs::read(&path).await.ok() } else { None };
+    let _ = tokio::fs::remove_file(&path).await;
+    audio.ok_or_else(|| AppError::Unknown("espeak-ng failed to synthesize audio".to_string()))
+}
@@ -0,0 +1,82 @@
+//! macOS 系统语音：通过系统自带的 `say` 命令朗读、导出音频和列出语音，
+//! 与 AVSpeechSynthesizer 使用同一套语音，无需额外的 Objective-C 绑定。
+
+use crate::error::{AppError, Result};
+use crate::tts::models::TtsVoice;
+use tokio::process::Command;
+
+fn say_error(e: std::io::Error) -> AppError {
+    AppError::Unknown(format!("Failed to run say: {}", e))
+}
+
+/// 解析 `say -v ?` 的输出，每行形如 `Daniel              en_GB    # Hello! My name is Daniel.`
+pub(crate) fn parse_voices(output: &str) -> Vec<TtsVoice> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let spec = line.split('#').next()?.trim();
+            let (name, locale) = spec.rsplit_once(char::is_whitespace)?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| TtsVoice {
+                id: name.to_string(),
+                name: name.to_string(),
+                language: locale.replace('_', "-"),
+                // `say` 不提供性别
+                gender: String::new(),
+                engine: "system".to_string(),
+            })
+        })
+        .collect()
+}
+
+pub async fn voices() -> Result<Vec<TtsVoice>> {
+    let output = Command::new("say").args(["-v", "?"]).output().await.map_err(say_error)?;
+    Ok(parse_voices(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `uk` / `us` 取该口音的第一个已安装语音，其他值按语音名使用
+async fn resolve_voice(voice: Option<&str>) -> Option<String> {
+    let voice = voice.map(str::trim).filter(|v| !v.is_empty())?;
+    let locale = match voice.to_ascii_lowercase().as_str() {
+        "uk" => "en-GB",
+        "us" => "en-US",
+        _ => return Some(voice.to_string()),
+    };
+    let voices = voices().await.ok()?;
+    voices.into_iter().find(|v| v.language == locale).map(|v| v.id)
+}
+
+/// 构造 `say` 命令；指定 `output` 时写入 WAV 文件而不是发声
+fn say_command(text: &str, voice: Option<&str>, output: Option<&std::path::Path>) -> std::process::Command {
+    let mut command = std::process::Command::new("say");
+    if let Some(voice) = voice {
+        command.args(["-v", voice]);
+    }
+    if let Some(output) = output {
+        command.arg("-o").arg(output).args(["--file-format=WAVE", "--data-format=LEI16@22050"]);
+    }
+    // `--` 之后的文本不会被当作选项解析
+    command.arg("--").arg(text);
+    command
+}
+
+/// 在后台朗读，可通过 `playback::stop` 等中断
+pub async fn speak(text: &str, voice: Option<&str>) -> Result<()> {
+    let voice = resolve_voice(voice).await;
+    let child = say_command(text, voice.as_deref(), None).spawn().map_err(say_error)?;
+    crate::tts::playback::track(child, None);
+    Ok(())
+}
+
+/// 合成为 16 位 WAV 数据
+pub async fn synthesize(text: &str, voice: Option<&str>) -> Result<Vec<u8>> {
+    let voice = resolve_voice(voice).await;
+    let path = std::env::temp_dir().join(format!("dict_tts_{}.wav", uuid::Uuid::new_v4().simple()));
+    let status = Command::from(say_command(text, voice.as_deref(), Some(&path)))
+        .status()
+        .await
+        .map_err(say_error)?;
+    let audio = if status.success() { tokio::fs::read(&path).await.ok() } else { None };
+    let _ = tokio::fs::remove_file(&path).await;
+    audio.ok_or_else(|| AppError::Unknown("say failed to synthesize audio".to_string()))
+}
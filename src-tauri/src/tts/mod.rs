@@ -1,11 +1,19 @@
+pub mod edge;
+pub mod events;
+#[cfg(target_os = "linux")]
+pub mod linux;
+#[cfg(target_os = "macos")]
+pub mod macos;
 pub mod models;
+pub mod playback;
+pub mod queue;
 
 use crate::error::{AppError, Result};
+use crate::tts::models::{TtsAudio, TtsRequest, TtsVoice};
+use base64::{engine::general_purpose, Engine as _};
 
 #[cfg(target_os = "windows")]
 use windows::{
-    Media::SpeechSynthesis::SpeechSynthesizer,
-    Media::Playback::MediaPlayer,
     Media::Core::MediaSource,
     core::HSTRING,
 };
@@ -15,14 +23,6 @@ use std::process::Command;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
-#[cfg(target_os = "windows")]
-use std::sync::Mutex;
-#[cfg(target_os = "windows")]
-use std::sync::OnceLock;
-
-#[cfg(target_os = "windows")]
-static GLOBAL_MEDIA_PLAYER: OnceLock<Mutex<Option<(MediaPlayer, windows::Media::SpeechSynthesis::SpeechSynthesisStream)>>> = OnceLock::new();
-
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
@@ -50,14 +50,157 @@ fn speak_with_powershell(text: &str) -> Result<()> {
     }
 }
 
-#[cfg(target_os = "windows")]
+/// 朗读文本；`engine` 为 `edge` 时使用在线神经网络语音，否则使用系统语音。
+/// 已有语音在播放时按 `queue::policy` 打断、排队或忽略
 pub async fn speak(request: crate::tts::models::TtsRequest) -> Result<crate::tts::models::TtsResponse> {
-    println!("TTS: Starting speech synthesis for text: {}", &request.text[..request.text.len().min(50)]);
-    
     // 检查文本是否为空
     if request.text.trim().is_empty() {
         return Err(AppError::Unknown("Text is empty".to_string()));
     }
+
+    let request = with_detected_voice(request).await;
+
+    if playback::is_playing() {
+        match queue::policy() {
+            queue::QueuePolicy::Interrupt => stop()?,
+            queue::QueuePolicy::Queue => {
+                let ahead = queue::push(request);
+                return Ok(crate::tts::models::TtsResponse {
+                    success: true,
+                    message: format!("TTS request queued ({} ahead)", ahead + 1),
+                });
+            }
+            queue::QueuePolicy::Ignore => {
+                return Ok(crate::tts::models::TtsResponse {
+                    success: false,
+                    message: "TTS is busy; request ignored".to_string(),
+                });
+            }
+        }
+    }
+
+    match request.engine.as_deref().unwrap_or("system") {
+        "system" => speak_with_system(request).await,
+        "edge" => speak_with_edge(request).await,
+        other => Err(AppError::InvalidRequest(format!("Unknown TTS engine: {}", other))),
+    }
+}
+
+/// 停止当前朗读并清空队列
+pub fn stop() -> Result<()> {
+    queue::clear();
+    playback::stop()
+}
+
+/// 检测结果的置信度低于该值时不自动换语音
+const AUTO_VOICE_MIN_CONFIDENCE: f64 = 0.5;
+
+/// 语言代码的别名：espeak-ng 的普通话语音为 `cmn`
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[("zh", "cmn")];
+
+/// 未指定语音、`auto` 或英文口音（`uk` / `us`）时可以按文本语言自动选择语音
+pub(crate) fn wants_auto_voice(voice: Option<&str>) -> bool {
+    match voice.map(str::trim) {
+        None | Some("") => true,
+        Some(voice) => ["auto", "uk", "us"].iter().any(|v| v.eq_ignore_ascii_case(voice)),
+    }
+}
+
+/// 文本的语言；英文或无法可靠判断时返回 None，保留默认的英文语音
+pub(crate) fn detected_language(text: &str) -> Option<String> {
+    crate::langdetect::detect(text)
+        .filter(|d| d.lang != "en" && d.confidence >= AUTO_VOICE_MIN_CONFIDENCE)
+        .map(|d| d.lang)
+}
+
+/// 语言代码主标签匹配 `lang`（如 `zh-CN`、`cmn` 匹配 `zh`）的第一个语音
+pub(crate) fn voice_for_language<'a>(voices: &'a [TtsVoice], lang: &str) -> Option<&'a TtsVoice> {
+    let alias = LANGUAGE_ALIASES.iter().find(|(l, _)| *l == lang).map(|(_, alias)| *alias);
+    voices.iter().find(|voice| {
+        let primary = voice.language.split(['-', '_']).next().unwrap_or_default();
+        primary.eq_ignore_ascii_case(lang) || alias.is_some_and(|alias| primary.eq_ignore_ascii_case(alias))
+    })
+}
+
+/// 按检测到的文本语言替换语音，避免用英文语音读中文等。Edge 直接使用语言代码；
+/// 系统语音取已安装的该语言语音，没有时保留原来的语音
+async fn with_detected_voice(mut request: TtsRequest) -> TtsRequest {
+    if !wants_auto_voice(request.voice.as_deref()) {
+        return request;
+    }
+    let Some(lang) = detected_language(&request.text) else {
+        return request;
+    };
+    match request.engine.as_deref().unwrap_or("system") {
+        "edge" => request.voice = Some(lang.clone()),
+        "system" => match system_voices().await {
+            Ok(voices) => {
+                if let Some(voice) = voice_for_language(&voices, &lang) {
+                    request.voice = Some(voice.id.clone());
+                }
+            }
+            Err(e) => println!("TTS: Failed to list voices for {}: {}", lang, e),
+        },
+        _ => {}
+    }
+    println!("TTS: Detected {} text, using voice {:?}", lang, request.voice);
+    request
+}
+
+/// 合成语音但不播放，返回音频数据及其格式（文件扩展名）
+async fn synthesize(request: &TtsRequest) -> Result<(Vec<u8>, &'static str)> {
+    if request.text.trim().is_empty() {
+        return Err(AppError::Unknown("Text is empty".to_string()));
+    }
+    let request = &with_detected_voice(request.clone()).await;
+
+    match request.engine.as_deref().unwrap_or("system") {
+        "system" => Ok((synthesize_system(&request.text, request.voice.as_deref()).await?, "wav")),
+        "edge" => {
+            let voice = edge::resolve_voice(request.voice.as_deref());
+            Ok((edge::synthesize(&request.text, &voice, request.proxy.as_ref()).await?, "mp3"))
+        }
+        other => Err(AppError::InvalidRequest(format!("Unknown TTS engine: {}", other))),
+    }
+}
+
+/// 把合成的语音导出为音频文件（系统语音为 WAV，Edge 语音为 MP3），用于制作发音卡片。
+/// 指定 `path` 时写入该文件，否则返回 base64 编码的音频
+pub async fn speak_to_file(request: TtsRequest, path: Option<String>) -> Result<TtsAudio> {
+    let (audio, format) = synthesize(&request).await?;
+    println!("TTS: Synthesized {} bytes of {}", audio.len(), format);
+
+    match path {
+        Some(path) => {
+            if let Some(parent) = std::path::Path::new(&path).parent().filter(|p| !p.as_os_str().is_empty()) {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&path, &audio).await?;
+            Ok(TtsAudio { format: format.to_string(), path: Some(path), data: None })
+        }
+        None => Ok(TtsAudio {
+            format: format.to_string(),
+            path: None,
+            data: Some(general_purpose::STANDARD.encode(audio)),
+        }),
+    }
+}
+
+async fn speak_with_edge(request: crate::tts::models::TtsRequest) -> Result<crate::tts::models::TtsResponse> {
+    let voice = edge::resolve_voice(request.voice.as_deref());
+    println!("TTS: Synthesizing with Edge voice {}", voice);
+    let (audio, boundaries) = edge::synthesize_with_boundaries(&request.text, &voice, request.proxy.as_ref()).await?;
+    playback::play_audio(audio, "audio/mpeg").await?;
+    events::follow(boundaries);
+    Ok(crate::tts::models::TtsResponse {
+        success: true,
+        message: format!("TTS playback started ({})", voice),
+    })
+}
+
+#[cfg(target_os = "windows")]
+async fn speak_with_system(request: crate::tts::models::TtsRequest) -> Result<crate::tts::models::TtsResponse> {
+    println!("TTS: Starting speech synthesis for text: {}", &request.text[..request.text.len().min(50)]);
     
     // 首先尝试使用 Windows Media Foundation
     let result = try_speak_with_media_foundation(&request.text, request.voice.as_deref()).await;
@@ -74,6 +217,8 @@ pub async fn speak(request: crate::tts::models::TtsRequest) -> Result<crate::tts
             println!("TTS: Media Foundation failed: {}, trying PowerShell", e);
             // 备用方案：使用 PowerShell
             speak_with_powershell(&request.text)?;
+            // PowerShell 朗读完才返回
+            events::finished("ended");
             Ok(crate::tts::models::TtsResponse {
                 success: true,
                 message: "TTS playback started (PowerShell)".to_string(),
@@ -89,6 +234,11 @@ async fn try_speak_with_media_foundation(text: &str, voice_preference: Option<&s
     // 创建语音合成器
     let synthesizer = SpeechSynthesizer::new()
         .map_err(|e| AppError::Unknown(format!("Failed to create synthesizer: {:?}", e)))?;
+    // 输出词边界，用于 `tts-progress` 事件
+    synthesizer
+        .Options()
+        .and_then(|options| options.SetIncludeWordBoundaryMetadata(true))
+        .map_err(|e| AppError::Unknown(format!("Failed to enable word boundaries: {:?}", e)))?;
     
     println!("TTS: Synthesizer created, voice preference: {:?}", voice_preference);
     
@@ -98,7 +248,12 @@ async fn try_speak_with_media_foundation(text: &str, voice_preference: Option<&s
     if let Some(voice_type) = voice_preference {
         println!("TTS: Requested voice type: {}", voice_type);
         // 注意：Windows 需要通过 "设置 -> 时间和语言 -> 语音" 安装对应语音包
-        // 代码层面无法直接强制切换，只能通过 SSML 标记语言指定语言
+        // uk / us 无法直接强制切换；`list_voices` 返回的语音 ID 或名称可以直接指定
+        if let Some(voice) = find_system_voice(voice_type) {
+            synthesizer
+                .SetVoice(&voice)
+                .map_err(|e| AppError::Unknown(format!("Failed to set voice: {:?}", e)))?;
+        }
     }
     
     let stream = synthesizer
@@ -108,9 +263,7 @@ async fn try_speak_with_media_foundation(text: &str, voice_preference: Option<&s
         .map_err(|e| AppError::Unknown(format!("Synthesis failed: {:?}", e)))?;
     
     println!("TTS: Stream created");
-    
-    let player = MediaPlayer::new()
-        .map_err(|e| AppError::Unknown(format!("Failed to create media player: {:?}", e)))?;
+    let boundaries = word_boundaries(&stream).unwrap_or_default();
     
     let content_type = stream.ContentType()
         .map_err(|e| AppError::Unknown(format!("Failed to get content type: {:?}", e)))?;
@@ -118,25 +271,152 @@ async fn try_speak_with_media_foundation(text: &str, voice_preference: Option<&s
     let source = MediaSource::CreateFromStream(&stream, &content_type)
         .map_err(|e| AppError::Unknown(format!("Failed to create media source: {:?}", e)))?;
     
-    player.SetSource(&source)
-        .map_err(|e| AppError::Unknown(format!("Failed to set source: {:?}", e)))?;
-    
-    player.Play()
-        .map_err(|e| AppError::Unknown(format!("Failed to play: {:?}", e)))?;
-    
-    println!("TTS: Playback started");
-    
-    let mut global_player = GLOBAL_MEDIA_PLAYER
-        .get_or_init(|| Mutex::new(None))
-        .lock()
-        .map_err(|e| AppError::Unknown(format!("Failed to lock global player: {}", e)))?;
-    
-    *global_player = Some((player, stream));
-    
+    playback::play_source(&source, stream.into())?;
+    events::follow(boundaries);
     Ok(())
 }
 
-#[cfg(not(target_os = "windows"))]
-pub async fn speak(_request: crate::tts::models::TtsRequest) -> Result<crate::tts::models::TtsResponse> {
-    Err(AppError::PlatformNotSupported("Windows Speech API is only available on Windows platform".to_string()))
+/// 合成流中的词边界（SpeechCue），位置按输入文本的 UTF-16 下标计
+#[cfg(target_os = "windows")]
+fn word_boundaries(
+    stream: &windows::Media::SpeechSynthesis::SpeechSynthesisStream,
+) -> windows::core::Result<Vec<events::WordBoundary>> {
+    use windows::core::ComInterface;
+    use windows::Media::Core::SpeechCue;
+
+    let mut boundaries = Vec::new();
+    for track in stream.TimedMetadataTracks()? {
+        for cue in track.Cues()? {
+            let cue: SpeechCue = cue.cast()?;
+            let start = cue.StartPositionInInput()?.Value()?.max(0) as usize;
+            let end = cue.EndPositionInInput()?.Value()?.max(0) as usize;
+            boundaries.push(events::WordBoundary {
+                offset: start,
+                // EndPositionInInput 指向词的最后一个字符
+                length: (end + 1).saturating_sub(start),
+                word: cue.Text()?.to_string(),
+                audio_offset: std::time::Duration::from_nanos(cue.StartTime()?.Duration.max(0) as u64 * 100),
+            });
+        }
+    }
+    Ok(boundaries)
+}
+
+/// 用系统语音合成 WAV 数据
+#[cfg(target_os = "windows")]
+async fn synthesize_system(text: &str, voice: Option<&str>) -> Result<Vec<u8>> {
+    use windows::Media::SpeechSynthesis::SpeechSynthesizer;
+    use windows::Storage::Streams::DataReader;
+
+    let error = |e: windows::core::Error| AppError::Unknown(format!("Synthesis failed: {:?}", e));
+    let synthesizer = SpeechSynthesizer::new().map_err(error)?;
+    if let Some(voice) = voice.and_then(find_system_voice) {
+        synthesizer.SetVoice(&voice).map_err(error)?;
+    }
+    let stream = synthesizer
+        .SynthesizeTextToStreamAsync(&HSTRING::from(text))
+        .map_err(error)?
+        .await
+        .map_err(error)?;
+
+    let size = stream.Size().map_err(error)? as u32;
+    let reader = DataReader::CreateDataReader(&stream).map_err(error)?;
+    reader.LoadAsync(size).map_err(error)?.await.map_err(error)?;
+    let mut audio = vec![0u8; size as usize];
+    reader.ReadBytes(&mut audio).map_err(error)?;
+    Ok(audio)
+}
+
+#[cfg(target_os = "macos")]
+async fn synthesize_system(text: &str, voice: Option<&str>) -> Result<Vec<u8>> {
+    macos::synthesize(text, voice).await
+}
+
+#[cfg(target_os = "linux")]
+async fn synthesize_system(text: &str, voice: Option<&str>) -> Result<Vec<u8>> {
+    linux::synthesize(text, voice).await
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+async fn synthesize_system(_text: &str, _voice: Option<&str>) -> Result<Vec<u8>> {
+    Err(AppError::PlatformNotSupported("System speech is not available on this platform; use the edge engine".to_string()))
+}
+
+/// 按 ID 或显示名称查找已安装的系统语音
+#[cfg(target_os = "windows")]
+fn find_system_voice(voice: &str) -> Option<windows::Media::SpeechSynthesis::VoiceInformation> {
+    let voices = windows::Media::SpeechSynthesis::SpeechSynthesizer::AllVoices().ok()?;
+    voices.into_iter().find(|info| {
+        info.Id().map(|id| id.to_string() == voice).unwrap_or(false)
+            || info.DisplayName().map(|name| name.to_string().eq_ignore_ascii_case(voice)).unwrap_or(false)
+    })
+}
+
+#[cfg(target_os = "windows")]
+async fn system_voices() -> Result<Vec<TtsVoice>> {
+    use windows::Media::SpeechSynthesis::{SpeechSynthesizer, VoiceGender};
+
+    let error = |e: windows::core::Error| AppError::Unknown(format!("Failed to list voices: {:?}", e));
+    let mut voices = Vec::new();
+    for info in SpeechSynthesizer::AllVoices().map_err(error)? {
+        voices.push(TtsVoice {
+            id: info.Id().map_err(error)?.to_string(),
+            name: info.DisplayName().map_err(error)?.to_string(),
+            language: info.Language().map_err(error)?.to_string(),
+            gender: if info.Gender().map_err(error)? == VoiceGender::Female { "female" } else { "male" }.to_string(),
+            engine: "system".to_string(),
+        });
+    }
+    Ok(voices)
+}
+
+#[cfg(target_os = "macos")]
+async fn system_voices() -> Result<Vec<TtsVoice>> {
+    macos::voices().await
+}
+
+#[cfg(target_os = "linux")]
+async fn system_voices() -> Result<Vec<TtsVoice>> {
+    linux::voices().await
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+async fn system_voices() -> Result<Vec<TtsVoice>> {
+    Ok(Vec::new())
+}
+
+/// 列出可用语音：系统安装的语音，`include_online` 时附带 Edge 在线语音。
+/// 在线语音列表获取失败时只返回系统语音；`proxy` 为获取在线语音使用的代理
+pub async fn list_voices(include_online: bool, proxy: Option<serde_json::Value>) -> Result<Vec<TtsVoice>> {
+    let mut voices = system_voices().await?;
+    if include_online {
+        match edge::list_voices(proxy.as_ref()).await {
+            Ok(online) => voices.extend(online),
+            Err(e) => println!("TTS: Failed to list Edge voices: {}", e),
+        }
+    }
+    Ok(voices)
+}
+
+#[cfg(target_os = "macos")]
+async fn speak_with_system(request: crate::tts::models::TtsRequest) -> Result<crate::tts::models::TtsResponse> {
+    macos::speak(&request.text, request.voice.as_deref()).await?;
+    Ok(crate::tts::models::TtsResponse {
+        success: true,
+        message: "TTS playback started (say)".to_string(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+async fn speak_with_system(request: crate::tts::models::TtsRequest) -> Result<crate::tts::models::TtsResponse> {
+    linux::speak(&request.text, request.voice.as_deref()).await?;
+    Ok(crate::tts::models::TtsResponse {
+        success: true,
+        message: "TTS playback started".to_string(),
+    })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+async fn speak_with_system(_request: crate::tts::models::TtsRequest) -> Result<crate::tts::models::TtsResponse> {
+    Err(AppError::PlatformNotSupported("System speech is not available on this platform; use the edge engine".to_string()))
 }
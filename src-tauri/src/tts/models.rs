@@ -3,7 +3,39 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TtsRequest {
     pub text: String,
-    pub voice: Option<String>, // "uk" 或 "us"
+    /// 系统语音为 "uk" 或 "us"；Edge 语音还可以是语言代码（如 "ja-JP"）或完整语音名（如 "en-US-AriaNeural"）
+    pub voice: Option<String>,
+    /// 朗读引擎：`system`（默认）或 `edge`
+    #[serde(default)]
+    pub engine: Option<String>,
+    /// Edge 语音使用的代理，格式同服务配置中的 `proxy`（通常传全局代理）
+    #[serde(default)]
+    pub proxy: Option<serde_json::Value>,
+}
+
+/// 一个可用的朗读语音
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsVoice {
+    /// 作为 `TtsRequest.voice` 传入即可使用该语音
+    pub id: String,
+    pub name: String,
+    /// 语言代码，如 "en-US"
+    pub language: String,
+    /// "male" 或 "female"
+    pub gender: String,
+    /// 使用该语音时的朗读引擎：`system` 或 `edge`
+    pub engine: String,
+}
+
+/// `speak_to_file` 的结果：写入文件时给出路径，否则给出 base64 编码的音频
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtsAudio {
+    /// 音频格式：`wav` 或 `mp3`
+    pub format: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,283 @@
+//! 播放合成好的音频。Windows 使用全局的 MediaPlayer，其他平台写入临时文件后交给系统播放器。
+//! 同一时间只控制最近一次的播放，`stop` / `pause` / `resume` 作用于它；播放结束时发出 `tts-finished` /
+//! `tts-error` 事件并朗读队列中的下一条。
+
+use crate::error::{AppError, Result};
+
+#[cfg(target_os = "windows")]
+use windows::{
+    core::{IInspectable, HSTRING},
+    Foundation::TypedEventHandler,
+    Media::Core::MediaSource,
+    Media::Playback::{MediaPlayer, MediaPlayerFailedEventArgs},
+};
+
+#[cfg(target_os = "windows")]
+use std::sync::{atomic::{AtomicU64, Ordering}, Mutex, OnceLock};
+
+/// 正在播放的播放器及其音频流；流需与播放器一起保持存活
+#[cfg(target_os = "windows")]
+static GLOBAL_MEDIA_PLAYER: OnceLock<Mutex<Option<(MediaPlayer, IInspectable)>>> = OnceLock::new();
+
+/// 每次开始播放或停止时递增，用于判断结束事件是否属于当前播放
+#[cfg(target_os = "windows")]
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(target_os = "windows")]
+fn global_player() -> Result<std::sync::MutexGuard<'static, Option<(MediaPlayer, IInspectable)>>> {
+    GLOBAL_MEDIA_PLAYER
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .map_err(|e| AppError::Unknown(format!("Failed to lock global player: {}", e)))
+}
+
+/// 是否有朗读正在播放或暂停
+#[cfg(target_os = "windows")]
+pub fn is_playing() -> bool {
+    global_player().map(|player| player.is_some()).unwrap_or(false)
+}
+
+/// 播放自然结束或失败（`error` 为错误信息）：释放播放器、发出事件并朗读队列中的下一条
+#[cfg(target_os = "windows")]
+fn ended(generation: u64, error: Option<String>) {
+    if GENERATION.load(Ordering::SeqCst) != generation {
+        return;
+    }
+    if let Ok(mut player) = global_player() {
+        player.take();
+    }
+    match error {
+        Some(error) => crate::tts::events::failed(error),
+        None => crate::tts::events::finished("ended"),
+    }
+    crate::tts::queue::advance();
+}
+
+/// 订阅播放结束和失败事件
+#[cfg(target_os = "windows")]
+fn watch(player: &MediaPlayer, generation: u64) -> Result<()> {
+    player
+        .MediaEnded(&TypedEventHandler::new(move |_, _| {
+            ended(generation, None);
+            Ok(())
+        }))
+        .map_err(|e| AppError::Unknown(format!("Failed to watch playback: {:?}", e)))?;
+    player
+        .MediaFailed(&TypedEventHandler::new(move |_, args: &Option<MediaPlayerFailedEventArgs>| {
+            let message = args
+                .as_ref()
+                .and_then(|args| args.ErrorMessage().ok())
+                .map(|message| message.to_string())
+                .filter(|message| !message.is_empty())
+                .unwrap_or_else(|| "Media playback failed".to_string());
+            ended(generation, Some(message));
+            Ok(())
+        }))
+        .map_err(|e| AppError::Unknown(format!("Failed to watch playback: {:?}", e)))?;
+    Ok(())
+}
+
+/// 播放媒体源；`stream` 是媒体源背后的流
+#[cfg(target_os = "windows")]
+pub fn play_source(source: &MediaSource, stream: IInspectable) -> Result<()> {
+    let player = MediaPlayer::new()
+        .map_err(|e| AppError::Unknown(format!("Failed to create media player: {:?}", e)))?;
+
+    player.SetSource(source)
+        .map_err(|e| AppError::Unknown(format!("Failed to set source: {:?}", e)))?;
+
+    // 先登记为当前播放再订阅事件和开始播放：失败事件可能在 `Play` 返回前就在其他线程触发，
+    // 之后才登记会让已结束的播放器留在全局状态里，`is_playing` 一直为 true，队列停滞
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    *global_player()? = Some((player.clone(), stream));
+
+    let started = watch(&player, generation)
+        .and_then(|_| player.Play().map_err(|e| AppError::Unknown(format!("Failed to play: {:?}", e))));
+    if let Err(e) = started {
+        // 没能开始播放：释放仍属于本次播放的播放器
+        if GENERATION.load(Ordering::SeqCst) == generation {
+            global_player()?.take();
+        }
+        return Err(e);
+    }
+
+    println!("TTS: Playback started");
+    Ok(())
+}
+
+/// 对当前播放器执行操作；`take` 为 true 时同时把它移出全局状态。没有播放器时不做任何事
+#[cfg(target_os = "windows")]
+fn with_player(take: bool, f: impl FnOnce(&MediaPlayer) -> windows::core::Result<()>) -> Result<()> {
+    let mut global_player = global_player()?;
+    let current = if take { global_player.take() } else { global_player.clone() };
+    match current {
+        Some((player, _)) => f(&player).map_err(|e| AppError::Unknown(format!("Media player error: {:?}", e))),
+        None => Ok(()),
+    }
+}
+
+/// 停止并释放当前播放器
+#[cfg(target_os = "windows")]
+pub fn stop() -> Result<()> {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    crate::tts::events::cancel();
+    let was_playing = is_playing();
+    with_player(true, |player| player.Pause())?;
+    if was_playing {
+        crate::tts::events::finished("stopped");
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+pub fn pause() -> Result<()> {
+    crate::tts::events::pause();
+    with_player(false, |player| player.Pause())
+}
+
+#[cfg(target_os = "windows")]
+pub fn resume() -> Result<()> {
+    crate::tts::events::resume();
+    with_player(false, |player| player.Play())
+}
+
+/// 播放编码后的音频（如 MP3）
+#[cfg(target_os = "windows")]
+pub async fn play_audio(audio: Vec<u8>, content_type: &str) -> Result<()> {
+    use windows::Storage::Streams::{DataWriter, InMemoryRandomAccessStream};
+
+    let error = |e: windows::core::Error| AppError::Unknown(format!("Failed to buffer audio: {:?}", e));
+    let stream = InMemoryRandomAccessStream::new().map_err(error)?;
+    let writer = DataWriter::CreateDataWriter(&stream).map_err(error)?;
+    writer.WriteBytes(&audio).map_err(error)?;
+    writer.StoreAsync().map_err(error)?.await.map_err(error)?;
+    writer.FlushAsync().map_err(error)?.await.map_err(error)?;
+    writer.DetachStream().map_err(error)?;
+    stream.Seek(0).map_err(error)?;
+
+    let source = MediaSource::CreateFromStream(&stream, &HSTRING::from(content_type))
+        .map_err(|e| AppError::Unknown(format!("Failed to create media source: {:?}", e)))?;
+    play_source(&source, stream.into())
+}
+
+/// 当前播放器进程的 PID；进程退出后清空
+#[cfg(not(target_os = "windows"))]
+static CURRENT_PLAYER: std::sync::Mutex<Option<u32>> = std::sync::Mutex::new(None);
+
+/// 向当前播放器进程发送信号
+#[cfg(not(target_os = "windows"))]
+fn signal(pid: u32, signal: &str) -> Result<()> {
+    std::process::Command::new("kill")
+        .args([signal, &pid.to_string()])
+        .status()
+        .map_err(|e| AppError::Unknown(format!("Failed to signal audio player: {}", e)))?;
+    Ok(())
+}
+
+/// 是否有朗读正在播放或暂停
+#[cfg(not(target_os = "windows"))]
+pub fn is_playing() -> bool {
+    CURRENT_PLAYER.lock().unwrap().is_some()
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn stop() -> Result<()> {
+    crate::tts::events::cancel();
+    let Some(pid) = CURRENT_PLAYER.lock().unwrap().take() else {
+        return Ok(());
+    };
+    // 已暂停的进程要先恢复才会处理 SIGTERM
+    signal(pid, "-TERM").and_then(|_| signal(pid, "-CONT"))?;
+    crate::tts::events::finished("stopped");
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn pause() -> Result<()> {
+    crate::tts::events::pause();
+    match *CURRENT_PLAYER.lock().unwrap() {
+        Some(pid) => signal(pid, "-STOP"),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn resume() -> Result<()> {
+    crate::tts::events::resume();
+    match *CURRENT_PLAYER.lock().unwrap() {
+        Some(pid) => signal(pid, "-CONT"),
+        None => Ok(()),
+    }
+}
+
+/// 把已启动的播放进程（播放器或直接发声的合成命令）设为当前播放，退出后删除 `cleanup` 指向的临时文件；
+/// 未被停止或替换的进程退出时发出 `tts-finished`（退出码非 0 时为 `tts-error`）并朗读队列中的下一条
+#[cfg(not(target_os = "windows"))]
+pub fn track(mut child: std::process::Child, cleanup: Option<std::path::PathBuf>) {
+    let pid = child.id();
+    *CURRENT_PLAYER.lock().unwrap() = Some(pid);
+    std::thread::spawn(move || {
+        let status = child.wait();
+        if let Some(path) = cleanup {
+            let _ = std::fs::remove_file(path);
+        }
+        let finished = {
+            let mut current = CURRENT_PLAYER.lock().unwrap();
+            let finished = *current == Some(pid);
+            if finished {
+                *current = None;
+            }
+            finished
+        };
+        if finished {
+            match status {
+                Ok(status) if status.success() => crate::tts::events::finished("ended"),
+                Ok(status) => crate::tts::events::failed(format!("Speech process exited with {}", status)),
+                Err(e) => crate::tts::events::failed(format!("Failed to wait for speech process: {}", e)),
+            }
+            crate::tts::queue::advance();
+        }
+    });
+}
+
+/// 可用的命令行播放器及其参数，按优先级排列
+#[cfg(not(target_os = "windows"))]
+const PLAYERS: &[(&str, &[&str])] = &[
+    ("afplay", &[]),
+    ("mpv", &["--no-video", "--really-quiet"]),
+    ("ffplay", &["-nodisp", "-autoexit", "-loglevel", "quiet"]),
+    ("mpg123", &["-q"]),
+];
+
+/// 只能播放 WAV 的播放器，PulseAudio / ALSA 环境下通常自带
+#[cfg(not(target_os = "windows"))]
+const WAV_PLAYERS: &[(&str, &[&str])] = &[("paplay", &[]), ("aplay", &["-q"])];
+
+/// 播放编码后的音频：写入临时文件后用第一个可用的播放器在后台播放，播放结束后删除文件
+#[cfg(not(target_os = "windows"))]
+pub async fn play_audio(audio: Vec<u8>, content_type: &str) -> Result<()> {
+    let is_wav = content_type.contains("wav");
+    let extension = if is_wav { "wav" } else { "mp3" };
+    let path = std::env::temp_dir().join(format!("dict_tts_{}.{}", uuid::Uuid::new_v4().simple(), extension));
+    tokio::fs::write(&path, &audio).await?;
+
+    let wav_players = if is_wav { WAV_PLAYERS } else { &[] };
+    for (program, args) in PLAYERS.iter().chain(wav_players) {
+        let spawned = std::process::Command::new(program)
+            .args(*args)
+            .arg(&path)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        if let Ok(child) = spawned {
+            println!("TTS: Playing audio with {}", program);
+            track(child, Some(path));
+            return Ok(());
+        }
+    }
+
+    let _ = std::fs::remove_file(&path);
+    Err(AppError::PlatformNotSupported(
+        "No audio player found; install mpv, ffplay or mpg123".to_string(),
+    ))
+}
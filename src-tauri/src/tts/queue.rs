@@ -0,0 +1,78 @@
+//! 朗读队列：已有语音在播放（含暂停）时再次 `speak` 的处理方式。
+//! `interrupt` 打断当前朗读并清空队列（默认），`queue` 排到队尾，`ignore` 忽略新请求。
+//! 策略保存在数据目录的 `tts_queue.json` 中，重启后保留。
+
+use crate::error::Result;
+use crate::tts::models::TtsRequest;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueuePolicy {
+    #[default]
+    Interrupt,
+    Queue,
+    Ignore,
+}
+
+/// None 表示尚未从磁盘加载
+static POLICY: Mutex<Option<QueuePolicy>> = Mutex::new(None);
+static PENDING: Mutex<VecDeque<TtsRequest>> = Mutex::new(VecDeque::new());
+
+fn policy_file() -> PathBuf {
+    crate::paths::data_dir().join("tts_queue.json")
+}
+
+fn load() -> QueuePolicy {
+    std::fs::read_to_string(policy_file())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn policy() -> QueuePolicy {
+    *POLICY.lock().unwrap().get_or_insert_with(load)
+}
+
+/// 设置策略并写回磁盘
+pub fn set_policy(policy: QueuePolicy) -> Result<()> {
+    let mut guard = POLICY.lock().unwrap();
+    let path = policy_file();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&policy)?)?;
+    *guard = Some(policy);
+    Ok(())
+}
+
+/// 加入队尾，返回排在它前面的请求数
+pub(crate) fn push(request: TtsRequest) -> usize {
+    let mut pending = PENDING.lock().unwrap();
+    pending.push_back(request);
+    pending.len() - 1
+}
+
+pub(crate) fn clear() {
+    PENDING.lock().unwrap().clear();
+}
+
+pub(crate) fn pop() -> Option<TtsRequest> {
+    PENDING.lock().unwrap().pop_front()
+}
+
+/// 当前朗读自然结束后调用：朗读下一条排队的请求，失败时跳过它继续
+pub(crate) fn advance() {
+    if let Some(request) = pop() {
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::tts::speak(request).await {
+                println!("TTS: Queued speech failed: {}", e);
+                crate::tts::events::failed(e.to_string());
+                advance();
+            }
+        });
+    }
+}
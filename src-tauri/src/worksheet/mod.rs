@@ -0,0 +1,361 @@
+//! 可打印的词汇练习单：从选定词条生成完形填空、词义匹配和释义练习，
+//! 经模板渲染为 HTML；PDF 由无头 Edge/Chrome 打印 HTML 得到
+
+pub mod template;
+
+use crate::error::{AppError, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const PAGE_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{{title}}</title>
+<style>
+body { font-family: system-ui, sans-serif; max-width: 780px; margin: 2em auto; line-height: 1.6; }
+h1 { margin-bottom: 0.2em; }
+.header { display: flex; gap: 2em; color: #444; margin-bottom: 1.5em; }
+section { margin-bottom: 2em; }
+ol { padding-left: 1.5em; }
+.blank { display: inline-block; min-width: 8em; border-bottom: 1px solid #000; }
+.matching { display: grid; grid-template-columns: 1fr 2fr; gap: 0.3em 2em; }
+.line { border-bottom: 1px solid #999; height: 1.6em; }
+.word-bank { border: 1px solid #999; padding: 0.5em 1em; }
+.answers { page-break-before: always; }
+@media print { body { margin: 0 1cm; } }
+</style>
+</head>
+<body>
+<h1>{{title}}</h1>
+<div class="header"><span>Name: <span class="blank"></span></span><span>Date: <span class="blank"></span></span></div>
+{{{sections}}}
+{{{answers}}}
+</body>
+</html>
+"#;
+
+const SECTION_TEMPLATE: &str = r#"<section>
+<h2>{{heading}}</h2>
+<p>{{instructions}}</p>
+{{{body}}}
+</section>
+"#;
+
+/// 练习单中的一个词条
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VocabEntry {
+    pub word: String,
+    pub definition: String,
+    #[serde(default)]
+    pub example: Option<String>,
+    #[serde(default)]
+    pub translation: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExerciseKind {
+    Cloze,
+    Matching,
+    Definitions,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorksheetFormat {
+    Html,
+    Pdf,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorksheetOptions {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default = "default_exercises")]
+    pub exercises: Vec<ExerciseKind>,
+    #[serde(default = "default_true")]
+    pub answer_key: bool,
+    /// 打乱顺序的种子，相同种子生成相同的练习单
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_exercises() -> Vec<ExerciseKind> {
+    vec![ExerciseKind::Cloze, ExerciseKind::Matching, ExerciseKind::Definitions]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// HTML 为原文，PDF 为 base64
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorksheetOutput {
+    pub mime_type: &'static str,
+    pub content: String,
+}
+
+/// 确定性的洗牌（xorshift），避免为此引入随机数依赖
+fn shuffled<T: Clone>(items: &[T], seed: u64) -> Vec<T> {
+    let mut state = seed | 1;
+    let mut items = items.to_vec();
+    for i in (1..items.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        items.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    items
+}
+
+fn letter(index: usize) -> String {
+    let mut label = String::new();
+    let mut n = index;
+    loop {
+        label.insert(0, (b'A' + (n % 26) as u8) as char);
+        if n < 26 {
+            break;
+        }
+        n = n / 26 - 1;
+    }
+    label
+}
+
+/// `text` 开头与 `word` 不区分大小写相同时，返回匹配部分在 `text` 中的字节长度。
+/// 逐字符比较，大小写转换改变字节长度（如 `İ`）时也不会错位
+fn match_ignore_case(text: &str, word: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for expected in word.chars() {
+        let (_, c) = chars.next()?;
+        if !c.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(end, _)| end))
+}
+
+/// 将例句中的目标词（不区分大小写，按整词）替换为空格线
+fn blank_out(sentence: &str, word: &str) -> Option<String> {
+    if word.is_empty() {
+        return None;
+    }
+    let mut out = String::new();
+    let mut last = 0;
+    let mut found = false;
+    for (pos, _) in sentence.char_indices() {
+        if pos < last {
+            continue;
+        }
+        let Some(len) = match_ignore_case(&sentence[pos..], word) else {
+            continue;
+        };
+        let end = pos + len;
+        let boundary_before = sentence[..pos].chars().next_back().is_none_or(|c| !c.is_alphanumeric());
+        let boundary_after = sentence[end..].chars().next().is_none_or(|c| !c.is_alphanumeric());
+        if !boundary_before || !boundary_after {
+            continue;
+        }
+        out.push_str(&template::escape_html(&sentence[last..pos]));
+        out.push_str("<span class=\"blank\"></span>");
+        last = end;
+        found = true;
+    }
+    out.push_str(&template::escape_html(&sentence[last..]));
+    found.then_some(out)
+}
+
+fn section(heading: &str, instructions: &str, body: String) -> String {
+    let values = HashMap::from([
+        ("heading", heading.to_string()),
+        ("instructions", instructions.to_string()),
+        ("body", body),
+    ]);
+    template::render(SECTION_TEMPLATE, &values)
+}
+
+/// 返回练习 HTML 与答案 HTML；没有可用词条时返回 `None`
+fn exercise(kind: ExerciseKind, entries: &[VocabEntry], seed: u64) -> Option<(String, String)> {
+    let esc = template::escape_html;
+    match kind {
+        ExerciseKind::Cloze => {
+            let items: Vec<(&VocabEntry, String)> = entries
+                .iter()
+                .filter_map(|e| e.example.as_deref().and_then(|ex| blank_out(ex, &e.word)).map(|s| (e, s)))
+                .collect();
+            if items.is_empty() {
+                return None;
+            }
+            let bank = shuffled(&items.iter().map(|(e, _)| esc(&e.word)).collect::<Vec<_>>(), seed).join(" · ");
+            let list: String = items.iter().map(|(_, s)| format!("<li>{}</li>\n", s)).collect();
+            let answers: String = items.iter().map(|(e, _)| format!("<li>{}</li>\n", esc(&e.word))).collect();
+            Some((
+                section(
+                    "Fill in the blanks",
+                    "Complete each sentence with a word from the box.",
+                    format!("<div class=\"word-bank\">{}</div>\n<ol>\n{}</ol>\n", bank, list),
+                ),
+                format!("<h3>Fill in the blanks</h3>\n<ol>\n{}</ol>\n", answers),
+            ))
+        }
+        ExerciseKind::Matching => {
+            if entries.len() < 2 {
+                return None;
+            }
+            let order = shuffled(&(0..entries.len()).collect::<Vec<_>>(), seed.wrapping_add(1));
+            let mut body = String::from("<div class=\"matching\">\n");
+            for (row, entry) in entries.iter().enumerate() {
+                let definition = &entries[order[row]].definition;
+                body.push_str(&format!(
+                    "<div>{}. {} ____</div><div>{}. {}</div>\n",
+                    row + 1,
+                    esc(&entry.word),
+                    letter(row),
+                    esc(definition)
+                ));
+            }
+            body.push_str("</div>\n");
+            let answers: String = entries
+                .iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    let position = order.iter().position(|i| *i == index).unwrap_or(0);
+                    format!("<li>{} — {}</li>\n", esc(&entry.word), letter(position))
+                })
+                .collect();
+            Some((
+                section("Matching", "Write the letter of the matching definition next to each word.", body),
+                format!("<h3>Matching</h3>\n<ol>\n{}</ol>\n", answers),
+            ))
+        }
+        ExerciseKind::Definitions => {
+            let list: String = entries
+                .iter()
+                .map(|e| format!("<li>{}<div class=\"line\"></div></li>\n", esc(&e.word)))
+                .collect();
+            let answers: String = entries
+                .iter()
+                .map(|e| {
+                    let translation = e.translation.as_deref().map(|t| format!(" ({})", esc(t))).unwrap_or_default();
+                    format!("<li>{}{}: {}</li>\n", esc(&e.word), translation, esc(&e.definition))
+                })
+                .collect();
+            Some((
+                section("Definitions", "Write a short definition for each word.", format!("<ol>\n{}</ol>\n", list)),
+                format!("<h3>Definitions</h3>\n<ol>\n{}</ol>\n", answers),
+            ))
+        }
+    }
+}
+
+pub fn render_html(entries: &[VocabEntry], options: &WorksheetOptions) -> Result<String> {
+    let entries: Vec<VocabEntry> = entries.iter().filter(|e| !e.word.trim().is_empty()).cloned().collect();
+    if entries.is_empty() {
+        return Err(AppError::InvalidRequest("No vocabulary entries selected".to_string()));
+    }
+
+    let seed = options.seed.unwrap_or_else(|| chrono::Utc::now().timestamp_millis() as u64);
+    let mut sections = String::new();
+    let mut answers = String::new();
+    for kind in &options.exercises {
+        if let Some((exercise, answer)) = exercise(*kind, &entries, seed) {
+            sections.push_str(&exercise);
+            answers.push_str(&answer);
+        }
+    }
+    if sections.is_empty() {
+        return Err(AppError::InvalidRequest("Selected entries cannot produce any exercise".to_string()));
+    }
+
+    let answers = if options.answer_key {
+        format!("<section class=\"answers\">\n<h2>Answer key</h2>\n{}</section>\n", answers)
+    } else {
+        String::new()
+    };
+    let values = HashMap::from([
+        ("title", options.title.clone().unwrap_or_else(|| "Vocabulary worksheet".to_string())),
+        ("sections", sections),
+        ("answers", answers),
+    ]);
+    Ok(template::render(PAGE_TEMPLATE, &values))
+}
+
+pub async fn generate(entries: Vec<VocabEntry>, options: WorksheetOptions, format: WorksheetFormat) -> Result<WorksheetOutput> {
+    let html = render_html(&entries, &options)?;
+    match format {
+        WorksheetFormat::Html => Ok(WorksheetOutput { mime_type: "text/html", content: html }),
+        WorksheetFormat::Pdf => {
+            let pdf = print_to_pdf(&html).await?;
+            Ok(WorksheetOutput {
+                mime_type: "application/pdf",
+                content: general_purpose::STANDARD.encode(pdf),
+            })
+        }
+    }
+}
+
+fn find_browser() -> Option<String> {
+    #[cfg(target_os = "windows")]
+    let candidates = [
+        r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe",
+        r"C:\Program Files\Microsoft\Edge\Application\msedge.exe",
+        r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+    ];
+    #[cfg(not(target_os = "windows"))]
+    let candidates = ["chromium", "chromium-browser", "google-chrome", "microsoft-edge"];
+
+    candidates.iter().find_map(|candidate| {
+        let available = if std::path::Path::new(candidate).is_absolute() {
+            std::path::Path::new(candidate).exists()
+        } else {
+            crate::ocr::paddle::create_command(candidate).arg("--version").output().is_ok_and(|o| o.status.success())
+        };
+        available.then(|| candidate.to_string())
+    })
+}
+
+/// 无头浏览器打印 PDF 的最长等待时间
+const PDF_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// 用无头浏览器把 HTML 打印为 PDF；超时时终止浏览器进程
+async fn print_to_pdf(html: &str) -> Result<Vec<u8>> {
+    let browser = tokio::task::spawn_blocking(find_browser)
+        .await
+        .map_err(|e| AppError::Unknown(format!("PDF task failed: {}", e)))?
+        .ok_or_else(|| AppError::ServiceUnavailable("PDF export requires Microsoft Edge or Chrome".to_string()))?;
+
+    let dir = std::env::temp_dir().join(format!("dict-worksheet-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    let html_path = dir.join("worksheet.html");
+    let pdf_path = dir.join("worksheet.pdf");
+    std::fs::write(&html_path, html)?;
+
+    let url = url::Url::from_file_path(&html_path)
+        .map_err(|_| AppError::Unknown("Invalid worksheet path".to_string()))?;
+    let mut command = tokio::process::Command::from(crate::ocr::paddle::create_command(&browser));
+    command
+        .args([
+            "--headless",
+            "--disable-gpu",
+            "--no-pdf-header-footer",
+            &format!("--print-to-pdf={}", pdf_path.display()),
+            url.as_str(),
+        ])
+        .kill_on_drop(true);
+
+    let result = match tokio::time::timeout(PDF_TIMEOUT, command.output()).await {
+        Ok(Ok(_)) if pdf_path.exists() => std::fs::read(&pdf_path).map_err(AppError::from),
+        Ok(Ok(output)) => Err(AppError::Unknown(format!(
+            "Browser did not produce a PDF: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ))),
+        Ok(Err(e)) => Err(AppError::Unknown(format!("Failed to run browser: {}", e))),
+        Err(_) => Err(AppError::Timeout(format!("Browser did not finish printing within {}s", PDF_TIMEOUT.as_secs()))),
+    };
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
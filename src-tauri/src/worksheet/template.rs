@@ -0,0 +1,35 @@
+//! 极简模板：`{{name}}` 插入转义后的值，`{{{name}}}` 插入原始 HTML
+
+use std::collections::HashMap;
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn render(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let raw = rest[start..].starts_with("{{{");
+        let (open, close) = if raw { ("{{{", "}}}") } else { ("{{", "}}") };
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find(close) else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = after[..end].trim();
+        let value = values.get(name).map(String::as_str).unwrap_or_default();
+        if raw {
+            out.push_str(value);
+        } else {
+            out.push_str(&escape_html(value));
+        }
+        rest = &after[end + close.len()..];
+    }
+    out.push_str(rest);
+    out
+}
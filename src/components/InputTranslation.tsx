@@ -96,7 +96,13 @@ export default function InputTranslation({ initialText, initialOcrInfo }: { init
         service: string
         delta?: string
         text?: string
-        error?: string
+        error?: {
+          service: string
+          category: string
+          status?: number
+          retryable: boolean
+          message: string
+        }
         done?: boolean
         all_done?: boolean
       }
@@ -133,7 +139,7 @@ export default function InputTranslation({ initialText, initialOcrInfo }: { init
         } else {
           const current = next[index]
           if (payload.error) {
-            next[index] = { ...current, error: payload.error }
+            next[index] = { ...current, error: payload.error.message }
           } else if (payload.text) {
             next[index] = { ...current, text: payload.text }
             if (translationMetaRef.current) {
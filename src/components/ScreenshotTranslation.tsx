@@ -21,6 +21,8 @@ export default function ScreenshotTranslation() {
   const [currentPos, setCurrentPos] = useState({ x: 0, y: 0 })
   // Removed preCaptureStateRef as it's no longer needed for overlay window
   const monitorRef = useRef<MonitorInfo | null>(null)
+  // Silent OCR: the backend copies the text and shows a native notification instead of the main window
+  const silentRef = useRef(false)
 
   const { ocrLanguage, ocrEngine, ocrShowResult } = useSettingsStore()
 
@@ -191,6 +193,19 @@ export default function ScreenshotTranslation() {
 
       window.dispatchEvent(new CustomEvent('debug-log', { detail: `Capturing absolute rect: ${absX},${absY} ${physW}x${physH} (Physical) Lang: ${ocrLanguage}` }));
 
+      if (silentRef.current) {
+        await invoke('silent_ocr', {
+          x: absX,
+          y: absY,
+          w: physW,
+          h: physH,
+          language: ocrLanguage,
+          engine: ocrEngine
+        });
+        window.dispatchEvent(new CustomEvent('debug-log', { detail: 'Silent OCR copied to clipboard' }));
+        return;
+      }
+
       // Call backend to capture and OCR directly
       // This is the native way: let backend handle the heavy lifting
       const ocrResult = await invoke<{ text: string, confidence: number }>('capture_and_ocr_with_engine', {
@@ -248,7 +263,10 @@ export default function ScreenshotTranslation() {
         userFriendlyMsg = 'No valid text detected. Please select again.';
       }
       window.dispatchEvent(new CustomEvent('debug-log', { detail: `Error: ${errorStr} -> User message: ${userFriendlyMsg}` }));
-      showErrorNotification(userFriendlyMsg)
+      // Silent OCR failures are already reported by the backend's native notification
+      if (!silentRef.current) {
+        showErrorNotification(userFriendlyMsg)
+      }
     } finally {
       setIsProcessing(false) // Ensure processing state is cleared
       await restoreWindow()
@@ -415,12 +433,23 @@ export default function ScreenshotTranslation() {
       }
     }
 
-    window.addEventListener('trigger-screenshot-ocr', handleTrigger)
+    const handleScreenshotTrigger = () => {
+      silentRef.current = false
+      handleTrigger()
+    }
+    const handleSilentTrigger = () => {
+      silentRef.current = true
+      handleTrigger()
+    }
+
+    window.addEventListener('trigger-screenshot-ocr', handleScreenshotTrigger)
+    window.addEventListener('trigger-silent-ocr', handleSilentTrigger)
     window.dispatchEvent(new CustomEvent('debug-log', { detail: 'Event listener for trigger-screenshot-ocr registered' }));
     
     return () => {
       listeners.forEach(p => p.then(f => f()));
-      window.removeEventListener('trigger-screenshot-ocr', handleTrigger)
+      window.removeEventListener('trigger-screenshot-ocr', handleScreenshotTrigger)
+      window.removeEventListener('trigger-silent-ocr', handleSilentTrigger)
       window.dispatchEvent(new CustomEvent('debug-log', { detail: 'Event listener for trigger-screenshot-ocr removed' }));
     }
   }, [])